@@ -0,0 +1,184 @@
+//!
+//! Axis-aligned bounding boxes
+//!
+
+use crate::Scalar;
+
+
+///
+/// A closed, axis-aligned box in `N` dimensions
+///
+/// Both `min` and `max` lie inside the box. For integer grids this means `max` is the last cell covered,
+/// not one past it
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Aabb;
+///
+/// let a = Aabb::new([0, 0], [4, 2]);
+/// let b = Aabb::new([3, 1], [8, 8]);
+///
+/// assert_eq!(a.extent(ax!(x)), 4);
+/// assert!(a.contains(&[4, 2]));
+/// assert_eq!(a.intersection(&b), Some(Aabb::new([3, 1], [4, 2])));
+/// assert_eq!(a.union(&b), Aabb::new([0, 0], [8, 8]));
+/// # }
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Aabb<T, const N: usize> {
+    /// The smallest value of every axis
+    pub min: [T; N],
+    /// The largest value of every axis
+    pub max: [T; N],
+}
+
+impl<T, const N: usize> Aabb<T, N> {
+
+    ///
+    /// Creates a box spanning `min` to `max` inclusive
+    ///
+    /// No check is made that `min` is not greater than `max`, see [`Aabb::is_empty`]
+    ///
+    pub const fn new(min: [T; N], max: [T; N]) -> Self {
+        Self { min, max }
+    }
+
+}
+
+impl<T: Scalar, const N: usize> Aabb<T, N> {
+
+    ///
+    /// Returns the smallest box containing every point, or `None` if there are no points
+    ///
+    pub fn from_points(points: &[[T; N]]) -> Option<Self> {
+        let (first, rest) = points.split_first()?;
+        let mut aabb = Self::new(*first, *first);
+        for p in rest {
+            aabb.expand_to(p);
+        }
+        Some(aabb)
+    }
+
+    ///
+    /// Returns whether `min` is greater than `max` along any axis, in which case the box contains nothing
+    ///
+    pub fn is_empty(&self) -> bool {
+        (0..N).any(|i| self.min[i].partial_cmp(&self.max[i]).is_none_or(|o| o.is_gt()))
+    }
+
+    ///
+    /// Returns the length of the box along `axis`
+    ///
+    pub fn extent(&self, axis: usize) -> T {
+        self.max[axis] - self.min[axis]
+    }
+
+    ///
+    /// Returns the length of the box along every axis
+    ///
+    pub fn extents(&self) -> [T; N] {
+        core::array::from_fn(|i| self.extent(i))
+    }
+
+    ///
+    /// Returns the point halfway between `min` and `max`
+    ///
+    /// For integers the result is rounded the same way integer division rounds
+    ///
+    pub fn center(&self) -> [T; N] {
+        let two = T::ONE + T::ONE;
+        core::array::from_fn(|i| self.min[i] + (self.max[i] - self.min[i]) / two)
+    }
+
+    ///
+    /// Returns whether `point` lies inside the box or on its boundary
+    ///
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        (0..N).all(|i| self.min[i] <= point[i] && point[i] <= self.max[i])
+    }
+
+    ///
+    /// Returns whether the two boxes share at least one point
+    ///
+    pub fn intersects(&self, other: &Self) -> bool {
+        (0..N).all(|i| self.min[i] <= other.max[i] && other.min[i] <= self.max[i])
+    }
+
+    ///
+    /// Returns the box covered by both boxes, or `None` if they do not intersect
+    ///
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        if !self.intersects(other) {
+            return None;
+        }
+        Some(Self::new(
+            core::array::from_fn(|i| max(self.min[i], other.min[i])),
+            core::array::from_fn(|i| min(self.max[i], other.max[i])),
+        ))
+    }
+
+    ///
+    /// Returns the smallest box containing both boxes
+    ///
+    pub fn union(&self, other: &Self) -> Self {
+        Self::new(
+            core::array::from_fn(|i| min(self.min[i], other.min[i])),
+            core::array::from_fn(|i| max(self.max[i], other.max[i])),
+        )
+    }
+
+    ///
+    /// Grows the box just enough to contain `point`
+    ///
+    pub fn expand_to(&mut self, point: &[T; N]) {
+        for (i, &v) in point.iter().enumerate() {
+            self.min[i] = min(self.min[i], v);
+            self.max[i] = max(self.max[i], v);
+        }
+    }
+
+}
+
+// `Ord::min`/`max` are unavailable for floats, so these pick by `PartialOrd`
+pub(crate) fn min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a { b } else { a }
+}
+
+pub(crate) fn max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a { b } else { a }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn from_points_works() {
+        let points = [[1.0, 5.0], [-2.0, 3.0], [0.5, 7.0]];
+        assert_eq!(Aabb::from_points(&points), Some(Aabb::new([-2.0, 3.0], [1.0, 7.0])));
+        assert_eq!(Aabb::<f32, 2>::from_points(&[]), None);
+    }
+
+    #[test]
+    fn measurements_work() {
+        let aabb = Aabb::new([0u32, 2, 4], [10, 6, 5]);
+        assert_eq!(aabb.extents(), [10, 4, 1]);
+        assert_eq!(aabb.center(), [5, 4, 4]);
+        assert!(!aabb.is_empty());
+        assert!(Aabb::new([1, 1], [0, 1]).is_empty());
+    }
+
+    #[test]
+    fn intersection_works() {
+        let a = Aabb::new([0, 0], [1, 1]);
+        let b = Aabb::new([2, 0], [3, 1]);
+        assert!(!a.intersects(&b));
+        assert_eq!(a.intersection(&b), None);
+        assert!(a.intersects(&Aabb::new([1, 1], [2, 2])));
+    }
+
+}
@@ -0,0 +1,256 @@
+//!
+//! Values tagged with the axis they measure along, so index arithmetic is checked by the type system
+//!
+
+use core::marker::PhantomData;
+use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use crate::marker::AxisMarker;
+use crate::{Scalar, Shape, SignedScalar};
+
+
+///
+/// A coordinate or offset along the axis named by the marker `A`
+///
+/// Offsets along the same axis add and subtract to another offset along it, while mixing axes fails to
+/// compile. Multiplying by the [`Stride`] of the same axis gives the untagged flat offset that position
+/// contributes, and dividing two offsets gives their untagged ratio
+///
+/// ```
+/// use axmac::marker::{X, Y};
+/// use axmac::{Along, Shape, Stride};
+///
+/// let shape = Shape::new([640, 480]);
+/// let (x, y) = (Along::<usize, X>::new(10), Along::<usize, Y>::new(3));
+///
+/// let flat = x * Stride::of(&shape) + y * Stride::of(&shape);
+/// assert_eq!(shape.flat_index(&[10, 3]), Some(flat));
+/// assert_eq!((x + Along::new(5)).get(), 15);
+/// assert_eq!(x / Along::new(5), 2);
+/// ```
+///
+/// ```compile_fail
+/// use axmac::marker::{X, Y};
+/// use axmac::Along;
+///
+/// // error: cannot add an offset along y to one along x
+/// let sum = Along::<usize, X>::new(1) + Along::<usize, Y>::new(2);
+/// ```
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Along<T, A> {
+    value: T,
+    axis: PhantomData<A>,
+}
+
+impl<T, A: AxisMarker> Along<T, A> {
+
+    ///
+    /// Tags `value` with the axis `A`
+    ///
+    pub const fn new(value: T) -> Self {
+        Self { value, axis: PhantomData }
+    }
+
+    ///
+    /// Returns the component of `coord` along `A`, failing to compile if it has no such component
+    ///
+    pub fn of<const N: usize>(coord: &[T; N]) -> Self
+    where
+        T: Copy,
+    {
+        const { assert!(A::INDEX < N, "axmac: the coordinate has no component along the axis") };
+        Self::new(coord[A::INDEX])
+    }
+
+    ///
+    /// Returns the untagged value
+    ///
+    pub fn get(self) -> T {
+        self.value
+    }
+
+}
+
+impl<T: Scalar, A: AxisMarker> Add for Along<T, A> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.value + rhs.value)
+    }
+}
+
+impl<T: Scalar, A: AxisMarker> Sub for Along<T, A> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.value - rhs.value)
+    }
+}
+
+impl<T: Scalar, A: AxisMarker> AddAssign for Along<T, A> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Scalar, A: AxisMarker> SubAssign for Along<T, A> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: SignedScalar, A: AxisMarker> Neg for Along<T, A> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.value)
+    }
+}
+
+///
+/// Scales the offset by an untagged count
+///
+impl<T: Scalar, A: AxisMarker> Mul<T> for Along<T, A> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.value * rhs)
+    }
+}
+
+///
+/// Returns the untagged ratio of two offsets along the same axis
+///
+impl<T: Scalar, A: AxisMarker> Div for Along<T, A> {
+    type Output = T;
+
+    fn div(self, rhs: Self) -> T {
+        self.value / rhs.value
+    }
+}
+
+///
+/// Returns the untagged flat offset of a position along the axis of the stride
+///
+impl<T: Scalar, A: AxisMarker> Mul<Stride<T, A>> for Along<T, A> {
+    type Output = T;
+
+    fn mul(self, rhs: Stride<T, A>) -> T {
+        self.value * rhs.value
+    }
+}
+
+
+///
+/// The distance in elements between neighbouring positions along the axis named by the marker `A`
+///
+/// Strides multiply with an [`Along`] of the same axis into an untagged flat offset, and with an untagged
+/// count into a larger stride along the same axis. Adding strides has no meaning and fails to compile
+///
+/// ```
+/// use axmac::marker::{X, Y};
+/// use axmac::{Along, Shape, Stride};
+///
+/// let row = Stride::<usize, Y>::of(&Shape::new([640, 480]));
+/// assert_eq!(row.get(), 640);
+/// assert_eq!(row * Along::new(2), 1280);
+/// assert_eq!((row * 2).get(), 1280);
+/// ```
+///
+/// ```compile_fail
+/// use axmac::marker::{X, Y};
+/// use axmac::{Along, Stride};
+///
+/// // error: a stride along y cannot step along x
+/// let flat = Stride::<usize, Y>::new(640) * Along::<usize, X>::new(1);
+/// ```
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Stride<T, A> {
+    value: T,
+    axis: PhantomData<A>,
+}
+
+impl<T, A: AxisMarker> Stride<T, A> {
+
+    ///
+    /// Tags `value` as the stride of the axis `A`
+    ///
+    pub const fn new(value: T) -> Self {
+        Self { value, axis: PhantomData }
+    }
+
+    ///
+    /// Returns the untagged value
+    ///
+    pub fn get(self) -> T {
+        self.value
+    }
+
+}
+
+impl<A: AxisMarker> Stride<usize, A> {
+
+    ///
+    /// Returns the stride of `shape` along `A`, failing to compile if it has no such axis
+    ///
+    /// Positions along a [reversed](Shape::reverse_axis) axis count from its far end, so their offsets
+    /// are those of `extent - 1 - position`
+    ///
+    pub const fn of<const N: usize>(shape: &Shape<N>) -> Self {
+        const { assert!(A::INDEX < N, "axmac: the shape has no dimension along the axis") };
+        Self::new(shape.stride(A::INDEX))
+    }
+
+}
+
+///
+/// Steps the stride by an untagged count of positions
+///
+impl<T: Scalar, A: AxisMarker> Mul<T> for Stride<T, A> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self::new(self.value * rhs)
+    }
+}
+
+///
+/// Returns the untagged flat offset of a position along the axis of the stride
+///
+impl<T: Scalar, A: AxisMarker> Mul<Along<T, A>> for Stride<T, A> {
+    type Output = T;
+
+    fn mul(self, rhs: Along<T, A>) -> T {
+        self.value * rhs.value
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::marker::{ConstAxis, X, Z};
+
+    #[test]
+    fn flat_offsets_match_shape() {
+        let shape = Shape::col_major([4, 5, 6]);
+        let coord = [3, 1, 2];
+        let flat = Along::<usize, X>::of(&coord) * Stride::of(&shape)
+            + Along::<_, ConstAxis<1>>::of(&coord) * Stride::of(&shape)
+            + Stride::of(&shape) * Along::<_, Z>::of(&coord);
+        assert_eq!(shape.flat_index(&coord), Some(flat));
+    }
+
+    #[test]
+    fn offsets_combine_along_one_axis() {
+        let mut x = Along::<i32, X>::new(4);
+        x -= Along::new(6);
+        assert_eq!(-x, Along::new(2));
+        assert_eq!((x * 3).get(), -6);
+    }
+
+}
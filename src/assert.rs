@@ -0,0 +1,139 @@
+//!
+//! Assertions for tests of index math which name the axes at fault when they fail
+//!
+
+
+///
+/// Asserts that two coordinates are equal, panicking with every axis along which they differ
+///
+/// Either side may be an array, a [`Coord`](crate::Coord) or an [`AxisMap`](crate::AxisMap) of the same
+/// component type, and comparing coordinates of different lengths fails to compile. As with
+/// `assert_eq!`, a format string and arguments may follow to add to the message
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Coord3;
+///
+/// assert_coord_eq!(Coord3::new([1, 2, 3]), [1, 2, 3]);
+/// # }
+/// ```
+///
+/// ```should_panic
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // panics: axmac: coordinates differ along y (2 != 5), z (3 != 0): after the step
+/// assert_coord_eq!([1, 2, 3], [1, 5, 0], "after the {}", "step");
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If the coordinates differ along any axis
+///
+#[macro_export]
+macro_rules! assert_coord_eq {
+    ( $left:expr, $right:expr $(,)? ) => {
+        $crate::__private::assert_coord_eq(&$left, &$right, ::core::option::Option::None)
+    };
+    ( $left:expr, $right:expr, $($arg:tt)+ ) => {
+        $crate::__private::assert_coord_eq(&$left, &$right, ::core::option::Option::Some(::core::format_args!($($arg)+)))
+    };
+}
+
+
+///
+/// Asserts that two [`Shape`](crate::Shape)s are equal, panicking with every axis along which their
+/// extent, stride or direction differ
+///
+/// The message goes on to [describe](crate::Shape::describe) both shapes in full. A format string and
+/// arguments may follow to add to it
+///
+/// ```should_panic
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Shape;
+///
+/// // panics: axmac: shapes differ along x (stride 1 != 3), y (stride 2 != 1)
+/// assert_shape_eq!(Shape::new([2, 3]), Shape::col_major([2, 3]));
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If the shapes differ along any axis
+///
+#[macro_export]
+macro_rules! assert_shape_eq {
+    ( $left:expr, $right:expr $(,)? ) => {
+        $crate::__private::assert_shape_eq(&$left, &$right, ::core::option::Option::None)
+    };
+    ( $left:expr, $right:expr, $($arg:tt)+ ) => {
+        $crate::__private::assert_shape_eq(&$left, &$right, ::core::option::Option::Some(::core::format_args!($($arg)+)))
+    };
+}
+
+
+///
+/// Asserts that a coordinate lies inside a [`Shape`](crate::Shape) or array of extents, panicking with
+/// every axis along which it does not
+///
+/// The coordinate may be an array, [`Coord`](crate::Coord) or [`AxisMap`](crate::AxisMap) of `usize`
+/// with one component per extent, or it fails to compile. A format string and arguments may follow to
+/// add to the message
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Shape;
+///
+/// assert_ax_in_bounds!([3, 1], Shape::new([4, 2]));
+/// assert_ax_in_bounds!([0, 0, 5], [1, 1, 6]);
+/// # }
+/// ```
+///
+/// ```should_panic
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // panics: axmac: [4, 1] is out of bounds along x (4 is not below 4)
+/// assert_ax_in_bounds!([4, 1], [4, 2]);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If the coordinate lies outside the extent of any axis
+///
+#[macro_export]
+macro_rules! assert_ax_in_bounds {
+    ( $coord:expr, $extents:expr $(,)? ) => {
+        $crate::__private::assert_in_bounds(&$coord, &$extents, ::core::option::Option::None)
+    };
+    ( $coord:expr, $extents:expr, $($arg:tt)+ ) => {
+        $crate::__private::assert_in_bounds(&$coord, &$extents, ::core::option::Option::Some(::core::format_args!($($arg)+)))
+    };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{Axis, Shape};
+
+    #[test]
+    #[should_panic(expected = "axmac: coordinates differ along x (1 != 2), axis 5 (0 != 1): frame 3\n  left: [1, 0, 0, 0, 0, 0]")]
+    fn coord_mismatch_names_axes() {
+        assert_coord_eq!([1, 0, 0, 0, 0, 0], [2, 0, 0, 0, 0, 1], "frame {}", 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac: shapes differ along y (extent 3 != 4, reversed false != true)\n  left: x: extent 2")]
+    fn shape_mismatch_names_fields() {
+        assert_shape_eq!(Shape::new([2, 3]), Shape::new([2, 3]), "equal shapes pass");
+        assert_shape_eq!(Shape::new([2, 3]), Shape::with_strides([2, 4], [1, 2]).reverse_axis(Axis::Y));
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac: [0, 7, 9] is out of bounds along y (7 is not below 7), z (9 is not below 2)\n  extents: [1, 7, 2]")]
+    fn bounds_list_every_axis() {
+        assert_ax_in_bounds!(crate::Coord3::new([0, 6, 1]), Shape::new([1, 7, 2]));
+        assert_ax_in_bounds!([0, 7, 9], [1, 7, 2]);
+    }
+
+}
@@ -0,0 +1,373 @@
+//!
+//! Per-axis counters and extents shared between threads
+//!
+
+use core::sync::atomic::{AtomicI32, AtomicIsize, AtomicU32, AtomicUsize, Ordering};
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::{AtomicI64, AtomicU64};
+
+use crate::{Aabb, Axis, AxisMap, Scalar};
+
+
+///
+/// One atomic counter per axis, for accumulating per-axis statistics from several threads without locks
+///
+/// Counters are updated with relaxed ordering, the usual choice for statistics: every update is counted,
+/// but a [`snapshot`](Self::snapshot) taken while other threads are counting may see some axes further
+/// along than others
+///
+/// ```
+/// use axmac::{AtomicAxisMap, Axis};
+///
+/// static MOVES: AtomicAxisMap<3> = AtomicAxisMap::new();
+///
+/// std::thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| {
+///             for _ in 0..100 {
+///                 MOVES.fetch_add(Axis::X, 1);
+///                 MOVES.fetch_add(Axis::Z, 2);
+///             }
+///         });
+///     }
+/// });
+/// assert_eq!(MOVES.snapshot().into_array(), [400, 0, 800]);
+/// assert_eq!(MOVES.take()[Axis::Z], 800);
+/// assert_eq!(MOVES.load(Axis::Z), 0);
+/// ```
+///
+#[derive(Debug)]
+pub struct AtomicAxisMap<const N: usize> {
+    counts: [AtomicUsize; N],
+}
+
+impl<const N: usize> AtomicAxisMap<N> {
+
+    ///
+    /// Creates the counters with every axis at zero
+    ///
+    pub const fn new() -> Self {
+        Self { counts: [const { AtomicUsize::new(0) }; N] }
+    }
+
+    ///
+    /// Adds `n` to the counter of `axis`, wrapping around on overflow, and returns its previous value
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not less than `N`
+    ///
+    pub fn fetch_add(&self, axis: Axis, n: usize) -> usize {
+        self.counter(axis).fetch_add(n, Ordering::Relaxed)
+    }
+
+    ///
+    /// Returns the counter of `axis`
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not less than `N`
+    ///
+    pub fn load(&self, axis: Axis) -> usize {
+        self.counter(axis).load(Ordering::Relaxed)
+    }
+
+    ///
+    /// Returns the counter of every axis
+    ///
+    pub fn snapshot(&self) -> AxisMap<usize, N> {
+        AxisMap::from_fn(|axis| self.counts[axis].load(Ordering::Relaxed))
+    }
+
+    ///
+    /// Returns the counter of every axis and sets each back to zero, so no update is lost between the two
+    ///
+    pub fn take(&self) -> AxisMap<usize, N> {
+        AxisMap::from_fn(|axis| self.counts[axis].swap(0, Ordering::Relaxed))
+    }
+
+    ///
+    /// Returns the counters, now that no other thread can be updating them
+    ///
+    pub fn into_inner(self) -> AxisMap<usize, N> {
+        AxisMap::new(self.counts.map(AtomicUsize::into_inner))
+    }
+
+    fn counter(&self, axis: Axis) -> &AtomicUsize {
+        let axis = axis.as_usize();
+        assert!(axis < N, "axmac: axis is past the last counter of the map");
+        &self.counts[axis]
+    }
+
+}
+
+impl<const N: usize> Default for AtomicAxisMap<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> From<AxisMap<usize, N>> for AtomicAxisMap<N> {
+    fn from(counts: AxisMap<usize, N>) -> Self {
+        Self { counts: counts.into_array().map(AtomicUsize::new) }
+    }
+}
+
+
+///
+/// Values whose lowest and highest can be tracked in an atomic, for [`AxisExtentTracker`]
+///
+/// Implemented for the 32 and 64 bit integers and pointer sized integers, which the atomics compare
+/// directly, and for floats, whose bits are updated in a compare and swap loop. NaN never moves a float
+/// extent
+///
+pub trait AtomicExtent: Scalar {
+    /// The atomic holding a value
+    type Atomic: Send + Sync;
+
+    /// The value every tracked minimum starts from, which any other lowers
+    const HIGHEST: Self;
+    /// The value every tracked maximum starts from, which any other raises
+    const LOWEST: Self;
+
+    /// Creates an atomic holding `value`
+    fn atomic(value: Self) -> Self::Atomic;
+    /// Returns the value held by `atomic`
+    fn load(atomic: &Self::Atomic) -> Self;
+    /// Lowers the value held by `atomic` to `value` if it is greater
+    fn fetch_min(atomic: &Self::Atomic, value: Self);
+    /// Raises the value held by `atomic` to `value` if it is less
+    fn fetch_max(atomic: &Self::Atomic, value: Self);
+}
+
+macro_rules! impl_atomic_extent_int {
+    ( $( $(#[$cfg:meta])* $t:ty => $atomic:ty ),* $(,)? ) => {
+        $(
+            $(#[$cfg])*
+            impl AtomicExtent for $t {
+                type Atomic = $atomic;
+
+                const HIGHEST: Self = <$t>::MAX;
+                const LOWEST: Self = <$t>::MIN;
+
+                fn atomic(value: Self) -> $atomic {
+                    <$atomic>::new(value)
+                }
+
+                fn load(atomic: &$atomic) -> Self {
+                    atomic.load(Ordering::Relaxed)
+                }
+
+                fn fetch_min(atomic: &$atomic, value: Self) {
+                    atomic.fetch_min(value, Ordering::Relaxed);
+                }
+
+                fn fetch_max(atomic: &$atomic, value: Self) {
+                    atomic.fetch_max(value, Ordering::Relaxed);
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic_extent_int! {
+    i32 => AtomicI32,
+    u32 => AtomicU32,
+    isize => AtomicIsize,
+    usize => AtomicUsize,
+    #[cfg(target_has_atomic = "64")]
+    i64 => AtomicI64,
+    #[cfg(target_has_atomic = "64")]
+    u64 => AtomicU64,
+}
+
+macro_rules! impl_atomic_extent_float {
+    ( $( $(#[$cfg:meta])* $t:ty => $atomic:ty ),* $(,)? ) => {
+        $(
+            $(#[$cfg])*
+            impl AtomicExtent for $t {
+                type Atomic = $atomic;
+
+                const HIGHEST: Self = <$t>::INFINITY;
+                const LOWEST: Self = <$t>::NEG_INFINITY;
+
+                fn atomic(value: Self) -> $atomic {
+                    <$atomic>::new(value.to_bits())
+                }
+
+                fn load(atomic: &$atomic) -> Self {
+                    <$t>::from_bits(atomic.load(Ordering::Relaxed))
+                }
+
+                fn fetch_min(atomic: &$atomic, value: Self) {
+                    // Comparisons with NaN are false, so it never replaces the held value
+                    let _ = atomic.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                        (value < <$t>::from_bits(bits)).then(|| value.to_bits())
+                    });
+                }
+
+                fn fetch_max(atomic: &$atomic, value: Self) {
+                    let _ = atomic.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+                        (value > <$t>::from_bits(bits)).then(|| value.to_bits())
+                    });
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic_extent_float! {
+    f32 => AtomicU32,
+    #[cfg(target_has_atomic = "64")]
+    f64 => AtomicU64,
+}
+
+
+///
+/// The lowest and highest value seen along every axis, updated from several threads without locks
+///
+/// Threads computing the bounding box of their share of a point cloud [`include`](Self::include) their
+/// points, and [`aabb`](Self::aabb) gives the box around all of them once they are done
+///
+/// ```
+/// use axmac::AxisExtentTracker;
+///
+/// let points: Vec<[f32; 3]> = (0..1000).map(|i| {
+///     let t = i as f32;
+///     [t.sin() * 2.0, t * 0.5, -t]
+/// }).collect();
+///
+/// let tracker = AxisExtentTracker::new();
+/// std::thread::scope(|s| {
+///     for chunk in points.chunks(250) {
+///         let tracker = &tracker;
+///         s.spawn(move || chunk.iter().for_each(|p| tracker.include(p)));
+///     }
+/// });
+///
+/// let aabb = tracker.aabb().unwrap();
+/// assert_eq!((aabb.min[1], aabb.max[1]), (0.0, 499.5));
+/// assert_eq!(aabb.min[2], -999.0);
+/// assert!(aabb.max[0] <= 2.0);
+/// ```
+///
+pub struct AxisExtentTracker<T: AtomicExtent, const N: usize> {
+    min: [T::Atomic; N],
+    max: [T::Atomic; N],
+}
+
+impl<T: AtomicExtent, const N: usize> AxisExtentTracker<T, N> {
+
+    ///
+    /// Creates the tracker having seen no values
+    ///
+    pub fn new() -> Self {
+        Self {
+            min: core::array::from_fn(|_| T::atomic(T::HIGHEST)),
+            max: core::array::from_fn(|_| T::atomic(T::LOWEST)),
+        }
+    }
+
+    ///
+    /// Widens the extent of every axis to take in `point`
+    ///
+    pub fn include(&self, point: &[T; N]) {
+        for ((min, max), &value) in self.min.iter().zip(&self.max).zip(point) {
+            T::fetch_min(min, value);
+            T::fetch_max(max, value);
+        }
+    }
+
+    ///
+    /// Widens the extent of `axis` alone to take in `value`
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not less than `N`
+    ///
+    pub fn include_along(&self, axis: Axis, value: T) {
+        let axis = axis.as_usize();
+        assert!(axis < N, "axmac: axis is past the last extent of the tracker");
+        T::fetch_min(&self.min[axis], value);
+        T::fetch_max(&self.max[axis], value);
+    }
+
+    ///
+    /// Returns the lowest and highest value seen along `axis`, or `None` if none has been
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not less than `N`
+    ///
+    pub fn extent(&self, axis: Axis) -> Option<(T, T)> {
+        let axis = axis.as_usize();
+        assert!(axis < N, "axmac: axis is past the last extent of the tracker");
+        let (min, max) = (T::load(&self.min[axis]), T::load(&self.max[axis]));
+        if min <= max { Some((min, max)) } else { None }
+    }
+
+    ///
+    /// Returns the box around every value seen, or `None` if some axis has seen none
+    ///
+    pub fn aabb(&self) -> Option<Aabb<T, N>> {
+        let min = self.min.each_ref().map(T::load);
+        let max = self.max.each_ref().map(T::load);
+        if (0..N).all(|axis| min[axis] <= max[axis]) { Some(Aabb::new(min, max)) } else { None }
+    }
+
+}
+
+impl<T: AtomicExtent, const N: usize> Default for AxisExtentTracker<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: AtomicExtent + core::fmt::Debug, const N: usize> core::fmt::Debug for AxisExtentTracker<T, N> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AxisExtentTracker")
+            .field("min", &self.min.each_ref().map(T::load))
+            .field("max", &self.max.each_ref().map(T::load))
+            .finish()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn counts_wrap_and_convert() {
+        let map = AtomicAxisMap::from(AxisMap::new([usize::MAX, 5]));
+        assert_eq!(map.fetch_add(Axis::X, 2), usize::MAX);
+        assert_eq!(map.fetch_add(Axis::Y, 1), 5);
+        assert_eq!(map.into_inner().into_array(), [1, 6]);
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac: axis is past the last counter of the map")]
+    fn axes_must_fit() {
+        AtomicAxisMap::<2>::new().fetch_add(Axis::Z, 1);
+    }
+
+    #[test]
+    fn extents_start_empty() {
+        let tracker = AxisExtentTracker::<i64, 2>::new();
+        assert_eq!(tracker.aabb(), None);
+        tracker.include_along(Axis::X, -4);
+        assert_eq!(tracker.extent(Axis::X), Some((-4, -4)));
+        assert_eq!(tracker.aabb(), None);
+        tracker.include(&[7, 3]);
+        assert_eq!(tracker.aabb(), Some(Aabb::new([-4, 3], [7, 3])));
+
+        let floats = AxisExtentTracker::<f64, 1>::default();
+        floats.include(&[f64::NAN]);
+        assert_eq!(floats.extent(Axis::X), None);
+        floats.include(&[-0.5]);
+        assert_eq!(floats.extent(Axis::X), Some((-0.5, -0.5)));
+    }
+
+}
@@ -0,0 +1,372 @@
+//!
+//! The typed counterpart of the `ax!` identifiers
+//!
+
+use crate::{InvalidAxis, Scalar};
+
+
+///
+/// One of the axes _x_, _y_, _z_ or _w_ as a runtime value
+///
+/// Converts into the same `usize` that `ax!` produces, so it can be passed to every API taking an axis
+/// index
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Axis;
+///
+/// assert_eq!(Axis::Z.as_usize(), ax!(z));
+/// assert_eq!(usize::from(Axis::W), 3);
+/// assert_eq!(Axis::Y as usize, 1);
+/// assert_eq!(Axis::try_from(2), Ok(Axis::Z));
+///
+/// // The macros can produce axes directly
+/// assert_eq!(ax!(@enum z), Axis::Z);
+/// assert_eq!(axs![@enum w, x], [Axis::W, Axis::X]);
+/// assert!(Axis::iter().eq(Axis::ALL));
+/// # }
+/// ```
+///
+/// # Stability
+///
+/// The discriminants are part of the public API and match `ax!` in every version, so `Axis as usize` is
+/// safe to persist. `Axis` is deliberately exhaustive and never gains variants, not even from cargo
+/// features: features are additive, so a feature-gated variant enabled by any crate in the dependency
+/// graph would break exhaustive matches in every other. The `input` and `motion` vocabularies instead have
+/// their own enums, unified by the `#[non_exhaustive]` [`AnyAxis`]
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(usize)]
+pub enum Axis {
+    X = ax!(x),
+    Y = ax!(y),
+    Z = ax!(z),
+    W = ax!(w),
+}
+
+impl Axis {
+
+    ///
+    /// The number of axes
+    ///
+    pub const COUNT: usize = 4;
+
+    ///
+    /// Every axis, in index order
+    ///
+    pub const ALL: [Self; 4] = [Self::X, Self::Y, Self::Z, Self::W];
+
+    ///
+    /// Returns the same index `ax!` produces for this axis
+    ///
+    pub const fn as_usize(self) -> usize {
+        self as usize
+    }
+
+    ///
+    /// Returns the axis `ax!` maps to `index`, or `None` past _w_
+    ///
+    pub const fn from_usize(index: usize) -> Option<Self> {
+        match index {
+            0 => Some(Axis::X),
+            1 => Some(Axis::Y),
+            2 => Some(Axis::Z),
+            3 => Some(Axis::W),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Returns the identifier `ax!` takes for this axis, such as `"y"`, which is also how it displays
+    ///
+    /// ```
+    /// use axmac::Axis;
+    ///
+    /// assert_eq!(Axis::Z.name(), "z");
+    /// assert_eq!(Axis::W.to_string(), "w");
+    /// ```
+    ///
+    pub const fn name(self) -> &'static str {
+        match self {
+            Axis::X => "x",
+            Axis::Y => "y",
+            Axis::Z => "z",
+            Axis::W => "w",
+        }
+    }
+
+    ///
+    /// Iterates over every axis, in index order
+    ///
+    pub fn iter() -> core::array::IntoIter<Self, 4> {
+        Self::ALL.into_iter()
+    }
+
+    ///
+    /// Returns the unit vector along this axis, with one at its index and zero everywhere else
+    ///
+    /// See [`unit!`] for a version checked at compile time
+    ///
+    /// ```
+    /// use axmac::Axis;
+    ///
+    /// assert_eq!(Axis::Y.unit::<f32, 3>(), [0.0, 1.0, 0.0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If the axis is not less than `N`
+    ///
+    pub fn unit<T: Scalar, const N: usize>(self) -> [T; N] {
+        assert!(self.as_usize() < N, "axmac: a unit vector needs a component for its axis");
+        core::array::from_fn(|i| if i == self.as_usize() { T::ONE } else { T::ZERO })
+    }
+
+}
+
+impl core::fmt::Display for Axis {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl From<Axis> for usize {
+    fn from(axis: Axis) -> Self {
+        axis.as_usize()
+    }
+}
+
+impl TryFrom<usize> for Axis {
+    type Error = InvalidAxis;
+
+    fn try_from(index: usize) -> Result<Self, Self::Error> {
+        Self::from_usize(index).ok_or(InvalidAxis(index))
+    }
+}
+
+
+///
+/// An axis from any enabled vocabulary with an axis enum of its own, [`Axis`] and the enums of the `input`
+/// and `motion` features
+///
+/// The other vocabularies, such as `imu` and `rgba`, only name indices for `ax!` and have no variant here.
+/// New variants appear as vocabulary features are enabled, so matches on it need a wildcard arm. Code
+/// which only cares about _x_, _y_, _z_ and _w_ should match on [`Axis`] instead, which stays exhaustive
+///
+/// ```
+/// use axmac::{AnyAxis, Axis};
+///
+/// fn describe(axis: AnyAxis) -> &'static str {
+///     match axis {
+///         AnyAxis::Base(Axis::X) => "horizontal",
+///         AnyAxis::Base(_) => "spatial",
+///         _ => "other",
+///     }
+/// }
+///
+/// assert_eq!(describe(Axis::X.into()), "horizontal");
+/// assert_eq!(AnyAxis::from(Axis::Z).as_usize(), 2);
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum AnyAxis {
+    /// One of _x_, _y_, _z_ or _w_
+    Base(Axis),
+    /// A game controller axis
+    #[cfg(feature = "input")]
+    Gamepad(crate::GamepadAxis),
+    /// A CNC or printer axis
+    #[cfg(feature = "motion")]
+    Motion(crate::MotionAxis),
+}
+
+impl AnyAxis {
+
+    ///
+    /// Returns the index `ax!` produces for this axis within its vocabulary
+    ///
+    pub const fn as_usize(self) -> usize {
+        match self {
+            AnyAxis::Base(axis) => axis.as_usize(),
+            #[cfg(feature = "input")]
+            AnyAxis::Gamepad(axis) => axis.as_usize(),
+            #[cfg(feature = "motion")]
+            AnyAxis::Motion(axis) => axis.as_usize(),
+        }
+    }
+
+    ///
+    /// Returns the axis if it is one of _x_, _y_, _z_ or _w_
+    ///
+    pub const fn base(self) -> Option<Axis> {
+        match self {
+            AnyAxis::Base(axis) => Some(axis),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
+}
+
+impl From<Axis> for AnyAxis {
+    fn from(axis: Axis) -> Self {
+        AnyAxis::Base(axis)
+    }
+}
+
+#[cfg(feature = "input")]
+impl From<crate::GamepadAxis> for AnyAxis {
+    fn from(axis: crate::GamepadAxis) -> Self {
+        AnyAxis::Gamepad(axis)
+    }
+}
+
+#[cfg(feature = "motion")]
+impl From<crate::MotionAxis> for AnyAxis {
+    fn from(axis: crate::MotionAxis) -> Self {
+        AnyAxis::Motion(axis)
+    }
+}
+
+impl From<AnyAxis> for usize {
+    fn from(axis: AnyAxis) -> Self {
+        axis.as_usize()
+    }
+}
+
+
+///
+/// Creates the unit vector along an axis, with the element type and length inferred or given
+///
+/// An axis past the end of the vector fails to compile
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let up: [f32; 3] = unit!(y);
+/// assert_eq!(up, [0.0, 1.0, 0.0]);
+///
+/// assert_eq!(unit!(w; [u8; 4]), [0, 0, 0, 1]);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: a unit vector needs a component for its axis
+/// let v: [i32; 2] = unit!(z);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! unit {
+    ( $axis:ident ) => {
+        $crate::__private::Unit::<{ $crate::ax!($axis) }>::vector()
+    };
+    ( $axis:ident; [ $t:ty; $n:expr ] ) => {
+        $crate::__private::Unit::<{ $crate::ax!($axis) }>::vector::<$t, { $n }>()
+    };
+}
+
+
+///
+/// Matches a runtime [`Axis`] and expands the body once per axis, with the axis index bound to a `const`
+///
+/// The body is monomorphized for each axis, so kernels generic over a const axis get a specialized fast
+/// path while the caller keeps a runtime axis parameter
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Axis;
+///
+/// fn sum_along<const A: usize>(points: &[[f32; 4]]) -> f32 {
+///     points.iter().map(|p| p[A]).sum()
+/// }
+///
+/// let points = [[1.0, 2.0, 3.0, 4.0], [5.0, 6.0, 7.0, 8.0]];
+/// let axis = Axis::Z;
+/// assert_eq!(with_axis!(axis; |const A| sum_along::<A>(&points)), 10.0);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! with_axis {
+    // Block bodies are spliced in directly, so they share a scope with the const
+    ( $axis:expr; |const $a:ident| { $( $body:tt )* } ) => {
+        match $axis {
+            $crate::Axis::X => { const $a: usize = $crate::ax!(x); $( $body )* }
+            $crate::Axis::Y => { const $a: usize = $crate::ax!(y); $( $body )* }
+            $crate::Axis::Z => { const $a: usize = $crate::ax!(z); $( $body )* }
+            $crate::Axis::W => { const $a: usize = $crate::ax!(w); $( $body )* }
+        }
+    };
+    ( $axis:expr; |const $a:ident| $body:expr ) => {
+        match $axis {
+            $crate::Axis::X => { const $a: usize = $crate::ax!(x); $body }
+            $crate::Axis::Y => { const $a: usize = $crate::ax!(y); $body }
+            $crate::Axis::Z => { const $a: usize = $crate::ax!(z); $body }
+            $crate::Axis::W => { const $a: usize = $crate::ax!(w); $body }
+        }
+    };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn matches_ax() {
+        assert_eq!(Axis::X.as_usize(), ax!(x));
+        assert_eq!(Axis::Y.as_usize(), ax!(y));
+        assert_eq!(usize::from(Axis::Z), ax!(z));
+        assert_eq!(usize::from(Axis::W), ax!(w));
+        assert_eq!(Axis::W as usize + 1, Axis::COUNT);
+        assert_eq!(Axis::from_usize(ax!(z)), Some(Axis::Z));
+        assert_eq!(Axis::from_usize(Axis::COUNT), None);
+        assert_eq!(Axis::try_from(Axis::COUNT), Err(InvalidAxis(4)));
+        assert!(Axis::iter().map(usize::from).eq(0..Axis::COUNT));
+        assert_eq!(axs![@enum z; 2], [Axis::Z; 2]);
+    }
+
+    #[test]
+    fn unit_works() {
+        assert_eq!(Axis::X.unit::<i32, 2>(), [1, 0]);
+        assert_eq!(unit!(z; [f64; 4]), [0.0, 0.0, 1.0, 0.0]);
+        let v: [u16; 3] = unit!(y);
+        assert_eq!(v, [0, 1, 0]);
+    }
+
+    #[test]
+    fn with_axis_works() {
+        let v: [u8; 4] = [10, 20, 30, 40];
+        for axis in Axis::iter() {
+            let unit: [u8; 4] = with_axis!(axis; |const A| crate::__private::Unit::<A>::vector());
+            assert_eq!(with_axis!(axis; |const A| { v[A] }), v[axis.as_usize()]);
+            assert_eq!(unit, axis.unit());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac:")]
+    fn unit_out_of_range_panics() {
+        let _ = Axis::W.unit::<f32, 3>();
+    }
+
+    #[test]
+    fn any_axis_works() {
+        let axis = AnyAxis::from(Axis::Y);
+        assert_eq!(axis.base(), Some(Axis::Y));
+        assert_eq!(usize::from(axis), ax!(y));
+    }
+
+    #[cfg(feature = "motion")]
+    #[test]
+    fn any_axis_vocabularies_work() {
+        let axis = AnyAxis::from(crate::MotionAxis::E);
+        assert_eq!(axis.base(), None);
+        assert_eq!(axis.as_usize(), ax!(e));
+    }
+
+}
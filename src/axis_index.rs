@@ -0,0 +1,191 @@
+//!
+//! Reading and writing components by [`Axis`] through a trait
+//!
+
+use crate::Axis;
+
+
+///
+/// Values whose components can be read and written by [`Axis`]
+///
+/// Implemented for arrays, slices and tuples of up to four components of one type, so generic code can
+/// name components the same way whatever holds them
+///
+/// ```
+/// use axmac::{Axis, AxisIndex};
+///
+/// fn flip_y<V: AxisIndex<Item = f32>>(v: &mut V) {
+///     *v.y_mut() = -*v.y();
+/// }
+///
+/// let mut a = [1.0, 2.0, 3.0];
+/// let mut t = (1.0, 2.0);
+/// flip_y(&mut a);
+/// flip_y(&mut t);
+/// assert_eq!(a, [1.0, -2.0, 3.0]);
+/// assert_eq!(t, (1.0, -2.0));
+///
+/// assert_eq!(a.get_axis(Axis::W), None);
+/// assert_eq!(a[..2].axis(Axis::X), &1.0);
+/// ```
+///
+pub trait AxisIndex {
+    /// The type of the components
+    type Item;
+
+    ///
+    /// Returns the component of `axis`, or `None` if there is no such component
+    ///
+    fn get_axis(&self, axis: Axis) -> Option<&Self::Item>;
+
+    ///
+    /// Returns the component of `axis` mutably, or `None` if there is no such component
+    ///
+    fn get_axis_mut(&mut self, axis: Axis) -> Option<&mut Self::Item>;
+
+    ///
+    /// Returns the component of `axis`
+    ///
+    /// # Panics
+    ///
+    /// If there is no such component
+    ///
+    fn axis(&self, axis: Axis) -> &Self::Item {
+        self.get_axis(axis).expect("axmac: value has no component for the axis")
+    }
+
+    ///
+    /// Returns the component of `axis` mutably
+    ///
+    /// # Panics
+    ///
+    /// If there is no such component
+    ///
+    fn axis_mut(&mut self, axis: Axis) -> &mut Self::Item {
+        self.get_axis_mut(axis).expect("axmac: value has no component for the axis")
+    }
+
+    /// Returns the x component, panicking if there is none
+    fn x(&self) -> &Self::Item {
+        self.axis(Axis::X)
+    }
+
+    /// Returns the y component, panicking if there is none
+    fn y(&self) -> &Self::Item {
+        self.axis(Axis::Y)
+    }
+
+    /// Returns the z component, panicking if there is none
+    fn z(&self) -> &Self::Item {
+        self.axis(Axis::Z)
+    }
+
+    /// Returns the w component, panicking if there is none
+    fn w(&self) -> &Self::Item {
+        self.axis(Axis::W)
+    }
+
+    /// Returns the x component mutably, panicking if there is none
+    fn x_mut(&mut self) -> &mut Self::Item {
+        self.axis_mut(Axis::X)
+    }
+
+    /// Returns the y component mutably, panicking if there is none
+    fn y_mut(&mut self) -> &mut Self::Item {
+        self.axis_mut(Axis::Y)
+    }
+
+    /// Returns the z component mutably, panicking if there is none
+    fn z_mut(&mut self) -> &mut Self::Item {
+        self.axis_mut(Axis::Z)
+    }
+
+    /// Returns the w component mutably, panicking if there is none
+    fn w_mut(&mut self) -> &mut Self::Item {
+        self.axis_mut(Axis::W)
+    }
+}
+
+impl<T> AxisIndex for [T] {
+    type Item = T;
+
+    fn get_axis(&self, axis: Axis) -> Option<&T> {
+        self.get(axis.as_usize())
+    }
+
+    fn get_axis_mut(&mut self, axis: Axis) -> Option<&mut T> {
+        self.get_mut(axis.as_usize())
+    }
+}
+
+impl<T, const N: usize> AxisIndex for [T; N] {
+    type Item = T;
+
+    fn get_axis(&self, axis: Axis) -> Option<&T> {
+        self.get(axis.as_usize())
+    }
+
+    fn get_axis_mut(&mut self, axis: Axis) -> Option<&mut T> {
+        self.get_mut(axis.as_usize())
+    }
+}
+
+// Tuples of one component type, matched field by field since tuples cannot be indexed at runtime
+macro_rules! impl_tuples {
+    ( $( ( $( $field:tt => $axis:ident ),+ ) );+ $(;)? ) => {
+        $(
+            impl<T> AxisIndex for ( $( impl_tuples!(@item $field), )+ ) {
+                type Item = T;
+
+                fn get_axis(&self, axis: Axis) -> Option<&T> {
+                    match axis {
+                        $( Axis::$axis => Some(&self.$field), )+
+                        #[allow(unreachable_patterns)]
+                        _ => None,
+                    }
+                }
+
+                fn get_axis_mut(&mut self, axis: Axis) -> Option<&mut T> {
+                    match axis {
+                        $( Axis::$axis => Some(&mut self.$field), )+
+                        #[allow(unreachable_patterns)]
+                        _ => None,
+                    }
+                }
+            }
+        )+
+    };
+    ( @item $field:tt ) => { T };
+}
+
+impl_tuples!(
+    (0 => X);
+    (0 => X, 1 => Y);
+    (0 => X, 1 => Y, 2 => Z);
+    (0 => X, 1 => Y, 2 => Z, 3 => W);
+);
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn tuples_work() {
+        let mut t = (1, 2, 3, 4);
+        assert_eq!(*t.w(), 4);
+        *t.axis_mut(Axis::Z) += 10;
+        assert_eq!(t, (1, 2, 13, 4));
+        assert_eq!((5,).get_axis(Axis::Y), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac:")]
+    fn missing_axis_panics() {
+        let v: &[u8] = &[1, 2];
+        let _ = v.z();
+    }
+
+}
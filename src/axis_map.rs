@@ -0,0 +1,286 @@
+//!
+//! Dense per-axis storage
+//!
+
+use core::ops::{Index, IndexMut};
+
+use crate::Axis;
+
+
+///
+/// Holds exactly one value per axis, addressed by the indices `ax!` produces
+///
+/// Where [`AxisBundle`](crate::AxisBundle) lets values be moved out one by one, a map always holds a value
+/// for every axis, which suits per-axis parameters and results. `N` defaults to the four axes _x_, _y_, _z_
+/// and _w_
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{Axis, AxisMap};
+///
+/// let mut scale: AxisMap<f32, 3> = AxisMap::splat(1.0);
+/// scale[ax!(y)] = 2.0;
+///
+/// assert_eq!(scale[Axis::Y], 2.0);
+/// assert_eq!(scale.map(|s| s * 10.0).into_array(), [10.0, 20.0, 10.0]);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// Indexing panics if the axis is not less than `N`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AxisMap<T, const N: usize = 4>([T; N]);
+
+impl<T, const N: usize> AxisMap<T, N> {
+
+    ///
+    /// Creates a map from one value per axis
+    ///
+    pub const fn new(values: [T; N]) -> Self {
+        Self(values)
+    }
+
+    ///
+    /// Creates a map by calling `f` with the index of every axis
+    ///
+    pub fn from_fn<F: FnMut(usize) -> T>(f: F) -> Self {
+        Self(core::array::from_fn(f))
+    }
+
+    ///
+    /// Creates a map by calling `f` with the name of every axis, `x` to `w`, or its index past _w_
+    ///
+    /// Suits reading per-axis parameters from anything keyed by name, such as configuration files or the
+    /// environment
+    ///
+    /// ```
+    /// use axmac::AxisMap;
+    ///
+    /// let config = [("x", 2.0), ("z", 0.5)];
+    /// let scale: AxisMap<f32, 3> = AxisMap::from_key_fn(|name| {
+    ///     config.iter().find(|(key, _)| *key == name).map_or(1.0, |(_, value)| *value)
+    /// });
+    /// assert_eq!(scale.into_array(), [2.0, 1.0, 0.5]);
+    /// ```
+    ///
+    pub fn from_key_fn<F: FnMut(&str) -> T>(mut f: F) -> Self {
+        Self::from_fn(|axis| with_name(axis, &mut f))
+    }
+
+    ///
+    /// Creates a map by calling `f` with the name of every axis as [`from_key_fn`](Self::from_key_fn)
+    /// does, stopping at the first error
+    ///
+    /// ```
+    /// use axmac::AxisMap;
+    ///
+    /// let parsed: Result<AxisMap<u32, 2>, _> = AxisMap::try_from_key_fn(|name| match name {
+    ///     "x" => "640".parse(),
+    ///     _ => "480".parse(),
+    /// });
+    /// assert_eq!(parsed.map(AxisMap::into_array), Ok([640, 480]));
+    /// ```
+    ///
+    pub fn try_from_key_fn<E, F: FnMut(&str) -> Result<T, E>>(mut f: F) -> Result<Self, E> {
+        let mut values = [const { None }; N];
+        for (axis, value) in values.iter_mut().enumerate() {
+            *value = Some(with_name(axis, &mut f)?);
+        }
+        Ok(Self(values.map(|value| value.expect("every axis was given a value"))))
+    }
+
+    ///
+    /// Returns the values as an array indexed by axis
+    ///
+    pub const fn as_array(&self) -> &[T; N] {
+        &self.0
+    }
+
+    ///
+    /// Returns the values as a mutable array indexed by axis
+    ///
+    pub const fn as_mut_array(&mut self) -> &mut [T; N] {
+        &mut self.0
+    }
+
+    ///
+    /// Consumes the map, returning the values as an array indexed by axis
+    ///
+    pub fn into_array(self) -> [T; N] {
+        self.0
+    }
+
+    ///
+    /// Returns the value of `axis`, or `None` if `axis` is not less than `N`
+    ///
+    pub const fn get(&self, axis: usize) -> Option<&T> {
+        if axis < N { Some(&self.0[axis]) } else { None }
+    }
+
+    ///
+    /// Returns the value of `axis` mutably, or `None` if `axis` is not less than `N`
+    ///
+    pub const fn get_mut(&mut self, axis: usize) -> Option<&mut T> {
+        if axis < N { Some(&mut self.0[axis]) } else { None }
+    }
+
+    ///
+    /// Applies `f` to the value of every axis
+    ///
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> AxisMap<U, N> {
+        AxisMap(self.0.map(f))
+    }
+
+    ///
+    /// Iterates over every axis index together with its value
+    ///
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (usize, &T)> + ExactSizeIterator {
+        self.0.iter().enumerate()
+    }
+
+    ///
+    /// Iterates mutably over every axis index together with its value
+    ///
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = (usize, &mut T)> + ExactSizeIterator {
+        self.0.iter_mut().enumerate()
+    }
+
+}
+
+#[cfg(feature = "std")]
+impl<const N: usize> AxisMap<Option<std::string::String>, N> {
+
+    ///
+    /// Reads the environment variable of every axis, named by `prefix` and the axis name in upper case,
+    /// so a prefix of `SCALE_` reads `SCALE_X`, `SCALE_Y` and so on
+    ///
+    /// Axes whose variable is unset or not unicode hold `None`. Axes past _w_ are named by index
+    ///
+    /// ```
+    /// use axmac::AxisMap;
+    ///
+    /// // With AXMAC_DOC_SCALE_Y=2 set, and the rest unset
+    /// # std::env::set_var("AXMAC_DOC_SCALE_Y", "2");
+    /// let scale = AxisMap::<_, 3>::from_env("AXMAC_DOC_SCALE_")
+    ///     .map(|value| value.and_then(|v| v.parse().ok()).unwrap_or(1.0));
+    /// assert_eq!(scale.into_array(), [1.0, 2.0, 1.0]);
+    /// ```
+    ///
+    pub fn from_env(prefix: &str) -> Self {
+        Self::from_key_fn(|name| std::env::var(std::format!("{}{}", prefix, name.to_uppercase())).ok())
+    }
+
+}
+
+impl<T: Clone, const N: usize> AxisMap<T, N> {
+
+    ///
+    /// Creates a map holding `value` for every axis
+    ///
+    pub fn splat(value: T) -> Self {
+        Self::from_fn(|_| value.clone())
+    }
+
+}
+
+impl<T: Default, const N: usize> Default for AxisMap<T, N> {
+    fn default() -> Self {
+        Self::from_fn(|_| T::default())
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for AxisMap<T, N> {
+    fn from(values: [T; N]) -> Self {
+        Self(values)
+    }
+}
+
+impl<T, const N: usize> From<AxisMap<T, N>> for [T; N] {
+    fn from(map: AxisMap<T, N>) -> Self {
+        map.0
+    }
+}
+
+impl<T, const N: usize> Index<usize> for AxisMap<T, N> {
+    type Output = T;
+
+    fn index(&self, axis: usize) -> &T {
+        &self.0[axis]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for AxisMap<T, N> {
+    fn index_mut(&mut self, axis: usize) -> &mut T {
+        &mut self.0[axis]
+    }
+}
+
+impl<T, const N: usize> Index<Axis> for AxisMap<T, N> {
+    type Output = T;
+
+    fn index(&self, axis: Axis) -> &T {
+        &self.0[axis.as_usize()]
+    }
+}
+
+impl<T, const N: usize> IndexMut<Axis> for AxisMap<T, N> {
+    fn index_mut(&mut self, axis: Axis) -> &mut T {
+        &mut self.0[axis.as_usize()]
+    }
+}
+
+// Calls `f` with the name of `axis`, spelling out the index of axes past w
+fn with_name<R, F: FnMut(&str) -> R>(axis: usize, f: &mut F) -> R {
+    if let Some(name) = Axis::from_usize(axis).map(Axis::name) {
+        return f(name);
+    }
+    let mut digits = [0; 20];
+    let mut start = digits.len();
+    let mut rest = axis;
+    loop {
+        start -= 1;
+        digits[start] = b'0' + (rest % 10) as u8;
+        rest /= 10;
+        if rest == 0 {
+            break;
+        }
+    }
+    f(core::str::from_utf8(&digits[start..]).expect("digits are ascii"))
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn indexing_works() {
+        let mut map = AxisMap::from_fn(|axis| axis * 10);
+        assert_eq!(map[ax!(z)], 20);
+        map[Axis::W] += 1;
+        assert_eq!(map.into_array(), [0, 10, 20, 31]);
+        assert_eq!(map.get(ax!(w) + 1), None);
+    }
+
+    #[test]
+    fn iter_works() {
+        let map: AxisMap<char, 2> = ['a', 'b'].into();
+        let pairs: std::vec::Vec<_> = map.iter().collect();
+        assert_eq!(pairs, [(0, &'a'), (1, &'b')]);
+        assert_eq!(AxisMap::<u8>::default().as_array(), &[0; 4]);
+    }
+
+    #[test]
+    fn keys_name_axes() {
+        let names = AxisMap::<_, 12>::from_key_fn(|name| name.len() * 100 + name.as_bytes()[0] as usize);
+        assert_eq!(names[ax!(w)], 100 + b'w' as usize);
+        assert_eq!(names[11], 200 + b'1' as usize);
+        let failed = AxisMap::<u8, 3>::try_from_key_fn(|name| if name == "y" { Err(name.len()) } else { Ok(0) });
+        assert_eq!(failed, Err(1));
+    }
+
+}
@@ -0,0 +1,356 @@
+//!
+//! Compact sets of axes
+//!
+
+use core::fmt;
+use core::iter::FusedIterator;
+
+use crate::{Axis, InvalidAxis};
+
+
+///
+/// A set of up to eight axes, stored as a bitmask where bit `i` is axis `i`
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::AxisSet;
+///
+/// let set = AxisSet::from_axes(&axs![x, z]);
+/// assert!(set.contains(ax!(z)));
+/// assert!(!set.contains(ax!(y)));
+/// assert_eq!(set.len(), 2);
+///
+/// let mut iter = set.iter();
+/// assert_eq!(iter.next(), Some(ax!(x)));
+/// assert_eq!(iter.next(), Some(ax!(z)));
+/// assert_eq!(iter.next(), None);
+/// # }
+/// ```
+///
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AxisSet(u8);
+
+impl AxisSet {
+
+    ///
+    /// The largest number of axes a set can hold
+    ///
+    pub const CAPACITY: usize = 8;
+
+    ///
+    /// The set holding no axes
+    ///
+    pub const EMPTY: Self = Self(0);
+
+    ///
+    /// Returns the set of the first `n` axes
+    ///
+    /// # Panics
+    ///
+    /// If `n` is greater than [`AxisSet::CAPACITY`]
+    ///
+    pub const fn first(n: usize) -> Self {
+        assert!(n <= Self::CAPACITY, "axmac: AxisSet holds at most 8 axes");
+        Self(((1u16 << n) - 1) as u8)
+    }
+
+    ///
+    /// Returns the set holding every axis in `axes`
+    ///
+    /// # Panics
+    ///
+    /// If any axis is not less than [`AxisSet::CAPACITY`]
+    ///
+    pub const fn from_axes(axes: &[usize]) -> Self {
+        let mut set = Self::EMPTY;
+        let mut i = 0;
+        while i < axes.len() {
+            set = set.with(axes[i]);
+            i += 1;
+        }
+        set
+    }
+
+    ///
+    /// Returns the bitmask backing the set
+    ///
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    ///
+    /// Creates a set from a bitmask, in which bit `i` marks axis `i`
+    ///
+    pub const fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    ///
+    /// Returns a copy of the set with `axis` added
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not less than [`AxisSet::CAPACITY`]
+    ///
+    pub const fn with(self, axis: usize) -> Self {
+        assert!(axis < Self::CAPACITY, "axmac: AxisSet holds at most 8 axes");
+        Self(self.0 | 1 << axis)
+    }
+
+    ///
+    /// Returns a copy of the set with `axis` removed
+    ///
+    pub const fn without(self, axis: usize) -> Self {
+        if axis < Self::CAPACITY { Self(self.0 & !(1 << axis)) } else { self }
+    }
+
+    ///
+    /// Adds `axis` to the set, returning whether it was newly added
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not less than [`AxisSet::CAPACITY`]
+    ///
+    pub fn insert(&mut self, axis: usize) -> bool {
+        let added = !self.contains(axis);
+        *self = self.with(axis);
+        added
+    }
+
+    ///
+    /// Removes `axis` from the set, returning whether it was present
+    ///
+    pub fn remove(&mut self, axis: usize) -> bool {
+        let present = self.contains(axis);
+        *self = self.without(axis);
+        present
+    }
+
+    ///
+    /// Returns whether `axis` is in the set
+    ///
+    pub const fn contains(self, axis: usize) -> bool {
+        axis < Self::CAPACITY && self.0 & 1 << axis != 0
+    }
+
+    ///
+    /// Returns whether the typed `axis` is in the set
+    ///
+    pub const fn contains_axis(self, axis: Axis) -> bool {
+        self.contains(axis.as_usize())
+    }
+
+    ///
+    /// Returns the axes in either set
+    ///
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    ///
+    /// Returns the axes in both sets
+    ///
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    ///
+    /// Returns the axes in this set but not `other`
+    ///
+    pub const fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    ///
+    /// Returns the number of axes in the set
+    ///
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    ///
+    /// Returns whether the set holds no axes
+    ///
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    ///
+    /// Iterates over the axes in the set in ascending order
+    ///
+    pub const fn iter(self) -> AxisSetIter {
+        AxisSetIter(self.0)
+    }
+
+}
+
+impl fmt::Debug for AxisSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl From<u8> for AxisSet {
+    fn from(bits: u8) -> Self {
+        Self::from_bits(bits)
+    }
+}
+
+impl From<AxisSet> for u8 {
+    fn from(set: AxisSet) -> Self {
+        set.bits()
+    }
+}
+
+impl<const N: usize> TryFrom<[usize; N]> for AxisSet {
+    type Error = InvalidAxis;
+
+    ///
+    /// Collects plain axes, as `axs!` gives them, checking each is less than [`AxisSet::CAPACITY`]
+    ///
+    /// ```
+    /// # #[macro_use] extern crate axmac; fn main() {
+    /// use axmac::{AxisSet, InvalidAxis};
+    ///
+    /// assert_eq!(AxisSet::try_from(axs![x, z]), Ok(AxisSet::from_axes(&[0, 2])));
+    /// assert_eq!(AxisSet::try_from([1, 8]), Err(InvalidAxis(8)));
+    /// # }
+    /// ```
+    ///
+    fn try_from(axes: [usize; N]) -> Result<Self, InvalidAxis> {
+        match axes.iter().find(|&&axis| axis >= Self::CAPACITY) {
+            Some(&axis) => Err(InvalidAxis(axis)),
+            None => Ok(Self::from_axes(&axes)),
+        }
+    }
+}
+
+impl IntoIterator for AxisSet {
+    type Item = usize;
+    type IntoIter = AxisSetIter;
+
+    fn into_iter(self) -> AxisSetIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<usize> for AxisSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::EMPTY, Self::with)
+    }
+}
+
+
+///
+/// An [`AxisSet`] used to filter or lock axes, such as the constrained axes of a physics body
+///
+pub type AxisMask = AxisSet;
+
+
+///
+/// Converts a list of axis identifiers into a `u8` bitmask, in which bit `i` marks axis `i`
+///
+/// The mask is a constant, and converts into an [`AxisMask`] for typed use
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{Axis, AxisMask};
+///
+/// const LOCKED: u8 = axm!(x, z);
+/// assert_eq!(LOCKED, 0b0101);
+///
+/// let mask = AxisMask::from(LOCKED);
+/// assert!(mask.contains_axis(Axis::Z));
+/// assert!(!mask.contains_axis(Axis::Y));
+/// assert_eq!(u8::from(mask.union(axm!(y).into())), 0b0111);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! axm {
+    ( $( $axis:ident ),* $(,)? ) => {
+        const { $crate::AxisSet::from_axes(&[ $( $crate::ax!($axis) ),* ]).bits() }
+    };
+}
+
+
+///
+/// Iterator over the axes of an [`AxisSet`], in ascending order
+///
+#[derive(Clone, Debug)]
+pub struct AxisSetIter(u8);
+
+impl Iterator for AxisSetIter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.0 == 0 {
+            return None;
+        }
+        let axis = self.0.trailing_zeros() as usize;
+        self.0 &= self.0 - 1;
+        Some(axis)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for AxisSetIter {}
+
+impl FusedIterator for AxisSetIter {}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn set_operations_work() {
+        let a = AxisSet::from_axes(&axs![x, y]);
+        let b = AxisSet::from_axes(&axs![y, z]);
+        assert_eq!(a.union(b), AxisSet::first(3));
+        assert_eq!(a.intersection(b), AxisSet::EMPTY.with(ax!(y)));
+        assert_eq!(a.difference(b).bits(), 0b1);
+        assert_eq!(AxisSet::first(8).len(), 8);
+    }
+
+    #[test]
+    fn insert_and_remove_work() {
+        let mut set = AxisSet::EMPTY;
+        assert!(set.insert(ax!(w)));
+        assert!(!set.insert(ax!(w)));
+        assert!(set.remove(ax!(w)));
+        assert!(!set.remove(ax!(w)));
+        assert!(set.is_empty());
+        assert!(!set.contains(100));
+    }
+
+    #[test]
+    fn iter_works() {
+        let set: AxisSet = [3, 0, 7].into_iter().collect();
+        let axes: std::vec::Vec<_> = set.iter().collect();
+        assert_eq!(axes, [0, 3, 7]);
+        assert_eq!(set.iter().len(), 3);
+    }
+
+    #[test]
+    fn axm_works() {
+        assert_eq!(axm!(), 0);
+        assert_eq!(axm!(w, x, w), 0b1001);
+        let mask = AxisMask::from(axm!(y, z));
+        assert_eq!(mask.iter().collect::<std::vec::Vec<_>>(), axs![y, z]);
+        assert_eq!(u8::from(mask), 0b0110);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range_panics() {
+        let _ = AxisSet::EMPTY.with(8);
+    }
+
+}
@@ -0,0 +1,202 @@
+//!
+//! Representative shaped workloads for benchmarking kernels across axis orders
+//!
+//! Benchmarks of grid kernels usually want the same data walked along each axis, laid out in each order,
+//! and stored interleaved or planar. The generators here build those workloads from the crate's own types,
+//! with contents that are deterministic so runs stay comparable. They carry no timing of their own, and plug
+//! into whichever harness measures them
+//!
+//! ```
+//! use axmac::bench::{axis_orders, filled, strided_walk};
+//! use axmac::{Axis, Shape};
+//!
+//! let shape = Shape::new([64, 64, 8]);
+//! let volume: Vec<f32> = filled(&shape);
+//!
+//! // Summing along every axis in turn, where only the x walk is unit stride
+//! for axis in [Axis::X, Axis::Y, Axis::Z] {
+//!     let sum: f32 = strided_walk(&shape, axis).map(|i| volume[i]).sum();
+//!     assert!(sum > 0.0);
+//! }
+//!
+//! // Every layout of the same extents, each a separate case of the benchmark
+//! assert_eq!(axis_orders::<3>().map(|order| shape.dense_in_order(order)).count(), 6);
+//! ```
+//!
+
+use core::iter::FusedIterator;
+
+use alloc::vec::Vec;
+
+use crate::{Axis, AxisPriority, NdIndex, Shape};
+
+
+///
+/// Iterator over the flat indices of every position of a shape, walking one axis fastest
+///
+/// Created by [`strided_walk`]
+///
+#[derive(Clone, Debug)]
+pub struct Walk<const N: usize> {
+    shape: Shape<N>,
+    coords: NdIndex<usize, N>,
+}
+
+impl<const N: usize> Iterator for Walk<N> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        self.coords.next().map(|coord| self.shape.flat_index_unchecked(&coord))
+    }
+}
+
+impl<const N: usize> FusedIterator for Walk<N> {}
+
+///
+/// Walks every position of `shape` with `axis` innermost and the other axes in ascending order, yielding
+/// flat indices
+///
+/// Walking the contiguous axis of a shape measures the sequential case, and walking any other the strided
+/// case, with the same number of accesses
+///
+/// # Panics
+///
+/// If `axis` is not less than `N`
+///
+pub fn strided_walk<const N: usize>(shape: &Shape<N>, axis: Axis) -> Walk<N> {
+    let axis = axis.as_usize();
+    assert!(axis < N, "axmac: axis is past the last dimension of the shape");
+    let order = core::array::from_fn(|i| match i {
+        0 => axis,
+        i if i <= axis => i - 1,
+        i => i,
+    });
+    let coords = NdIndex::new([0; N], *shape.extents()).with_priority(AxisPriority::new(order));
+    Walk { shape: *shape, coords }
+}
+
+
+///
+/// Iterator over every order of `N` axes, created by [`axis_orders`]
+///
+#[derive(Clone, Debug)]
+pub struct AxisOrders<const N: usize> {
+    next: Option<[usize; N]>,
+}
+
+impl<const N: usize> Iterator for AxisOrders<N> {
+    type Item = AxisPriority<N>;
+
+    fn next(&mut self) -> Option<AxisPriority<N>> {
+        let current = self.next?;
+        let mut next = current;
+        // Steps to the next permutation in lexicographic order, ending after the descending one
+        self.next = match (1..N).rev().find(|&i| next[i - 1] < next[i]) {
+            Some(i) => {
+                let j = (i..N).rev().find(|&j| next[j] > next[i - 1]).unwrap_or(i);
+                next.swap(i - 1, j);
+                next[i..].reverse();
+                Some(next)
+            }
+            None => None,
+        };
+        Some(AxisPriority::new(current))
+    }
+}
+
+impl<const N: usize> FusedIterator for AxisOrders<N> {}
+
+///
+/// Iterates over every order of `N` axes, innermost axis first, from x fastest to x slowest
+///
+/// There are `N!` of them, 24 for four axes
+///
+pub fn axis_orders<const N: usize>() -> AxisOrders<N> {
+    AxisOrders { next: Some(core::array::from_fn(|i| i)) }
+}
+
+
+///
+/// How the components of a list of records are stored, for [`records`]
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Interleave {
+    /// Array of structures, storing the components of each record next to each other
+    Aos,
+    /// Structure of arrays, storing each component of every record in a plane of its own
+    Soa,
+}
+
+///
+/// Builds `count` records of `components` values each, stored as `layout` says, and the shape indexing them
+/// as `[component, record]`
+///
+/// The same record holds the same values under either layout, so a kernel run over both compares the two
+/// layouts alone
+///
+/// ```
+/// use axmac::bench::{records, Interleave};
+///
+/// let (aos_shape, aos) = records::<u32>(3, 1000, Interleave::Aos);
+/// let (soa_shape, soa) = records::<u32>(3, 1000, Interleave::Soa);
+/// let at = |shape: &axmac::Shape<2>, buf: &[u32], c, r| buf[shape.flat_index(&[c, r]).unwrap()];
+/// assert_eq!(at(&aos_shape, &aos, 2, 17), at(&soa_shape, &soa, 2, 17));
+/// assert_eq!(soa_shape.stride(1), 1);
+/// ```
+///
+pub fn records<T: From<u16>>(components: usize, count: usize, layout: Interleave) -> (Shape<2>, Vec<T>) {
+    let shape = match layout {
+        Interleave::Aos => Shape::new([components, count]),
+        Interleave::Soa => Shape::col_major([components, count]),
+    };
+    (shape, filled(&shape))
+}
+
+///
+/// Returns a buffer holding `shape`, with deterministic values which depend on the coordinate of each
+/// position alone, so every layout of the same extents holds the same grid
+///
+/// Values are scattered over `1..=u16::MAX`, so sums neither vanish nor hint at the walk order. Offsets no
+/// position maps to are zero
+///
+pub fn filled<T: From<u16>, const N: usize>(shape: &Shape<N>) -> Vec<T> {
+    let mut buf: Vec<T> = (0..shape.required_len()).map(|_| T::from(0)).collect();
+    for coord in shape.coords() {
+        let key = coord.iter().fold(0u64, |key, &c| key.wrapping_mul(0x9e37_79b9).wrapping_add(c as u64 + 1));
+        let mixed = (key ^ (key >> 29)).wrapping_mul(0xbf58_476d_1ce4_e5b9) >> 48;
+        buf[shape.flat_index_unchecked(&coord)] = T::from((mixed % u16::MAX as u64) as u16 + 1);
+    }
+    buf
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn walks_cover_every_position() {
+        let shape = Shape::new([3, 4, 2]);
+        let along_y: Vec<usize> = strided_walk(&shape, Axis::Y).take(5).collect();
+        assert_eq!(along_y, [0, 3, 6, 9, 1]);
+        let mut seen: Vec<usize> = strided_walk(&shape, Axis::Z).collect();
+        seen.sort_unstable();
+        assert!(seen.iter().copied().eq(0..24));
+    }
+
+    #[test]
+    fn orders_are_distinct_and_complete() {
+        let orders: Vec<[usize; 3]> = axis_orders::<3>().map(|o| o.inner_to_outer()).collect();
+        assert_eq!(orders, [[0, 1, 2], [0, 2, 1], [1, 0, 2], [1, 2, 0], [2, 0, 1], [2, 1, 0]]);
+        assert_eq!(axis_orders::<0>().count(), 1);
+        let shape = Shape::new([2, 3]);
+        assert_eq!(filled::<u16, 2>(&shape), {
+            let planar = shape.dense_in_order(AxisPriority::LAST_FASTEST);
+            let buf: Vec<u16> = filled(&planar);
+            shape.coords().map(|c| buf[planar.flat_index_unchecked(&c)]).collect::<Vec<_>>()
+        });
+    }
+
+}
@@ -0,0 +1,158 @@
+//!
+//! Per-axis ownership of peripherals and other non-`Copy` values
+//!
+
+
+///
+/// Holds at most one value per axis, each of which can be moved out individually
+///
+/// Designed for embedded code where every motion axis owns its own driver, pin or PWM channel. Values are
+/// addressed by the indices `ax!` produces, so a bundle works with any of the axis vocabularies
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::AxisBundle;
+///
+/// struct StepPin(u8);
+///
+/// let mut pins = AxisBundle::new([StepPin(2), StepPin(3), StepPin(4)]);
+///
+/// // Hand the z pin to its own task, keeping the others
+/// let z = pins.take(ax!(z)).unwrap();
+/// assert_eq!(z.0, 4);
+/// assert!(pins.take(ax!(z)).is_none());
+///
+/// let [x, y, z] = pins.split();
+/// assert_eq!(x.map(|p| p.0), Some(2));
+/// assert_eq!(y.map(|p| p.0), Some(3));
+/// assert!(z.is_none());
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// All methods taking an `axis` panic if it is not less than `N`
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AxisBundle<T, const N: usize> {
+    slots: [Option<T>; N],
+}
+
+impl<T, const N: usize> AxisBundle<T, N> {
+
+    ///
+    /// Creates a bundle holding one value for every axis
+    ///
+    pub fn new(values: [T; N]) -> Self {
+        Self { slots: values.map(Some) }
+    }
+
+    ///
+    /// Creates a bundle holding no values
+    ///
+    pub const fn empty() -> Self {
+        Self { slots: [const { None }; N] }
+    }
+
+    ///
+    /// Stores `value` for `axis`, returning the value previously stored there
+    ///
+    pub fn insert(&mut self, axis: usize, value: T) -> Option<T> {
+        self.slots[axis].replace(value)
+    }
+
+    ///
+    /// Moves the value of `axis` out of the bundle
+    ///
+    pub fn take(&mut self, axis: usize) -> Option<T> {
+        self.slots[axis].take()
+    }
+
+    ///
+    /// Returns the value of `axis`, if it has not been taken
+    ///
+    pub fn get(&self, axis: usize) -> Option<&T> {
+        self.slots[axis].as_ref()
+    }
+
+    ///
+    /// Returns the value of `axis` mutably, if it has not been taken
+    ///
+    pub fn get_mut(&mut self, axis: usize) -> Option<&mut T> {
+        self.slots[axis].as_mut()
+    }
+
+    ///
+    /// Returns whether `axis` currently holds a value
+    ///
+    pub fn contains(&self, axis: usize) -> bool {
+        self.slots[axis].is_some()
+    }
+
+    ///
+    /// Consumes the bundle, returning the value of every axis in index order
+    ///
+    pub fn split(self) -> [Option<T>; N] {
+        self.slots
+    }
+
+    ///
+    /// Iterates over the axes still holding a value, alongside their values
+    ///
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &T)> {
+        self.slots.iter().enumerate().filter_map(|(axis, slot)| slot.as_ref().map(|v| (axis, v)))
+    }
+
+    ///
+    /// Iterates mutably over the axes still holding a value, alongside their values
+    ///
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (usize, &mut T)> {
+        self.slots.iter_mut().enumerate().filter_map(|(axis, slot)| slot.as_mut().map(|v| (axis, v)))
+    }
+
+}
+
+impl<T, const N: usize> Default for AxisBundle<T, N> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for AxisBundle<T, N> {
+    fn from(values: [T; N]) -> Self {
+        Self::new(values)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn take_and_insert_work() {
+        let mut bundle = AxisBundle::<u8, 4>::empty();
+        assert_eq!(bundle.insert(ax!(w), 7), None);
+        assert_eq!(bundle.insert(ax!(w), 8), Some(7));
+        assert!(bundle.contains(ax!(w)));
+        assert!(!bundle.contains(ax!(x)));
+        assert_eq!(bundle.take(ax!(w)), Some(8));
+        assert_eq!(bundle.get(ax!(w)), None);
+    }
+
+    #[test]
+    fn iter_skips_taken() {
+        let mut bundle = AxisBundle::new([10, 20, 30]);
+        bundle.take(ax!(y));
+        for (_, v) in bundle.iter_mut() {
+            *v += 1;
+        }
+        let mut iter = bundle.iter();
+        assert_eq!(iter.next(), Some((ax!(x), &11)));
+        assert_eq!(iter.next(), Some((ax!(z), &31)));
+        assert_eq!(iter.next(), None);
+    }
+
+}
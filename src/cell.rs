@@ -0,0 +1,148 @@
+//!
+//! Spatial hashing of integer grid cells
+//!
+
+
+// Per axis multipliers of the spatial hash of Teschner et al., with a fourth prime for w
+const PRIMES: [u64; 4] = [73_856_093, 19_349_663, 83_492_791, 25_165_843];
+
+
+///
+/// The integer coordinates of a cell in a uniform grid, hashed for broadphase structures
+///
+/// [`spatial_hash`](Self::spatial_hash) multiplies each coordinate by a large prime and xors the results,
+/// the standard hash for uniform grids. Through [`Hash`](core::hash::Hash) the key also works in any hash
+/// map, hashing the coordinates themselves. [`cell_hash!`] builds and hashes a key from axis-named
+/// coordinates given in any order
+///
+/// ```
+/// use axmac::CellKey;
+///
+/// let key = CellKey::new([3, -1, 7]);
+/// assert_eq!(key.axis(axmac::Axis::Y), -1);
+/// assert_eq!(key.spatial_hash(), CellKey::new([3, -1, 7]).spatial_hash());
+/// assert!(key.bucket(1024) < 1024);
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CellKey<const N: usize>(pub [i64; N]);
+
+impl<const N: usize> CellKey<N> {
+
+    ///
+    /// Creates a key for the cell at `cell`
+    ///
+    pub const fn new(cell: [i64; N]) -> Self {
+        Self(cell)
+    }
+
+    ///
+    /// Returns the coordinate of the cell along `axis`
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not less than `N`
+    ///
+    pub const fn axis(self, axis: crate::Axis) -> i64 {
+        self.0[axis.as_usize()]
+    }
+
+    ///
+    /// Returns the spatial hash of the cell, failing to compile for more than four axes
+    ///
+    pub const fn spatial_hash(self) -> u64 {
+        const { assert!(N <= PRIMES.len(), "axmac: cells are hashed along at most four axes") };
+        let mut hash = 0;
+        let mut i = 0;
+        while i < N {
+            hash ^= (self.0[i] as u64).wrapping_mul(PRIMES[i]);
+            i += 1;
+        }
+        hash
+    }
+
+    ///
+    /// Returns the bucket of a table with `buckets` entries that the cell falls in
+    ///
+    /// # Panics
+    ///
+    /// If `buckets` is zero
+    ///
+    pub const fn bucket(self, buckets: usize) -> usize {
+        assert!(buckets > 0, "axmac: a hash table needs at least one bucket");
+        (self.spatial_hash() % buckets as u64) as usize
+    }
+
+}
+
+impl<const N: usize> From<[i64; N]> for CellKey<N> {
+    fn from(cell: [i64; N]) -> Self {
+        Self(cell)
+    }
+}
+
+impl<const N: usize> From<CellKey<N>> for [i64; N] {
+    fn from(key: CellKey<N>) -> Self {
+        key.0
+    }
+}
+
+
+///
+/// Returns the spatial hash of a grid cell given by axis-named integer coordinates, in any order
+///
+/// The axes named must be the first `N`, such as _x_ and _y_ or _x_, _y_ and _z_, each given once.
+/// Coordinates may be any integer type converting losslessly into `i64`. The result equals that of
+/// [`CellKey::spatial_hash`] for the same cell
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::CellKey;
+///
+/// let (cx, cy, cz) = (4i32, -2i32, 9i32);
+/// let hash = cell_hash!(x: cx, y: cy, z: cz);
+/// assert_eq!(hash, cell_hash!(z: cz, x: cx, y: cy));
+/// assert_eq!(hash, CellKey::new([4, -2, 9]).spatial_hash());
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: cell_hash! needs each of the first N axes exactly once
+/// let hash = cell_hash!(x: 1, z: 2);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! cell_hash {
+    ( $( $axis:ident : $coord:expr ),+ $(,)? ) => {{
+        const _: () = assert!(
+            $crate::__private::axes_are_permutation(&[ $( $crate::ax!($axis) ),+ ]),
+            "axmac: cell_hash! needs each of the first N axes exactly once",
+        );
+        $crate::__private::cell_key([ $( ($crate::ax!($axis), i64::from($coord)) ),+ ]).spatial_hash()
+    }};
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn hash_matches_reference() {
+        let key = CellKey::new([1, 2, 3]);
+        assert_eq!(key.spatial_hash(), 73_856_093 ^ (2 * 19_349_663) ^ (3 * 83_492_791));
+        assert_eq!(CellKey::<0>::new([]).spatial_hash(), 0);
+        assert_eq!(CellKey::new([5, 5]).bucket(1), 0);
+    }
+
+    #[test]
+    fn macro_orders_axes() {
+        assert_eq!(cell_hash!(y: 2i32, x: -1i32), CellKey::new([-1, 2]).spatial_hash());
+        assert_ne!(cell_hash!(x: 2u8, y: 1u8), cell_hash!(x: 1u8, y: 2u8));
+    }
+
+}
@@ -0,0 +1,125 @@
+//!
+//! Parsing command line arguments such as `--crop x=10..20,y=..100` into regions
+//!
+//! Values take the compact form of [`Roi::parse`]: `<axis>=<range>` items separated by commas or
+//! whitespace, with the axis named `x` to `w` or written `#<index>:`, and the range in any of the forms
+//! `a..b`, `a..=b`, `a..`, `..b` and `..`. Errors are the [`ParseError`]s of that parser, with the flag
+//! at fault, ready to print for the user
+//!
+//! ```
+//! # #[macro_use] extern crate axmac; fn main() {
+//! use axmac::cli;
+//!
+//! let args = ["tool", "--crop", "x=10..20,y=..100", "in.png"].map(String::from);
+//! assert_eq!(cli::roi_flag(args, "--crop"), Ok(Some(roi!(x: 10..20, y: ..100))));
+//!
+//! let args = ["tool", "--crop=x=10..20,z=5.."].map(String::from);
+//! let err = cli::roi_flag::<2, _>(args, "--crop").unwrap_err();
+//! assert_eq!(err.to_string(), "axmac: token 1 does not name an axis, in --crop");
+//! # }
+//! ```
+//!
+
+use std::fmt;
+use std::string::String;
+
+use crate::{ParseError, Roi};
+
+
+///
+/// Returned when a command line argument does not describe a region
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ArgError {
+    /// The flag is the last argument, with no value after it
+    MissingValue {
+        /// The flag given
+        flag: String,
+    },
+    /// The value given for the flag is not the compact form of a region
+    Invalid {
+        /// The flag given
+        flag: String,
+        /// Why [`Roi::parse`] rejected the value
+        error: ParseError,
+    },
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArgError::MissingValue { flag } => write!(f, "axmac: {} needs a region such as x=10..20,y=..100", flag),
+            ArgError::Invalid { flag, error } => write!(f, "{}, in {}", error, flag),
+        }
+    }
+}
+
+impl std::error::Error for ArgError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ArgError::MissingValue { .. } => None,
+            ArgError::Invalid { error, .. } => Some(error),
+        }
+    }
+}
+
+
+///
+/// Finds `flag` among `args`, given either as `--crop x=..` or `--crop=x=..`, and parses its value with
+/// [`Roi::parse`]
+///
+/// Returns `None` when the flag is absent, and the last region when it is given more than once, as most
+/// tools treat repeated flags. `args` is typically [`std::env::args`]
+///
+pub fn roi_flag<const N: usize, I: IntoIterator<Item = String>>(args: I, flag: &str) -> Result<Option<Roi<N>>, ArgError> {
+    let mut args = args.into_iter();
+    let mut roi = None;
+    while let Some(arg) = args.next() {
+        let value = match arg.strip_prefix(flag) {
+            Some("") => args.next().ok_or_else(|| ArgError::MissingValue { flag: flag.into() })?,
+            Some(rest) if rest.starts_with('=') => rest[1..].into(),
+            _ => continue,
+        };
+        roi = Some(Roi::parse(&value).map_err(|error| ArgError::Invalid { flag: flag.into(), error })?);
+    }
+    Ok(roi)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| String::from(*arg)).collect()
+    }
+
+    #[test]
+    fn values_take_the_compact_form() {
+        let given = args(&["--crop", "#4:=1..2, x=3.."]);
+        assert_eq!(roi_flag::<5, _>(given, "--crop"), Ok(Some(Roi::new([3, 0, 0, 0, 1], [usize::MAX, usize::MAX, usize::MAX, usize::MAX, 2]))));
+        assert_eq!(roi_flag::<2, _>(args(&["--crop="]), "--crop"), Ok(Some(Roi::FULL)));
+        let invalid = |value: &str| roi_flag::<2, _>(args(&["--crop", value]), "--crop").unwrap_err();
+        for value in ["y=1..2,y=..3", "x=1..,y", "x=1..2 z=3.."] {
+            let error = Roi::<2>::parse(value).unwrap_err();
+            assert_eq!(invalid(value), ArgError::Invalid { flag: "--crop".into(), error });
+        }
+        assert_eq!(invalid("x=1..,y").to_string(), "axmac: axis y has an invalid value, in --crop");
+    }
+
+    #[test]
+    fn flags_take_the_last_value() {
+        let given = args(&["--crop=x=1..2", "--cropped", "--crop", "y=..3"]);
+        assert_eq!(roi_flag::<2, _>(given, "--crop"), Ok(Some(Roi::new([0, 0], [usize::MAX, 3]))));
+        assert_eq!(roi_flag::<2, _>(args(&["-v"]), "--crop"), Ok(None));
+        assert_eq!(
+            roi_flag::<2, _>(args(&["--crop"]), "--crop").unwrap_err().to_string(),
+            "axmac: --crop needs a region such as x=10..20,y=..100",
+        );
+    }
+
+}
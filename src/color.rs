@@ -0,0 +1,189 @@
+//!
+//! Per-channel metadata for the `r`, `g`, `b` and `a` color vocabulary
+//!
+
+use core::ops::{Index, RangeInclusive};
+
+use crate::AxisMap;
+
+
+///
+/// How a single color channel is stored
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Channel {
+    /// The number of bits the channel occupies, zero if the format lacks the channel
+    pub bits: u8,
+    /// Whether the channel's value has already been multiplied by alpha
+    pub premultiplied: bool,
+}
+
+impl Channel {
+
+    ///
+    /// Creates a channel stored in `bits` bits
+    ///
+    pub const fn new(bits: u8, premultiplied: bool) -> Self {
+        Self { bits, premultiplied }
+    }
+
+    ///
+    /// Returns the largest value the channel can hold
+    ///
+    /// # Panics
+    ///
+    /// If the channel is wider than 32 bits
+    ///
+    pub const fn max(&self) -> u32 {
+        assert!(self.bits <= 32, "axmac: channels wider than 32 bits are not supported");
+        if self.bits == 32 { u32::MAX } else { (1 << self.bits) - 1 }
+    }
+
+    ///
+    /// Returns every value the channel can hold
+    ///
+    pub const fn range(&self) -> RangeInclusive<u32> {
+        0..=self.max()
+    }
+
+}
+
+
+///
+/// The channels of a packed color format, indexed by the `r`, `g`, `b` and `a` identifiers
+///
+/// Codecs usually hold per-channel settings in arrays, so the metadata is addressed by the same indices
+/// `ax!` produces. [`channel_range!`] reads the range of a channel by name
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::ColorLayout;
+///
+/// let layout = ColorLayout::RGB565;
+/// assert_eq!(layout[ax!(g)].bits, 6);
+/// assert_eq!(layout.total_bits(), 16);
+/// assert_eq!(channel_range!(r in layout), 0..=31);
+/// assert_eq!(channel_range!(a), 0..=255);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// Indexing panics if the axis is not less than 4
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ColorLayout {
+    /// The metadata of every channel
+    pub channels: AxisMap<Channel>,
+}
+
+impl ColorLayout {
+
+    /// Eight bits per channel with straight alpha
+    pub const RGBA8: Self = Self::new([8, 8, 8, 8], false);
+    /// Eight bits per channel with premultiplied alpha
+    pub const RGBA8_PREMULTIPLIED: Self = Self::new([8, 8, 8, 8], true);
+    /// Sixteen bits per channel with straight alpha
+    pub const RGBA16: Self = Self::new([16, 16, 16, 16], false);
+    /// Ten bits per color channel with two bits of alpha
+    pub const RGB10A2: Self = Self::new([10, 10, 10, 2], false);
+    /// Five bits of red and blue and six of green, without alpha
+    pub const RGB565: Self = Self::new([5, 6, 5, 0], false);
+
+    ///
+    /// Creates a layout from the bit depth of every channel
+    ///
+    /// `premultiplied` applies to the color channels only, since alpha is never multiplied by itself
+    ///
+    pub const fn new(bits: [u8; 4], premultiplied: bool) -> Self {
+        let [r, g, b, a] = bits;
+        Self {
+            channels: AxisMap::new([
+                Channel::new(r, premultiplied),
+                Channel::new(g, premultiplied),
+                Channel::new(b, premultiplied),
+                Channel::new(a, false),
+            ]),
+        }
+    }
+
+    ///
+    /// Returns the metadata of the channel `ax!` maps to `axis`
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not less than 4
+    ///
+    pub const fn channel(&self, axis: usize) -> Channel {
+        self.channels.as_array()[axis]
+    }
+
+    ///
+    /// Returns every value the channel `axis` can hold
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not less than 4
+    ///
+    pub const fn range(&self, axis: usize) -> RangeInclusive<u32> {
+        self.channel(axis).range()
+    }
+
+    ///
+    /// Returns the number of bits a pixel occupies
+    ///
+    pub const fn total_bits(&self) -> u32 {
+        let [r, g, b, a] = *self.channels.as_array();
+        r.bits as u32 + g.bits as u32 + b.bits as u32 + a.bits as u32
+    }
+
+}
+
+impl Index<usize> for ColorLayout {
+    type Output = Channel;
+
+    fn index(&self, axis: usize) -> &Channel {
+        &self.channels[axis]
+    }
+}
+
+
+///
+/// Returns the range of values a color channel can hold, given by name
+///
+/// The layout defaults to [`ColorLayout::RGBA8`], while `channel_range!(g in layout)` reads another
+///
+#[macro_export]
+macro_rules! channel_range {
+    ( $channel:ident ) => {
+        $crate::ColorLayout::RGBA8.range($crate::ax!($channel))
+    };
+    ( $channel:ident in $layout:expr ) => {
+        ($layout).range($crate::ax!($channel))
+    };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn ranges_work() {
+        assert_eq!(channel_range!(b), 0..=255);
+        assert_eq!(channel_range!(a in ColorLayout::RGB10A2), 0..=3);
+        assert_eq!(channel_range!(a in ColorLayout::RGB565), 0..=0);
+        assert_eq!(Channel::new(32, false).max(), u32::MAX);
+    }
+
+    #[test]
+    fn premultiplied_skips_alpha() {
+        let layout = ColorLayout::RGBA8_PREMULTIPLIED;
+        assert!(layout[ax!(r)].premultiplied);
+        assert!(!layout.channel(ax!(a)).premultiplied);
+        assert_eq!(ColorLayout::RGBA16.total_bits(), 64);
+    }
+
+}
@@ -0,0 +1,382 @@
+//!
+//! Moving single components between arrays of points and contiguous columns
+//!
+
+use crate::Axis;
+
+
+// Elements processed per unrolled step, wide enough to fill a 256 bit register with `f32`s
+const LANES: usize = 8;
+
+// Points transposed at once by transpose_aos_soa, small enough for the whole block to stay in registers
+const BLOCK: usize = 8;
+
+
+///
+/// Copies the `axis` component of every point into the start of `out`, one value per point
+///
+/// The loop is split into fixed size chunks the compiler can vectorize, making this a cheap way to hand
+/// one column of an array of structs to SIMD or GPU code
+///
+/// ```
+/// use axmac::{gather_axis, Axis};
+///
+/// let points = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+/// let mut ys = [0.0; 2];
+/// gather_axis(&points, Axis::Y, &mut ys);
+/// assert_eq!(ys, [2.0, 5.0]);
+/// ```
+///
+/// # Panics
+///
+/// If `axis` is not less than `N`, or `out` is shorter than `points`
+///
+pub fn gather_axis<T: Copy, const N: usize>(points: &[[T; N]], axis: Axis, out: &mut [T]) {
+    let axis = axis.as_usize();
+    assert!(axis < N, "axmac: axis is out of bounds for the points");
+    let out = &mut out[..points.len()];
+
+    let mut src = points.chunks_exact(LANES);
+    let mut dst = out.chunks_exact_mut(LANES);
+    for (src, dst) in (&mut src).zip(&mut dst) {
+        for i in 0..LANES {
+            dst[i] = src[i][axis];
+        }
+    }
+    for (point, value) in src.remainder().iter().zip(dst.into_remainder()) {
+        *value = point[axis];
+    }
+}
+
+///
+/// Writes each value of `src` into the `axis` component of the matching point, the inverse of
+/// [`gather_axis`]
+///
+/// `src` should hold exactly one value per point. Debug builds check this, while release builds write
+/// only as many points as both slices cover
+///
+/// ```
+/// use axmac::{scatter_axis, Axis};
+///
+/// let mut points = [[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+/// scatter_axis(&mut points, Axis::Z, &[0.0, -1.0]);
+/// assert_eq!(points, [[1.0, 2.0, 0.0], [4.0, 5.0, -1.0]]);
+/// ```
+///
+/// # Panics
+///
+/// If `axis` is not less than `N`, or in debug builds if `src` and `points` differ in length
+///
+pub fn scatter_axis<T: Copy, const N: usize>(points: &mut [[T; N]], axis: Axis, src: &[T]) {
+    let axis = axis.as_usize();
+    assert!(axis < N, "axmac: axis is out of bounds for the points");
+    debug_assert_eq!(points.len(), src.len(), "axmac: scatter_axis needs one value per point");
+    let len = points.len().min(src.len());
+    let (points, src) = (&mut points[..len], &src[..len]);
+
+    let mut dst = points.chunks_exact_mut(LANES);
+    let mut src = src.chunks_exact(LANES);
+    for (dst, src) in (&mut dst).zip(&mut src) {
+        for i in 0..LANES {
+            dst[i][axis] = src[i];
+        }
+    }
+    for (point, &value) in dst.into_remainder().iter_mut().zip(src.remainder()) {
+        point[axis] = value;
+    }
+}
+
+///
+/// Rearranges `buf` in place from an array of `n_points` points with `N` components each into `N`
+/// columns of `n_points` values
+///
+/// Points are transposed in blocks held in registers, after which whole blocks of each column are moved
+/// into place, so most of the work happens in contiguous runs rather than one element at a time
+///
+/// ```
+/// use axmac::transpose_aos_soa;
+///
+/// let mut buf = [1, 2, 3, 10, 20, 30];
+/// transpose_aos_soa::<_, 3>(&mut buf, 2);
+/// assert_eq!(buf, [1, 10, 2, 20, 3, 30]);
+/// ```
+///
+/// # Panics
+///
+/// If `buf` does not hold exactly `N * n_points` values. Using an `N` outside of 2 to 4 fails to compile
+///
+pub fn transpose_aos_soa<T: Copy, const N: usize>(buf: &mut [T], n_points: usize) {
+    const { assert!(2 <= N && N <= 4, "axmac: transpose_aos_soa supports 2 to 4 components") };
+    assert_eq!(buf.len(), N * n_points, "axmac: buffer must hold N components per point");
+
+    let blocks = n_points / BLOCK;
+    let full = blocks * BLOCK;
+    let tail = n_points - full;
+    let (head, rest) = buf.split_at_mut(full * N);
+
+    // Each block becomes N short columns, which are then gathered into full columns
+    for block in head.chunks_exact_mut(BLOCK * N) {
+        transpose_block::<T, N>(block);
+    }
+    permute_chunks::<T, BLOCK>(head, blocks, N);
+    permute_chunks::<T, 1>(rest, tail, N);
+
+    // Splice the leftover points onto the end of each column, one column at a time
+    for c in 0..N - 1 {
+        let start = c * n_points + full;
+        let end = start + (N - c - 1) * full + tail;
+        buf[start..end].rotate_right(tail);
+    }
+}
+
+// Transposes BLOCK points of N components into N columns of BLOCK values
+fn transpose_block<T: Copy, const N: usize>(block: &mut [T]) {
+    let points: [[T; N]; BLOCK] = core::array::from_fn(|p| core::array::from_fn(|c| block[p * N + c]));
+    for (c, column) in block.chunks_exact_mut(BLOCK).enumerate() {
+        for (value, point) in column.iter_mut().zip(&points) {
+            *value = point[c];
+        }
+    }
+}
+
+// Transposes a row major `rows` by `cols` matrix of W element chunks in place by following the cycles of
+// the permutation, each cycle being moved starting from its smallest index
+fn permute_chunks<T: Copy, const W: usize>(buf: &mut [T], rows: usize, cols: usize) {
+    let len = rows * cols;
+    let dest = |i: usize| i % cols * rows + i / cols;
+
+    for start in 1..len.saturating_sub(1) {
+        let mut i = dest(start);
+        while i > start {
+            i = dest(i);
+        }
+        if i != start {
+            continue;
+        }
+
+        let mut carry: [T; W] = core::array::from_fn(|k| buf[start * W + k]);
+        let mut i = start;
+        loop {
+            i = dest(i);
+            for (slot, value) in buf[i * W..(i + 1) * W].iter_mut().zip(carry.iter_mut()) {
+                core::mem::swap(slot, value);
+            }
+            if i == start {
+                break;
+            }
+        }
+    }
+}
+
+///
+/// Packs `C` planar channels of equal length into `out`, channel varying fastest
+///
+/// In [`Shape`](crate::Shape) terms the channel is the x axis of `out` and the frame the y axis, so frame
+/// `i` of channel `c` lands at `out[i * C + c]`
+///
+/// ```
+/// use axmac::interleave;
+///
+/// let (left, right) = ([1, 2, 3], [-1, -2, -3]);
+/// let mut stereo = [0; 6];
+/// interleave([&left, &right], &mut stereo);
+/// assert_eq!(stereo, [1, -1, 2, -2, 3, -3]);
+/// ```
+///
+/// # Panics
+///
+/// If the channels differ in length, or `out` does not hold exactly `C` values per frame
+///
+pub fn interleave<T: Copy, const C: usize>(channels: [&[T]; C], out: &mut [T]) {
+    let frames = channels.first().map_or(0, |c| c.len());
+    assert!(channels.iter().all(|c| c.len() == frames), "axmac: interleaved channels must be the same length");
+    assert_eq!(out.len(), C * frames, "axmac: interleaved buffer must hold one value per channel per frame");
+
+    for (c, channel) in channels.iter().enumerate() {
+        for (frame, &value) in out.chunks_exact_mut(C).zip(channel.iter()) {
+            frame[c] = value;
+        }
+    }
+}
+
+///
+/// Splits `packed`, in which the channel varies fastest, into `C` planar channels, the inverse of
+/// [`interleave`]
+///
+/// ```
+/// use axmac::deinterleave;
+///
+/// let rgb = [255, 0, 10, 128, 64, 20];
+/// let (mut r, mut g, mut b) = ([0; 2], [0; 2], [0; 2]);
+/// deinterleave(&rgb, [&mut r, &mut g, &mut b]);
+/// assert_eq!((r, g, b), ([255, 128], [0, 64], [10, 20]));
+/// ```
+///
+/// # Panics
+///
+/// If the channels differ in length, or `packed` does not hold exactly `C` values per frame
+///
+pub fn deinterleave<T: Copy, const C: usize>(packed: &[T], channels: [&mut [T]; C]) {
+    let frames = channels.first().map_or(0, |c| c.len());
+    assert!(channels.iter().all(|c| c.len() == frames), "axmac: interleaved channels must be the same length");
+    assert_eq!(packed.len(), C * frames, "axmac: interleaved buffer must hold one value per channel per frame");
+
+    for (c, channel) in channels.into_iter().enumerate() {
+        for (value, frame) in channel.iter_mut().zip(packed.chunks_exact(C)) {
+            *value = frame[c];
+        }
+    }
+}
+
+///
+/// Reorders `points` in place so the one at index `n` is the point that would be there if they were sorted
+/// by their `axis` component, and returns it
+///
+/// Points before `n` have components no greater than it and points after no less, in no order otherwise.
+/// Selection takes linear time even in the worst case and never allocates, so k-d tree construction and
+/// percentile queries work without sorting whole slices. Components that never compare, such as NaN,
+/// order after all others
+///
+/// ```
+/// use axmac::{select_nth_along, Axis};
+///
+/// let mut points = [[0, 9], [1, 2], [2, 7], [3, 4], [4, 5]];
+/// let median = select_nth_along(&mut points, Axis::Y, 2);
+/// assert_eq!(*median, [4, 5]);
+/// assert!(points[..2].iter().all(|p| p[1] <= 5));
+/// ```
+///
+/// # Panics
+///
+/// If `axis` is not less than `N`, or `n` is not less than the number of points
+///
+pub fn select_nth_along<T: PartialOrd, const N: usize>(points: &mut [[T; N]], axis: Axis, n: usize) -> &mut [T; N] {
+    let axis = axis.as_usize();
+    assert!(axis < N, "axmac: axis is out of bounds for the points");
+    assert!(n < points.len(), "axmac: cannot select past the last point");
+    let (_, nth, _) = points.select_nth_unstable_by(n, |a, b| crate::math::nan_last(&a[axis], &b[axis]));
+    nth
+}
+
+
+///
+/// Iterates over one named component of an interleaved buffer such as `[x, y, z, x, y, z, ...]`
+///
+/// `stride` is the number of components per element. Prefixing the buffer with `mut` yields mutable
+/// references instead, for processing vertex or sample data in place
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let mut vertices = [0.0, 1.0, 2.0, 10.0, 11.0, 12.0];
+///
+/// let ys: Vec<f32> = component_iter!(vertices, stride: 3, axis: y).copied().collect();
+/// assert_eq!(ys, [1.0, 11.0]);
+///
+/// for z in component_iter!(mut vertices, stride: 3, axis: z) {
+///     *z = -*z;
+/// }
+/// assert_eq!(vertices, [0.0, 1.0, -2.0, 10.0, 11.0, -12.0]);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If the axis is not less than `stride`
+///
+#[macro_export]
+macro_rules! component_iter {
+    ( mut $buf:expr, stride: $stride:expr, axis: $axis:ident $(,)? ) => {
+        $crate::__private::strided_mut(&mut $buf[..], $stride, $crate::ax!($axis))
+    };
+    ( $buf:expr, stride: $stride:expr, axis: $axis:ident $(,)? ) => {
+        $crate::__private::strided(&$buf[..], $stride, $crate::ax!($axis))
+    };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn gather_handles_remainders() {
+        // Long enough to cover a full chunk and a partial one
+        let points: std::vec::Vec<[usize; 3]> = (0..11).map(|i| [i, i * 10, i * 100]).collect();
+        let mut out = [0; 12];
+        gather_axis(&points, Axis::Z, &mut out);
+        assert_eq!(out[..11], (0..11).map(|i| i * 100).collect::<std::vec::Vec<_>>()[..]);
+        assert_eq!(out[11], 0);
+    }
+
+    #[test]
+    fn scatter_inverts_gather() {
+        let mut points: std::vec::Vec<[i32; 2]> = (0..19).map(|i| [i, -i]).collect();
+        let mut column = [0; 19];
+        gather_axis(&points, Axis::X, &mut column);
+        points.iter_mut().for_each(|p| p[0] = 0);
+        scatter_axis(&mut points, Axis::X, &column);
+        assert!(points.iter().enumerate().all(|(i, p)| *p == [i as i32, -(i as i32)]));
+    }
+
+    fn check_transpose<const N: usize>(n_points: usize) {
+        let mut buf: std::vec::Vec<usize> = (0..N * n_points).collect();
+        transpose_aos_soa::<_, N>(&mut buf, n_points);
+        for c in 0..N {
+            for p in 0..n_points {
+                assert_eq!(buf[c * n_points + p], p * N + c, "N = {N}, n_points = {n_points}");
+            }
+        }
+    }
+
+    #[test]
+    fn transpose_matches_naive() {
+        for n_points in [0, 1, 5, 8, 9, 16, 23, 64, 67] {
+            check_transpose::<2>(n_points);
+            check_transpose::<3>(n_points);
+            check_transpose::<4>(n_points);
+        }
+    }
+
+    #[test]
+    fn select_nth_matches_sorting() {
+        let mut points: std::vec::Vec<[i64; 2]> = (0..101).map(|i| [i, (i * 37) % 101]).collect();
+        for n in [0, 50, 100] {
+            assert_eq!(select_nth_along(&mut points, Axis::Y, n)[1], n as i64);
+            assert!(points[..n].iter().all(|p| p[1] < n as i64));
+        }
+        let mut points = [[f32::NAN], [2.0], [f32::NAN], [0.0], [1.0]];
+        assert_eq!(*select_nth_along(&mut points, Axis::X, 2), [2.0]);
+        assert!(select_nth_along(&mut points, Axis::X, 3)[0].is_nan());
+    }
+
+    #[test]
+    fn interleave_round_trips() {
+        let planar = [[1, 2, 3, 4], [5, 6, 7, 8], [9, 10, 11, 12]];
+        let mut packed = [0; 12];
+        interleave([&planar[0], &planar[1], &planar[2]], &mut packed);
+        assert_eq!(packed[..6], [1, 5, 9, 2, 6, 10]);
+
+        let mut back = [[0; 4]; 3];
+        let [a, b, c] = &mut back;
+        deinterleave(&packed, [a, b, c]);
+        assert_eq!(back, planar);
+    }
+
+    #[test]
+    fn component_iter_handles_short_buffers() {
+        // A trailing partial element still yields the components it has
+        let buf = [0, 1, 2, 3, 4, 5, 6, 7];
+        assert!(component_iter!(buf, stride: 3, axis: y).eq(&[1, 4, 7]));
+        assert!(component_iter!(buf, stride: 3, axis: z).eq(&[2, 5]));
+        assert_eq!(component_iter!(buf[..1], stride: 4, axis: w).count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn gather_checks_axis() {
+        gather_axis(&[[0; 2]], Axis::Z, &mut [0]);
+    }
+
+}
@@ -0,0 +1,441 @@
+//!
+//! One-line text forms of axes, shapes and regions, such as `x640 y480 z3`, for logs, snapshots and tools
+//!
+//! The `FromStr` implementations also make [`Axis`], [`AxisSet`] and [`Shape`] command line values: an
+//! argument parser such as clap accepts them through its `value_parser!` with no further glue, so a tool
+//! can take `--axis z --shape x=1024,y=768`
+//!
+
+use core::fmt::{self, Write};
+use core::str::FromStr;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::{Axis, AxisSet, Roi, Shape};
+
+
+///
+/// Returned when parsing the compact form of a [`Shape`] or [`Roi`] fails, naming the axis at fault
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParseError {
+    /// A token does not start with an axis name, or names an axis past the last dimension
+    UnknownAxis {
+        /// The position of the token among the tokens, from zero
+        token: usize,
+    },
+    /// An axis is given more than once
+    Duplicate {
+        /// The axis at fault
+        axis: usize,
+    },
+    /// A shape gives no extent for an axis
+    Missing {
+        /// The axis at fault
+        axis: usize,
+    },
+    /// The value given for an axis is not a number, or for a region not a range
+    Invalid {
+        /// The axis at fault
+        axis: usize,
+    },
+    /// A shape gives strides along other axes but not this one
+    MissingStride {
+        /// The axis at fault
+        axis: usize,
+    },
+    /// A shape has more positions, or spans more elements, than a `usize` can count, counting up to this
+    /// axis
+    Overflow {
+        /// The axis at fault
+        axis: usize,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (axis, problem) = match *self {
+            ParseError::UnknownAxis { token } => return write!(f, "axmac: token {} does not name an axis", token),
+            ParseError::Duplicate { axis } => (axis, "is given more than once"),
+            ParseError::Missing { axis } => (axis, "is missing"),
+            ParseError::Invalid { axis } => (axis, "has an invalid value"),
+            ParseError::MissingStride { axis } => (axis, "needs a stride, as other axes have one"),
+            ParseError::Overflow { axis } => (axis, "overflows a usize"),
+        };
+        match Axis::from_usize(axis) {
+            Some(name) => write!(f, "axmac: axis {} {}", name, problem),
+            None => write!(f, "axmac: axis {} {}", axis, problem),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+
+impl<const N: usize> Shape<N> {
+
+    ///
+    /// Returns the compact form of the shape, one `<axis><extent>` token per axis such as `x640 y480 z3`
+    ///
+    /// Shapes other than the densely packed layout of [`Shape::new`] follow every extent with its stride,
+    /// negative along [reversed](Shape::reverse_axis) axes, as in `x3/1 y2/-8`. Axes past _w_ are written
+    /// by index, as in `#4:2`. [`Shape::parse`] reads the form back into an equal shape
+    ///
+    /// ```
+    /// use axmac::{Axis, Shape};
+    ///
+    /// assert_eq!(Shape::new([640, 480, 3]).to_compact_string(), "x640 y480 z3");
+    /// assert_eq!(Shape::with_strides([3, 2], [1, 8]).reverse_axis(Axis::Y).to_compact_string(), "x3/1 y2/-8");
+    /// ```
+    ///
+    #[cfg(feature = "alloc")]
+    pub fn to_compact_string(&self) -> String {
+        let mut s = String::new();
+        let _ = self.write_compact(&mut s);
+        s
+    }
+
+    ///
+    /// Writes the compact form of [`to_compact_string`](Self::to_compact_string) to any sink, without
+    /// allocating
+    ///
+    pub fn write_compact<W: Write>(&self, w: &mut W) -> fmt::Result {
+        let dense = *self == Shape::new(*self.extents());
+        for axis in 0..N {
+            if axis > 0 {
+                w.write_char(' ')?;
+            }
+            write_axis(w, axis)?;
+            write!(w, "{}", self.extent(axis))?;
+            if !dense {
+                let sign = if self.is_reversed(axis) { "-" } else { "" };
+                write!(w, "/{}{}", sign, self.stride(axis))?;
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Parses the compact form written by [`to_compact_string`](Self::to_compact_string)
+    ///
+    /// Tokens are separated by whitespace or commas, may put an `=` after the axis, and may name the axes
+    /// in any order, but every axis needs an extent. Either every axis or none has a stride, and without
+    /// strides the shape is densely packed as by [`Shape::new`]. Shapes too large to index with a `usize`
+    /// are rejected. This is also the `FromStr` form
+    ///
+    /// ```
+    /// use axmac::{ParseError, Shape};
+    ///
+    /// assert_eq!(Shape::parse("y480 x640"), Ok(Shape::new([640, 480])));
+    /// assert_eq!("x=1024,y=768".parse(), Ok(Shape::new([1024, 768])));
+    /// assert_eq!(Shape::<2>::parse("x3/4 y4/1"), Ok(Shape::col_major([3, 4])));
+    /// assert_eq!(Shape::<3>::parse("x640 y480"), Err(ParseError::Missing { axis: 2 }));
+    /// assert_eq!(Shape::<2>::parse("x640 yes"), Err(ParseError::Invalid { axis: 1 }));
+    /// ```
+    ///
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let mut extents = [None; N];
+        let mut strides = [None; N];
+        for token in tokens::<N>(s) {
+            let (axis, value) = token?;
+            if extents[axis].is_some() {
+                return Err(ParseError::Duplicate { axis });
+            }
+            let invalid = ParseError::Invalid { axis };
+            let (extent, stride) = match value.split_once('/') {
+                Some((extent, stride)) => {
+                    let (reversed, magnitude) = match stride.strip_prefix('-') {
+                        Some(magnitude) => (true, magnitude),
+                        None => (false, stride),
+                    };
+                    (extent, Some((magnitude.parse::<usize>().map_err(|_| invalid)?, reversed)))
+                }
+                None => (value, None),
+            };
+            extents[axis] = Some(extent.parse::<usize>().map_err(|_| invalid)?);
+            strides[axis] = stride;
+        }
+
+        let mut shape_extents = [0; N];
+        for (axis, extent) in extents.into_iter().enumerate() {
+            shape_extents[axis] = extent.ok_or(ParseError::Missing { axis })?;
+        }
+        if strides.iter().all(Option::is_none) {
+            if let Some(axis) = Shape::dense_overflow_axis(&shape_extents) {
+                return Err(ParseError::Overflow { axis });
+            }
+            return Ok(Shape::new(shape_extents));
+        }
+        let mut shape_strides = [0; N];
+        for (axis, stride) in strides.iter().enumerate() {
+            shape_strides[axis] = stride.ok_or(ParseError::MissingStride { axis })?.0;
+        }
+        if let Some(axis) = Shape::strided_overflow_axis(&shape_extents, &shape_strides) {
+            return Err(ParseError::Overflow { axis });
+        }
+        let mut shape = Shape::with_strides(shape_extents, shape_strides);
+        for (axis, stride) in strides.iter().enumerate() {
+            if let Some((_, true)) = stride {
+                shape = shape.reverse_index(axis);
+            }
+        }
+        Ok(shape)
+    }
+
+}
+
+
+impl<const N: usize> Roi<N> {
+
+    ///
+    /// Returns the compact form of the region, one `<axis><range>` token per axis such as `x10..20 y..`
+    ///
+    /// Ranges starting at zero leave out their start, and unbounded ranges their end. [`Roi::parse`]
+    /// reads the form back into an equal region
+    ///
+    /// ```
+    /// # #[macro_use] extern crate axmac; fn main() {
+    /// assert_eq!(roi!(x: 10..20, y: ..100, z: 2..).to_compact_string(), "x10..20 y..100 z2..");
+    /// # }
+    /// ```
+    ///
+    #[cfg(feature = "alloc")]
+    pub fn to_compact_string(&self) -> String {
+        let mut s = String::new();
+        let _ = self.write_compact(&mut s);
+        s
+    }
+
+    ///
+    /// Writes the compact form of [`to_compact_string`](Self::to_compact_string) to any sink, without
+    /// allocating
+    ///
+    pub fn write_compact<W: Write>(&self, w: &mut W) -> fmt::Result {
+        for axis in 0..N {
+            if axis > 0 {
+                w.write_char(' ')?;
+            }
+            write_axis(w, axis)?;
+            let range = self.range(axis);
+            if range.start > 0 {
+                write!(w, "{}", range.start)?;
+            }
+            w.write_str("..")?;
+            if !self.is_unbounded(axis) {
+                write!(w, "{}", range.end)?;
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Parses the compact form written by [`to_compact_string`](Self::to_compact_string)
+    ///
+    /// Tokens are separated by whitespace or commas, may put an `=` after the axis, and may name the axes
+    /// in any order. Ranges may also be inclusive, as in `x2..=4`, and axes not named cover their whole
+    /// extent
+    ///
+    /// ```
+    /// # #[macro_use] extern crate axmac; fn main() {
+    /// use axmac::{ParseError, Roi};
+    ///
+    /// assert_eq!(Roi::parse("y..100 x10..20"), Ok(roi!(x: 10..20, y: ..100)));
+    /// assert_eq!(Roi::parse("x2..=4"), Ok(roi!(x: 2..5, y: ..)));
+    /// assert_eq!(Roi::<2>::parse("x2..4 z1.."), Err(ParseError::UnknownAxis { token: 1 }));
+    /// # }
+    /// ```
+    ///
+    pub fn parse(s: &str) -> Result<Self, ParseError> {
+        let (mut start, mut end) = ([0; N], [usize::MAX; N]);
+        let mut seen = [false; N];
+        for token in tokens::<N>(s) {
+            let (axis, value) = token?;
+            if core::mem::replace(&mut seen[axis], true) {
+                return Err(ParseError::Duplicate { axis });
+            }
+            (start[axis], end[axis]) = parse_range(value).ok_or(ParseError::Invalid { axis })?;
+        }
+        Ok(Self::new(start, end))
+    }
+
+}
+
+
+///
+/// Parses an axis name, `x` to `w`
+///
+/// ```
+/// use axmac::{Axis, ParseError};
+///
+/// assert_eq!("z".parse(), Ok(Axis::Z));
+/// assert_eq!("q".parse::<Axis>(), Err(ParseError::UnknownAxis { token: 0 }));
+/// ```
+///
+impl FromStr for Axis {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        match split_axis(s.trim()) {
+            Some((axis, "")) => Axis::from_usize(axis).ok_or(ParseError::UnknownAxis { token: 0 }),
+            _ => Err(ParseError::UnknownAxis { token: 0 }),
+        }
+    }
+}
+
+///
+/// Parses axis names or indices separated by whitespace or commas, such as `x,z` or `0 2 5`
+///
+/// ```
+/// use axmac::AxisSet;
+///
+/// assert_eq!("x,z".parse(), Ok(AxisSet::from_axes(&[0, 2])));
+/// assert_eq!("".parse(), Ok(AxisSet::EMPTY));
+/// ```
+///
+impl FromStr for AxisSet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let mut set = AxisSet::EMPTY;
+        for (token, text) in split_tokens(s) {
+            let axis = match split_axis(text) {
+                Some((axis, "")) => axis,
+                _ => text.parse().map_err(|_| ParseError::UnknownAxis { token })?,
+            };
+            if axis >= AxisSet::CAPACITY {
+                return Err(ParseError::UnknownAxis { token });
+            }
+            if !set.insert(axis) {
+                return Err(ParseError::Duplicate { axis });
+            }
+        }
+        Ok(set)
+    }
+}
+
+///
+/// Parses the form of [`Shape::parse`]
+///
+impl<const N: usize> FromStr for Shape<N> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Shape::parse(s)
+    }
+}
+
+
+///
+/// Parses `a..b`, `a..=b`, `a..`, `..b` or `..` into a start and end, with `usize::MAX` as an unbounded
+/// end, as [`Roi`] stores them
+///
+pub(crate) fn parse_range(s: &str) -> Option<(usize, usize)> {
+    let (start, end) = s.split_once("..")?;
+    let start = if start.is_empty() { 0 } else { start.parse().ok()? };
+    let end = match end.strip_prefix('=') {
+        Some(end) => end.parse::<usize>().ok()?.checked_add(1)?,
+        None if end.is_empty() => usize::MAX,
+        None => end.parse().ok()?,
+    };
+    Some((start, end))
+}
+
+///
+/// Splits `name` into the index of the axis it starts with, `x` to `w` or `#<index>:`, and the rest, or
+/// `None` if it starts with no axis
+///
+pub(crate) fn split_axis(name: &str) -> Option<(usize, &str)> {
+    if let Some(rest) = name.strip_prefix('#') {
+        let (index, rest) = rest.split_once(':')?;
+        return Some((index.parse().ok()?, rest));
+    }
+    let axis = match name.as_bytes().first()? {
+        b'x' => 0,
+        b'y' => 1,
+        b'z' => 2,
+        b'w' => 3,
+        _ => return None,
+    };
+    Some((axis, &name[1..]))
+}
+
+// Iterates over the axis and value of every token separated by whitespace or commas
+fn tokens<const N: usize>(s: &str) -> impl Iterator<Item = Result<(usize, &str), ParseError>> {
+    split_tokens(s).map(|(token, text)| match split_axis(text) {
+        Some((axis, value)) if axis < N => Ok((axis, value.strip_prefix('=').unwrap_or(value))),
+        _ => Err(ParseError::UnknownAxis { token }),
+    })
+}
+
+fn split_tokens(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    s.split(|c: char| c.is_whitespace() || c == ',').filter(|text| !text.is_empty()).enumerate()
+}
+
+fn write_axis<W: Write>(w: &mut W, axis: usize) -> fmt::Result {
+    match crate::Axis::from_usize(axis).map(crate::Axis::name) {
+        Some(name) => w.write_str(name),
+        None => write!(w, "#{}:", axis),
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::Axis;
+
+    #[test]
+    fn shapes_round_trip() {
+        let mut s = std::string::String::new();
+        for shape in [
+            Shape::new([2, 3, 4, 5, 6]),
+            Shape::col_major([2, 3, 4, 5, 6]),
+            Shape::new([1, 2, 1, 1, 1]).reverse_axis(Axis::X).reverse_index(4),
+        ] {
+            s.clear();
+            shape.write_compact(&mut s).unwrap();
+            assert_eq!(Shape::parse(&s), Ok(shape));
+        }
+        assert_eq!(s, "x1/-1 y2/1 z1/2 w1/2 #4:1/-2");
+        assert_eq!(Shape::<2>::parse("x1 y1/1"), Err(ParseError::MissingStride { axis: 0 }));
+        assert_eq!(Shape::<1>::parse("x1 x2"), Err(ParseError::Duplicate { axis: 0 }));
+        assert_eq!("x=2, #4:=3 y1 z1 w1".parse(), Ok(Shape::new([2, 1, 1, 1, 3])));
+        assert_eq!("y 7, 8".parse::<AxisSet>(), Err(ParseError::UnknownAxis { token: 2 }));
+        assert_eq!("y, 1".parse::<AxisSet>(), Err(ParseError::Duplicate { axis: 1 }));
+    }
+
+    #[test]
+    fn oversized_shapes_are_rejected() {
+        let huge = "x4294967296 y4294967296";
+        assert_eq!(Shape::<2>::parse(huge), Err(ParseError::Overflow { axis: 1 }));
+        assert_eq!(huge.parse::<Shape<2>>(), Err(ParseError::Overflow { axis: 1 }));
+        let far = std::format!("x2/{} y2/1", usize::MAX);
+        assert_eq!(Shape::<2>::parse(&far), Err(ParseError::Overflow { axis: 0 }));
+        assert_eq!(Shape::<3>::parse("x0 y4294967296 z4294967296"), Err(ParseError::Overflow { axis: 2 }));
+    }
+
+    #[test]
+    fn regions_round_trip() {
+        let mut s = std::string::String::new();
+        let roi = Roi::new([0, 3], [usize::MAX, 3]);
+        roi.write_compact(&mut s).unwrap();
+        assert_eq!(s, "x.. y3..3");
+        assert_eq!(Roi::parse(&s), Ok(roi));
+        let past_end = std::format!("x1..={}", usize::MAX);
+        assert_eq!(Roi::<1>::parse(&past_end), Err(ParseError::Invalid { axis: 0 }));
+    }
+
+    #[test]
+    fn errors_name_axes() {
+        use std::string::ToString;
+
+        assert_eq!(ParseError::Invalid { axis: 2 }.to_string(), "axmac: axis z has an invalid value");
+        assert_eq!(ParseError::Missing { axis: 5 }.to_string(), "axmac: axis 5 is missing");
+        assert_eq!(ParseError::UnknownAxis { token: 0 }.to_string(), "axmac: token 0 does not name an axis");
+    }
+
+}
@@ -0,0 +1,86 @@
+//!
+//! The `ax!`, `axs!` and `axr!` macros exactly as version 0.1 expanded them, for migrating gradually
+//!
+//! Since 0.1 the crate root macros name each other through `$crate::` paths and answer mistakes with
+//! their own errors. Code relying on the old expansions, most often a local `ax!` extending the
+//! vocabulary which `axs!` and `axr!` picked up from the call site, can import these instead and move to
+//! the crate root macros one module at a time
+//!
+//! ```
+//! use axmac::compat::{axr, axs};
+//!
+//! // A project vocabulary layered over the crate's, seen by the 0.1 `axs!` and `axr!`
+//! macro_rules! ax {
+//!     (t) => { 4usize };
+//!     ($axis:ident) => { axmac::ax!($axis) };
+//! }
+//!
+//! assert_eq!(axs![x, t], [0, 4]);
+//! assert_eq!(axr!(z..t), 2..4);
+//! ```
+//!
+
+pub use crate::__compat_0_1_ax as ax;
+pub use crate::__compat_0_1_axr as axr;
+pub use crate::__compat_0_1_axs as axs;
+
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __compat_0_1_ax {
+
+    (x) => { 0usize };
+    (y) => { 1usize };
+    (z) => { 2usize };
+    (w) => { 3usize };
+
+}
+
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __compat_0_1_axs {
+
+    // [x, x, w, z, y, z]
+    ( $( $d:ident ), * ) => { [ $( ax!($d), )* ] };
+
+    // [z; 3]
+    ( $d:ident; $i:expr ) => { [ax!($d); $i] };
+
+}
+
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __compat_0_1_axr {
+
+    // Ident to Ident
+    //  Range x..w
+    ( $a:ident..$b:ident ) => { ax!($a)..ax!($b) };
+    //  RangeInclusive y..=z
+    ( $a:ident..=$b:ident ) => { ax!($a)..=ax!($b) };
+
+    // Ident to Expr
+    //  Range z..6
+    ( $a:ident..$b:expr ) => { ax!($a)..$b };
+    //  RangeInclusive w..=9
+    ( $a:ident..=$b:expr ) => { ax!($a)..=$b };
+
+    // Inf to Ident
+    //  RangeTo ..w
+    ( ..$a:ident ) => { ..ax!($a) };
+    //  RangeToInclusive ..=z
+    ( ..=$a:ident ) => { ..=ax!($a) };
+
+    // Ident to Inf
+    //  RangeFrom x..
+    ( $a:ident.. ) => { ax!($a).. };
+
+    // Expr to Ident
+    //  Range (0)..z
+    ( ($a:expr)..$b:ident )  => { $a..ax!($b) };
+    // RangeInclusive (1)..=w
+    ( ($a:expr)..=$b:ident )  => { $a..=ax!($b) };
+
+}
+
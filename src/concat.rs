@@ -0,0 +1,71 @@
+//!
+//! Building names from the axis vocabulary
+//!
+
+
+///
+/// Concatenates a prefix, an axis identifier and optionally a suffix into a string literal
+///
+/// The axis is checked against the enabled vocabularies, so a typo fails to compile rather than producing
+/// a wrong name. The result is a literal, usable wherever `concat!` is, including `#[doc = ...]` and
+/// `#[export_name = ...]` attributes of generated per-axis items
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// assert_eq!(ax_concat!(get_, y), "get_y");
+/// assert_eq!(ax_concat!(set_, w, _unchecked), "set_w_unchecked");
+///
+/// #[doc = ax_concat!(Returns_the_, z)]
+/// fn z_doc() {}
+/// # }
+/// ```
+///
+/// Declarative macros cannot create new identifiers on stable Rust, so the result is always a string. Code
+/// generators stamping out methods such as `get_y` should name each item explicitly, or use the axis
+/// identifier itself as the name through [`for_axes!`](crate::for_axes)
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let name = ax_concat!(get_, q);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! ax_concat {
+
+    ( $prefix:ident, $axis:ident ) => {
+        $crate::ax!(@with $crate::__ax_concat ($prefix, $axis, ,) $axis)
+    };
+
+    ( $prefix:ident, $axis:ident, $suffix:ident ) => {
+        $crate::ax!(@with $crate::__ax_concat ($prefix, $axis, $suffix,) $axis)
+    };
+
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_concat {
+    ( $prefix:ident, $axis:ident, $( $suffix:ident )?, $index:literal ) => {
+        concat!(stringify!($prefix), stringify!($axis) $( , stringify!($suffix) )?)
+    };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn it_works() {
+        assert_eq!(ax_concat!(get_, x), "get_x");
+        assert_eq!(ax_concat!(axis, z, _len), "axisz_len");
+    }
+
+    #[cfg(feature = "input")]
+    #[test]
+    fn vocabularies_work() {
+        assert_eq!(ax_concat!(on_, ry), "on_ry");
+    }
+
+}
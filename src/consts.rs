@@ -0,0 +1,215 @@
+//!
+//! Axis indices as `pub const` items
+//!
+//! Const generic arguments on stable Rust must be braced unless they are a literal or a single identifier.
+//! A macro invocation in that position is parsed as a type, so `Grid<ax!(z)>` fails and must be written
+//! `Grid<{ ax!(z) }>`. Importing these constants allows the braceless form
+//!
+//! ```
+//! use axmac::consts::Z;
+//!
+//! struct Axis<const A: usize>;
+//!
+//! impl<const A: usize> Axis<A> {
+//!     const INDEX: usize = A;
+//! }
+//!
+//! assert_eq!(Axis::<Z>::INDEX, 2);
+//! ```
+//!
+//! The constants share their names with the [marker types](crate::marker), so the two cannot be glob
+//! imported into the same scope
+//!
+
+
+/// The index of the x axis
+pub const X: usize = ax!(x);
+/// The index of the y axis
+pub const Y: usize = ax!(y);
+/// The index of the z axis
+pub const Z: usize = ax!(z);
+/// The index of the w axis
+pub const W: usize = ax!(w);
+
+///
+/// The number of axis indices named by the enabled vocabularies
+///
+/// Every identifier accepted by `ax!` with the current feature set maps to an index less than this
+///
+pub const COUNT: usize = {
+    #[allow(unused_mut)]
+    let mut count = 4;
+    #[cfg(feature = "input")]
+    if count < 6 { count = 6; }
+    #[cfg(feature = "imu")]
+    if count < 6 { count = 6; }
+    #[cfg(feature = "motion")]
+    if count < 7 { count = 7; }
+    count
+};
+
+#[cfg(feature = "input")]
+mod input {
+    /// The index of the left stick's x axis
+    pub const LX: usize = ax!(lx);
+    /// The index of the left stick's y axis
+    pub const LY: usize = ax!(ly);
+    /// The index of the right stick's x axis
+    pub const RX: usize = ax!(rx);
+    /// The index of the right stick's y axis
+    pub const RY: usize = ax!(ry);
+    /// The index of the left trigger
+    pub const LT: usize = ax!(lt);
+    /// The index of the right trigger
+    pub const RT: usize = ax!(rt);
+}
+#[cfg(feature = "input")]
+pub use input::*;
+
+#[cfg(feature = "imu")]
+mod imu {
+    /// The index of the accelerometer's x axis
+    pub const AX: usize = ax!(ax);
+    /// The index of the accelerometer's y axis
+    pub const AY: usize = ax!(ay);
+    /// The index of the accelerometer's z axis
+    pub const AZ: usize = ax!(az);
+    /// The index of the gyroscope's x axis
+    pub const GX: usize = ax!(gx);
+    /// The index of the gyroscope's y axis
+    pub const GY: usize = ax!(gy);
+    /// The index of the gyroscope's z axis
+    pub const GZ: usize = ax!(gz);
+}
+#[cfg(feature = "imu")]
+pub use imu::*;
+
+#[cfg(feature = "motion")]
+mod motion {
+    /// The index of the extruder axis
+    pub const E: usize = ax!(e);
+    /// The index of the rotary axis about x
+    pub const A: usize = ax!(a);
+    /// The index of the rotary axis about y
+    pub const B: usize = ax!(b);
+    /// The index of the rotary axis about z
+    pub const C: usize = ax!(c);
+}
+#[cfg(feature = "motion")]
+pub use motion::*;
+
+#[cfg(feature = "rgba")]
+mod rgba {
+    /// The index of the red channel
+    pub const R: usize = ax!(r);
+    /// The index of the green channel
+    pub const G: usize = ax!(g);
+    /// The index of the blue channel
+    pub const B: usize = ax!(b);
+    /// The index of the alpha channel
+    pub const A: usize = ax!(a);
+}
+#[cfg(feature = "rgba")]
+pub use rgba::*;
+
+#[cfg(feature = "uvst")]
+mod uvst {
+    /// The index of the first texture coordinate
+    pub const U: usize = ax!(u);
+    /// The index of the second texture coordinate
+    pub const V: usize = ax!(v);
+    /// The index of the third texture coordinate
+    pub const S: usize = ax!(s);
+    /// The index of the fourth texture coordinate
+    pub const T: usize = ax!(t);
+}
+#[cfg(feature = "uvst")]
+pub use uvst::*;
+
+#[cfg(feature = "whd")]
+mod whd {
+    /// The index of the width extent
+    pub const WIDTH: usize = ax!(width);
+    /// The index of the height extent
+    pub const HEIGHT: usize = ax!(height);
+    /// The index of the depth extent
+    pub const DEPTH: usize = ax!(depth);
+}
+#[cfg(feature = "whd")]
+pub use whd::*;
+
+#[cfg(feature = "spectral")]
+mod spectral {
+    /// The index of the frequency axis
+    pub const FREQ: usize = ax!(freq);
+    /// The index of the time axis
+    pub const TIME: usize = ax!(time);
+}
+#[cfg(feature = "spectral")]
+pub use spectral::*;
+
+
+///
+/// Expands to the path of the constant in [`consts`](crate::consts) holding an axis' index
+///
+/// Takes the upper case name of the constant. Being a path rather than a literal, the expansion can be
+/// used in patterns and in braced const generic arguments
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// struct Grid<const A: usize>;
+/// let _grid: Grid<{ ax_const!(Z) }> = Grid;
+///
+/// let name = match 1 {
+///     ax_const!(X) => "x",
+///     ax_const!(Y) => "y",
+///     _ => "other",
+/// };
+/// assert_eq!(name, "y");
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! ax_const {
+    ( $name:ident ) => { $crate::consts::$name };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    struct Const<const A: usize>;
+
+    impl<const A: usize> Const<A> {
+        const VALUE: usize = A;
+    }
+
+    #[test]
+    fn const_generic_arguments_work() {
+        assert_eq!(Const::<X>::VALUE, ax!(x));
+        assert_eq!(Const::<W>::VALUE, ax!(w));
+        assert_eq!(Const::<{ ax_const!(Y) }>::VALUE, ax!(y));
+    }
+
+    #[test]
+    fn count_works() {
+        const { assert!(COUNT > ax!(w)) };
+        #[cfg(feature = "input")]
+        const { assert!(COUNT > ax!(rt)) };
+        #[cfg(feature = "imu")]
+        const { assert!(COUNT > ax!(gz)) };
+        #[cfg(feature = "motion")]
+        const { assert!(COUNT > ax!(c)) };
+    }
+
+    #[cfg(feature = "motion")]
+    #[test]
+    fn vocabularies_work() {
+        assert_eq!(Const::<E>::VALUE, ax!(e));
+        assert_eq!(ax_const!(C), ax!(c));
+    }
+
+}
@@ -0,0 +1,239 @@
+//!
+//! Output sizes of convolutions and pooling windows along each axis
+//!
+
+use core::iter::FusedIterator;
+
+use crate::NdIndex;
+
+
+///
+/// Computes the number of outputs of a convolution along an axis of `len` inputs
+///
+/// Parameters are given by name in any order, each at most once. `kernel` is required, while `stride`
+/// and `dilation` default to one and `pad`, the number of positions added at each end, to zero. The
+/// result is the standard `(len + 2 * pad - dilation * (kernel - 1) - 1) / stride + 1`, or zero if the
+/// dilated kernel is wider than the padded input. The macro is a const expression, so with constant
+/// inputs a kernel or stride of zero fails to compile
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// assert_eq!(conv_out_len!(224; kernel: 7, stride: 2, pad: 3), 112);
+/// assert_eq!(conv_out_len!(32; kernel: 3), 30);
+/// assert_eq!(conv_out_len!(32; dilation: 2, kernel: 3, pad: 2), 32);
+/// assert_eq!(conv_out_len!(2; kernel: 5), 0);
+///
+/// const POOLED: usize = conv_out_len!(28; kernel: 2, stride: 2);
+/// assert_eq!(POOLED, 14);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: conv_out_len! needs a stride of at least one
+/// const LEN: usize = conv_out_len!(28; kernel: 2, stride: 0);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If the kernel or stride is zero
+///
+#[macro_export]
+macro_rules! conv_out_len {
+    ( $len:expr; $( $param:ident : $value:expr ),+ $(,)? ) => {{
+        // Giving every parameter leaves the defaults unused
+        #[allow(clippy::needless_update)]
+        let params = $crate::__private::ConvParams { $( $param: $value, )+ ..$crate::__private::ConvParams::DEFAULT };
+        params.out_len($len)
+    }};
+}
+
+
+///
+/// Computes the extents after a convolution with its own parameters along each axis named
+///
+/// Takes an array of extents, then per axis the same named parameters as [`conv_out_len!`] in
+/// parentheses. Axes not named keep their extent, as for the channel axis of an image. Naming an axis
+/// twice or past the last dimension fails to compile
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // A 3 channel 64x48 image, indexed [channel, x, y]
+/// let extents = [3, 64, 48];
+/// let out = conv_out_shape!(extents; y: (kernel: 3, stride: 2, pad: 1), z: (kernel: 3, stride: 2, pad: 1));
+/// assert_eq!(out, [3, 32, 24]);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If any kernel or stride is zero
+///
+#[macro_export]
+macro_rules! conv_out_shape {
+    ( $extents:expr; $( $axis:ident : ( $( $param:ident : $value:expr ),+ $(,)? ) ),* $(,)? ) => {{
+        const _: () = assert!(
+            $crate::__private::indices_are_unique(&[ $( $crate::ax!($axis) ),* ]),
+            "axmac: conv_out_shape! names an axis more than once",
+        );
+        #[allow(clippy::needless_update)]
+        let named = [ $( (
+            $crate::ax!($axis),
+            $crate::__private::ConvParams { $( $param: $value, )+ ..$crate::__private::ConvParams::DEFAULT },
+        ) ),* ];
+        $crate::__private::ConvAxes::<{ $crate::__private::max_index(&[ $( $crate::ax!($axis) ),* ]) }>::apply($extents, named)
+    }};
+}
+
+
+///
+/// Returns the input position of the first tap of the window producing output `out_idx`, which is
+/// negative when the window starts in the padding
+///
+/// Takes the `stride` and `pad` of [`conv_out_len!`] by name, defaulting to one and zero, for writing
+/// custom pooling and convolution kernels. The macro is a const expression
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// assert_eq!(window_origin!(0; stride: 2, pad: 1), -1);
+/// assert_eq!(window_origin!(5; stride: 2, pad: 1), 9);
+/// assert_eq!(window_origin!(3; pad: 2), 1);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! window_origin {
+    ( $out:expr; $( $param:ident : $value:expr ),* $(,)? ) => {{
+        #[allow(clippy::needless_update)]
+        let params = $crate::__private::ConvParams { $( $param: $value, )* ..$crate::__private::ConvParams::DEFAULT };
+        params.window_origin($out)
+    }};
+}
+
+
+///
+/// Iterator over the input coordinates covered by the window of one output, with the x axis varying
+/// fastest, created by [`window_coords!`]
+///
+/// Taps falling in the padding, outside the input, are skipped
+///
+#[derive(Clone, Debug)]
+pub struct Window<const N: usize> {
+    origin: [isize; N],
+    dilation: [usize; N],
+    extents: [usize; N],
+    taps: NdIndex<usize, N>,
+}
+
+impl<const N: usize> Window<N> {
+
+    pub(crate) fn new(origin: [isize; N], kernel: [usize; N], dilation: [usize; N], extents: [usize; N]) -> Self {
+        Self { origin, dilation, extents, taps: NdIndex::new([0; N], kernel) }
+    }
+
+}
+
+impl<const N: usize> Iterator for Window<N> {
+    type Item = [usize; N];
+
+    fn next(&mut self) -> Option<[usize; N]> {
+        'taps: for tap in self.taps.by_ref() {
+            let mut coord = [0; N];
+            for axis in 0..N {
+                let pos = self.origin[axis] + (tap[axis] * self.dilation[axis]) as isize;
+                if pos < 0 || pos as usize >= self.extents[axis] {
+                    continue 'taps;
+                }
+                coord[axis] = pos as usize;
+            }
+            return Some(coord);
+        }
+        None
+    }
+}
+
+impl<const N: usize> FusedIterator for Window<N> {}
+
+
+///
+/// Iterates over the input coordinates covered by the window producing the output at `out` from an
+/// input with the given extents
+///
+/// The output coordinate is an array literal, an identifier or a parenthesized expression
+///
+/// Each axis named takes the parameters of [`conv_out_len!`], while axes not named map each output
+/// straight onto the input at the same position, as for the channel axis of an image. Taps in the
+/// padding are skipped, so a pooling kernel visits only real inputs. Naming an axis twice or past the
+/// last dimension fails to compile
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // 2x2 max pooling with stride 2 over a 4x4 image
+/// let image = [1, 5, 2, 0, 3, 4, 8, 1, 0, 0, 1, 1, 9, 2, 1, 6];
+/// let pooled = |ox: usize, oy: usize| {
+///     window_coords!([ox, oy] in [4, 4]; x: (kernel: 2, stride: 2), y: (kernel: 2, stride: 2))
+///         .map(|[x, y]| image[x + y * 4])
+///         .max()
+/// };
+/// assert_eq!(pooled(1, 0), Some(8));
+/// assert_eq!(pooled(0, 1), Some(9));
+///
+/// // A padded window at the corner only covers the inputs inside the image
+/// let taps = window_coords!([0, 0] in [4, 4]; x: (kernel: 3, pad: 1), y: (kernel: 3, pad: 1));
+/// assert!(taps.eq([[0, 0], [1, 0], [0, 1], [1, 1]]));
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If any kernel or stride is zero
+///
+#[macro_export]
+macro_rules! window_coords {
+    ( $out:tt in $extents:expr; $( $axis:ident : ( $( $param:ident : $value:expr ),+ $(,)? ) ),* $(,)? ) => {{
+        const _: () = assert!(
+            $crate::__private::indices_are_unique(&[ $( $crate::ax!($axis) ),* ]),
+            "axmac: window_coords! names an axis more than once",
+        );
+        #[allow(clippy::needless_update)]
+        let named = [ $( (
+            $crate::ax!($axis),
+            $crate::__private::ConvParams { $( $param: $value, )+ ..$crate::__private::ConvParams::DEFAULT },
+        ) ),* ];
+        $crate::__private::ConvAxes::<{ $crate::__private::max_index(&[ $( $crate::ax!($axis) ),* ]) }>::window($out, $extents, named)
+    }};
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn matches_formula() {
+        for len in 0..20 {
+            for (kernel, stride, pad, dilation) in [(1, 1, 0, 1), (3, 2, 1, 1), (5, 3, 0, 2), (2, 1, 4, 3)] {
+                let padded = (len + 2 * pad) as isize;
+                let expected = ((padded - dilation * (kernel - 1) - 1).div_euclid(stride) + 1).max(0) as usize;
+                let got = conv_out_len!(len; kernel: kernel as usize, stride: stride as usize, pad: pad, dilation: dilation as usize);
+                assert_eq!(got, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn window_matches_origin() {
+        let taps: std::vec::Vec<_> = window_coords!([3, 1] in [11, 3]; x: (kernel: 3, stride: 2, dilation: 2)).collect();
+        let origin = window_origin!(3; stride: 2) as usize;
+        assert_eq!(taps, [[origin, 1], [origin + 2, 1], [origin + 4, 1]]);
+        assert_eq!(window_coords!([0, 0] in [2, 2]; x: (kernel: 2, pad: 5)).count(), 0);
+    }
+
+    #[test]
+    fn shape_keeps_unnamed_axes() {
+        assert_eq!(conv_out_shape!([10, 10]; x: (kernel: 3)), [8, 10]);
+        assert_eq!(conv_out_shape!([10, 10];), [10, 10]);
+    }
+
+}
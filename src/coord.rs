@@ -0,0 +1,215 @@
+//!
+//! Fixed size coordinates with named components
+//!
+
+use core::ops::{Add, Index, IndexMut, Mul, Sub};
+
+use crate::Scalar;
+
+
+///
+/// An `N` dimensional coordinate whose components are read by axis
+///
+/// A thin wrapper around `[T; N]` which adds component-wise arithmetic and the accessors `x()`, `y()`,
+/// `z()` and `w()` for the dimensionalities that have them
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Coord3;
+///
+/// let a = Coord3::new([1, 2, 3]);
+/// let b = Coord3::new([10, 20, 30]);
+///
+/// assert_eq!((a + b).y(), 22);
+/// assert_eq!((b - a)[ax!(z)], 27);
+/// assert_eq!((a * 2).into_array(), [2, 4, 6]);
+/// # }
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Coord<T, const N: usize>(pub [T; N]);
+
+/// A 2 dimensional [`Coord`]
+pub type Coord2<T> = Coord<T, 2>;
+/// A 3 dimensional [`Coord`]
+pub type Coord3<T> = Coord<T, 3>;
+/// A 4 dimensional [`Coord`]
+pub type Coord4<T> = Coord<T, 4>;
+
+impl<T, const N: usize> Coord<T, N> {
+
+    ///
+    /// Wraps an array of components
+    ///
+    pub const fn new(components: [T; N]) -> Self {
+        Self(components)
+    }
+
+    ///
+    /// Returns the components as an array
+    ///
+    pub const fn as_array(&self) -> &[T; N] {
+        &self.0
+    }
+
+    ///
+    /// Returns the components as a mutable array
+    ///
+    pub const fn as_mut_array(&mut self) -> &mut [T; N] {
+        &mut self.0
+    }
+
+    ///
+    /// Unwraps the array of components
+    ///
+    pub fn into_array(self) -> [T; N] {
+        self.0
+    }
+
+    ///
+    /// Returns the component of `axis`, or `None` if the coordinate has no such axis
+    ///
+    pub const fn get(&self, axis: usize) -> Option<&T> {
+        if axis < N { Some(&self.0[axis]) } else { None }
+    }
+
+    ///
+    /// Returns the component of `axis` mutably, or `None` if the coordinate has no such axis
+    ///
+    pub const fn get_mut(&mut self, axis: usize) -> Option<&mut T> {
+        if axis < N { Some(&mut self.0[axis]) } else { None }
+    }
+
+}
+
+impl<T: Copy, const N: usize> Coord<T, N> {
+
+    ///
+    /// Creates a coordinate with every component set to `v`
+    ///
+    pub const fn splat(v: T) -> Self {
+        Self([v; N])
+    }
+
+}
+
+impl<T: Scalar, const N: usize> Coord<T, N> {
+
+    ///
+    /// The coordinate with every component set to zero
+    ///
+    pub const ZERO: Self = Self([T::ZERO; N]);
+
+}
+
+// Component accessors, implemented per dimensionality so `z()` does not exist on a 2-D coordinate
+macro_rules! impl_accessors {
+    ( $n:literal; $( $name:ident, $name_mut:ident => $axis:ident ); * ) => {
+        impl<T: Copy> Coord<T, $n> {
+            $(
+                #[doc = concat!("Returns the ", stringify!($axis), " component")]
+                pub const fn $name(&self) -> T {
+                    self.0[ax!($axis)]
+                }
+
+                #[doc = concat!("Returns the ", stringify!($axis), " component mutably")]
+                pub fn $name_mut(&mut self) -> &mut T {
+                    &mut self.0[ax!($axis)]
+                }
+            )*
+        }
+    };
+}
+
+impl_accessors!(2; x, x_mut => x; y, y_mut => y);
+impl_accessors!(3; x, x_mut => x; y, y_mut => y; z, z_mut => z);
+impl_accessors!(4; x, x_mut => x; y, y_mut => y; z, z_mut => z; w, w_mut => w);
+
+impl<T: Default, const N: usize> Default for Coord<T, N> {
+    fn default() -> Self {
+        Self(core::array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for Coord<T, N> {
+    fn from(components: [T; N]) -> Self {
+        Self(components)
+    }
+}
+
+impl<T, const N: usize> From<Coord<T, N>> for [T; N] {
+    fn from(coord: Coord<T, N>) -> Self {
+        coord.0
+    }
+}
+
+impl<T, const N: usize> AsRef<[T; N]> for Coord<T, N> {
+    fn as_ref(&self) -> &[T; N] {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Coord<T, N> {
+    type Output = T;
+
+    fn index(&self, axis: usize) -> &T {
+        &self.0[axis]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Coord<T, N> {
+    fn index_mut(&mut self, axis: usize) -> &mut T {
+        &mut self.0[axis]
+    }
+}
+
+impl<T: Scalar, const N: usize> Add for Coord<T, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self(core::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<T: Scalar, const N: usize> Sub for Coord<T, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self(core::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<T: Scalar, const N: usize> Mul<T> for Coord<T, N> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        Self(self.0.map(|v| v * rhs))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn accessors_work() {
+        let mut c = Coord4::new([1.0, 2.0, 3.0, 4.0]);
+        *c.w_mut() = 5.0;
+        assert_eq!([c.x(), c.y(), c.z(), c.w()], [1.0, 2.0, 3.0, 5.0]);
+        assert_eq!(c.get(ax!(z)), Some(&3.0));
+        assert_eq!(Coord2::new([1u8, 2]).get(ax!(z)), None);
+    }
+
+    #[test]
+    fn arithmetic_works() {
+        let a = Coord2::new([3i32, -4]);
+        assert_eq!(a + Coord2::splat(1), Coord2::new([4, -3]));
+        assert_eq!(a - a, Coord2::ZERO);
+        assert_eq!(a * -1, Coord2::new([-3, 4]));
+    }
+
+}
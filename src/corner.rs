@@ -0,0 +1,173 @@
+//!
+//! Corners of unit cells and hypercubes, indexed with one bit per axis
+//!
+//! Corner `i` of an N-D cube lies at `1` along every axis whose bit is set in `i` and at `0` along the
+//! others, so the x bit is the lowest. Moving along one axis toggles its bit, which is how marching cubes
+//! style code steps between corners
+//!
+
+use core::iter::FusedIterator;
+
+use crate::Axis;
+
+
+///
+/// Returns the corner reached by moving from `corner` along each named axis, toggling their bits
+///
+/// Works on any integer corner index and is a const expression. Naming an axis twice fails to compile,
+/// since the moves would cancel out
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // Corner 0b011 lies at x = 1, y = 1, z = 0
+/// assert_eq!(toggle_axis!(0b011; z), 0b111);
+/// assert_eq!(toggle_axis!(0b011; x, z), 0b110);
+///
+/// const OPPOSITE: u8 = toggle_axis!(0u8; x, y, z);
+/// assert_eq!(OPPOSITE, 7);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: toggle_axis! names an axis more than once
+/// let corner = toggle_axis!(0; y, y);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! toggle_axis {
+    ( $corner:expr; $( $axis:ident ),+ $(,)? ) => {{
+        const _: () = assert!(
+            $crate::__private::indices_are_unique(&[ $( $crate::ax!($axis) ),+ ]),
+            "axmac: toggle_axis! names an axis more than once",
+        );
+        $corner ^ ( 0 $( | 1 << $crate::ax!($axis) )+ )
+    }};
+}
+
+
+///
+/// Returns the `N` corners next to `corner`, reached by moving along each axis in turn
+///
+/// ```
+/// use axmac::corner_neighbors;
+///
+/// assert_eq!(corner_neighbors::<3>(0b101), [0b100, 0b111, 0b001]);
+/// ```
+///
+pub const fn corner_neighbors<const N: usize>(corner: usize) -> [usize; N] {
+    const { assert!(N <= Axis::COUNT, "axmac: corners have at most four axes") };
+    let mut out = [0; N];
+    let mut i = 0;
+    while i < N {
+        out[i] = corner ^ (1 << i);
+        i += 1;
+    }
+    out
+}
+
+///
+/// Returns the position of `corner` along each of `N` axes, as zero or one
+///
+/// ```
+/// use axmac::corner_coord;
+///
+/// assert_eq!(corner_coord::<3>(0b110), [0, 1, 1]);
+/// ```
+///
+pub const fn corner_coord<const N: usize>(corner: usize) -> [usize; N] {
+    const { assert!(N <= Axis::COUNT, "axmac: corners have at most four axes") };
+    let mut out = [0; N];
+    let mut i = 0;
+    while i < N {
+        out[i] = (corner >> i) & 1;
+        i += 1;
+    }
+    out
+}
+
+
+///
+/// Returns the moves visiting every corner of an `N`-D cube once, starting from corner zero and toggling
+/// a single axis per move
+///
+/// Corners are visited in Gray code order, the standard traversal of a hypercube along its edges. Each
+/// move gives the axis moved along and the corner reached, for `2^N - 1` moves in all
+///
+/// ```
+/// use axmac::{gray_moves, Axis};
+///
+/// let square: Vec<_> = gray_moves::<2>().collect();
+/// assert_eq!(square, [(Axis::X, 0b01), (Axis::Y, 0b11), (Axis::X, 0b10)]);
+/// assert_eq!(gray_moves::<4>().len(), 15);
+/// ```
+///
+pub const fn gray_moves<const N: usize>() -> GrayMoves<N> {
+    const { assert!(N <= Axis::COUNT, "axmac: corners have at most four axes") };
+    GrayMoves { step: 1 }
+}
+
+
+///
+/// Iterator over the moves of a Gray code traversal of a hypercube, created by [`gray_moves`]
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GrayMoves<const N: usize> {
+    step: usize,
+}
+
+impl<const N: usize> Iterator for GrayMoves<N> {
+    type Item = (Axis, usize);
+
+    fn next(&mut self) -> Option<(Axis, usize)> {
+        if self.step >= 1 << N {
+            return None;
+        }
+        let step = self.step;
+        self.step += 1;
+        // The bit that changes between consecutive Gray codes is the lowest set bit of the step
+        let axis = Axis::from_usize(step.trailing_zeros() as usize)?;
+        Some((axis, step ^ (step >> 1)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (1usize << N).saturating_sub(self.step);
+        (len, Some(len))
+    }
+}
+
+impl<const N: usize> ExactSizeIterator for GrayMoves<N> {}
+
+impl<const N: usize> FusedIterator for GrayMoves<N> {}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn moves_visit_every_corner_once() {
+        let mut corner = 0;
+        let mut seen = [false; 16];
+        seen[0] = true;
+        for (axis, next) in gray_moves::<4>() {
+            assert!(corner_neighbors::<4>(corner).contains(&next));
+            assert_eq!(corner ^ next, 1 << axis.as_usize());
+            assert!(!core::mem::replace(&mut seen[next], true));
+            corner = next;
+        }
+        assert!(seen.iter().all(|&s| s));
+        assert_eq!(gray_moves::<0>().next(), None);
+    }
+
+    #[test]
+    fn toggles_match_coords() {
+        let corner = toggle_axis!(0usize; w, y);
+        assert_eq!(corner_coord::<4>(corner), [0, 1, 0, 1]);
+        assert_eq!(toggle_axis!(corner; y), 0b1000);
+    }
+
+}
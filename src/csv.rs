@@ -0,0 +1,96 @@
+//!
+//! Export of 2-D slices of shaped buffers as CSV, for debugging dumps and spreadsheets
+//!
+
+use std::fmt::{Display, Write as _};
+use std::io::{self, Write};
+use std::string::String;
+
+use crate::{Axis, Shape};
+
+
+///
+/// Writes the 2-D slice of `buf` spanned by `row_axis` and `col_axis` to `w` as CSV, one line per
+/// position along `row_axis`
+///
+/// Every other axis is held at its index in `fixed`, whose entries for the row and column axes are
+/// ignored. Cells are quoted as RFC 4180 asks when their text holds a comma, quote or line break, and
+/// lines end in `\n`. No header is written
+///
+/// ```
+/// use axmac::{write_csv, Axis, Shape};
+///
+/// // A 3x2x2 volume, dumping the y-z plane at x = 1 with z down the rows
+/// let buf: Vec<u32> = (0..12).collect();
+/// let mut out = Vec::new();
+/// write_csv(&mut out, &buf, &Shape::new([3, 2, 2]), Axis::Z, Axis::Y, [1, 0, 0]).unwrap();
+/// assert_eq!(String::from_utf8(out).unwrap(), "1,4\n7,10\n");
+/// ```
+///
+/// # Panics
+///
+/// If the row and column axes are the same or not less than `N`, a fixed index lies outside its extent,
+/// or `buf` is too short for `shape`
+///
+pub fn write_csv<W: Write, T: Display, const N: usize>(
+    mut w: W,
+    buf: &[T],
+    shape: &Shape<N>,
+    row_axis: Axis,
+    col_axis: Axis,
+    fixed: [usize; N],
+) -> io::Result<()> {
+    let (row, col) = (row_axis.as_usize(), col_axis.as_usize());
+    assert!(row < N && col < N, "axmac: write_csv axes must be less than the number of dimensions");
+    assert!(row != col, "axmac: write_csv needs different axes for rows and columns");
+    assert!(buf.len() >= shape.required_len(), "axmac: buffer is too short for its shape");
+    for (axis, &i) in fixed.iter().enumerate() {
+        assert!(axis == row || axis == col || i < shape.extent(axis), "axmac: fixed index is outside the shape");
+    }
+
+    let mut coord = fixed;
+    let mut cell = String::new();
+    for r in 0..shape.extent(row) {
+        coord[row] = r;
+        for c in 0..shape.extent(col) {
+            coord[col] = c;
+            if c > 0 {
+                w.write_all(b",")?;
+            }
+            cell.clear();
+            // Writing to a string cannot fail
+            let _ = write!(cell, "{}", buf[shape.flat_index_unchecked(&coord)]);
+            if cell.contains([',', '"', '\n', '\r']) {
+                write!(w, "\"{}\"", cell.replace('"', "\"\""))?;
+            } else {
+                w.write_all(cell.as_bytes())?;
+            }
+        }
+        w.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn cells_are_quoted() {
+        let buf = ["a", "b,c", "say \"hi\"", "d"];
+        let mut out = Vec::new();
+        write_csv(&mut out, &buf, &Shape::new([2, 2]), Axis::Y, Axis::X, [0; 2]).unwrap();
+        assert_eq!(out, b"a,\"b,c\"\n\"say \"\"hi\"\"\",d\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac: fixed index is outside the shape")]
+    fn fixed_indices_are_checked() {
+        let _ = write_csv(Vec::new(), &[0; 8], &Shape::new([2, 2, 2]), Axis::X, Axis::Y, [0, 0, 2]);
+    }
+
+}
@@ -0,0 +1,74 @@
+//!
+//! Cubemap face selection
+//!
+
+use crate::{dominant_axis, Axis, SignedAxis, TieBreak};
+
+
+///
+/// Returns the cubemap face `dir` points at and the face-local texture coordinates it hits
+///
+/// Follows the face orientations shared by OpenGL, Vulkan and Direct3D, with both coordinates in `0..=1`
+/// and `v` increasing downwards across the face image. `dir` need not be normalized. When it points exactly
+/// at an edge or corner, the face of the lowest axis wins
+///
+/// ```
+/// use axmac::{cubemap_face, SignedAxis};
+///
+/// assert_eq!(cubemap_face([0.0, 0.0, 1.0]), (SignedAxis::POS_Z, [0.5, 0.5]));
+/// assert_eq!(cubemap_face([-2.0, 1.0, 0.0]), (SignedAxis::NEG_X, [0.5, 0.25]));
+/// ```
+///
+/// A zero or NaN `dir` points at no face, and produces NaN coordinates
+///
+pub fn cubemap_face(dir: [f32; 3]) -> (SignedAxis, [f32; 2]) {
+    let [x, y, z] = dir;
+    let axis = dominant_axis(&dir, TieBreak::First).unwrap_or(Axis::X);
+    let major = dir[axis.as_usize()];
+    let face = SignedAxis::new(axis, major < 0.0);
+
+    let (s, t) = match (axis, face.is_negative()) {
+        (Axis::X, false) => (-z, -y),
+        (Axis::X, true)  => (z, -y),
+        (Axis::Y, false) => (x, z),
+        (Axis::Y, true)  => (x, -z),
+        (_, false)       => (x, -y),
+        (_, true)        => (-x, -y),
+    };
+    let m = major.abs();
+    (face, [0.5 * (s / m + 1.0), 0.5 * (t / m + 1.0)])
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn face_centers_work() {
+        let faces = [
+            ([1.0, 0.0, 0.0], SignedAxis::POS_X),
+            ([-1.0, 0.0, 0.0], SignedAxis::NEG_X),
+            ([0.0, 1.0, 0.0], SignedAxis::POS_Y),
+            ([0.0, -1.0, 0.0], SignedAxis::NEG_Y),
+            ([0.0, 0.0, 1.0], SignedAxis::POS_Z),
+            ([0.0, 0.0, -1.0], SignedAxis::NEG_Z),
+        ];
+        for (dir, face) in faces {
+            assert_eq!(cubemap_face(dir), (face, [0.5, 0.5]));
+        }
+    }
+
+    #[test]
+    fn orientation_works() {
+        // Up on the side faces is towards the top of the face image
+        assert_eq!(cubemap_face([1.0, 0.5, 0.0]).1, [0.5, 0.25]);
+        assert_eq!(cubemap_face([0.0, 0.5, -1.0]).1, [0.5, 0.25]);
+        // +y is seen from above, with -z at the top of the image
+        assert_eq!(cubemap_face([0.5, 1.0, -0.5]), (SignedAxis::POS_Y, [0.75, 0.25]));
+        assert!(cubemap_face([0.0; 3]).1[0].is_nan());
+    }
+
+}
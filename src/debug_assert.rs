@@ -0,0 +1,69 @@
+//!
+//! Debug-only validation of axis indices computed at runtime
+//!
+
+
+///
+/// Checks that an axis index, array of indices or range produced at runtime lies within the axes named
+/// by the enabled vocabularies, evaluating to the checked value
+///
+/// The expression arms of `axr!` accept any `usize`, so a bound computed at runtime can run past the last
+/// axis without any error until the range is used. The check only runs when `debug_assertions` are
+/// enabled, compiling down to the value itself in release builds
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let coords = [1.0, 2.0, 3.0, 4.0];
+/// let n = 3;
+/// assert_eq!(coords[debug_ax_assert!(axr!(y..(n)))], [2.0, 3.0]);
+/// assert_eq!(debug_ax_assert!(axs![x, z]), [0, 2]);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// In debug builds, if any index referred to is not less than [`consts::COUNT`](crate::consts::COUNT)
+///
+#[macro_export]
+macro_rules! debug_ax_assert {
+    ( $axes:expr $(,)? ) => {{
+        let axes = $axes;
+        debug_assert!(
+            $crate::__private::AxisBounds::within(&axes, $crate::consts::COUNT),
+            "axmac: {:?} is out of range for the {} enabled axes",
+            axes,
+            $crate::consts::COUNT,
+        );
+        axes
+    }};
+}
+
+
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+
+    use crate::consts::COUNT;
+
+    #[test]
+    fn in_range_passes_through() {
+        assert_eq!(debug_ax_assert!(ax!(w)), 3);
+        assert_eq!(debug_ax_assert!(axr!(x..(COUNT))), 0..COUNT);
+        assert_eq!(debug_ax_assert!(axr!(..=w)), ..=3);
+        assert_eq!(debug_ax_assert!(..), ..);
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac:")]
+    fn index_out_of_range_panics() {
+        debug_ax_assert!(COUNT);
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac:")]
+    fn range_out_of_range_panics() {
+        let end = COUNT + 1;
+        debug_ax_assert!(axr!(y..(end)));
+    }
+
+}
@@ -0,0 +1,259 @@
+//!
+//! User defined axis vocabularies
+//!
+
+
+///
+/// Defines an `ax!`-like macro for a custom set of axis names
+///
+/// Each name is mapped to the `usize` index given for it. The generated macro is an ordinary
+/// `macro_rules!` macro, so it must be defined before it is used and can be exported with
+/// `#[macro_export]` like any other
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// define_axes!(grid { row => 0, col => 1, layer => 2 });
+///
+/// let cells = [[[0u8; 2]; 3]; 4];
+/// assert_eq!(grid!(layer), 2);
+/// assert_eq!(cells.len(), 4);
+/// # }
+/// ```
+///
+/// The generated macro also accepts the forms of [`axs!`](crate::axs), wrapped in brackets, and of
+/// [`axr!`](crate::axr), so the whole family works with the custom names
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// define_axes!(grid { row => 0, col => 1, layer => 2 });
+///
+/// assert_eq!(grid!([layer, row]), [2, 0]);
+/// assert_eq!(grid!([col; 3]), [1, 1, 1]);
+/// assert_eq!(grid!(row..layer), 0..2);
+/// assert_eq!(grid!(col..=5), 1..=5);
+/// assert_eq!(grid!((0)..=col), 0..=1);
+/// assert_eq!(grid!(..layer), ..2);
+/// # }
+/// ```
+///
+/// # Validation
+///
+/// Mistakes in the mapping are rejected when the macro is defined, naming the offending axis
+///
+/// - Two names mapped to the same index
+/// - A name given more than once
+/// - A Rust keyword used as a name, which later forms of the generated macro could not parse reliably
+///
+/// An optional `assert` block locks the numbering of chosen axes, so reordering the mapping during a
+/// refactor fails to compile instead of silently moving data between axes
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: define_axes! assert failed, `lat` is no longer 1
+/// define_axes!(geo { time => 0, lon => 1, lat => 2 } assert { time == 0, lat == 1 });
+/// # }
+/// ```
+///
+/// Prefixing the macro name with `dense` additionally requires the indices to cover `0..n` without gaps
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: define_axes! maps `layer` to an index leaving a gap
+/// define_axes!(dense grid { row => 0, col => 1, layer => 3 });
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: define_axes! maps `row` to an index already in use
+/// define_axes!(grid { row => 0, col => 0 });
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: define_axes! names `row` more than once
+/// define_axes!(grid { row => 0, row => 1 });
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: `in` is a Rust keyword and cannot name an axis
+/// define_axes!(grid { row => 0, in => 1 });
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! define_axes {
+
+    (
+        $( #[$meta:meta] )* dense $name:ident { $( $axis:ident => $idx:expr ),+ $(,)? }
+        $( assert { $( $locked:ident == $expected:expr ),* $(,)? } )?
+    ) => {
+        $( $crate::__define_axes_reserved!($axis); )+
+        $crate::define_axes!(@check dense [ $( $axis => $idx ),+ ]);
+        $crate::define_axes!(@emit ($) $( #[$meta] )* $name [ $( $axis => $idx ),+ ]);
+        $( $( $crate::define_axes!(@assert $name $locked $expected); )* )?
+    };
+
+    (
+        $( #[$meta:meta] )* $name:ident { $( $axis:ident => $idx:expr ),+ $(,)? }
+        $( assert { $( $locked:ident == $expected:expr ),* $(,)? } )?
+    ) => {
+        $( $crate::__define_axes_reserved!($axis); )+
+        $crate::define_axes!(@check sparse [ $( $axis => $idx ),+ ]);
+        $crate::define_axes!(@emit ($) $( #[$meta] )* $name [ $( $axis => $idx ),+ ]);
+        $( $( $crate::define_axes!(@assert $name $locked $expected); )* )?
+    };
+
+    ( @check $mode:ident [ $( $axis:ident => $idx:expr ),+ ] ) => {
+        const _: () = {
+            let indices = [ $( $crate::__private::axis_index($idx) ),+ ];
+            let names = [ $( stringify!($axis) ),+ ];
+            $(
+                assert!(
+                    $crate::__private::count_name(stringify!($axis), &names) == 1,
+                    concat!("axmac: define_axes! names `", stringify!($axis), "` more than once"),
+                );
+                assert!(
+                    $crate::__private::count_index($idx, &indices) == 1,
+                    concat!("axmac: define_axes! maps `", stringify!($axis), "` to an index already in use"),
+                );
+                $crate::define_axes!(@gap $mode $axis $idx, indices);
+            )+
+        };
+    };
+
+    ( @gap dense $axis:ident $idx:expr, $indices:ident ) => {
+        assert!(
+            $idx < $indices.len(),
+            concat!("axmac: define_axes! maps `", stringify!($axis), "` to an index leaving a gap"),
+        );
+    };
+    ( @gap sparse $axis:ident $idx:expr, $indices:ident ) => {};
+
+    ( @assert $name:ident $locked:ident $expected:expr ) => {
+        const _: () = assert!(
+            $name!($locked) == $expected,
+            concat!("axmac: define_axes! assert failed, `", stringify!($locked), "` is no longer ", stringify!($expected)),
+        );
+    };
+
+    // The `$` token is passed in so the generated macro can declare metavariables of its own
+    ( @emit ($d:tt) $( #[$meta:meta] )* $name:ident [ $( $axis:ident => $idx:expr ),+ ] ) => {
+        $( #[$meta] )*
+        macro_rules! $name {
+            $( ($axis) => { $crate::__private::axis_index($idx) }; )+
+
+            // The `axs!` forms, wrapped in brackets
+            ( [ $d( $d a:ident ),* $d(,)? ] ) => { [ $d( $name!($d a) ),* ] };
+            ( [ $d a:ident ; $d n:expr ] ) => { [ $name!($d a); $d n ] };
+
+            // The `axr!` forms
+            ( $d a:ident .. $d b:ident ) => { $name!($d a)..$name!($d b) };
+            ( $d a:ident ..= $d b:ident ) => { $name!($d a)..=$name!($d b) };
+            ( $d a:ident .. $d b:expr ) => { $name!($d a)..$d b };
+            ( $d a:ident ..= $d b:expr ) => { $name!($d a)..=$d b };
+            ( .. $d a:ident ) => { ..$name!($d a) };
+            ( ..= $d a:ident ) => { ..=$name!($d a) };
+            ( $d a:ident .. ) => { $name!($d a).. };
+            ( ($d a:expr) .. $d b:ident ) => { $d a..$name!($d b) };
+            ( ($d a:expr) ..= $d b:ident ) => { $d a..=$name!($d b) };
+
+            ( $d other:tt ) => {
+                compile_error!(concat!("axmac: `", stringify!($d other), "` is not an axis of ", stringify!($name), "!"))
+            };
+        }
+    };
+
+}
+
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __define_axes_reserved {
+    (as) => { $crate::__define_axes_reserved!(@error as); };
+    (async) => { $crate::__define_axes_reserved!(@error async); };
+    (await) => { $crate::__define_axes_reserved!(@error await); };
+    (break) => { $crate::__define_axes_reserved!(@error break); };
+    (const) => { $crate::__define_axes_reserved!(@error const); };
+    (continue) => { $crate::__define_axes_reserved!(@error continue); };
+    (crate) => { $crate::__define_axes_reserved!(@error crate); };
+    (dyn) => { $crate::__define_axes_reserved!(@error dyn); };
+    (else) => { $crate::__define_axes_reserved!(@error else); };
+    (enum) => { $crate::__define_axes_reserved!(@error enum); };
+    (extern) => { $crate::__define_axes_reserved!(@error extern); };
+    (false) => { $crate::__define_axes_reserved!(@error false); };
+    (fn) => { $crate::__define_axes_reserved!(@error fn); };
+    (for) => { $crate::__define_axes_reserved!(@error for); };
+    (if) => { $crate::__define_axes_reserved!(@error if); };
+    (impl) => { $crate::__define_axes_reserved!(@error impl); };
+    (in) => { $crate::__define_axes_reserved!(@error in); };
+    (let) => { $crate::__define_axes_reserved!(@error let); };
+    (loop) => { $crate::__define_axes_reserved!(@error loop); };
+    (match) => { $crate::__define_axes_reserved!(@error match); };
+    (mod) => { $crate::__define_axes_reserved!(@error mod); };
+    (move) => { $crate::__define_axes_reserved!(@error move); };
+    (mut) => { $crate::__define_axes_reserved!(@error mut); };
+    (pub) => { $crate::__define_axes_reserved!(@error pub); };
+    (ref) => { $crate::__define_axes_reserved!(@error ref); };
+    (return) => { $crate::__define_axes_reserved!(@error return); };
+    (self) => { $crate::__define_axes_reserved!(@error self); };
+    (Self) => { $crate::__define_axes_reserved!(@error Self); };
+    (static) => { $crate::__define_axes_reserved!(@error static); };
+    (struct) => { $crate::__define_axes_reserved!(@error struct); };
+    (super) => { $crate::__define_axes_reserved!(@error super); };
+    (trait) => { $crate::__define_axes_reserved!(@error trait); };
+    (true) => { $crate::__define_axes_reserved!(@error true); };
+    (type) => { $crate::__define_axes_reserved!(@error type); };
+    (unsafe) => { $crate::__define_axes_reserved!(@error unsafe); };
+    (use) => { $crate::__define_axes_reserved!(@error use); };
+    (where) => { $crate::__define_axes_reserved!(@error where); };
+    (while) => { $crate::__define_axes_reserved!(@error while); };
+    ( @error $kw:tt ) => {
+        compile_error!(concat!("axmac: `", stringify!($kw), "` is a Rust keyword and cannot name an axis"));
+    };
+    ( $axis:ident ) => {};
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    define_axes!(grid { row => 0, col => 1, layer => 2 });
+    define_axes!(dense time { t => 1, lat => 0 });
+    define_axes!(sparse_bands { red => 0, nir => 7 } assert { nir == 7 });
+    define_axes!(dense geo { time => 0, lat => 1, lon => 2 } assert { time == 0, lat == 1, });
+
+    #[test]
+    fn it_works() {
+        assert_eq!([grid!(row), grid!(col), grid!(layer)], [0, 1, 2]);
+        assert_eq!([time!(lat), time!(t)], [0, 1]);
+        assert_eq!(sparse_bands!(nir), 7);
+        assert_eq!(geo!(lon), 2);
+    }
+
+    #[test]
+    fn const_indices_work() {
+        const LAYER: usize = 2;
+        define_axes!(volume { depth => LAYER, height => LAYER - 1 });
+        let sizes = [4, 5, 6];
+        assert_eq!(sizes[volume!(depth)], 6);
+        assert_eq!(sizes[volume!(height)], 5);
+    }
+
+    #[test]
+    fn array_and_range_forms_work() {
+        let n = 4;
+        assert_eq!(grid!([layer, col, row,]), [2, 1, 0]);
+        assert_eq!(sparse_bands!([nir; 2]), [7, 7]);
+        assert_eq!(grid!(col..=layer), 1..=2);
+        assert_eq!(grid!(row..(n)), 0..4);
+        assert_eq!(grid!(..=col), ..=1);
+        assert_eq!(grid!(layer..), 2..);
+        assert_eq!(grid!((n - 4)..layer), 0..2);
+    }
+
+}
@@ -0,0 +1,154 @@
+//!
+//! Delta coding of shaped buffers along a named axis, ahead of compression
+//!
+
+use crate::{Axis, Shape};
+
+
+///
+/// Values which can be replaced by their difference from the previous value and restored from it
+///
+/// Integers wrap around, so decoding restores every value exactly whatever the differences. Floats use
+/// plain subtraction, which may lose the lowest bits
+///
+pub trait DeltaCode: Copy {
+    /// Returns the difference from `prev`
+    fn delta(self, prev: Self) -> Self;
+    /// Returns the value this difference from `prev` was computed from
+    fn undelta(self, prev: Self) -> Self;
+}
+
+macro_rules! impl_delta_int {
+    ( $( $t:ty ),* ) => {
+        $(
+            impl DeltaCode for $t {
+                fn delta(self, prev: Self) -> Self {
+                    self.wrapping_sub(prev)
+                }
+
+                fn undelta(self, prev: Self) -> Self {
+                    self.wrapping_add(prev)
+                }
+            }
+        )*
+    };
+}
+
+impl_delta_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl DeltaCode for f32 {
+    fn delta(self, prev: Self) -> Self {
+        self - prev
+    }
+
+    fn undelta(self, prev: Self) -> Self {
+        self + prev
+    }
+}
+
+impl DeltaCode for f64 {
+    fn delta(self, prev: Self) -> Self {
+        self - prev
+    }
+
+    fn undelta(self, prev: Self) -> Self {
+        self + prev
+    }
+}
+
+
+///
+/// Replaces every element of `buf` by its difference from the element before it along `axis`, in place
+///
+/// The first element of every line along `axis` is kept as it is. Smooth images and slowly changing time
+/// series turn into runs of small numbers, which compress far better. Lines follow the coordinates of
+/// `shape`, so reversed axes are coded in their logical order
+///
+/// ```
+/// use axmac::{delta_decode_along, delta_encode_along, Axis, Shape};
+///
+/// // Two rows of a gradient
+/// let mut buf = [10u8, 12, 14, 16, 50, 49, 48, 47];
+/// let shape = Shape::new([4, 2]);
+///
+/// delta_encode_along(&mut buf, &shape, Axis::X);
+/// assert_eq!(buf, [10, 2, 2, 2, 50, 255, 255, 255]);
+///
+/// delta_decode_along(&mut buf, &shape, Axis::X);
+/// assert_eq!(buf, [10, 12, 14, 16, 50, 49, 48, 47]);
+/// ```
+///
+/// # Panics
+///
+/// If `axis` is not less than `N`, or `buf` is too short for `shape`
+///
+pub fn delta_encode_along<T: DeltaCode, const N: usize>(buf: &mut [T], shape: &Shape<N>, axis: Axis) {
+    for_lines(buf, shape, axis, |buf, len, at| {
+        for k in (1..len).rev() {
+            buf[at(k)] = buf[at(k)].delta(buf[at(k - 1)]);
+        }
+    });
+}
+
+///
+/// Restores a buffer coded by [`delta_encode_along`] along the same axis, in place
+///
+/// # Panics
+///
+/// As [`delta_encode_along`]
+///
+pub fn delta_decode_along<T: DeltaCode, const N: usize>(buf: &mut [T], shape: &Shape<N>, axis: Axis) {
+    for_lines(buf, shape, axis, |buf, len, at| {
+        for k in 1..len {
+            buf[at(k)] = buf[at(k)].undelta(buf[at(k - 1)]);
+        }
+    });
+}
+
+// Calls `f` on every line along `axis` with its length and the flat index of its k-th element
+fn for_lines<T, F, const N: usize>(buf: &mut [T], shape: &Shape<N>, axis: Axis, mut f: F)
+where
+    F: FnMut(&mut [T], usize, &dyn Fn(usize) -> usize),
+{
+    let axis = axis.as_usize();
+    assert!(axis < N, "axmac: axis is past the last dimension of the shape");
+    assert!(buf.len() >= shape.required_len(), "axmac: buffer is too short for its shape");
+
+    let mut starts = *shape.extents();
+    let len = core::mem::replace(&mut starts[axis], 1);
+    for start in crate::NdIndex::new([0; N], starts) {
+        let at = |k: usize| {
+            let mut coord = start;
+            coord[axis] = k;
+            shape.flat_index_unchecked(&coord)
+        };
+        f(buf, len, &at);
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn columns_round_trip() {
+        let original: [i16; 12] = core::array::from_fn(|i| ((i * 7919) % 101) as i16 - 50);
+        let shape = Shape::col_major([3, 4]).reverse_axis(Axis::Y);
+        let mut buf = original;
+        delta_encode_along(&mut buf, &shape, Axis::Y);
+        assert_ne!(buf, original);
+        delta_decode_along(&mut buf, &shape, Axis::Y);
+        assert_eq!(buf, original);
+    }
+
+    #[test]
+    fn first_elements_are_kept() {
+        let mut buf = [1.0, 2.0, 4.0, 8.0, 16.0, 32.0];
+        delta_encode_along(&mut buf, &Shape::new([2, 3]), Axis::Y);
+        assert_eq!(buf, [1.0, 2.0, 3.0, 6.0, 12.0, 24.0]);
+    }
+
+}
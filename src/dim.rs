@@ -0,0 +1,247 @@
+//!
+//! Type level dimensionalities, for code written once for every supported number of axes
+//!
+
+use core::marker::PhantomData;
+
+use crate::marker::{AxisMarker, W, X, Y, Z};
+use crate::{Aabb, AxisMap, AxisSet, Coord, InvalidAxis, Shape};
+
+
+///
+/// Names a number of axes at the type level
+///
+/// Only the supported dimensionalities, [`D1`] to [`D4`], implement [`Dimensionality`]
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Dim<const N: usize>;
+
+/// One axis, _x_
+pub type D1 = Dim<1>;
+/// Two axes, _x_ and _y_
+pub type D2 = Dim<2>;
+/// Three axes, _x_ to _z_
+pub type D3 = Dim<3>;
+/// Four axes, _x_ to _w_
+pub type D4 = Dim<4>;
+
+
+///
+/// Implemented by [`D1`] to [`D4`], carrying the number of axes and the crate's types of that
+/// dimensionality
+///
+/// ```
+/// use axmac::{Dimensionality, Dimensioned, Shape, D3};
+///
+/// fn is_volume<S: Dimensioned>(_: &S) -> bool {
+///     S::Dim::AXES == 3
+/// }
+///
+/// fn origin<D: Dimensionality>() -> D::Array<i32>
+/// where
+///     D::Array<i32>: Default,
+/// {
+///     Default::default()
+/// }
+///
+/// assert!(is_volume(&Shape::new([4, 4, 4])));
+/// assert!(!is_volume(&[1.0, 2.0]));
+/// assert_eq!(origin::<D3>(), [0, 0, 0]);
+/// assert!(D3::check_axis(3).is_err());
+/// ```
+///
+pub trait Dimensionality: Copy + Default + 'static {
+    /// The number of axes
+    const AXES: usize;
+
+    /// One value per axis
+    type Array<T>;
+    /// A [`Coord`] with one component per axis
+    type Coord<T>;
+    /// A [`Shape`] with one extent per axis
+    type Shape;
+    /// An [`Aabb`] with one bound per axis
+    type Aabb<T>;
+
+    ///
+    /// Returns whether `axis` is one of the axes
+    ///
+    fn contains_axis(axis: usize) -> bool {
+        axis < Self::AXES
+    }
+
+    ///
+    /// Returns `axis` unchanged if it is one of the axes
+    ///
+    fn check_axis(axis: usize) -> Result<usize, InvalidAxis> {
+        if Self::contains_axis(axis) { Ok(axis) } else { Err(InvalidAxis(axis)) }
+    }
+
+    ///
+    /// Returns the set of every axis
+    ///
+    fn axes() -> AxisSet {
+        AxisSet::first(Self::AXES)
+    }
+}
+
+
+///
+/// Implemented by every fixed-dimensional value of a supported dimensionality, naming it
+///
+pub trait Dimensioned {
+    /// The dimensionality of the value
+    type Dim: Dimensionality;
+}
+
+
+macro_rules! impl_dims {
+    ( $( $n:literal ),+ ) => {
+        $(
+            impl Dimensionality for Dim<$n> {
+                const AXES: usize = $n;
+                type Array<T> = [T; $n];
+                type Coord<T> = Coord<T, $n>;
+                type Shape = Shape<$n>;
+                type Aabb<T> = Aabb<T, $n>;
+            }
+
+            impl<T> Dimensioned for [T; $n] {
+                type Dim = Dim<$n>;
+            }
+
+            impl<T> Dimensioned for Coord<T, $n> {
+                type Dim = Dim<$n>;
+            }
+
+            impl<T> Dimensioned for AxisMap<T, $n> {
+                type Dim = Dim<$n>;
+            }
+
+            impl<T> Dimensioned for Aabb<T, $n> {
+                type Dim = Dim<$n>;
+            }
+
+            impl Dimensioned for Shape<$n> {
+                type Dim = Dim<$n>;
+            }
+        )+
+    };
+}
+
+impl_dims!(1, 2, 3, 4);
+
+
+///
+/// Implemented by the [marker types](crate::marker) of the axes a dimensionality has, so `Z: AxisOf<D2>`
+/// does not hold
+///
+#[diagnostic::on_unimplemented(message = "axmac: `{Self}` is not an axis of `{D}`")]
+pub trait AxisOf<D: Dimensionality>: AxisMarker {}
+
+macro_rules! impl_axis_of {
+    ( $( $marker:ident => $( $n:literal ),+ );+ ) => {
+        $( $( impl AxisOf<Dim<$n>> for $marker {} )+ )+
+    };
+}
+
+impl_axis_of!(X => 1, 2, 3, 4; Y => 2, 3, 4; Z => 3, 4; W => 4);
+
+
+///
+/// Accesses the component of axis `A` in values of dimensionality `D`, see [`component`]
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Component<A, D>(PhantomData<(A, D)>);
+
+impl<A: AxisOf<D>, D: Dimensionality> Component<A, D> {
+
+    ///
+    /// Returns the component of `v`
+    ///
+    pub fn get<T>(self, v: &D::Array<T>) -> &T
+    where
+        D::Array<T>: AsRef<[T]>,
+    {
+        &v.as_ref()[A::INDEX]
+    }
+
+    ///
+    /// Returns the component of `v` mutably
+    ///
+    pub fn get_mut<T>(self, v: &mut D::Array<T>) -> &mut T
+    where
+        D::Array<T>: AsMut<[T]>,
+    {
+        &mut v.as_mut()[A::INDEX]
+    }
+
+}
+
+///
+/// Selects the component of axis `A` in values of dimensionality `D`, failing to compile if `D` has no
+/// such axis
+///
+/// Unlike enabling or disabling vocabulary features, the check is made separately at every call site
+///
+/// ```
+/// use axmac::marker::{Y, Z};
+/// use axmac::{component, D2, D3};
+///
+/// let mut v = [1.0, 2.0, 3.0];
+/// assert_eq!(component::<Z, D3>().get(&v), &3.0);
+/// *component::<Y, D3>().get_mut(&mut v) = 0.0;
+/// assert_eq!(v, [1.0, 0.0, 3.0]);
+/// ```
+///
+/// ```compile_fail
+/// use axmac::marker::Z;
+/// use axmac::{component, D2};
+///
+/// // error: axmac: `axmac::marker::Z` is not an axis of `Dim<2>`
+/// let z = component::<Z, D2>().get(&[1.0, 2.0]);
+/// ```
+///
+pub const fn component<A: AxisOf<D>, D: Dimensionality>() -> Component<A, D> {
+    Component(PhantomData)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::Axis;
+
+    fn axes_of<V: Dimensioned>(_: &V) -> usize {
+        V::Dim::AXES
+    }
+
+    #[test]
+    fn matches_the_types() {
+        assert_eq!(axes_of(&Coord::new([0u8; 2])), 2);
+        assert_eq!(axes_of(&Aabb::new([0.0], [1.0])), 1);
+        assert_eq!(axes_of(&AxisMap::<u8>::splat(0)), D4::AXES);
+        assert_eq!(D4::AXES, Axis::COUNT);
+        let shape: <D2 as Dimensionality>::Shape = Shape::new([3, 4]);
+        assert_eq!(axes_of(&shape), 2);
+    }
+
+    #[test]
+    fn axis_checks_work() {
+        assert!(D2::contains_axis(ax!(y)));
+        assert_eq!(D2::check_axis(ax!(z)), Err(InvalidAxis(2)));
+        assert_eq!(D3::axes(), AxisSet::from_axes(&axs![x, y, z]));
+    }
+
+    #[test]
+    fn component_works() {
+        let mut v = [1, 2, 3, 4];
+        assert_eq!(*component::<W, D4>().get(&v), 4);
+        *component::<X, D4>().get_mut(&mut v) = 0;
+        assert_eq!(component::<X, D1>().get(&[7]), &7);
+        assert_eq!(v[0], 0);
+    }
+
+}
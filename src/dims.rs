@@ -0,0 +1,134 @@
+//!
+//! Axis labels carried by shapes and regions in development builds, checked where two are combined
+//!
+
+use core::hash::{Hash, Hasher};
+
+use crate::{Axis, Roi, Shape};
+
+
+// Optional labels of every axis, which never take part in comparisons or hashing so that labelling a
+// shape leaves its equality untouched
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct DimLabels<const N: usize>(Option<[&'static str; N]>);
+
+impl<const N: usize> DimLabels<N> {
+
+    pub(crate) const NONE: Self = Self(None);
+
+    // Returns the labels given by either side, refusing two sides which label an axis differently
+    #[track_caller]
+    pub(crate) fn merge(self, other: Self) -> Self {
+        if let (Some(a), Some(b)) = (self.0, other.0) {
+            for axis in 0..N {
+                if a[axis] != b[axis] {
+                    match Axis::from_usize(axis) {
+                        Some(name) => panic!(
+                            "axmac: axis {} is labelled {:?} on one side and {:?} on the other",
+                            name, a[axis], b[axis],
+                        ),
+                        None => panic!(
+                            "axmac: axis {} is labelled {:?} on one side and {:?} on the other",
+                            axis, a[axis], b[axis],
+                        ),
+                    }
+                }
+            }
+        }
+        Self(self.0.or(other.0))
+    }
+
+}
+
+impl<const N: usize> PartialEq for DimLabels<N> {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl<const N: usize> Eq for DimLabels<N> {}
+
+impl<const N: usize> Hash for DimLabels<N> {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+
+impl<const N: usize> Shape<N> {
+
+    ///
+    /// Labels every axis of the shape, such as `["row", "col"]`, for checking against the shapes and
+    /// regions it is combined with
+    ///
+    /// Labels never change how a shape compares or hashes. Where two labelled operands meet, as the source
+    /// and destination of [`copy_region`](crate::copy_region), an axis labelled differently on each panics
+    /// naming both labels, catching a row major image mixed up with a column major one
+    ///
+    /// ```should_panic
+    /// use axmac::{copy_region, Roi, Shape};
+    ///
+    /// let camera = Shape::new([640, 480]).with_labels(["col", "row"]);
+    /// let texture = Shape::col_major([480, 640]).with_labels(["row", "col"]);
+    /// let (src, mut dst) = (vec![0u8; 640 * 480], vec![0u8; 640 * 480]);
+    ///
+    /// // panics: axmac: axis x is labelled "col" on one side and "row" on the other
+    /// let _ = copy_region(&src, &camera, &Roi::FULL, &mut dst, &texture, [0, 0]);
+    /// ```
+    ///
+    pub const fn with_labels(mut self, labels: [&'static str; N]) -> Self {
+        self.labels = DimLabels(Some(labels));
+        self
+    }
+
+    ///
+    /// Returns the labels given by [`with_labels`](Self::with_labels), if any
+    ///
+    pub const fn labels(&self) -> Option<&[&'static str; N]> {
+        self.labels.0.as_ref()
+    }
+
+}
+
+impl<const N: usize> Roi<N> {
+
+    ///
+    /// Labels every axis of the region, for checking against the shapes and regions it is combined with
+    ///
+    /// As for [`Shape::with_labels`]
+    ///
+    pub const fn with_labels(mut self, labels: [&'static str; N]) -> Self {
+        self.labels = DimLabels(Some(labels));
+        self
+    }
+
+    ///
+    /// Returns the labels given by [`with_labels`](Self::with_labels), if any
+    ///
+    pub const fn labels(&self) -> Option<&[&'static str; N]> {
+        self.labels.0.as_ref()
+    }
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn labels_are_not_compared() {
+        let shape = Shape::new([2, 3]);
+        assert_eq!(shape.with_labels(["a", "b"]), shape);
+        assert_eq!(shape.with_labels(["a", "b"]).labels(), Some(&["a", "b"]));
+        let roi = Roi::new([0, 0], [1, 1]).with_labels(["a", "b"]).intersect(&Roi::FULL);
+        assert_eq!(roi.labels(), Some(&["a", "b"]));
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac: axis y is labelled \"b\" on one side and \"c\" on the other")]
+    fn mismatches_panic() {
+        let _ = Roi::<2>::FULL.with_labels(["a", "b"]).intersect(&Roi::FULL.with_labels(["a", "c"]));
+    }
+
+}
@@ -0,0 +1,201 @@
+//!
+//! Printing flat buffers as the grids their shapes describe
+//!
+
+use core::fmt::{self, Display, Formatter, Write};
+
+use crate::{NdIndex, Shape};
+
+
+///
+/// Formats a flat buffer as rows along x, stacked along y, with one titled plane per position of any
+/// further axes
+///
+/// Columns and rows are labelled with their axis and coordinate, and every cell is right aligned to the
+/// widest. A precision given to the formatter, as in `{:.2}`, applies to every element. Needs no
+/// allocation, so it works on `no_std` targets with any [`fmt::Write`] sink
+///
+/// ```
+/// use axmac::{Shape, ShapedDisplay};
+///
+/// let buf = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+/// let grid = ShapedDisplay::new(&buf, &Shape::new([3, 2, 2])).to_string();
+/// assert_eq!(grid, "\
+/// z=0
+///     x=0 x=1 x=2
+/// y=0   0   1   2
+/// y=1   3   4   5
+///
+/// z=1
+///     x=0 x=1 x=2
+/// y=0   6   7   8
+/// y=1   9  10  11
+/// ");
+///
+/// let floats = [0.5, 1.25];
+/// assert_eq!(format!("{:.1}", ShapedDisplay::new(&floats, &Shape::new([2]))), "x=0 x=1\n0.5 1.2\n");
+/// ```
+///
+/// # Panics
+///
+/// Creating the display panics if `buf` is too short for `shape`
+///
+#[derive(Clone, Copy, Debug)]
+pub struct ShapedDisplay<'a, T, const N: usize> {
+    buf: &'a [T],
+    shape: Shape<N>,
+}
+
+impl<'a, T: Display, const N: usize> ShapedDisplay<'a, T, N> {
+
+    ///
+    /// Creates a display of `buf` laid out by `shape`
+    ///
+    pub fn new(buf: &'a [T], shape: &Shape<N>) -> Self {
+        assert!(buf.len() >= shape.required_len(), "axmac: buffer is too short for its shape");
+        Self { buf, shape: *shape }
+    }
+
+    fn element(&self, coord: &[usize; N]) -> &T {
+        &self.buf[self.shape.flat_index_unchecked(coord)]
+    }
+
+}
+
+impl<T: Display, const N: usize> Display for ShapedDisplay<'_, T, N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let precision = f.precision();
+        let extents = *self.shape.extents();
+        if N == 0 || self.shape.is_empty() {
+            return writeln!(f, "(empty)");
+        }
+
+        let width = self.shape.coords()
+            .map(|c| measure(|w| write_element(w, self.element(&c), precision)))
+            .chain([measure(|w| write_label(w, 0, extents[0] - 1))])
+            .max()
+            .unwrap_or(0);
+        let row_label = if N > 1 { measure(|w| write_label(w, 1, extents[1] - 1)) } else { 0 };
+
+        let mut planes = extents;
+        planes[0] = 1;
+        if N > 1 {
+            planes[1] = 1;
+        }
+        for (p, plane) in NdIndex::new([0; N], planes).enumerate() {
+            if N > 2 {
+                if p > 0 {
+                    writeln!(f)?;
+                }
+                for (axis, &coord) in plane.iter().enumerate().skip(2) {
+                    if axis > 2 {
+                        f.write_str(", ")?;
+                    }
+                    write_label(f, axis, coord)?;
+                }
+                writeln!(f)?;
+            }
+
+            pad(f, row_label)?;
+            for x in 0..extents[0] {
+                if row_label > 0 || x > 0 {
+                    f.write_char(' ')?;
+                }
+                pad(f, width - measure(|w| write_label(w, 0, x)))?;
+                write_label(f, 0, x)?;
+            }
+            writeln!(f)?;
+
+            let rows = if N > 1 { extents[1] } else { 1 };
+            for y in 0..rows {
+                let mut coord = plane;
+                if N > 1 {
+                    coord[1] = y;
+                    pad(f, row_label - measure(|w| write_label(w, 1, y)))?;
+                    write_label(f, 1, y)?;
+                }
+                for x in 0..extents[0] {
+                    coord[0] = x;
+                    let element = self.element(&coord);
+                    if row_label > 0 || x > 0 {
+                        f.write_char(' ')?;
+                    }
+                    pad(f, width - measure(|w| write_element(w, element, precision)))?;
+                    write_element(f, element, precision)?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+fn write_element<W: Write, T: Display>(w: &mut W, element: &T, precision: Option<usize>) -> fmt::Result {
+    match precision {
+        Some(precision) => write!(w, "{:.*}", precision, element),
+        None => write!(w, "{}", element),
+    }
+}
+
+fn write_label<W: Write>(w: &mut W, axis: usize, coord: usize) -> fmt::Result {
+    match crate::Axis::from_usize(axis) {
+        Some(name) => write!(w, "{}={}", name, coord),
+        None => write!(w, "#{}={}", axis, coord),
+    }
+}
+
+fn pad<W: Write>(w: &mut W, n: usize) -> fmt::Result {
+    (0..n).try_for_each(|_| w.write_char(' '))
+}
+
+// Returns the number of characters `write` produces
+fn measure<F: FnOnce(&mut Counter) -> fmt::Result>(write: F) -> usize {
+    let mut counter = Counter(0);
+    let _ = write(&mut counter);
+    counter.0
+}
+
+struct Counter(usize);
+
+impl Write for Counter {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn strided_layouts_print_by_coordinate() {
+        let buf = [1, 2, 3, 4, 5, 6];
+        let shape = Shape::col_major([2, 3]);
+        assert_eq!(ShapedDisplay::new(&buf, &shape).to_string(), "    x=0 x=1\ny=0   1   4\ny=1   2   5\ny=2   3   6\n");
+    }
+
+    #[test]
+    fn labels_further_axes() {
+        let buf = [-1; 4];
+        let text = ShapedDisplay::new(&buf, &Shape::new([1, 1, 2, 2])).to_string();
+        assert!(text.starts_with("z=0, w=0\n    x=0\ny=0  -1\n\nz=1, w=0\n"));
+        assert_eq!(ShapedDisplay::new(&buf, &Shape::new([0, 2])).to_string(), "(empty)\n");
+    }
+
+    #[test]
+    fn row_labels_align() {
+        let buf: std::vec::Vec<u8> = (0..11).collect();
+        let text = ShapedDisplay::new(&buf, &Shape::new([1, 11])).to_string();
+        let lines: std::vec::Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "     x=0");
+        assert_eq!(lines[1], " y=0   0");
+        assert_eq!(lines[11], "y=10  10");
+    }
+
+}
@@ -0,0 +1,102 @@
+//!
+//! Error types shared by the runtime APIs
+//!
+
+use core::fmt;
+
+
+///
+/// Returned when a runtime `usize` does not correspond to any axis of the requested vocabulary
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InvalidAxis(pub usize);
+
+impl fmt::Display for InvalidAxis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "axmac: no axis has the index {}", self.0)
+    }
+}
+
+
+///
+/// Returned by checked indexing when a coordinate lies outside the extent of one of its axes
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct OutOfBounds {
+    /// The first axis whose coordinate was out of bounds
+    pub axis: usize,
+    /// The extent of that axis
+    pub extent: usize,
+    /// The coordinate given along that axis
+    pub got: usize,
+}
+
+impl OutOfBounds {
+
+    ///
+    /// Returns the name of the offending axis, if it is one of _x_, _y_, _z_ or _w_
+    ///
+    pub const fn axis_name(&self) -> Option<&'static str> {
+        match self.axis {
+            0 => Some("x"),
+            1 => Some("y"),
+            2 => Some("z"),
+            3 => Some("w"),
+            _ => None,
+        }
+    }
+
+}
+
+impl fmt::Display for OutOfBounds {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.axis_name() {
+            Some(name) => write!(f, "axmac: index {} is out of bounds for axis {} with extent {}", self.got, name, self.extent),
+            None => write!(f, "axmac: index {} is out of bounds for axis {} with extent {}", self.got, self.axis, self.extent),
+        }
+    }
+}
+
+
+
+///
+/// Returned when axes converted into an [`AxisPerm`](crate::AxisPerm) do not name every axis exactly once
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InvalidPermutation {
+    /// The first position whose axis is past the last or repeats an earlier one
+    pub position: usize,
+    /// The axis given at that position
+    pub axis: usize,
+}
+
+impl fmt::Display for InvalidPermutation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "axmac: axis {} at position {} is past the last axis or repeated", self.axis, self.position)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn display_works() {
+        assert_eq!(InvalidAxis(9).to_string(), "axmac: no axis has the index 9");
+
+        let named = OutOfBounds { axis: ax!(y), extent: 4, got: 7 };
+        assert_eq!(named.to_string(), "axmac: index 7 is out of bounds for axis y with extent 4");
+
+        let unnamed = OutOfBounds { axis: 5, extent: 2, got: 2 };
+        assert_eq!(unnamed.axis_name(), None);
+        assert_eq!(unnamed.to_string(), "axmac: index 2 is out of bounds for axis 5 with extent 2");
+
+        let perm = InvalidPermutation { position: 2, axis: 0 };
+        assert_eq!(perm.to_string(), "axmac: axis 0 at position 2 is past the last axis or repeated");
+    }
+
+}
@@ -0,0 +1,195 @@
+//!
+//! Rotation orders for Euler angles
+//!
+
+use crate::Axis;
+
+
+///
+/// The two families of Euler angle conventions
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EulerKind {
+    /// Three distinct axes, such as `x, y, z`, also known as Cardan or nautical angles
+    TaitBryan,
+    /// The first axis repeated last, such as `z, x, z`
+    Proper,
+}
+
+
+///
+/// The sequence of axes three elemental rotations are applied about
+///
+/// Any sequence of the axes _x_, _y_ and _z_ in which no axis directly follows itself is valid, since a
+/// repeated rotation about one axis loses a degree of freedom. Whether the rotations are intrinsic or
+/// extrinsic is left to the consuming code, with [`reversed`](Self::reversed) converting between the two.
+/// Build one with [`euler!`]
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{Axis, EulerKind, EulerOrder};
+///
+/// let order = euler!(z, x, z);
+/// assert_eq!(order.kind(), EulerKind::Proper);
+/// assert_eq!(order.iter().collect::<Vec<_>>(), [Axis::Z, Axis::X, Axis::Z]);
+///
+/// assert_eq!(EulerOrder::XYZ.reversed(), EulerOrder::ZYX);
+/// assert_eq!(EulerOrder::try_new([Axis::X, Axis::X, Axis::Y]), None);
+/// # }
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EulerOrder {
+    axes: [Axis; 3],
+}
+
+impl EulerOrder {
+
+    /// Rotations about x, then y, then z
+    pub const XYZ: Self = Self::new([Axis::X, Axis::Y, Axis::Z]);
+    /// Rotations about x, then z, then y
+    pub const XZY: Self = Self::new([Axis::X, Axis::Z, Axis::Y]);
+    /// Rotations about y, then x, then z
+    pub const YXZ: Self = Self::new([Axis::Y, Axis::X, Axis::Z]);
+    /// Rotations about y, then z, then x
+    pub const YZX: Self = Self::new([Axis::Y, Axis::Z, Axis::X]);
+    /// Rotations about z, then x, then y
+    pub const ZXY: Self = Self::new([Axis::Z, Axis::X, Axis::Y]);
+    /// Rotations about z, then y, then x, the yaw, pitch, roll order of aerospace
+    pub const ZYX: Self = Self::new([Axis::Z, Axis::Y, Axis::X]);
+    /// Rotations about z, then x, then z, the classical convention of mechanics
+    pub const ZXZ: Self = Self::new([Axis::Z, Axis::X, Axis::Z]);
+    /// Rotations about z, then y, then z
+    pub const ZYZ: Self = Self::new([Axis::Z, Axis::Y, Axis::Z]);
+
+    ///
+    /// Creates an order from three axes
+    ///
+    /// # Panics
+    ///
+    /// If an axis is _w_ or directly follows itself, which fails to compile in const contexts
+    ///
+    pub const fn new(axes: [Axis; 3]) -> Self {
+        match Self::try_new(axes) {
+            Some(order) => order,
+            None => panic!("axmac: Euler rotations must be about x, y or z, with no axis repeated consecutively"),
+        }
+    }
+
+    ///
+    /// Creates an order from three axes, or returns `None` if an axis is _w_ or directly follows itself
+    ///
+    pub const fn try_new(axes: [Axis; 3]) -> Option<Self> {
+        let [a, b, c] = axes;
+        let spatial = !matches!(a, Axis::W) && !matches!(b, Axis::W) && !matches!(c, Axis::W);
+        if spatial && a as usize != b as usize && b as usize != c as usize {
+            Some(Self { axes })
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Returns the axes in the order their rotations are applied
+    ///
+    pub const fn axes(&self) -> [Axis; 3] {
+        self.axes
+    }
+
+    ///
+    /// Returns whether the order is Tait-Bryan or proper Euler
+    ///
+    pub const fn kind(&self) -> EulerKind {
+        if self.axes[0] as usize == self.axes[2] as usize { EulerKind::Proper } else { EulerKind::TaitBryan }
+    }
+
+    ///
+    /// Returns the axes in the opposite order
+    ///
+    /// Intrinsic rotations in one order equal extrinsic rotations in the reversed order
+    ///
+    pub const fn reversed(&self) -> Self {
+        let [a, b, c] = self.axes;
+        Self { axes: [c, b, a] }
+    }
+
+    ///
+    /// Iterates over the axes in the order their rotations are applied
+    ///
+    pub fn iter(&self) -> core::array::IntoIter<Axis, 3> {
+        self.axes.into_iter()
+    }
+
+}
+
+impl IntoIterator for EulerOrder {
+    type Item = Axis;
+    type IntoIter = core::array::IntoIter<Axis, 3>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+
+///
+/// Creates an [`EulerOrder`] from three axis identifiers, validated at compile time
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::EulerOrder;
+///
+/// assert_eq!(euler!(z, y, x), EulerOrder::ZYX);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: Euler rotations must be about x, y or z, with no axis repeated consecutively
+/// let order = euler!(x, x, y);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! euler {
+    ( $a:ident, $b:ident, $c:ident $(,)? ) => {{
+        const ORDER: $crate::EulerOrder = $crate::EulerOrder::new([
+            $crate::__private::axis($crate::ax!($a)),
+            $crate::__private::axis($crate::ax!($b)),
+            $crate::__private::axis($crate::ax!($c)),
+        ]);
+        ORDER
+    }};
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn validation_works() {
+        let axes = [Axis::X, Axis::Y, Axis::Z];
+        let mut valid = 0;
+        for a in axes {
+            for b in axes {
+                for c in axes {
+                    valid += EulerOrder::try_new([a, b, c]).is_some() as usize;
+                }
+            }
+        }
+        assert_eq!(valid, 12);
+        assert_eq!(EulerOrder::try_new([Axis::X, Axis::Y, Axis::W]), None);
+    }
+
+    #[test]
+    fn macro_and_kinds_work() {
+        assert_eq!(euler!(x, y, z), EulerOrder::XYZ);
+        assert_eq!(euler!(z, x, z).kind(), EulerKind::Proper);
+        assert_eq!(EulerOrder::YXZ.kind(), EulerKind::TaitBryan);
+        assert_eq!(EulerOrder::ZXZ.reversed(), EulerOrder::ZXZ);
+    }
+
+}
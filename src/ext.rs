@@ -0,0 +1,166 @@
+//!
+//! Importing tensor layouts described outside the crate, such as DLPack's, into [`Shape`]s
+//!
+
+use core::fmt;
+
+use crate::{AxisSet, Shape};
+
+
+///
+/// An externally described layout converted into a [`Shape`], created by [`Shape::from_ext`]
+///
+/// Shapes only have positive strides, so axes with a negative stride are reported in `flipped` and the
+/// shape has them running the other way. Its position zero then lies `offset` elements before the
+/// external data pointer, so the shape indexes the buffer starting there. Reversing the flipped axes with
+/// [`Shape::reverse_axis`] restores the external coordinate order
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ExtShape<const N: usize> {
+    /// The layout with every stride made positive
+    pub shape: Shape<N>,
+    /// The axes whose external stride was negative, along which coordinates run the other way
+    pub flipped: AxisSet,
+    /// How many elements before the external data pointer the shape's position zero lies
+    pub offset: usize,
+}
+
+
+///
+/// Returned by [`Shape::from_ext`] when an external layout cannot be represented
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExtError {
+    /// The descriptor does not have one extent per dimension, or one stride per extent
+    Rank {
+        /// The number of dimensions of the shape
+        expected: usize,
+        /// The number of extents or strides given
+        got: usize,
+    },
+    /// An extent along `axis` is negative
+    NegativeExtent {
+        /// The axis at fault
+        axis: usize,
+    },
+    /// An extent or stride along `axis` does not fit in a `usize`, or the buffer it spans is too large
+    Overflow {
+        /// The axis at fault
+        axis: usize,
+    },
+}
+
+impl fmt::Display for ExtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |axis: usize| crate::Axis::from_usize(axis).map(crate::Axis::name);
+        match *self {
+            ExtError::Rank { expected, got } => {
+                write!(f, "axmac: expected {} external dimensions, got {}", expected, got)
+            }
+            ExtError::NegativeExtent { axis } => match name(axis) {
+                Some(name) => write!(f, "axmac: external extent of axis {} is negative", name),
+                None => write!(f, "axmac: external extent of axis {} is negative", axis),
+            },
+            ExtError::Overflow { axis } => match name(axis) {
+                Some(name) => write!(f, "axmac: external layout of axis {} overflows a usize", name),
+                None => write!(f, "axmac: external layout of axis {} overflows a usize", axis),
+            },
+        }
+    }
+}
+
+
+impl<const N: usize> Shape<N> {
+
+    ///
+    /// Converts extents and strides in elements from an external tensor descriptor, such as a DLPack
+    /// `DLTensor` handed over FFI, with `dims[0]` the x axis
+    ///
+    /// Empty `strides` stand for the compact layout a descriptor without strides has, with the last axis
+    /// contiguous as in [`Shape::col_major`]. Negative strides are flipped into positive ones, and the
+    /// axes affected reported by name in [`ExtShape::flipped`]
+    ///
+    /// ```
+    /// use axmac::{Axis, ExtError, Shape};
+    ///
+    /// // A column major 3x2 matrix read back to front along its columns
+    /// let ext = Shape::<2>::from_ext(&[3, 2], &[-1, 3]).unwrap();
+    /// assert_eq!(ext.shape, Shape::with_strides([3, 2], [1, 3]));
+    /// assert!(ext.flipped.contains_axis(Axis::X));
+    /// assert_eq!(ext.offset, 2);
+    ///
+    /// assert_eq!(Shape::<2>::from_ext(&[4, 5], &[]).unwrap().shape, Shape::col_major([4, 5]));
+    /// assert_eq!(Shape::<3>::from_ext(&[4, 5], &[]), Err(ExtError::Rank { expected: 3, got: 2 }));
+    /// assert_eq!(Shape::<2>::from_ext(&[4, -1], &[]), Err(ExtError::NegativeExtent { axis: 1 }));
+    /// ```
+    ///
+    pub fn from_ext(dims: &[i64], strides: &[i64]) -> Result<ExtShape<N>, ExtError> {
+        if dims.len() != N {
+            return Err(ExtError::Rank { expected: N, got: dims.len() });
+        }
+        if !strides.is_empty() && strides.len() != N {
+            return Err(ExtError::Rank { expected: N, got: strides.len() });
+        }
+
+        let mut extents = [0; N];
+        for (axis, (extent, &dim)) in extents.iter_mut().zip(dims).enumerate() {
+            if dim < 0 {
+                return Err(ExtError::NegativeExtent { axis });
+            }
+            *extent = usize::try_from(dim).map_err(|_| ExtError::Overflow { axis })?;
+        }
+        if strides.is_empty() {
+            if let Some(axis) = Shape::dense_overflow_axis(&extents) {
+                return Err(ExtError::Overflow { axis });
+            }
+            return Ok(ExtShape { shape: Shape::col_major(extents), flipped: AxisSet::EMPTY, offset: 0 });
+        }
+
+        let (mut positive, mut flipped) = ([0; N], AxisSet::EMPTY);
+        for axis in 0..N {
+            positive[axis] = usize::try_from(strides[axis].unsigned_abs()).map_err(|_| ExtError::Overflow { axis })?;
+            if strides[axis] < 0 {
+                flipped = flipped.with(axis);
+            }
+        }
+        if let Some(axis) = Shape::strided_overflow_axis(&extents, &positive) {
+            return Err(ExtError::Overflow { axis });
+        }
+        // Part of the span checked above, so it cannot overflow either
+        let offset = flipped.into_iter().map(|axis| extents[axis].saturating_sub(1) * positive[axis]).sum();
+        Ok(ExtShape { shape: Shape::with_strides(extents, positive), flipped, offset })
+    }
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn flips_negative_strides() {
+        // A row major 2x3x2 volume flipped along y and z
+        let ext = Shape::<3>::from_ext(&[2, 3, 2], &[1, -2, -6]).unwrap();
+        assert_eq!(ext.flipped, AxisSet::from_axes(&[1, 2]));
+        assert_eq!(ext.offset, 2 * 2 + 6);
+        assert_eq!(ext.shape.required_len(), 12);
+
+        let external = ext.shape.reverse_axis(crate::Axis::Y).reverse_axis(crate::Axis::Z);
+        assert_eq!(external.flat_index(&[1, 2, 0]), Some(ext.offset + 1 - 2 * 2));
+    }
+
+    #[test]
+    fn errors_name_axes() {
+        assert_eq!(Shape::<1>::from_ext(&[2], &[1, 1]), Err(ExtError::Rank { expected: 1, got: 2 }));
+        assert_eq!(ExtError::NegativeExtent { axis: 2 }.to_string(), "axmac: external extent of axis z is negative");
+        assert_eq!(ExtError::Overflow { axis: 6 }.to_string(), "axmac: external layout of axis 6 overflows a usize");
+        assert_eq!(Shape::<1>::from_ext(&[i64::MAX], &[i64::MAX]), Err(ExtError::Overflow { axis: 0 }));
+        assert_eq!(Shape::<2>::from_ext(&[1 << 32, 1 << 32], &[]), Err(ExtError::Overflow { axis: 1 }));
+        assert_eq!(Shape::<2>::from_ext(&[5, 2], &[-(1 << 62), 1]), Err(ExtError::Overflow { axis: 0 }));
+    }
+
+}
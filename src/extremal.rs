@@ -0,0 +1,155 @@
+//!
+//! Finding the axis of the largest or smallest component
+//!
+
+use crate::{math, Axis, SignedScalar};
+
+
+///
+/// Which axis wins when several components are equally extreme
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TieBreak {
+    /// The lowest axis wins, so `[1, 1, 0]` has its maximum along _x_
+    #[default]
+    First,
+    /// The highest axis wins, so `[1, 1, 0]` has its maximum along _y_
+    Last,
+}
+
+
+fn extremal_axis<T: PartialOrd + Copy, const N: usize>(v: &[T; N], tie: TieBreak, better: fn(&T, &T) -> bool) -> Option<Axis> {
+    assert!(N <= Axis::COUNT, "axmac: axes past w have no Axis to report");
+
+    let mut best: Option<(usize, T)> = None;
+    for (axis, &c) in v.iter().enumerate() {
+        // NaN compares false with everything, itself included
+        #[allow(clippy::eq_op)]
+        if c != c {
+            continue;
+        }
+        let replace = match best {
+            None => true,
+            Some((_, b)) => better(&c, &b) || (tie == TieBreak::Last && c == b),
+        };
+        if replace {
+            best = Some((axis, c));
+        }
+    }
+    best.and_then(|(axis, _)| Axis::from_usize(axis))
+}
+
+///
+/// Returns the axis of the largest component of `v`, or `None` if every component is NaN
+///
+/// NaN components are skipped. See [`max_axis!`] for a shorthand
+///
+/// # Panics
+///
+/// If `N` is greater than 4
+///
+pub fn max_axis<T: PartialOrd + Copy, const N: usize>(v: &[T; N], tie: TieBreak) -> Option<Axis> {
+    extremal_axis(v, tie, |a, b| a > b)
+}
+
+///
+/// Returns the axis of the smallest component of `v`, or `None` if every component is NaN
+///
+/// NaN components are skipped. See [`min_axis!`] for a shorthand
+///
+/// # Panics
+///
+/// If `N` is greater than 4
+///
+pub fn min_axis<T: PartialOrd + Copy, const N: usize>(v: &[T; N], tie: TieBreak) -> Option<Axis> {
+    extremal_axis(v, tie, |a, b| a < b)
+}
+
+///
+/// Returns the axis of the component of `v` with the largest magnitude, the axis a direction is closest
+/// to
+///
+/// Used for dominant-axis projection, such as choosing which plane to project a triangle onto
+///
+/// ```
+/// use axmac::{dominant_axis, Axis, TieBreak};
+///
+/// assert_eq!(dominant_axis(&[0.2, -0.9, 0.4], TieBreak::First), Some(Axis::Y));
+/// ```
+///
+/// # Panics
+///
+/// If `N` is greater than 4
+///
+pub fn dominant_axis<T: SignedScalar, const N: usize>(v: &[T; N], tie: TieBreak) -> Option<Axis> {
+    max_axis(&v.map(math::abs), tie)
+}
+
+
+///
+/// Returns the [`Axis`] of the largest component of an array
+///
+/// Ties go to the lowest axis, unless `ties = last` is given. Evaluates to `None` only when every
+/// component is NaN
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Axis;
+///
+/// assert_eq!(max_axis!([3, 9, 9]), Some(Axis::Y));
+/// assert_eq!(max_axis!([3, 9, 9], ties = last), Some(Axis::Z));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! max_axis {
+    ( $v:expr ) => { $crate::max_axis(&$v, $crate::TieBreak::First) };
+    ( $v:expr, ties = first ) => { $crate::max_axis(&$v, $crate::TieBreak::First) };
+    ( $v:expr, ties = last ) => { $crate::max_axis(&$v, $crate::TieBreak::Last) };
+}
+
+///
+/// Returns the [`Axis`] of the smallest component of an array
+///
+/// Ties go to the lowest axis, unless `ties = last` is given. Evaluates to `None` only when every
+/// component is NaN
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Axis;
+///
+/// assert_eq!(min_axis!([0.0, -2.0, f32::NAN]), Some(Axis::Y));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! min_axis {
+    ( $v:expr ) => { $crate::min_axis(&$v, $crate::TieBreak::First) };
+    ( $v:expr, ties = first ) => { $crate::min_axis(&$v, $crate::TieBreak::First) };
+    ( $v:expr, ties = last ) => { $crate::min_axis(&$v, $crate::TieBreak::Last) };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn ties_work() {
+        assert_eq!(max_axis!([1, 1, 0]), Some(Axis::X));
+        assert_eq!(max_axis!([1, 1, 0], ties = last), Some(Axis::Y));
+        assert_eq!(min_axis!([5, 0, 0, 0], ties = last), Some(Axis::W));
+        assert_eq!(min_axis!([5, 0, 0, 0], ties = first), Some(Axis::Y));
+    }
+
+    #[test]
+    fn nan_and_empty_work() {
+        assert_eq!(max_axis!([f64::NAN, f64::NAN]), None);
+        assert_eq!(max_axis!([f64::NAN, 1.0]), Some(Axis::Y));
+        assert_eq!(max_axis(&[0u8; 0], TieBreak::First), None);
+        assert_eq!(dominant_axis(&[-7, 3, 6], TieBreak::First), Some(Axis::X));
+    }
+
+}
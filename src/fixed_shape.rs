@@ -0,0 +1,243 @@
+//!
+//! Shapes whose extents are known at compile time
+//!
+
+use crate::Shape;
+
+
+///
+/// A densely packed grid of up to four axes whose extents are const generics, with the x axis contiguous
+/// as in [`Shape::new`]
+///
+/// Axes left unnamed have an extent of one, so `FixedShape<8, 8>` is an 8x8 tile. Every index computation
+/// is a const fn, and [`LEN`](Self::LEN) sizes the array holding the grid. Converts into a runtime
+/// [`Shape`] of any dimensionality covering its longer axes
+///
+/// ```
+/// use axmac::{FixedShape, Shape};
+///
+/// // An 8x8 block of a block based codec
+/// type Block = FixedShape<8, 8>;
+/// let mut block = [0i16; Block::LEN];
+///
+/// const CORNER: usize = Block::flat_index_unchecked(&[7, 7]);
+/// block[CORNER] = 1;
+/// assert_eq!(CORNER, 63);
+/// assert_eq!(Block::flat_index(&[8, 0]), None);
+/// assert_eq!(Block::coord_of(9), Some([1, 1]));
+///
+/// assert_eq!(Block::to_shape(), Shape::new([8, 8]));
+/// assert_eq!(Shape::from(Block::default()), Shape::new([8, 8, 1]));
+/// ```
+///
+/// ```compile_fail
+/// use axmac::FixedShape;
+///
+/// // error: axmac: FixedShape has extents past the dimensions of the shape
+/// let shape = FixedShape::<4, 4, 2>::to_shape::<2>();
+/// ```
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct FixedShape<const X: usize, const Y: usize = 1, const Z: usize = 1, const W: usize = 1>;
+
+impl<const X: usize, const Y: usize, const Z: usize, const W: usize> FixedShape<X, Y, Z, W> {
+
+    /// The extent of every axis, unnamed ones being one
+    pub const EXTENTS: [usize; 4] = [X, Y, Z, W];
+
+    /// The stride of every axis
+    pub const STRIDES: [usize; 4] = [1, X, X * Y, X * Y * Z];
+
+    /// The number of positions in the grid, and the length of the array holding it
+    pub const LEN: usize = X * Y * Z * W;
+
+    ///
+    /// Returns the flat index of `coord`, or `None` if it lies outside the grid
+    ///
+    /// Coordinates may leave off trailing axes, which are then taken as zero
+    ///
+    pub const fn flat_index<const N: usize>(coord: &[usize; N]) -> Option<usize> {
+        const { assert!(N <= 4, "axmac: FixedShape has at most four axes") };
+        let mut i = 0;
+        while i < N {
+            if coord[i] >= Self::EXTENTS[i] {
+                return None;
+            }
+            i += 1;
+        }
+        Some(Self::flat_index_unchecked(coord))
+    }
+
+    ///
+    /// Returns the flat index of `coord` without checking it lies inside the grid
+    ///
+    pub const fn flat_index_unchecked<const N: usize>(coord: &[usize; N]) -> usize {
+        const { assert!(N <= 4, "axmac: FixedShape has at most four axes") };
+        let mut index = 0;
+        let mut i = 0;
+        while i < N {
+            index += coord[i] * Self::STRIDES[i];
+            i += 1;
+        }
+        index
+    }
+
+    ///
+    /// Returns the coordinate of the position at `index`, or `None` past the end of the grid
+    ///
+    /// Fails to compile when an axis past the first `N` has an extent other than one, which the
+    /// coordinate could not tell apart
+    ///
+    pub const fn coord_of<const N: usize>(index: usize) -> Option<[usize; N]> {
+        const { Self::assert_fits::<N>() };
+        if index >= Self::LEN {
+            return None;
+        }
+        let mut coord = [0; N];
+        let mut rest = index;
+        let mut i = 0;
+        while i < N && i < 4 {
+            coord[i] = rest % Self::EXTENTS[i];
+            rest /= Self::EXTENTS[i];
+            i += 1;
+        }
+        Some(coord)
+    }
+
+    ///
+    /// Returns the runtime [`Shape`] of the grid over its first `N` axes
+    ///
+    /// Fails to compile when an axis past the first `N` has an extent other than one
+    ///
+    pub const fn to_shape<const N: usize>() -> Shape<N> {
+        const { Self::assert_fits::<N>() };
+        let mut extents = [1; N];
+        let mut i = 0;
+        while i < N && i < 4 {
+            extents[i] = Self::EXTENTS[i];
+            i += 1;
+        }
+        Shape::new(extents)
+    }
+
+    const fn assert_fits<const N: usize>() {
+        let mut i = N;
+        while i < 4 {
+            assert!(Self::EXTENTS[i] == 1, "axmac: FixedShape has extents past the dimensions of the shape");
+            i += 1;
+        }
+    }
+
+}
+
+impl<const X: usize, const Y: usize, const Z: usize, const W: usize, const N: usize> From<FixedShape<X, Y, Z, W>>
+    for Shape<N>
+{
+    fn from(_: FixedShape<X, Y, Z, W>) -> Self {
+        FixedShape::<X, Y, Z, W>::to_shape()
+    }
+}
+
+
+
+///
+/// Computes the flat index of a coordinate in a [`FixedShape`] from the position along each named axis,
+/// during const evaluation
+///
+/// The positions must be constants. Axes left out are at zero, and an axis named twice or a position past
+/// the extent of its axis fails to compile instead of panicking at runtime
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::FixedShape;
+///
+/// type Tile = FixedShape<16, 16, 4>;
+/// let mut tile = [0u8; Tile::LEN];
+/// tile[fixed_index!(Tile; x: 5, y: 9)] = 1;
+/// assert_eq!(fixed_index!(Tile; z: 1, x: 2), 258);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: fixed_index! position is past the extent of its axis
+/// let index = fixed_index!(axmac::FixedShape<16, 16>; x: 5, y: 16);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! fixed_index {
+    ( $shape:ty ; $( $axis:ident : $pos:expr ),* $(,)? ) => {
+        const {
+            assert!(
+                $crate::__private::indices_are_unique(&[ $( $crate::ax!($axis) ),* ]),
+                "axmac: fixed_index! names an axis more than once",
+            );
+            match <$shape>::flat_index(&$crate::__private::fixed_coord([ $( ($crate::ax!($axis), $pos) ),* ])) {
+                Some(index) => index,
+                None => panic!("axmac: fixed_index! position is past the extent of its axis"),
+            }
+        }
+    };
+}
+
+
+///
+/// Names the array type holding one `T` for every position of a [`FixedShape`]
+///
+/// The length is the shape's [`LEN`](crate::FixedShape::LEN), so a static buffer declared with it can
+/// never disagree with the shape it is indexed through
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::FixedShape;
+///
+/// // The framebuffer of a 128x64 monochrome display
+/// type Screen = FixedShape<128, 64>;
+/// static BLANK: buffer_for!(Screen, u8) = [0; Screen::LEN];
+///
+/// let mut back: buffer_for!(Screen, u8) = BLANK;
+/// back[fixed_index!(Screen; x: 127, y: 63)] = 1;
+/// assert_eq!(back.len(), 8192);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! buffer_for {
+    ( $shape:ty , $t:ty $(,)? ) => {
+        [$t; <$shape>::LEN]
+    };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn indices_match_shape() {
+        type Volume = FixedShape<3, 4, 5>;
+        let shape = Shape::new([3, 4, 5]);
+        for index in 0..Volume::LEN {
+            let coord = Volume::coord_of::<3>(index).unwrap();
+            assert_eq!(Volume::flat_index(&coord), shape.flat_index(&coord));
+            assert_eq!(Volume::flat_index(&coord), Some(index));
+        }
+        assert_eq!(Volume::coord_of::<3>(Volume::LEN), None);
+        assert_eq!(Volume::flat_index(&[2, 3]), Some(11));
+    }
+
+    #[test]
+    fn shapes_grow_with_unit_axes() {
+        assert_eq!(FixedShape::<2, 3>::to_shape::<4>(), Shape::new([2, 3, 1, 1]));
+        assert_eq!(FixedShape::<2, 3>::to_shape::<6>(), Shape::new([2, 3, 1, 1, 1, 1]));
+        assert_eq!(FixedShape::<5>::STRIDES, [1, 5, 5, 5]);
+        assert_eq!(fixed_index!(FixedShape<2, 3, 4, 5>; w: 4, z: 3, y: 2, x: 1), FixedShape::<2, 3, 4, 5>::LEN - 1);
+        assert_eq!(fixed_index!(FixedShape<2, 3>;), 0);
+        let grid: buffer_for!(FixedShape<2, 3, 4>, f32) = [0.0; 24];
+        assert_eq!(grid.len(), FixedShape::<2, 3, 4>::LEN);
+    }
+
+}
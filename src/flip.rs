@@ -0,0 +1,138 @@
+//!
+//! Mirroring coordinates and buffers along named axes
+//!
+
+use crate::{AxisSet, Shape};
+
+
+///
+/// Returns `coord` mirrored along every axis in `axes`
+///
+/// Mirroring along an axis of extent `e` maps position `i` to `e - 1 - i`. Coordinates outside `shape`
+/// produce meaningless results
+///
+pub fn flip_coord<const N: usize>(coord: [usize; N], shape: &Shape<N>, axes: AxisSet) -> [usize; N] {
+    let mut out = coord;
+    for axis in axes {
+        if axis < N {
+            out[axis] = shape.extent(axis).wrapping_sub(1).wrapping_sub(coord[axis]);
+        }
+    }
+    out
+}
+
+///
+/// Returns the flat index of the position mirroring the one at flat index `index`
+///
+/// Returns `None` if `index` does not belong to any coordinate of `shape`
+///
+pub fn flip_flat_index<const N: usize>(index: usize, shape: &Shape<N>, axes: AxisSet) -> Option<usize> {
+    let coord = shape.coord_of(index)?;
+    Some(shape.flat_index_unchecked(&flip_coord(coord, shape, axes)))
+}
+
+///
+/// Mirrors the contents of `buf` along every axis in `axes`
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{flip_in_place, AxisSet, Shape};
+///
+/// // 0 1 2
+/// // 3 4 5
+/// let mut buf = [0, 1, 2, 3, 4, 5];
+/// let shape = Shape::new([3, 2]);
+///
+/// flip_in_place(&mut buf, &shape, AxisSet::from_axes(&axs![x]));
+/// assert_eq!(buf, [2, 1, 0, 5, 4, 3]);
+///
+/// flip_in_place(&mut buf, &shape, AxisSet::from_axes(&axs![y]));
+/// assert_eq!(buf, [5, 4, 3, 2, 1, 0]);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If `buf` is too short for `shape`
+///
+pub fn flip_in_place<T, const N: usize>(buf: &mut [T], shape: &Shape<N>, axes: AxisSet) {
+    assert!(buf.len() >= shape.required_len(), "axmac: buffer is too short for its shape");
+
+    for coord in shape.coords() {
+        let from = shape.flat_index_unchecked(&coord);
+        let to = shape.flat_index_unchecked(&flip_coord(coord, shape, axes));
+        // Each pair is visited twice, so only swap from the lower index
+        if from < to {
+            buf.swap(from, to);
+        }
+    }
+}
+
+
+///
+/// Mirrors a coordinate or flat index along one or more named axes of a [`Shape`]
+///
+/// Given a `[usize; N]` coordinate the mirrored coordinate is returned. Given a flat index, prefixed with
+/// `flat`, the mirrored flat index is returned as an `Option`, which is `None` if the index lies outside the
+/// shape
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Shape;
+///
+/// let shape = Shape::new([4, 3]);
+///
+/// assert_eq!(flip_index!([1, 0]; along y; shape), [1, 2]);
+/// assert_eq!(flip_index!([1, 0]; along x, y; shape), [2, 2]);
+/// assert_eq!(flip_index!(flat 1; along y; shape), Some(9));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! flip_index {
+
+    ( flat $i:expr; along $( $axis:ident ),+ ; $shape:expr ) => {
+        $crate::flip_flat_index($i, &$shape, $crate::AxisSet::from_axes(&[ $( $crate::ax!($axis) ),+ ]))
+    };
+
+    ( $c:expr; along $( $axis:ident ),+ ; $shape:expr ) => {
+        $crate::flip_coord($c, &$shape, $crate::AxisSet::from_axes(&[ $( $crate::ax!($axis) ),+ ]))
+    };
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn flip_is_an_involution() {
+        let shape = Shape::new([3, 2, 5]);
+        let axes = AxisSet::from_axes(&axs![x, z]);
+        let original: [u8; 30] = core::array::from_fn(|i| i as u8);
+
+        let mut buf = original;
+        flip_in_place(&mut buf, &shape, axes);
+        assert_ne!(buf, original);
+        assert_eq!(buf[0], original[shape.flat_index_unchecked(&[2, 0, 4])]);
+        flip_in_place(&mut buf, &shape, axes);
+        assert_eq!(buf, original);
+    }
+
+    #[test]
+    fn flat_index_works() {
+        let shape = Shape::new([4, 4]);
+        assert_eq!(flip_index!(flat 5; along x; shape), Some(6));
+        assert_eq!(flip_index!(flat 16; along x; shape), None);
+
+        let row = Shape::new([1, 3]);
+        assert_eq!(flip_flat_index(1, &row, AxisSet::from_axes(&[1])), Some(1));
+        assert_eq!(flip_index!(flat 0; along y; row), Some(2));
+        let column = Shape::col_major([3, 1]);
+        assert_eq!(flip_index!(flat 0; along x; column), Some(2));
+    }
+
+}
@@ -0,0 +1,87 @@
+//!
+//! Item level repetition over axes
+//!
+
+
+///
+/// Stamps out a group of items once per axis, with the axis name and index substituted
+///
+/// The closure-like header names two placeholders. Within the body, `$` followed by the first placeholder
+/// is replaced by the axis identifier, and `$` followed by the second is replaced by its index as a
+/// `usize` expression. The axes default to `x, y, z, w`, but any list of identifiers from the enabled
+/// vocabularies can be given in brackets first
+///
+/// Every repetition produces a complete copy of the body, so per-axis methods are generated as one `impl`
+/// block per axis
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// struct Vec3([f32; 3]);
+///
+/// for_axes!([x, y, z] |A, IDX| {
+///     impl Vec3 {
+///         pub fn $A(&self) -> f32 {
+///             self.0[$IDX]
+///         }
+///     }
+/// });
+///
+/// let v = Vec3([1.0, 2.0, 3.0]);
+/// assert_eq!(v.x() + v.y() + v.z(), 6.0);
+/// # }
+/// ```
+///
+/// Identifiers cannot be concatenated by declarative macros, so a name like `get_y` cannot be built from
+/// `y`. Names derived from the axis are available as strings through `stringify!($A)` or [`ax_concat!`]
+///
+#[macro_export]
+macro_rules! for_axes {
+
+    ( |$a:ident, $i:ident| { $( $body:tt )* } ) => {
+        $crate::for_axes!(@stamp ($) [x, y, z, w] $a $i { $( $body )* });
+    };
+
+    ( [ $( $axis:ident ),+ $(,)? ] |$a:ident, $i:ident| { $( $body:tt )* } ) => {
+        $crate::for_axes!(@stamp ($) [ $( $axis ),+ ] $a $i { $( $body )* });
+    };
+
+    // The `$` token is passed in so the generated macro can declare metavariables of its own
+    ( @stamp ($d:tt) [ $( $axis:ident ),+ ] $a:ident $i:ident { $( $body:tt )* } ) => {
+        macro_rules! __axmac_for_axes_stamp {
+            ( $d $a:ident, $d $i:expr ) => { $( $body )* };
+        }
+        $( __axmac_for_axes_stamp!($axis, $crate::ax!($axis)); )+
+    };
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    struct Rgba([u8; 4]);
+
+    for_axes!(|A, I| {
+        impl Rgba {
+            fn $A(&self) -> u8 {
+                self.0[$I]
+            }
+        }
+    });
+
+    // A second expansion in the same module must not disturb the first
+    for_axes!([y, w] |AXIS, INDEX| {
+        #[allow(non_upper_case_globals)]
+        const $AXIS: (&str, usize) = (stringify!($AXIS), $INDEX);
+    });
+
+    #[test]
+    fn it_works() {
+        let c = Rgba([1, 2, 3, 4]);
+        assert_eq!([c.x(), c.y(), c.z(), c.w()], [1, 2, 3, 4]);
+        assert_eq!(y, ("y", 1));
+        assert_eq!(w, ("w", 3));
+    }
+
+}
@@ -0,0 +1,173 @@
+//!
+//! Generation of arbitrary values from fuzzer input
+//!
+//! [`Arbitrary`] mirrors the trait of the same name from the `arbitrary` crate, turning raw bytes from a
+//! fuzzer into valid values of this crate's types. Sizes are limited so generated shapes stay cheap to
+//! iterate over
+//!
+//! ```
+//! use axmac::fuzz::{Arbitrary, Unstructured, MAX_EXTENT};
+//! use axmac::{AxisPerm, Shape};
+//!
+//! let mut u = Unstructured::new(&[7, 200, 3, 1, 0]);
+//! let shape = Shape::<2>::arbitrary(&mut u);
+//! let perm = AxisPerm::<3>::arbitrary(&mut u);
+//!
+//! assert!(shape.extents().iter().all(|&e| e <= MAX_EXTENT));
+//! assert!(AxisPerm::try_new(*perm.as_array()).is_some());
+//! ```
+//!
+
+use crate::{AxisPerm, AxisSet, Roi, Shape};
+
+///
+/// The largest extent generated along any axis
+///
+pub const MAX_EXTENT: usize = 16;
+
+
+///
+/// A cursor over fuzzer input
+///
+/// Once the input is exhausted every read returns zero, so generation never fails
+///
+#[derive(Clone, Debug)]
+pub struct Unstructured<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Unstructured<'a> {
+
+    ///
+    /// Wraps the bytes produced by a fuzzer
+    ///
+    pub const fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    ///
+    /// Returns the number of unread bytes
+    ///
+    pub const fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    ///
+    /// Returns whether every byte has been read
+    ///
+    pub const fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    ///
+    /// Reads a single byte
+    ///
+    pub fn byte(&mut self) -> u8 {
+        match self.data.split_first() {
+            Some((&b, rest)) => {
+                self.data = rest;
+                b
+            }
+            None => 0,
+        }
+    }
+
+    ///
+    /// Reads a value in `0..=max`
+    ///
+    pub fn int_up_to(&mut self, max: usize) -> usize {
+        let mut value = 0usize;
+        let mut span = max;
+        while span > 0 {
+            value = (value << 8) | self.byte() as usize;
+            span >>= 8;
+        }
+        value % (max.wrapping_add(1)).max(1)
+    }
+
+}
+
+
+///
+/// Types which can be generated from fuzzer input
+///
+pub trait Arbitrary: Sized {
+    /// Generates a value, consuming as much input as needed
+    fn arbitrary(u: &mut Unstructured<'_>) -> Self;
+}
+
+impl<const N: usize> Arbitrary for Shape<N> {
+    fn arbitrary(u: &mut Unstructured<'_>) -> Self {
+        let extents = core::array::from_fn(|_| u.int_up_to(MAX_EXTENT));
+        if u.byte() & 1 == 0 { Shape::new(extents) } else { Shape::col_major(extents) }
+    }
+}
+
+impl<const N: usize> Arbitrary for Roi<N> {
+    fn arbitrary(u: &mut Unstructured<'_>) -> Self {
+        let mut roi = Roi::FULL;
+        for axis in 0..N {
+            let start = u.int_up_to(MAX_EXTENT);
+            // Roughly a quarter of the ranges are left unbounded above
+            match u.byte() {
+                0..=63 => roi.set(axis, start..),
+                _ => roi.set(axis, start..start + u.int_up_to(MAX_EXTENT - start)),
+            }
+        }
+        roi
+    }
+}
+
+impl<const N: usize> Arbitrary for AxisPerm<N> {
+    fn arbitrary(u: &mut Unstructured<'_>) -> Self {
+        let mut axes = *AxisPerm::<N>::IDENTITY.as_array();
+        // Fisher-Yates shuffle, arranged so exhausted input leaves the axes in place
+        for i in (1..N).rev() {
+            axes.swap(i, i - u.int_up_to(i));
+        }
+        AxisPerm::new(axes)
+    }
+}
+
+impl Arbitrary for AxisSet {
+    fn arbitrary(u: &mut Unstructured<'_>) -> Self {
+        AxisSet::from_bits(u.byte())
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn exhausted_input_works() {
+        let mut u = Unstructured::new(&[]);
+        assert_eq!(Shape::<3>::arbitrary(&mut u).extents(), &[0, 0, 0]);
+        assert_eq!(AxisPerm::<4>::arbitrary(&mut u), AxisPerm::IDENTITY);
+        assert!(AxisSet::arbitrary(&mut u).is_empty());
+    }
+
+    #[test]
+    fn generated_values_are_valid() {
+        let data: std::vec::Vec<u8> = (0..=255).cycle().step_by(37).take(512).collect();
+        let mut u = Unstructured::new(&data);
+        while !u.is_empty() {
+            let shape = Shape::<3>::arbitrary(&mut u);
+            assert!(shape.len() <= MAX_EXTENT.pow(3));
+
+            let roi = Roi::<3>::arbitrary(&mut u);
+            for (axis, range) in roi.resolve(&shape).into_iter().enumerate() {
+                assert!(range.start <= range.end && range.end <= shape.extent(axis));
+                assert!(roi.range(axis).start <= MAX_EXTENT);
+                assert!(roi.is_unbounded(axis) || roi.range(axis).end <= MAX_EXTENT);
+            }
+
+            let perm = AxisPerm::<4>::arbitrary(&mut u);
+            assert!(AxisPerm::try_new(*perm.as_array()).is_some());
+        }
+    }
+
+}
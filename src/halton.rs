@@ -0,0 +1,182 @@
+//!
+//! Low discrepancy Halton sequences with a base per axis
+//!
+
+use core::iter::FusedIterator;
+
+use crate::Coord;
+
+
+///
+/// Iterator over the points of a Halton sequence, covering the unit cube more evenly than random points
+///
+/// Every axis has its own base, and the bases should be pairwise coprime, usually the first few primes, or
+/// the points line up along diagonals. Points lie in `0..1` along every axis. The sequence starts at index
+/// one, skipping the origin; [`halton!`] names the base of each axis
+///
+/// ```
+/// use axmac::{Coord, Halton};
+///
+/// let mut points = Halton::new([2, 3]);
+/// assert_eq!(points.next(), Some(Coord::new([0.5, 1.0 / 3.0])));
+/// assert_eq!(points.next(), Some(Coord::new([0.25, 2.0 / 3.0])));
+/// assert_eq!(points.index(), 3);
+/// ```
+///
+/// # Panics
+///
+/// If a base is below two
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Halton<const N: usize> {
+    bases: [u32; N],
+    index: u64,
+}
+
+impl<const N: usize> Halton<N> {
+
+    ///
+    /// Creates the sequence with the base of every axis, starting at index one
+    ///
+    pub const fn new(bases: [u32; N]) -> Self {
+        let mut i = 0;
+        while i < N {
+            assert!(bases[i] >= 2, "axmac: Halton bases must be at least two");
+            i += 1;
+        }
+        Self { bases, index: 1 }
+    }
+
+    ///
+    /// Moves the sequence to `index`, so the next point is the one at that index
+    ///
+    /// Giving every thread or frame its own range of indices splits one sequence between them
+    ///
+    pub const fn starting_at(mut self, index: u64) -> Self {
+        self.index = index;
+        self
+    }
+
+    ///
+    /// Returns the index of the next point
+    ///
+    pub const fn index(&self) -> u64 {
+        self.index
+    }
+
+    ///
+    /// Returns the base of every axis
+    ///
+    pub const fn bases(&self) -> &[u32; N] {
+        &self.bases
+    }
+
+    ///
+    /// Returns the point at `index` without moving the sequence
+    ///
+    pub fn at(&self, index: u64) -> Coord<f64, N> {
+        Coord::new(self.bases.map(|base| radical_inverse(index, base)))
+    }
+
+}
+
+// Mirrors the digits of `index` in `base` around the radix point
+fn radical_inverse(mut index: u64, base: u32) -> f64 {
+    let base = base as u64;
+    let mut digits = 0;
+    let mut denom = 1u64;
+    // Collecting the digits as an integer keeps the point exact until the final division, as long as
+    // they fit the mantissa of a float
+    while let Some(d) = denom.checked_mul(base).filter(|&d| index > 0 && d <= 1 << f64::MANTISSA_DIGITS) {
+        denom = d;
+        digits = digits * base + index % base;
+        index /= base;
+    }
+    digits as f64 / denom as f64
+}
+
+impl<const N: usize> Iterator for Halton<N> {
+    type Item = Coord<f64, N>;
+
+    fn next(&mut self) -> Option<Coord<f64, N>> {
+        let index = self.index.checked_add(1)?;
+        let point = self.at(self.index);
+        self.index = index;
+        Some(point)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Coord<f64, N>> {
+        self.index = self.index.checked_add(n as u64)?;
+        self.next()
+    }
+}
+
+impl<const N: usize> FusedIterator for Halton<N> {}
+
+
+///
+/// Creates a [`Halton`] sequence from the base of each named axis
+///
+/// The axes may be named in any order, but must each be one of the first N axes and named once, or the
+/// call fails to compile
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let points = halton!(y: 3, x: 2, z: 5);
+/// assert_eq!(points.bases(), &[2, 3, 5]);
+///
+/// // Stratified positions in a 640x480 window
+/// for p in points.take(16) {
+///     let (px, py) = (p[ax!(x)] * 640.0, p[ax!(y)] * 480.0);
+///     assert!(px < 640.0 && py < 480.0);
+/// }
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: halton! needs each of the first N axes once
+/// let points = halton!(x: 2, z: 3);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! halton {
+    ( $( $axis:ident : $base:expr ),+ $(,)? ) => {{
+        const _: () = assert!(
+            $crate::__private::axes_are_permutation(&[ $( $crate::ax!($axis) ),+ ]),
+            "axmac: halton! needs each of the first N axes once",
+        );
+        $crate::Halton::new($crate::__private::by_axis([ $( ($crate::ax!($axis), $base) ),+ ], 0))
+    }};
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn digits_are_mirrored() {
+        assert_eq!(radical_inverse(0, 2), 0.0);
+        assert_eq!(radical_inverse(6, 2), 0.375);
+        assert_eq!(radical_inverse(7, 3), 5.0 / 9.0);
+        assert!(radical_inverse(u64::MAX, 2) < 1.0);
+        assert!(radical_inverse(u64::MAX, u32::MAX) < 1.0);
+    }
+
+    #[test]
+    fn points_are_stratified() {
+        // Every one of the first 2^k points falls in its own slice of width 2^-k along the base 2 axis
+        let mut seen = [false; 16];
+        for p in halton!(x: 2, y: 3).starting_at(0).take(16) {
+            assert!(!core::mem::replace(&mut seen[(p[0] * 16.0) as usize], true));
+        }
+        let mut seq = Halton::new([2]);
+        assert_eq!(seq.nth(2), Some(seq.at(3)));
+        assert_eq!(seq.index(), 4);
+    }
+
+}
@@ -0,0 +1,383 @@
+//!
+//! Access by axis to the components of any fixed-length value, built-in or user defined
+//!
+
+use core::iter::FusedIterator;
+
+use crate::{Axis, AxisIndex, AxisMap, Coord};
+
+
+///
+/// Implemented by every value holding a known number of components, one per axis, adding that number to
+/// the access by [`Axis`] of [`AxisIndex`]
+///
+/// Arrays, homogeneous tuples of up to four elements, [`Coord`] and [`AxisMap`] implement it, and
+/// [`has_axes!`] implements both traits for structs with a field per axis. Code generic over it works the
+/// same on every container, and [`assert_same_axes!`] checks two of them agree at compile time. Unlike
+/// [`Dimensioned`](crate::Dimensioned), which names one of the supported dimensionalities, `DIM` may be
+/// any `usize`
+///
+/// ```
+/// use axmac::{Axis, AxisIndex, Coord, HasAxes};
+///
+/// fn height<V: HasAxes<Item = f32>>(v: &V) -> f32 {
+///     v.get_axis(Axis::Y).copied().unwrap_or(0.0)
+/// }
+///
+/// assert_eq!(height(&[1.0, 2.0, 3.0]), 2.0);
+/// assert_eq!(height(&(4.0, 5.0)), 5.0);
+/// assert_eq!(height(&Coord::new([6.0])), 0.0);
+/// ```
+///
+pub trait HasAxes: AxisIndex {
+    /// The number of axes
+    const DIM: usize;
+
+    ///
+    /// Iterates over every axis with its component, from _x_ up to the last axis or _w_
+    ///
+    /// ```
+    /// use axmac::{Axis, HasAxes};
+    ///
+    /// let v = (0.5f32, 2.0, 1.0);
+    /// let (longest, _) = v.axes().max_by(|a, b| a.1.total_cmp(b.1)).unwrap();
+    /// assert_eq!(longest, Axis::Y);
+    /// ```
+    ///
+    fn axes(&self) -> AxesIter<'_, Self> {
+        AxesIter { value: self, front: 0, back: Self::DIM.min(Axis::COUNT) }
+    }
+
+    ///
+    /// Copies the components into an array indexed by axis, failing to compile unless `N` is `DIM`
+    ///
+    /// Only _x_ to _w_ can be reached by [`Axis`], so values of more than four axes fail to compile too
+    ///
+    /// ```
+    /// use axmac::{AxisMap, HasAxes};
+    ///
+    /// let array: [u8; 3] = (1, 2, 3).to_array();
+    /// assert_eq!(array, AxisMap::new([1, 2, 3]).to_array());
+    /// ```
+    ///
+    fn to_array<const N: usize>(&self) -> [Self::Item; N]
+    where
+        Self::Item: Clone,
+    {
+        const { assert!(N == Self::DIM, "axmac: to_array needs an array of one component per axis") };
+        const { assert!(N <= Axis::COUNT, "axmac: to_array reaches only the x, y, z and w components") };
+        core::array::from_fn(|i| match Axis::from_usize(i).and_then(|axis| self.get_axis(axis)) {
+            Some(component) => component.clone(),
+            None => unreachable!("every axis below DIM has a component"),
+        })
+    }
+}
+
+
+///
+/// Iterator over the axes of a [`HasAxes`] value with their components, created by [`HasAxes::axes`]
+///
+#[derive(Debug)]
+pub struct AxesIter<'a, V: ?Sized> {
+    value: &'a V,
+    front: usize,
+    back: usize,
+}
+
+impl<V: ?Sized> Clone for AxesIter<'_, V> {
+    fn clone(&self) -> Self {
+        Self { value: self.value, front: self.front, back: self.back }
+    }
+}
+
+impl<'a, V: HasAxes + ?Sized> AxesIter<'a, V> {
+
+    // Returns the component of axis index `i`, below the end of the iterator
+    fn item(&self, i: usize) -> Option<(Axis, &'a V::Item)> {
+        let axis = Axis::from_usize(i)?;
+        Some((axis, self.value.get_axis(axis)?))
+    }
+
+}
+
+impl<'a, V: HasAxes + ?Sized> Iterator for AxesIter<'a, V> {
+    type Item = (Axis, &'a V::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        self.item(self.front - 1)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<V: HasAxes + ?Sized> DoubleEndedIterator for AxesIter<'_, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.item(self.back)
+    }
+}
+
+impl<V: HasAxes + ?Sized> ExactSizeIterator for AxesIter<'_, V> {}
+
+impl<V: HasAxes + ?Sized> FusedIterator for AxesIter<'_, V> {}
+
+impl<T, const N: usize> HasAxes for [T; N] {
+    const DIM: usize = N;
+}
+
+impl<T, const N: usize> AxisIndex for Coord<T, N> {
+    type Item = T;
+
+    fn get_axis(&self, axis: Axis) -> Option<&T> {
+        self.0.as_slice().get(axis.as_usize())
+    }
+
+    fn get_axis_mut(&mut self, axis: Axis) -> Option<&mut T> {
+        self.0.as_mut_slice().get_mut(axis.as_usize())
+    }
+}
+
+impl<T, const N: usize> HasAxes for Coord<T, N> {
+    const DIM: usize = N;
+}
+
+impl<T, const N: usize> AxisIndex for AxisMap<T, N> {
+    type Item = T;
+
+    fn get_axis(&self, axis: Axis) -> Option<&T> {
+        AxisMap::get(self, axis.as_usize())
+    }
+
+    fn get_axis_mut(&mut self, axis: Axis) -> Option<&mut T> {
+        AxisMap::get_mut(self, axis.as_usize())
+    }
+}
+
+impl<T, const N: usize> HasAxes for AxisMap<T, N> {
+    const DIM: usize = N;
+}
+
+// Tuples take their component access from `AxisIndex`
+macro_rules! impl_tuples {
+    ( $( $n:literal => ( $( $t:ident ),+ ) );+ ) => {
+        $(
+            impl<T> HasAxes for ( $( $t, )+ ) {
+                const DIM: usize = $n;
+            }
+        )+
+    };
+}
+
+impl_tuples!(
+    1 => (T);
+    2 => (T, T);
+    3 => (T, T, T);
+    4 => (T, T, T, T)
+);
+
+///
+/// Combines the components of two [`HasAxes`] values along each axis, returning the results in axis
+/// order
+///
+/// The values may be different containers with different component types, but must have the same
+/// number of axes as each other and as the array returned, or the call fails to compile
+///
+/// ```
+/// use axmac::{zip_axes, Coord3};
+///
+/// let position = Coord3::new([1, 2, 3]);
+/// let scale = [0.5, 2.0, 1.0];
+/// let scaled: [f32; 3] = zip_axes(&position, &scale, |_, &p, &s| p as f32 * s);
+/// assert_eq!(scaled, [0.5, 4.0, 3.0]);
+/// ```
+///
+/// ```compile_fail
+/// use axmac::{zip_axes, Coord3};
+///
+/// // error: axmac: zip_axes needs values with the same number of axes
+/// let sums: [i32; 3] = zip_axes(&Coord3::new([1, 2, 3]), &(1, 1), |_, a, b| a + b);
+/// ```
+///
+pub fn zip_axes<A, B, R, F, const N: usize>(a: &A, b: &B, mut f: F) -> [R; N]
+where
+    A: HasAxes + ?Sized,
+    B: HasAxes + ?Sized,
+    F: FnMut(Axis, &A::Item, &B::Item) -> R,
+{
+    const { assert!(A::DIM == B::DIM, "axmac: zip_axes needs values with the same number of axes") };
+    const { assert!(N == A::DIM, "axmac: zip_axes returns an array of one result per axis") };
+    const { assert!(N <= Axis::COUNT, "axmac: zip_axes reaches only the x, y, z and w components") };
+    core::array::from_fn(|i| {
+        let axis = Axis::from_usize(i).expect("every axis below four has an Axis");
+        match (a.get_axis(axis), b.get_axis(axis)) {
+            (Some(x), Some(y)) => f(axis, x, y),
+            _ => unreachable!("every axis below DIM has a component"),
+        }
+    })
+}
+
+
+///
+/// Implements [`HasAxes`] and [`AxisIndex`] for a struct with one field of the same type per axis
+///
+/// Takes the type, the component type and the axes in any order, each read from the field of the same
+/// name or from the field given after `=>`. The axes listed must be the first `N`, each exactly once,
+/// or the implementation fails to compile
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{Axis, AxisIndex, HasAxes};
+///
+/// struct Vec3 { x: f32, y: f32, z: f32 }
+/// has_axes!(Vec3: f32; x, y, z);
+///
+/// struct Pixel { col: u32, row: u32 }
+/// has_axes!(Pixel: u32; y => row, x => col);
+///
+/// let mut p = Pixel { col: 3, row: 7 };
+/// *p.get_axis_mut(Axis::X).unwrap() += 1;
+/// assert_eq!((p.col, p.get_axis(Axis::Y)), (4, Some(&7)));
+/// assert_eq!(<Vec3 as HasAxes>::DIM, 3);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! has_axes {
+
+    ( $ty:ty : $item:ty ; $( $axis:ident $( => $field:ident )? ),+ $(,)? ) => {
+        $crate::has_axes!(@fields $ty, $item; []; $( $axis $( => $field )? , )+);
+    };
+
+    ( @fields $ty:ty, $item:ty; [ $( $done:tt )* ]; $axis:ident => $field:ident , $( $rest:tt )* ) => {
+        $crate::has_axes!(@fields $ty, $item; [ $( $done )* ($axis $field) ]; $( $rest )*);
+    };
+    ( @fields $ty:ty, $item:ty; [ $( $done:tt )* ]; $axis:ident , $( $rest:tt )* ) => {
+        $crate::has_axes!(@fields $ty, $item; [ $( $done )* ($axis $axis) ]; $( $rest )*);
+    };
+
+    ( @fields $ty:ty, $item:ty; [ $( ($axis:ident $field:ident) )+ ]; ) => {
+        const _: () = assert!(
+            $crate::__private::axes_are_permutation(&[ $( $crate::ax!($axis) ),+ ]),
+            "axmac: has_axes! must list each of the first N axes exactly once",
+        );
+
+        impl $crate::AxisIndex for $ty {
+            type Item = $item;
+
+            fn get_axis(&self, axis: $crate::Axis) -> ::core::option::Option<&$item> {
+                $( if axis.as_usize() == $crate::ax!($axis) { return ::core::option::Option::Some(&self.$field); } )+
+                ::core::option::Option::None
+            }
+
+            fn get_axis_mut(&mut self, axis: $crate::Axis) -> ::core::option::Option<&mut $item> {
+                $( if axis.as_usize() == $crate::ax!($axis) { return ::core::option::Option::Some(&mut self.$field); } )+
+                ::core::option::Option::None
+            }
+        }
+
+        impl $crate::HasAxes for $ty {
+            const DIM: usize = [ $( $crate::ax!($axis) ),+ ].len();
+        }
+    };
+
+}
+
+
+///
+/// Checks at compile time that every value given has the same number of axes, as counted by
+/// [`HasAxes::DIM`]
+///
+/// The values are only borrowed, so references are passed dereferenced, as in `assert_same_axes!(*a, *b)`.
+/// In generic code the check is made for every instantiation, so a 2-D coordinate passed where 3-D
+/// values are expected fails to build rather than reading the wrong component
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{AxisIndex, Coord, HasAxes};
+///
+/// fn dot<A: HasAxes<Item = f32>, B: HasAxes<Item = f32>>(a: &A, b: &B) -> f32 {
+///     assert_same_axes!(*a, *b);
+///     axmac::Axis::iter().filter_map(|axis| Some(a.get_axis(axis)? * b.get_axis(axis)?)).sum()
+/// }
+/// assert_eq!(dot(&Coord::new([1.0, 2.0]), &(3.0, 4.0)), 11.0);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: assert_same_axes! was given values with different numbers of axes
+/// assert_same_axes!([1.0, 2.0], axmac::Coord::new([4.0, 4.0, 4.0]));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! assert_same_axes {
+    ( $first:expr $( , $rest:expr )+ $(,)? ) => {{
+        $( $crate::__private::same_axes(&$first, &$rest); )+
+    }};
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    struct Rgb {
+        red: u8,
+        green: u8,
+        blue: u8,
+    }
+
+    has_axes!(Rgb: u8; z => blue, x => red, y => green);
+
+    #[test]
+    fn containers_agree() {
+        let mut tuple = (1, 2, 3);
+        *tuple.get_axis_mut(Axis::Z).unwrap() = 30;
+        let map = AxisMap::new([1, 2, 30]);
+        for axis in Axis::iter() {
+            assert_eq!(tuple.get_axis(axis), map.get_axis(axis));
+        }
+        assert_eq!([0; 9].get_axis(Axis::W), Some(&0));
+        assert_eq!(<AxisMap<u8, 9> as HasAxes>::DIM, 9);
+    }
+
+    #[test]
+    fn structs_map_fields() {
+        let mut pixel = Rgb { red: 1, green: 2, blue: 3 };
+        *pixel.get_axis_mut(Axis::Y).unwrap() = 20;
+        assert_eq!(pixel.green, 20);
+        assert_eq!(pixel.get_axis(Axis::Z), Some(&pixel.blue));
+        assert_eq!(pixel.get_axis(Axis::W), None);
+        assert_same_axes!(pixel, (0, 0, 0), [0u8; 3]);
+    }
+
+    #[test]
+    fn axes_iterate_both_ways() {
+        let pixel = Rgb { red: 1, green: 2, blue: 3 };
+        assert!(pixel.axes().rev().eq([(Axis::Z, &3), (Axis::Y, &2), (Axis::X, &1)]));
+        assert_eq!(pixel.to_array(), [1, 2, 3]);
+        assert_eq!([0; 9].axes().len(), 4);
+        assert_eq!((7,).axes().next(), Some((Axis::X, &7)));
+    }
+
+    #[test]
+    fn zip_passes_axes() {
+        let pixel = Rgb { red: 1, green: 2, blue: 3 };
+        let labelled: [(Axis, u8); 3] = zip_axes(&pixel, &AxisMap::<u8, 3>::splat(10), |axis, &c, &s| (axis, c * s));
+        assert_eq!(labelled, [(Axis::X, 10), (Axis::Y, 20), (Axis::Z, 30)]);
+    }
+
+}
@@ -0,0 +1,116 @@
+//!
+//! Register helpers for multi-axis sensor drivers
+//!
+
+
+///
+/// Describes how a sensor lays out one sample per axis across consecutive registers
+///
+/// Most accelerometers and gyroscopes expose each axis as a 16-bit value spread over `stride` registers
+/// starting at `base`, in the same order as the `imu` vocabulary, so `ax!(gy)` can be used directly as the
+/// axis argument
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::RegisterLayout;
+///
+/// // The accelerometer of an MPU-6050, whose x, y and z samples start at 0x3B, 0x3D and 0x3F. Its gyroscope
+/// // starts at 0x43, after a temperature sample, so it needs a layout of its own rather than `ax!(gx)`
+/// const ACCEL: RegisterLayout = RegisterLayout::big_endian(0x3B);
+/// assert_eq!(ACCEL.register(ax!(az)), 0x3F);
+/// assert_eq!(RegisterLayout::big_endian(0x43).register(1), 0x45);
+///
+/// let burst = [0x01, 0x00, 0xFF, 0xFF, 0x00, 0x10];
+/// assert_eq!(ACCEL.samples::<3>(&burst), [256, -1, 16]);
+/// # }
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct RegisterLayout {
+    /// Address of the first register of the first axis
+    pub base: u8,
+    /// Number of registers between the first registers of consecutive axes
+    pub stride: u8,
+    /// Whether the high byte of each sample comes first
+    pub big_endian: bool,
+}
+
+impl RegisterLayout {
+
+    ///
+    /// A layout of 16-bit samples with the high byte first
+    ///
+    pub const fn big_endian(base: u8) -> Self {
+        Self { base, stride: 2, big_endian: true }
+    }
+
+    ///
+    /// A layout of 16-bit samples with the low byte first
+    ///
+    pub const fn little_endian(base: u8) -> Self {
+        Self { base, stride: 2, big_endian: false }
+    }
+
+    ///
+    /// Returns the address of the first register holding `axis`
+    ///
+    pub const fn register(&self, axis: usize) -> u8 {
+        self.base.wrapping_add((axis as u8).wrapping_mul(self.stride))
+    }
+
+    ///
+    /// Decodes the sample of `axis` from a burst read starting at `base`
+    ///
+    /// # Panics
+    ///
+    /// If `burst` is too short to hold the sample
+    ///
+    pub fn sample(&self, burst: &[u8], axis: usize) -> i16 {
+        let offset = axis * self.stride as usize;
+        let bytes = [burst[offset], burst[offset + 1]];
+        if self.big_endian {
+            i16::from_be_bytes(bytes)
+        } else {
+            i16::from_le_bytes(bytes)
+        }
+    }
+
+    ///
+    /// Decodes the samples of the first `N` axes from a burst read starting at `base`
+    ///
+    /// # Panics
+    ///
+    /// If `burst` is too short to hold every sample
+    ///
+    pub fn samples<const N: usize>(&self, burst: &[u8]) -> [i16; N] {
+        let mut out = [0; N];
+        for (axis, v) in out.iter_mut().enumerate() {
+            *v = self.sample(burst, axis);
+        }
+        out
+    }
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn register_works() {
+        let layout = RegisterLayout { base: 0x28, stride: 2, big_endian: false };
+        assert_eq!(layout.register(ax!(ax)), 0x28);
+        assert_eq!(layout.register(ax!(gz)), 0x32);
+    }
+
+    #[test]
+    fn samples_work() {
+        let burst = [0x00, 0x01, 0xFE, 0xFF];
+        assert_eq!(RegisterLayout::little_endian(0).samples::<2>(&burst), [256, -2]);
+        assert_eq!(RegisterLayout::big_endian(0).sample(&burst, ax!(ay)), -257);
+    }
+
+}
@@ -0,0 +1,101 @@
+//!
+//! Typed game controller axes
+//!
+//! [`GamepadAxis`] is the neutral naming layer between input backends. Its discriminants match the values
+//! produced by `ax!(lx)` through `ax!(rt)`, which are in turn the raw values of SDL's
+//! `SDL_GameControllerAxis` enum, so converting to and from SDL is a plain integer conversion
+//!
+
+use crate::InvalidAxis;
+
+
+///
+/// One of the six standard analog axes of a game controller
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(usize)]
+pub enum GamepadAxis {
+    LeftX        = ax!(lx),
+    LeftY        = ax!(ly),
+    RightX       = ax!(rx),
+    RightY       = ax!(ry),
+    LeftTrigger  = ax!(lt),
+    RightTrigger = ax!(rt),
+}
+
+impl GamepadAxis {
+
+    ///
+    /// Every gamepad axis, in index order
+    ///
+    pub const ALL: [Self; 6] = [
+        Self::LeftX, Self::LeftY, Self::RightX, Self::RightY, Self::LeftTrigger, Self::RightTrigger,
+    ];
+
+    ///
+    /// Returns the same index `ax!` produces for this axis
+    ///
+    pub const fn as_usize(self) -> usize {
+        self as usize
+    }
+
+    ///
+    /// Converts a raw `SDL_GameControllerAxis` value
+    ///
+    /// Returns `None` for `SDL_CONTROLLER_AXIS_INVALID` (-1) and any other out of range value
+    ///
+    pub const fn from_sdl(raw: i32) -> Option<Self> {
+        if raw < 0 || raw as usize >= Self::ALL.len() {
+            None
+        } else {
+            Some(Self::ALL[raw as usize])
+        }
+    }
+
+    ///
+    /// Converts to a raw `SDL_GameControllerAxis` value
+    ///
+    pub const fn to_sdl(self) -> i32 {
+        self as i32
+    }
+
+}
+
+impl From<GamepadAxis> for usize {
+    fn from(axis: GamepadAxis) -> Self {
+        axis.as_usize()
+    }
+}
+
+impl TryFrom<usize> for GamepadAxis {
+    type Error = InvalidAxis;
+
+    fn try_from(index: usize) -> Result<Self, Self::Error> {
+        Self::ALL.get(index).copied().ok_or(InvalidAxis(index))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn matches_ax() {
+        assert_eq!(usize::from(GamepadAxis::RightY), ax!(ry));
+        assert_eq!(GamepadAxis::try_from(ax!(lt)), Ok(GamepadAxis::LeftTrigger));
+        assert_eq!(GamepadAxis::try_from(6), Err(InvalidAxis(6)));
+    }
+
+    #[test]
+    fn sdl_round_trips() {
+        for axis in GamepadAxis::ALL {
+            assert_eq!(GamepadAxis::from_sdl(axis.to_sdl()), Some(axis));
+        }
+        assert_eq!(GamepadAxis::from_sdl(-1), None);
+        assert_eq!(GamepadAxis::from_sdl(6), None);
+    }
+
+}
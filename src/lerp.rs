@@ -0,0 +1,90 @@
+//!
+//! Interpolating between two values with named components, for animation and tweening
+//!
+
+use crate::{Axis, AxisMap, HasAxes, Scalar};
+
+
+///
+/// Interpolates linearly from `a` at a `t` of zero to `b` at one, along every axis
+///
+/// Returns the same container as the inputs, computing `a * (1 - t) + b * t` so both ends are exact.
+/// Values of `t` outside `0..=1` extrapolate
+///
+/// ```
+/// use axmac::{lerp_axes, Coord3};
+///
+/// let from = Coord3::new([0.0, 10.0, -4.0]);
+/// let to = Coord3::new([1.0, 20.0, 4.0]);
+/// assert_eq!(lerp_axes(&from, &to, 0.25), Coord3::new([0.25, 12.5, -2.0]));
+/// ```
+///
+pub fn lerp_axes<V>(a: &V, b: &V, t: V::Item) -> V
+where
+    V: HasAxes + Clone,
+    V::Item: Scalar,
+{
+    lerp_with(a, b, |_| t)
+}
+
+///
+/// Interpolates as [`lerp_axes`] with a factor of its own for every axis, so one axis can lead or lag
+/// the others
+///
+/// The map must have one factor per axis of the values, or the call fails to compile
+///
+/// ```
+/// use axmac::{lerp_axes_per, AxisMap};
+///
+/// // Ease in horizontally while already halfway there vertically
+/// let t = AxisMap::new([0.1, 0.5]);
+/// assert_eq!(lerp_axes_per(&[0.0, 0.0], &[10.0, 10.0], &t), [1.0, 5.0]);
+/// ```
+///
+pub fn lerp_axes_per<V, const N: usize>(a: &V, b: &V, t: &AxisMap<V::Item, N>) -> V
+where
+    V: HasAxes + Clone,
+    V::Item: Scalar,
+{
+    const { assert!(N == V::DIM, "axmac: lerp_axes_per needs one factor per axis") };
+    lerp_with(a, b, |axis| t[axis])
+}
+
+fn lerp_with<V, F>(a: &V, b: &V, t: F) -> V
+where
+    V: HasAxes + Clone,
+    V::Item: Scalar,
+    F: Fn(Axis) -> V::Item,
+{
+    let mut out = a.clone();
+    for axis in Axis::iter() {
+        if let (Some(&b), Some(out)) = (b.get_axis(axis), out.get_axis_mut(axis)) {
+            let t = t(axis);
+            *out = *out * (V::Item::ONE - t) + b * t;
+        }
+    }
+    out
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn ends_are_exact() {
+        let (a, b) = ((0.1f32, 1e8, -3.0), (0.7f32, 3.0, 1e-8));
+        assert_eq!(lerp_axes(&a, &b, 0.0), a);
+        assert_eq!(lerp_axes(&a, &b, 1.0), b);
+        assert_eq!(lerp_axes(&[2.0], &[4.0], 1.5), [5.0]);
+    }
+
+    #[test]
+    fn factors_follow_axes() {
+        let t = AxisMap::new([0.0, 1.0, 0.5, 0.25]);
+        assert_eq!(lerp_axes_per(&[0.0; 4], &[4.0; 4], &t), [0.0, 4.0, 2.0, 1.0]);
+    }
+
+}
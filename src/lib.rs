@@ -4,6 +4,12 @@
 
 #![no_std]
 
+#[cfg(any(test, feature = "std"))]
+extern crate std;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 
 ///
 /// Converts an identifier _x_, _y_, _z_ or _w_ to a `usize` value.
@@ -28,7 +34,7 @@
 /// assert_eq!(fourth, 3);
 ///
 /// // ERROR: Only allowed to use one of x, y, z or w
-/// // let fifth_axis = ax!(v);
+/// // let fifth_axis = ax!(q);
 ///
 /// // ERROR: Only accepts one identifier
 /// //        If multiple axes are what you need, see the 'axs' macro
@@ -36,6 +42,105 @@
 /// # }
 /// ```
 ///
+/// # Typed Axes
+///
+/// Prefixing the identifier with `@enum` produces an [`Axis`] instead, for runtime APIs taking an axis
+/// parameter. `axs!` accepts the same prefix
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Axis;
+///
+/// assert_eq!(ax!(@enum y), Axis::Y);
+/// assert_eq!(axs![@enum x, z], [Axis::X, Axis::Z]);
+/// # }
+/// ```
+///
+/// # Diagnostics
+///
+/// Mistakes in `ax!`, `axs!` and `axr!` are reported with a message naming the tokens at fault, and
+/// unknown identifiers are answered with every identifier the enabled features accept
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: expected one of x, y, z, w (got `q`)
+/// let i = ax!(q);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: expected one of x, y, z, w (got `5`)
+/// let a = axs![x, 5];
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: ax! takes a single axis identifier, use axs! for several (got `z, w`)
+/// let i = ax!(z, w);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: put parentheses around the start of the range, as in axr!((0)..z)
+/// let r = axr!(0..z);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: axr! takes a range with an axis identifier at one end, such as x..z, y..5 or (0)..w
+/// let n = 1;
+/// let r = axr!(n + 1..z);
+/// # }
+/// ```
+///
+/// # Feature Gated Vocabularies
+///
+/// Enabling the following features makes `ax!`, `axs!` and `axr!` accept further identifiers
+///
+/// | Feature | Identifiers |
+/// |---------|-------------|
+/// | `input` | `lx` => 0, `ly` => 1, `rx` => 2, `ry` => 3, `lt` => 4, `rt` => 5 |
+/// | `imu`   | `ax` => 0, `ay` => 1, `az` => 2, `gx` => 3, `gy` => 4, `gz` => 5 |
+/// | `motion` | `e` => 3, `a` => 4, `b` => 5, `c` => 6 |
+/// | `rgba`  | `r` => 0, `g` => 1, `b` => 2, `a` => 3 |
+/// | `uvst`  | `u` => 0, `v` => 1, `s` => 2, `t` => 3 |
+/// | `whd`   | `width` => 0, `height` => 1, `depth` => 2 |
+/// | `spectral` | `freq` => 0, `time` => 1 |
+///
+/// The `motion` and `rgba` features both name `a` and `b`, so they cannot be enabled together
+///
+/// # Use Within Other Macros
+///
+/// Declarative macros often capture tokens as `tt`s and need the index as a literal token of their own,
+/// for example to match on it or hand it to a further macro. The `@with` form calls back into such a macro
+/// with the index appended to any prefix tokens given in parentheses. The callback may be a path, so an
+/// exported macro can call back into its own crate through `$crate::`
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// macro_rules! component {
+///     ( $v:ident; $i:literal ) => { $v[$i] };
+/// }
+///
+/// macro_rules! get {
+///     ( $v:ident . $axis:tt ) => { ax!(@with component ($v;) $axis) };
+/// }
+///
+/// let v = [1.0, 2.0, 3.0];
+/// assert_eq!(get!(v.z), 3.0);
+///
+/// // Without a prefix, the index is the only argument
+/// macro_rules! as_lit {
+///     ( $i:literal ) => { $i };
+/// }
+/// assert_eq!(ax!(@with as_lit y), 1usize);
+/// # }
+/// ```
+///
 #[macro_export]
 macro_rules! ax {
 
@@ -44,6 +149,35 @@ macro_rules! ax {
     (z) => { 2usize };
     (w) => { 3usize };
 
+    // Callbacks, for macros holding axes as `tt`s
+    //  ax!(@with cb (prefix) y) => cb!(prefix 1usize)
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) x ) => { $( $cb )::+!($( $p )* 0usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) y ) => { $( $cb )::+!($( $p )* 1usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) z ) => { $( $cb )::+!($( $p )* 2usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) w ) => { $( $cb )::+!($( $p )* 3usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:ident ) => { $crate::__ax_input!(@with $( $cb )::+ ($( $p )*) $other) };
+    //  ax!(@with cb y) => cb!(1usize)
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:tt ) => { $crate::ax!(@unknown $other) };
+    ( @with $( $cb:ident )::+ $axis:tt ) => { $crate::ax!(@with $( $cb )::+ () $axis) };
+
+    // Typed axes, rejecting any index without an `Axis` at compile time
+    //  ax!(@enum z) => Axis::Z
+    ( @enum $axis:ident ) => { const { $crate::__private::axis($crate::ax!($axis)) } };
+
+    // Unknown axes, sent along the vocabulary chain to collect the names it would have accepted
+    ( @unknown $other:tt ) => { $crate::__ax_input!(@unknown ("x, y, z, w") $other) };
+
+    // Identifiers from the feature gated vocabularies
+    ( $other:ident ) => { $crate::__ax_input!($other) };
+
+    // Anything else, such as a literal or several axes
+    ( $other:tt ) => { $crate::ax!(@unknown $other) };
+    ( $( $other:tt )* ) => {
+        compile_error!(concat!(
+            "axmac: ax! takes a single axis identifier, use axs! for several (got `", stringify!($( $other )*), "`)",
+        ))
+    };
+
 }
 
 
@@ -57,8 +191,9 @@ macro_rules! ax {
 /// # Possible Variations
 ///
 /// ```
-/// # #[macro_use] extern crate axmac; fn main() {
-/// # use axmac::axs;
+/// // Only `axs!` needs to be imported, even though it expands to `ax!`
+/// use axmac::axs;
+///
 /// // Explicitly specifying items in array
 /// let arr =  axs![x, y, z, w];
 /// assert_eq!(arr, [0, 1, 2, 3]);
@@ -66,28 +201,54 @@ macro_rules! ax {
 /// // Repeat specified item N times
 /// let arr =  axs![w; 4];
 /// assert_eq!(arr, [3, 3, 3, 3]);
-/// # }
+///
+/// // Every axis in a range, as a constant
+/// const SPATIAL: [usize; 3] = axs![x..=z];
+/// assert_eq!(SPATIAL, [0, 1, 2]);
+/// assert_eq!(axs![y..w], [1, 2]);
 /// ```
 ///
 /// Using identifiers multiple times is allowed, this is only a more readable way to create arrays after
 /// all
 ///
 /// ```
-/// # #[macro_use] extern crate axmac; fn main() {
 /// # use axmac::axs;
 /// let arr  =  axs![x,x, z,z, y,y];
 /// assert_eq!(arr, [0,0, 2,2, 1,1]);
-/// # }
 /// ```
 ///
 #[macro_export]
 macro_rules! axs {
 
     // [x, x, w, z, y, z]
-    ( $( $d:ident ), * ) => { [ $( ax!($d), )* ] };
+    ( $( $d:ident ), * ) => { [ $( $crate::ax!($d), )* ] };
 
     // [z; 3]
-    ( $d:ident; $i:expr ) => { [ax!($d); $i] };
+    ( $d:ident; $i:expr ) => { [$crate::ax!($d); $i] };
+
+    // [@enum x, w] => [Axis::X, Axis::W]
+    ( @enum $( $d:ident ), * ) => { [ $( $crate::ax!(@enum $d), )* ] };
+
+    // [@enum z; 3]
+    ( @enum $d:ident; $i:expr ) => { [$crate::ax!(@enum $d); $i] };
+
+    // [x..=z] => [0, 1, 2]
+    ( $a:ident .. $b:ident ) => {
+        $crate::__private::axis_range::<{ $crate::ax!($b) - $crate::ax!($a) }>($crate::ax!($a))
+    };
+    ( $a:ident ..= $b:ident ) => {
+        $crate::__private::axis_range::<{ $crate::ax!($b) - $crate::ax!($a) + 1 }>($crate::ax!($a))
+    };
+
+    // Anything else is checked element by element, so the error names the element at fault
+    ( $( $d:tt ),* $(,)? ) => { [ $( $crate::ax!($d), )* ] };
+    ( $d:tt; $i:expr ) => { [$crate::ax!($d); $i] };
+    ( $( $other:tt )* ) => {
+        compile_error!(concat!(
+            "axmac: axs! takes axis identifiers separated by commas, or `axis; count` (got `",
+            stringify!($( $other )*), "`)",
+        ))
+    };
 
 }
 
@@ -102,8 +263,9 @@ macro_rules! axs {
 /// # Possible Variations
 ///
 /// ```
-/// # #[macro_use] extern crate axmac; fn main() {
-/// # use axmac::axr;
+/// // Only `axr!` needs to be imported, even though it expands to `ax!`
+/// use axmac::axr;
+///
 /// // PLEASE NOTE!
 /// //  x => 0usize
 /// //  y => 1
@@ -138,43 +300,280 @@ macro_rules! axs {
 /// // RangeInclusive with expression and identifier
 /// //  The parentheses around the expression are compulsory
 /// assert_eq!(axr!((1)..=w), 1..=3);
-/// # }
+/// ```
+///
+/// # Stepped and Reversed Ranges
+///
+/// Following a range with `step n` steps through it `n` at a time, while `rev` walks from the first end
+/// down to the second. Both expand to iterators rather than ranges
+///
+/// ```
+/// use axmac::axr;
+///
+/// assert!(axr!(x..w step 2).eq([0, 2]));
+/// assert!(axr!(x..=10 step 5).eq([0, 5, 10]));
+/// assert!(axr!(w..=x rev).eq([3, 2, 1, 0]));
+/// assert!(axr!(w..x rev).eq([3, 2, 1]));
 /// ```
 ///
 #[macro_export]
 macro_rules! axr {
 
+    // Stepped and reversed ranges, whose ends are axes, literals or parenthesised expressions
+    //  (x..w).step_by(2)
+    ( $a:tt .. $b:tt step $n:expr ) => { ($crate::axr!(@end $a)..$crate::axr!(@end $b)).step_by($n) };
+    ( $a:tt ..= $b:tt step $n:expr ) => { ($crate::axr!(@end $a)..=$crate::axr!(@end $b)).step_by($n) };
+    //  w down to x, inclusive
+    ( $a:tt ..= $b:tt rev ) => { ($crate::axr!(@end $b)..=$crate::axr!(@end $a)).rev() };
+    //  w down to x, stopping short of x
+    ( $a:tt .. $b:tt rev ) => { ($crate::axr!(@end $b) + 1..=$crate::axr!(@end $a)).rev() };
+
+    ( @end $a:ident ) => { $crate::ax!($a) };
+    ( @end $a:tt ) => { $a };
+
     // Ident to Ident
     //  Range x..w
-    ( $a:ident..$b:ident ) => { ax!($a)..ax!($b) };
+    ( $a:ident..$b:ident ) => { $crate::ax!($a)..$crate::ax!($b) };
     //  RangeInclusive y..=z
-    ( $a:ident..=$b:ident ) => { ax!($a)..=ax!($b) };
+    ( $a:ident..=$b:ident ) => { $crate::ax!($a)..=$crate::ax!($b) };
 
     // Ident to Expr
     //  Range z..6
-    ( $a:ident..$b:expr ) => { ax!($a)..$b };
+    ( $a:ident..$b:expr ) => { $crate::ax!($a)..$b };
     //  RangeInclusive w..=9
-    ( $a:ident..=$b:expr ) => { ax!($a)..=$b };
+    ( $a:ident..=$b:expr ) => { $crate::ax!($a)..=$b };
 
     // Inf to Ident
     //  RangeTo ..w
-    ( ..$a:ident ) => { ..ax!($a) };
+    ( ..$a:ident ) => { ..$crate::ax!($a) };
     //  RangeToInclusive ..=z
-    ( ..=$a:ident ) => { ..=ax!($a) };
+    ( ..=$a:ident ) => { ..=$crate::ax!($a) };
 
     // Ident to Inf
     //  RangeFrom x..
-    ( $a:ident.. ) => { ax!($a).. };
+    ( $a:ident.. ) => { $crate::ax!($a).. };
 
     // Expr to Ident
     //  Range (0)..z
-    ( ($a:expr)..$b:ident )  => { $a..ax!($b) };
+    ( ($a:expr)..$b:ident )  => { $a..$crate::ax!($b) };
     // RangeInclusive (1)..=w
-    ( ($a:expr)..=$b:ident )  => { $a..=ax!($b) };
+    ( ($a:expr)..=$b:ident )  => { $a..=$crate::ax!($b) };
+
+    // Mistakes
+    //  Expressions before the dots must be parenthesised, as `0..z` does not parse as an axis range
+    ( $a:literal .. $( $b:tt )* ) => {
+        compile_error!(concat!(
+            "axmac: put parentheses around the start of the range, as in axr!((", stringify!($a), ")..",
+            stringify!($( $b )*), ")",
+        ))
+    };
+    ( $a:literal ..= $( $b:tt )* ) => {
+        compile_error!(concat!(
+            "axmac: put parentheses around the start of the range, as in axr!((", stringify!($a), ")..=",
+            stringify!($( $b )*), ")",
+        ))
+    };
+    ( $( $other:tt )* ) => {
+        compile_error!(concat!(
+            "axmac: axr! takes a range with an axis identifier at one end, such as x..z, y..5 or (0)..w (got `",
+            stringify!($( $other )*), "`)",
+        ))
+    };
 
 }
 
 
+// Modules are declared after the macros so the macros are in textual scope within them
+mod aabb;
+mod along;
+mod assert;
+#[cfg(target_has_atomic = "ptr")]
+mod atomic;
+mod axis;
+mod axis_index;
+mod axis_map;
+mod axis_set;
+#[cfg(feature = "bench-util")]
+pub mod bench;
+mod bundle;
+mod cell;
+#[cfg(feature = "std")]
+pub mod cli;
+#[cfg(feature = "rgba")]
+mod color;
+mod column;
+#[cfg(feature = "compat-0-1")]
+pub mod compat;
+mod compact;
+mod concat;
+mod conv;
+pub mod consts;
+mod coord;
+mod corner;
+#[cfg(feature = "std")]
+mod csv;
+mod cubemap;
+mod debug_assert;
+mod define_axes;
+mod delta;
+mod dim;
+#[cfg(feature = "debug-dims")]
+mod dims;
+#[cfg(feature = "fmt")]
+mod display;
+mod error;
+mod euler;
+#[cfg(feature = "ext")]
+mod ext;
+mod extremal;
+mod fixed_shape;
+mod flip;
+mod for_axes;
+mod halton;
+mod has_axes;
+#[cfg(feature = "arbitrary")]
+pub mod fuzz;
+#[cfg(feature = "imu")]
+mod imu;
+#[cfg(feature = "input")]
+mod input;
+mod lerp;
+pub mod marker;
+mod marching;
+mod mat4;
+pub mod math;
+mod matrix;
+mod meta;
+#[cfg(feature = "motion")]
+mod motion;
+mod multi_slice;
+mod ndindex;
+#[cfg(feature = "npy")]
+mod npy;
+mod pack;
+mod pad;
+mod pages;
+mod perm;
+mod ping_pong;
+mod pipeline;
+mod plane;
+#[cfg(feature = "alloc")]
+mod points;
+mod priority;
+pub mod prelude;
+#[cfg(feature = "rand")]
+pub mod random;
+mod ray;
+mod remap;
+mod roi;
+mod rotate;
+mod router;
+mod rules;
+mod shape;
+mod signed_axis;
+mod slice;
+mod split;
+#[cfg(feature = "alloc")]
+mod soa;
+mod spacing;
+#[cfg(feature = "spectral")]
+pub mod spectral;
+#[doc(hidden)]
+#[path = "private.rs"]
+pub mod __private;
+mod sat;
+mod screen;
+mod scalar;
+pub mod stats;
+mod stencil;
+mod sweep;
+mod swizzle;
+mod tensor;
+mod ticks;
+mod tiles;
+#[cfg(all(feature = "tracing", target_has_atomic = "ptr"))]
+pub mod trace;
+mod transform;
+mod view;
+mod vocab;
+mod voxel;
+
+pub use aabb::Aabb;
+pub use along::{Along, Stride};
+#[cfg(target_has_atomic = "ptr")]
+pub use atomic::{AtomicAxisMap, AtomicExtent, AxisExtentTracker};
+pub use axis::{AnyAxis, Axis};
+pub use axis_index::AxisIndex;
+pub use axis_map::AxisMap;
+pub use axis_set::{AxisMask, AxisSet, AxisSetIter};
+pub use bundle::AxisBundle;
+pub use cell::CellKey;
+#[cfg(feature = "rgba")]
+pub use color::{Channel, ColorLayout};
+pub use column::{deinterleave, gather_axis, interleave, scatter_axis, select_nth_along, transpose_aos_soa};
+pub use conv::Window;
+pub use coord::{Coord, Coord2, Coord3, Coord4};
+pub use corner::{corner_coord, corner_neighbors, gray_moves, GrayMoves};
+#[cfg(feature = "std")]
+pub use csv::write_csv;
+pub use cubemap::cubemap_face;
+pub use delta::{delta_decode_along, delta_encode_along, DeltaCode};
+pub use dim::{component, AxisOf, Component, Dim, Dimensionality, Dimensioned, D1, D2, D3, D4};
+#[cfg(feature = "fmt")]
+pub use display::ShapedDisplay;
+pub use compact::ParseError;
+pub use error::{InvalidAxis, InvalidPermutation, OutOfBounds};
+pub use euler::{EulerKind, EulerOrder};
+#[cfg(feature = "ext")]
+pub use ext::{ExtError, ExtShape};
+pub use extremal::{dominant_axis, max_axis, min_axis, TieBreak};
+pub use fixed_shape::FixedShape;
+pub use flip::{flip_coord, flip_flat_index, flip_in_place};
+pub use halton::Halton;
+pub use has_axes::{zip_axes, AxesIter, HasAxes};
+#[cfg(feature = "imu")]
+pub use imu::RegisterLayout;
+#[cfg(feature = "input")]
+pub use input::GamepadAxis;
+pub use lerp::{lerp_axes, lerp_axes_per};
+pub use marching::{mc_corner_of, mc_edge_of, MC_CORNERS, MC_EDGES, MC_EDGE_AXES};
+pub use mat4::{mat4_col, mat4_mul_vec4, mat4_row, MatrixLayout};
+pub use meta::AxisMeta;
+pub use math::MathBackend;
+#[cfg(feature = "motion")]
+pub use motion::{MotionAxis, MotionLayout};
+pub use ndindex::{HalfOpen, NdIndex};
+#[cfg(feature = "npy")]
+pub use npy::{NpyDtype, NpyError, NpyHeader};
+pub use pad::{Border, Padded};
+pub use perm::AxisPerm;
+pub use ping_pong::PingPong;
+pub use pipeline::{AxisPipeline, AxisTransform, Curve};
+pub use plane::{AxisPlane, Side};
+#[cfg(feature = "alloc")]
+pub use points::Points;
+pub use priority::AxisPriority;
+pub use ray::{ray_aabb, RaySlabs};
+pub use remap::{basis_from, AxisRemap};
+pub use roi::Roi;
+pub use rotate::{rot90_coord, rot90_extents, rotate90};
+pub use router::AxisRouter;
+pub use rules::{AxisRules, RuleError};
+pub use shape::{Layout, Shape, ShapeDescription};
+pub use signed_axis::SignedAxis;
+pub use slice::AxisSlice;
+pub use stencil::{stencil_values, Boundary, StencilValues};
+pub use tensor::{kronecker_delta, levi_civita};
+pub use ticks::{ticks_along, AxisScale, Ticks};
+pub use tiles::Tiles;
+pub use transform::GridTransform;
+pub use view::{copy_region, copy_region_by, hash_region, runs_along, view, view_into, Runs, View};
+pub use sat::{min_penetration_axis, overlap_along, overlaps_all_axes};
+pub use scalar::{Scalar, SignedScalar};
+pub use sweep::{best_sweep_axis, sort_along, sweep_pairs};
+pub use voxel::{VoxelStep, VoxelTraverse};
+
+
 
 #[cfg(test)]
 mod tests {
@@ -188,6 +587,18 @@ mod tests {
             assert_eq!(ax!(z), 2);
             assert_eq!(ax!(w), 3);
         }
+
+        #[test]
+        fn with_callback_works() {
+            macro_rules! pair {
+                ( $a:literal, $b:literal ) => { [$a, $b] };
+            }
+            macro_rules! echo {
+                ( $i:literal ) => { $i };
+            }
+            assert_eq!(ax!(@with pair (7,) w), [7, 3]);
+            assert_eq!(ax!(@with echo x), 0);
+        }
     }
 
     #[cfg(test)]
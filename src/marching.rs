@@ -0,0 +1,226 @@
+//!
+//! The corner and edge numbering of a marching cubes cell, named by axis
+//!
+//! Corners and edges follow the numbering of the classic marching cubes case tables, so indices computed
+//! here can look up published triangle tables directly. Corners 0 to 3 go around the `z = 0` face
+//! starting at the origin and moving along x first, corners 4 to 7 repeat them at `z = 1`. Edges 0 to 3
+//! join consecutive corners of the lower face, edges 4 to 7 those of the upper face, and edges 8 to 11
+//! run along z from corners 0 to 3
+//!
+
+use crate::Axis;
+
+
+///
+/// The position of every marching cubes corner along x, y and z
+///
+pub const MC_CORNERS: [[usize; 3]; 8] = [
+    [0, 0, 0],
+    [1, 0, 0],
+    [1, 1, 0],
+    [0, 1, 0],
+    [0, 0, 1],
+    [1, 0, 1],
+    [1, 1, 1],
+    [0, 1, 1],
+];
+
+///
+/// The two corners joined by every marching cubes edge, lower corner first
+///
+pub const MC_EDGES: [[usize; 2]; 12] = [
+    [0, 1],
+    [1, 2],
+    [3, 2],
+    [0, 3],
+    [4, 5],
+    [5, 6],
+    [7, 6],
+    [4, 7],
+    [0, 4],
+    [1, 5],
+    [2, 6],
+    [3, 7],
+];
+
+///
+/// The axis every marching cubes edge runs along
+///
+pub const MC_EDGE_AXES: [Axis; 12] = {
+    let mut axes = [Axis::X; 12];
+    let mut e = 0;
+    while e < 12 {
+        let [from, to] = MC_EDGES[e];
+        let mut axis = 0;
+        while MC_CORNERS[from][axis] == MC_CORNERS[to][axis] {
+            axis += 1;
+        }
+        axes[e] = match Axis::from_usize(axis) {
+            Some(axis) => axis,
+            None => unreachable!(),
+        };
+        e += 1;
+    }
+    axes
+};
+
+///
+/// Returns the marching cubes corner at a position of zero or one along x, y and z
+///
+/// ```
+/// use axmac::{mc_corner_of, MC_CORNERS};
+///
+/// assert_eq!(mc_corner_of([1, 1, 0]), 2);
+/// assert_eq!(MC_CORNERS[mc_corner_of([0, 1, 1])], [0, 1, 1]);
+/// ```
+///
+/// # Panics
+///
+/// If any coordinate is above one
+///
+pub const fn mc_corner_of(coord: [usize; 3]) -> usize {
+    assert!(coord[0] <= 1 && coord[1] <= 1 && coord[2] <= 1, "axmac: cube corners lie at zero or one along each axis");
+    // Each face runs around rather than in binary order, so x counts backwards along the far side of y
+    4 * coord[2] + 2 * coord[1] + (coord[0] ^ coord[1])
+}
+
+///
+/// Returns the marching cubes edge running along `axis` through a position of zero or one along the
+/// other two axes
+///
+/// The coordinate along `axis` itself is ignored
+///
+/// ```
+/// use axmac::{mc_edge_of, Axis, MC_EDGES};
+///
+/// let edge = mc_edge_of(Axis::Y, [1, 0, 1]);
+/// assert_eq!(edge, 5);
+/// assert_eq!(MC_EDGES[edge], [5, 6]);
+/// ```
+///
+/// # Panics
+///
+/// If `axis` is _w_ or any other coordinate is above one
+///
+pub const fn mc_edge_of(axis: Axis, coord: [usize; 3]) -> usize {
+    assert!(axis.as_usize() < 3, "axmac: cube edges run along x, y or z");
+    let mut from = coord;
+    from[axis.as_usize()] = 0;
+    let from = mc_corner_of(from);
+    let mut e = 0;
+    while MC_EDGES[e][0] != from || MC_EDGE_AXES[e].as_usize() != axis.as_usize() {
+        e += 1;
+    }
+    e
+}
+
+
+///
+/// Returns the marching cubes corner on the named side of each of x, y and z, with `+` for the side at
+/// one and `-` for the side at zero
+///
+/// The axes may come in any order but must each be named once, or the call fails to compile. The macro is
+/// a const expression
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// assert_eq!(mc_corner!(-x, -y, -z), 0);
+/// assert_eq!(mc_corner!(+z, +x, -y), 5);
+///
+/// const TOP_FAR_LEFT: usize = mc_corner!(-x, +y, +z);
+/// assert_eq!(TOP_FAR_LEFT, 7);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: mc_corner! needs the side of each of x, y and z once
+/// let corner = mc_corner!(+x, +y);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! mc_corner {
+
+    ( @side + ) => { 1 };
+    ( @side - ) => { 0 };
+
+    ( $( $side:tt $axis:ident ),+ $(,)? ) => {{
+        const _: () = assert!(
+            [ $( $crate::ax!($axis) ),+ ].len() == 3 && $crate::__private::axes_are_permutation(&[ $( $crate::ax!($axis) ),+ ]),
+            "axmac: mc_corner! needs the side of each of x, y and z once",
+        );
+        $crate::mc_corner_of($crate::__private::cube_sides([ $( ($crate::ax!($axis), $crate::mc_corner!(@side $side)) ),+ ]))
+    }};
+
+}
+
+///
+/// Returns the marching cubes edge running along the first axis named, on the named side of each of the
+/// other two
+///
+/// Sides are written as for [`mc_corner!`]. The three axes must be x, y and z in any order, or the call
+/// fails to compile. The macro is a const expression
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// assert_eq!(mc_edge!(x; -y, -z), 0);
+/// assert_eq!(mc_edge!(z; +y, -x), 11);
+///
+/// const FRONT_TOP: usize = mc_edge!(x; -y, +z);
+/// assert_eq!(FRONT_TOP, 4);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: mc_edge! needs an axis, then the side of each of the other two
+/// let edge = mc_edge!(x; +x, -y);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! mc_edge {
+    ( $along:ident; $( $side:tt $axis:ident ),+ $(,)? ) => {{
+        const _: () = assert!(
+            [ $crate::ax!($along) $( , $crate::ax!($axis) )+ ].len() == 3
+                && $crate::__private::axes_are_permutation(&[ $crate::ax!($along) $( , $crate::ax!($axis) )+ ]),
+            "axmac: mc_edge! needs an axis, then the side of each of the other two",
+        );
+        $crate::mc_edge_of(
+            $crate::ax!(@enum $along),
+            $crate::__private::cube_sides([ $( ($crate::ax!($axis), $crate::mc_corner!(@side $side)) ),+ ]),
+        )
+    }};
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn tables_agree() {
+        for (corner, &coord) in MC_CORNERS.iter().enumerate() {
+            assert_eq!(mc_corner_of(coord), corner);
+        }
+        for (edge, &[from, to]) in MC_EDGES.iter().enumerate() {
+            let axis = MC_EDGE_AXES[edge];
+            let (a, b) = (MC_CORNERS[from], MC_CORNERS[to]);
+            assert_eq!((a[axis.as_usize()], b[axis.as_usize()]), (0, 1));
+            assert_eq!((0..3).filter(|&i| a[i] != b[i]).count(), 1);
+            assert_eq!(mc_edge_of(axis, b), edge);
+        }
+    }
+
+    #[test]
+    fn macros_name_sides() {
+        assert_eq!(mc_corner!(+x, +y, +z), 6);
+        assert_eq!(mc_corner!(-y, +x, -z), 1);
+        assert_eq!(mc_edge!(y; -x, +z), 7);
+        assert_eq!(mc_edge!(z; +x, +y), 10);
+    }
+
+}
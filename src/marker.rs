@@ -0,0 +1,157 @@
+//!
+//! Zero-sized types naming each axis at the type level
+//!
+
+use crate::{Axis, InvalidAxis};
+
+
+///
+/// Implemented by the marker types [`X`], [`Y`], [`Z`] and [`W`], tying each to its [`Axis`] and index
+///
+/// Lets generic code select an axis through a type parameter, so the choice is fixed at compile time
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::marker::{AxisMarker, Z};
+///
+/// fn component<A: AxisMarker>(v: &[f32; 4]) -> f32 {
+///     v[A::INDEX]
+/// }
+///
+/// assert_eq!(component::<Z>(&[1.0, 2.0, 3.0, 4.0]), 3.0);
+/// # }
+/// ```
+///
+pub trait AxisMarker: Copy + Default + 'static {
+    /// The axis named by the marker
+    const AXIS: Axis;
+    /// The index `ax!` produces for the axis
+    const INDEX: usize;
+}
+
+
+macro_rules! markers {
+    ( $( $name:ident => $axis:ident ),+ ) => {
+        $(
+            #[doc = concat!("Marks the ", stringify!($axis), " axis at the type level")]
+            #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+            pub struct $name;
+
+            impl AxisMarker for $name {
+                const AXIS: Axis = Axis::$name;
+                const INDEX: usize = ax!($axis);
+            }
+
+            impl From<$name> for Axis {
+                fn from(_: $name) -> Axis {
+                    Axis::$name
+                }
+            }
+        )+
+    };
+}
+
+markers!(X => x, Y => y, Z => z, W => w);
+
+
+///
+/// Marks the axis with index `A` at the type level, bridging const generic axis parameters and
+/// [`AxisMarker`]
+///
+/// Naming an index without an [`Axis`] fails to compile once the marker's axis is used
+///
+/// ```
+/// use axmac::marker::{AxisMarker, ConstAxis, DynAxis, Y};
+/// use axmac::Axis;
+///
+/// fn component<const A: usize>(v: &[f32; 4]) -> f32 {
+///     v[ConstAxis::<A>::INDEX]
+/// }
+///
+/// assert_eq!(component::<1>(&[1.0, 2.0, 3.0, 4.0]), 2.0);
+/// assert_eq!(Axis::from(ConstAxis::<2>), Axis::Z);
+/// assert_eq!(ConstAxis::<1>::try_from(Axis::Y), Ok(ConstAxis));
+///
+/// // Markers of different types can share one runtime representation
+/// let axes: [&dyn DynAxis; 3] = [&ConstAxis::<0>, &Y, &Axis::W];
+/// assert_eq!(axes.map(|a| a.index()), [0, 1, 3]);
+/// ```
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConstAxis<const A: usize>;
+
+impl<const A: usize> AxisMarker for ConstAxis<A> {
+    const AXIS: Axis = crate::__private::axis(A);
+    const INDEX: usize = A;
+}
+
+impl<const A: usize> From<ConstAxis<A>> for Axis {
+    fn from(_: ConstAxis<A>) -> Axis {
+        ConstAxis::<A>::AXIS
+    }
+}
+
+impl<const A: usize> TryFrom<Axis> for ConstAxis<A> {
+    type Error = InvalidAxis;
+
+    ///
+    /// Succeeds only if `axis` is the one with index `A`
+    ///
+    fn try_from(axis: Axis) -> Result<Self, Self::Error> {
+        if axis.as_usize() == A { Ok(Self) } else { Err(InvalidAxis(axis.as_usize())) }
+    }
+}
+
+
+///
+/// Erases an axis known at the type level to a runtime value, so markers of different types can be used
+/// through one `dyn DynAxis`
+///
+/// Implemented by every [`AxisMarker`] and by [`Axis`] itself
+///
+pub trait DynAxis {
+    /// The axis named
+    fn axis(&self) -> Axis;
+
+    /// The index `ax!` produces for the axis
+    fn index(&self) -> usize {
+        self.axis().as_usize()
+    }
+}
+
+impl<M: AxisMarker> DynAxis for M {
+    fn axis(&self) -> Axis {
+        M::AXIS
+    }
+}
+
+impl DynAxis for Axis {
+    fn axis(&self) -> Axis {
+        *self
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn matches_axis() {
+        assert_eq!(X::AXIS.as_usize(), X::INDEX);
+        assert_eq!(Axis::from(W), Axis::W);
+        assert_eq!(<Y as AxisMarker>::INDEX, ax!(y));
+    }
+
+    #[test]
+    fn const_axis_works() {
+        assert_eq!(ConstAxis::<3>::AXIS, W::AXIS);
+        assert_eq!(ConstAxis::<0>::try_from(Axis::Z), Err(InvalidAxis(2)));
+        for axis in Axis::iter() {
+            assert_eq!(crate::with_axis!(axis; |const A| ConstAxis::<A>.axis()), axis);
+        }
+    }
+
+}
@@ -0,0 +1,123 @@
+//!
+//! 4x4 matrix helpers over flat arrays, indexed by named rows and columns
+//!
+
+use crate::Scalar;
+
+
+///
+/// How the 16 elements of a flat 4x4 matrix are ordered
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MatrixLayout {
+    /// Each run of four elements is a row, as written in C or on paper
+    RowMajor,
+    /// Each run of four elements is a column, as used by OpenGL and most GPU APIs
+    ColumnMajor,
+}
+
+impl MatrixLayout {
+
+    ///
+    /// Returns the flat index of the element at `row` and `col`
+    ///
+    /// # Panics
+    ///
+    /// If `row` or `col` is not less than 4
+    ///
+    pub const fn index(self, row: usize, col: usize) -> usize {
+        assert!(row < 4 && col < 4, "axmac: a 4x4 matrix has no row or column past w");
+        match self {
+            MatrixLayout::RowMajor    => row * 4 + col,
+            MatrixLayout::ColumnMajor => col * 4 + row,
+        }
+    }
+
+}
+
+
+///
+/// Multiplies the matrix `m` by the column vector `v`
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{mat4_col, mat4_mul_vec4, MatrixLayout};
+///
+/// // A translation by (5, 6, 7), stored column major
+/// let m = [
+///     1.0, 0.0, 0.0, 0.0,
+///     0.0, 1.0, 0.0, 0.0,
+///     0.0, 0.0, 1.0, 0.0,
+///     5.0, 6.0, 7.0, 1.0,
+/// ];
+/// let layout = MatrixLayout::ColumnMajor;
+///
+/// assert_eq!(mat4_mul_vec4(&m, &[1.0, 1.0, 1.0, 1.0], layout), [6.0, 7.0, 8.0, 1.0]);
+/// assert_eq!(mat4_col(&m, ax!(w), layout), [5.0, 6.0, 7.0, 1.0]);
+/// # }
+/// ```
+///
+pub fn mat4_mul_vec4<T: Scalar>(m: &[T; 16], v: &[T; 4], layout: MatrixLayout) -> [T; 4] {
+    core::array::from_fn(|row| {
+        (0..4).fold(T::ZERO, |sum, col| sum + m[layout.index(row, col)] * v[col])
+    })
+}
+
+///
+/// Returns the column `col` of the matrix `m`
+///
+/// # Panics
+///
+/// If `col` is not less than 4
+///
+pub fn mat4_col<T: Scalar>(m: &[T; 16], col: usize, layout: MatrixLayout) -> [T; 4] {
+    core::array::from_fn(|row| m[layout.index(row, col)])
+}
+
+///
+/// Returns the row `row` of the matrix `m`
+///
+/// # Panics
+///
+/// If `row` is not less than 4
+///
+pub fn mat4_row<T: Scalar>(m: &[T; 16], row: usize, layout: MatrixLayout) -> [T; 4] {
+    core::array::from_fn(|col| m[layout.index(row, col)])
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const M: [i32; 16] = [
+         1,  2,  3,  4,
+         5,  6,  7,  8,
+         9, 10, 11, 12,
+        13, 14, 15, 16,
+    ];
+
+    #[test]
+    fn layouts_work() {
+        assert_eq!(mat4_row(&M, ax!(y), MatrixLayout::RowMajor), [5, 6, 7, 8]);
+        assert_eq!(mat4_col(&M, ax!(y), MatrixLayout::RowMajor), [2, 6, 10, 14]);
+        assert_eq!(mat4_row(&M, ax!(y), MatrixLayout::ColumnMajor), [2, 6, 10, 14]);
+        assert_eq!(mat4_col(&M, ax!(y), MatrixLayout::ColumnMajor), [5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn mul_vec4_works() {
+        let v = [1, 0, 0, 1];
+        assert_eq!(mat4_mul_vec4(&M, &v, MatrixLayout::RowMajor), [5, 13, 21, 29]);
+        assert_eq!(mat4_mul_vec4(&M, &v, MatrixLayout::ColumnMajor), [14, 16, 18, 20]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range_row_panics() {
+        let _ = mat4_row(&M, 4, MatrixLayout::RowMajor);
+    }
+
+}
@@ -0,0 +1,373 @@
+//!
+//! Pluggable square roots, logarithms and exponentials for the geometry and plotting helpers
+//!
+//! `core` offers no `sqrt`, `ln` or `exp` on `no_std` targets, so the helpers needing one take a
+//! [`MathBackend`] as a zero-sized argument. [`Soft`] works everywhere, while [`Std`] forwards to the standard library's
+//! hardware backed implementation when the `std` feature is enabled. [`DefaultMath`] names the best
+//! backend available
+//!
+//! ```
+//! use axmac::math::{self, Soft};
+//!
+//! let v = [3.0f32, 4.0];
+//! assert_eq!(math::length(&v, Soft), 5.0);
+//! assert_eq!(math::normalize(&v, Soft), Some([0.6, 0.8]));
+//! ```
+//!
+
+use core::cmp::Ordering;
+
+use crate::SignedScalar;
+
+
+///
+/// Supplies the operations `core` cannot provide without an FPU library
+///
+/// Implement it to route the geometry helpers through a platform specific library
+///
+pub trait MathBackend<T> {
+
+    ///
+    /// Returns the square root of `v`, or NaN if `v` is negative
+    ///
+    fn sqrt(&self, v: T) -> T;
+
+    ///
+    /// Returns the natural logarithm of `v`, NaN if `v` is negative and negative infinity if it is zero
+    ///
+    fn ln(&self, v: T) -> T;
+
+    ///
+    /// Returns `ln(1 + v)`, without losing the digits of small `v` to the addition
+    ///
+    fn ln_1p(&self, v: T) -> T;
+
+    ///
+    /// Returns `e^v`
+    ///
+    fn exp(&self, v: T) -> T;
+
+    ///
+    /// Returns `e^v - 1`, without losing the digits of small `v` to the subtraction
+    ///
+    fn exp_m1(&self, v: T) -> T;
+
+}
+
+
+///
+/// A portable backend accurate to within a couple of ULPs, with square roots by Newton's method and
+/// logarithms and exponentials by series. `f32` values are computed as `f64`
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Soft;
+
+macro_rules! impl_soft {
+    ( $t:ty, $bias:literal, $shift:literal, $scale:literal, $root:literal ) => {
+        impl MathBackend<$t> for Soft {
+            fn sqrt(&self, v: $t) -> $t {
+                if v.is_nan() || v < 0.0 {
+                    return <$t>::NAN;
+                }
+                if v == 0.0 || v.is_infinite() {
+                    return v;
+                }
+                // Subnormals lack the exponent bits the guess halves, so they are scaled by an even power of
+                // two into the normal range first, and the root scaled back by half that power
+                if v < <$t>::MIN_POSITIVE {
+                    return self.sqrt(v * $scale) / $root;
+                }
+
+                // Halving the exponent bits gives a guess within a factor of two of the root
+                let mut x = <$t>::from_bits((v.to_bits() >> 1) + ($bias << $shift));
+                for _ in 0..6 {
+                    x = 0.5 * (x + v / x);
+                }
+                x
+            }
+
+            fn ln(&self, v: $t) -> $t {
+                soft_ln(v.into()) as $t
+            }
+
+            fn ln_1p(&self, v: $t) -> $t {
+                soft_ln_1p(v.into()) as $t
+            }
+
+            fn exp(&self, v: $t) -> $t {
+                soft_exp(v.into()) as $t
+            }
+
+            fn exp_m1(&self, v: $t) -> $t {
+                soft_exp_m1(v.into()) as $t
+            }
+        }
+    };
+}
+
+impl_soft!(f32, 127u32, 22, 16777216.0, 4096.0);
+impl_soft!(f64, 1023u64, 51, 18014398509481984.0, 134217728.0);
+
+fn soft_ln(v: f64) -> f64 {
+    if v.is_nan() || v < 0.0 {
+        return f64::NAN;
+    }
+    if v == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if v.is_infinite() {
+        return v;
+    }
+
+    // Split off the binary exponent, scaling subnormals up first, to leave a mantissa near one
+    let (v, bias) = if v < f64::MIN_POSITIVE { (v * 18014398509481984.0, -54) } else { (v, 0) };
+    let bits = v.to_bits();
+    let mut exp = ((bits >> 52) & 0x7ff) as i32 - 1023 + bias;
+    let mut m = f64::from_bits((bits & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000);
+    if m > core::f64::consts::SQRT_2 {
+        m /= 2.0;
+        exp += 1;
+    }
+
+    // ln(m) = 2 atanh(s), with |s| below 0.18 so the series converges quickly
+    let s = (m - 1.0) / (m + 1.0);
+    exp as f64 * core::f64::consts::LN_2 + atanh_series(s)
+}
+
+// Returns 2 atanh(s), which is ln((1 + s) / (1 - s)), for |s| below 0.18
+fn atanh_series(s: f64) -> f64 {
+    let s2 = s * s;
+    let mut term = s;
+    let mut sum = 0.0;
+    for k in 0..12 {
+        sum += term / (2 * k + 1) as f64;
+        term *= s2;
+    }
+    2.0 * sum
+}
+
+fn soft_ln_1p(v: f64) -> f64 {
+    // Two terms are exact to rounding this close to zero, where halving `v` below could underflow
+    if abs(v) < 1e-8 {
+        return v - 0.5 * v * v;
+    }
+    if abs(v) < 0.4 { atanh_series(v / (2.0 + v)) } else { soft_ln(1.0 + v) }
+}
+
+fn soft_exp_m1(v: f64) -> f64 {
+    if abs(v) >= 0.35 {
+        return soft_exp(v) - 1.0;
+    }
+    let mut term = v;
+    let mut sum = v;
+    for n in 2..16 {
+        term *= v / n as f64;
+        sum += term;
+    }
+    sum
+}
+
+fn soft_exp(v: f64) -> f64 {
+    if v.is_nan() {
+        return v;
+    }
+    if v > 709.8 {
+        return f64::INFINITY;
+    }
+    if v < -745.2 {
+        return 0.0;
+    }
+
+    // e^v = 2^k e^r, with |r| at most half of ln 2 so the series converges quickly
+    let x = v / core::f64::consts::LN_2 + 0.5;
+    // Floors `x`, as the cast truncates toward zero
+    let k = x as i32 - i32::from((x as i32 as f64) > x);
+    // ln 2 split in two, the high part short enough that multiples of it are exact
+    let r = (v - k as f64 * 0.693_147_180_369_123_8) - k as f64 * 1.908_214_929_270_587_7e-10;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for n in 1..16 {
+        term *= r / n as f64;
+        sum += term;
+    }
+
+    // Apply 2^k in two halves, so neither factor overflows or underflows on its own
+    let half = |k: i32| f64::from_bits(((k + 1023) as u64) << 52);
+    sum * half(k / 2) * half(k - k / 2)
+}
+
+
+///
+/// Forwards to the standard library
+///
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Std;
+
+#[cfg(feature = "std")]
+impl MathBackend<f32> for Std {
+    fn sqrt(&self, v: f32) -> f32 {
+        v.sqrt()
+    }
+
+    fn ln(&self, v: f32) -> f32 {
+        v.ln()
+    }
+
+    fn ln_1p(&self, v: f32) -> f32 {
+        v.ln_1p()
+    }
+
+    fn exp(&self, v: f32) -> f32 {
+        v.exp()
+    }
+
+    fn exp_m1(&self, v: f32) -> f32 {
+        v.exp_m1()
+    }
+}
+
+#[cfg(feature = "std")]
+impl MathBackend<f64> for Std {
+    fn sqrt(&self, v: f64) -> f64 {
+        v.sqrt()
+    }
+
+    fn ln(&self, v: f64) -> f64 {
+        v.ln()
+    }
+
+    fn ln_1p(&self, v: f64) -> f64 {
+        v.ln_1p()
+    }
+
+    fn exp(&self, v: f64) -> f64 {
+        v.exp()
+    }
+
+    fn exp_m1(&self, v: f64) -> f64 {
+        v.exp_m1()
+    }
+}
+
+
+///
+/// The most accurate backend available with the enabled features
+///
+#[cfg(feature = "std")]
+pub type DefaultMath = Std;
+///
+/// The most accurate backend available with the enabled features
+///
+#[cfg(not(feature = "std"))]
+pub type DefaultMath = Soft;
+
+
+///
+/// Returns the absolute value of `v`
+///
+pub fn abs<T: SignedScalar>(v: T) -> T {
+    if v < T::ZERO { -v } else { v }
+}
+
+///
+/// Orders `a` and `b` totally, putting values incomparable even with themselves, such as NaN, after all
+/// others
+///
+pub(crate) fn nan_last<T: PartialOrd>(a: &T, b: &T) -> Ordering {
+    let unordered = |v: &T| v.partial_cmp(v).is_none();
+    unordered(a).cmp(&unordered(b)).then_with(|| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+}
+
+///
+/// Returns the dot product of two vectors
+///
+pub fn dot<T: SignedScalar, const N: usize>(a: &[T; N], b: &[T; N]) -> T {
+    a.iter().zip(b).fold(T::ZERO, |sum, (&a, &b)| sum + a * b)
+}
+
+///
+/// Returns the squared euclidean length of `v`, which needs no square root
+///
+pub fn length_squared<T: SignedScalar, const N: usize>(v: &[T; N]) -> T {
+    dot(v, v)
+}
+
+///
+/// Returns the euclidean length of `v`
+///
+pub fn length<T: SignedScalar, M: MathBackend<T>, const N: usize>(v: &[T; N], math: M) -> T {
+    math.sqrt(length_squared(v))
+}
+
+///
+/// Returns the euclidean distance between two points
+///
+pub fn distance<T: SignedScalar, M: MathBackend<T>, const N: usize>(a: &[T; N], b: &[T; N], math: M) -> T {
+    let d: [T; N] = core::array::from_fn(|i| a[i] - b[i]);
+    length(&d, math)
+}
+
+///
+/// Returns `v` scaled to unit length, or `None` if `v` has length zero
+///
+pub fn normalize<T: SignedScalar, M: MathBackend<T>, const N: usize>(v: &[T; N], math: M) -> Option<[T; N]> {
+    let len = length(v, math);
+    if len == T::ZERO {
+        None
+    } else {
+        Some(v.map(|c| c / len))
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn soft_sqrt_works() {
+        for v in [1e-30f64, 0.25, 2.0, 10.0, 12345.678, 1e300] {
+            let root = Soft.sqrt(v);
+            assert!((root * root - v).abs() <= v * 1e-15, "sqrt({}) = {}", v, root);
+        }
+        for v in [1e-30f32, 0.25, 2.0, 10.0, 12345.678, 1e30] {
+            let root = Soft.sqrt(v);
+            assert!((root * root - v).abs() <= v * 1e-6, "sqrt({}) = {}", v, root);
+        }
+        for v in [5e-324f64, 1e-310, f64::MIN_POSITIVE / 2.0] {
+            assert!((Soft.sqrt(v) - v.sqrt()).abs() <= v.sqrt() * 1e-15, "sqrt({}) = {}", v, Soft.sqrt(v));
+        }
+        for v in [1e-45f32, 1e-40, f32::MIN_POSITIVE / 2.0] {
+            assert!((Soft.sqrt(v) - v.sqrt()).abs() <= v.sqrt() * 1e-6, "sqrt({}) = {}", v, Soft.sqrt(v));
+        }
+        assert_eq!(Soft.sqrt(0.0f32), 0.0);
+        assert!(Soft.sqrt(-1.0f64).is_nan());
+        assert_eq!(Soft.sqrt(f32::INFINITY), f32::INFINITY);
+    }
+
+    #[test]
+    fn soft_logs_work() {
+        for v in [5e-324f64, 1e-30, 0.3, 1.0, 2.5, 1e300] {
+            assert!((Soft.ln(v) - v.ln()).abs() <= v.ln().abs() * 1e-14, "ln({})", v);
+            assert!((Soft.ln_1p(v) - v.ln_1p()).abs() <= v.ln_1p() * 1e-14, "ln_1p({})", v);
+        }
+        for v in [-700.0f64, -1.0, 1e-9, 0.2, 30.0, 700.0] {
+            assert!((Soft.exp(v) - v.exp()).abs() <= v.exp() * 1e-14, "exp({})", v);
+            assert!((Soft.exp_m1(v) - v.exp_m1()).abs() <= v.exp_m1().abs() * 1e-14, "exp_m1({})", v);
+        }
+        assert_eq!(Soft.ln(0.0f32), f32::NEG_INFINITY);
+        assert!((Soft.exp(1.0f32) - core::f32::consts::E).abs() <= f32::EPSILON * 3.0);
+    }
+
+    #[test]
+    fn helpers_work() {
+        assert_eq!(abs(-3i32), 3);
+        assert_eq!(dot(&[1, 2, 3], &[4, 5, 6]), 32);
+        assert_eq!(distance(&[1.0f64, 1.0], &[4.0, 5.0], DefaultMath::default()), 5.0);
+        assert_eq!(normalize(&[0.0f32, 0.0], Soft), None);
+    }
+
+}
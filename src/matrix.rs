@@ -0,0 +1,118 @@
+//!
+//! Named row and column indexing for matrix types such as those of `nalgebra`
+//!
+
+
+///
+/// Indexes a matrix by named row and column, as `m[(row, col)]`
+///
+/// Works with any type indexable by a `(row, col)` tuple, including every `nalgebra` matrix. The row and
+/// column are axis identifiers or parenthesised expressions, and may be given in either order
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// # struct Matrix([[f32; 3]; 3]);
+/// # impl core::ops::Index<(usize, usize)> for Matrix {
+/// #     type Output = f32;
+/// #     fn index(&self, (r, c): (usize, usize)) -> &f32 { &self.0[r][c] }
+/// # }
+/// # impl core::ops::IndexMut<(usize, usize)> for Matrix {
+/// #     fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut f32 { &mut self.0[r][c] }
+/// # }
+/// // With nalgebra, `let mut m = Matrix3::<f32>::identity();`
+/// let mut m = Matrix([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+///
+/// m_at!(m; row: y, col: x) = 5.0;
+/// assert_eq!(m_at!(m; col: x, row: y), 5.0);
+/// assert_eq!(m_at!(m; row: (2), col: z), 1.0);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! m_at {
+    ( $m:expr; row: $row:tt, col: $col:tt $(,)? ) => {
+        $m[($crate::m_at!(@index $row), $crate::m_at!(@index $col))]
+    };
+    ( $m:expr; col: $col:tt, row: $row:tt $(,)? ) => {
+        $crate::m_at!($m; row: $row, col: $col)
+    };
+
+    ( @index $axis:ident ) => { $crate::ax!($axis) };
+    ( @index $index:tt ) => { $index };
+}
+
+
+///
+/// Views the block of a matrix covered by named row and column ranges, as `m.view_range(rows, cols)`
+///
+/// Works with any type providing a `view_range` method, including every `nalgebra` matrix. The ranges
+/// are usually built with [`axr!`], and `..` selects every row or column
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// # use core::ops::RangeBounds;
+/// # struct Matrix([[i32; 4]; 4]);
+/// # impl Matrix {
+/// #     fn view_range(&self, rows: impl RangeBounds<usize>, cols: impl RangeBounds<usize>) -> Vec<i32> {
+/// #         let mut out = Vec::new();
+/// #         for (r, row) in self.0.iter().enumerate().filter(|(r, _)| rows.contains(r)) {
+/// #             out.extend(row.iter().enumerate().filter(|(c, _)| cols.contains(c)).map(|(_, &v)| v));
+/// #         }
+/// #         out
+/// #     }
+/// # }
+/// // With nalgebra, `let m = Matrix4::<i32>::from_fn(|r, c| (r * 4 + c) as i32);`
+/// let m = Matrix([[0, 1, 2, 3], [4, 5, 6, 7], [8, 9, 10, 11], [12, 13, 14, 15]]);
+///
+/// // The upper left 3x3 block
+/// let block = m_view!(m; rows: axr!(x..=z), cols: axr!(..w));
+/// assert_eq!(block, [0, 1, 2, 4, 5, 6, 8, 9, 10]);
+///
+/// assert_eq!(m_view!(m; rows: axr!(w..), cols: ..), [12, 13, 14, 15]);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! m_view {
+    ( $m:expr; rows: $rows:expr, cols: $cols:expr $(,)? ) => {
+        $m.view_range($rows, $cols)
+    };
+    ( $m:expr; cols: $cols:expr, rows: $rows:expr $(,)? ) => {
+        $crate::m_view!($m; rows: $rows, cols: $cols)
+    };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use core::ops::{Index, IndexMut};
+
+    // Indexed the way nalgebra matrices are, by (row, col)
+    struct Matrix<const R: usize, const C: usize>([[i32; C]; R]);
+
+    impl<const R: usize, const C: usize> Index<(usize, usize)> for Matrix<R, C> {
+        type Output = i32;
+
+        fn index(&self, (row, col): (usize, usize)) -> &i32 {
+            &self.0[row][col]
+        }
+    }
+
+    impl<const R: usize, const C: usize> IndexMut<(usize, usize)> for Matrix<R, C> {
+        fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut i32 {
+            &mut self.0[row][col]
+        }
+    }
+
+    #[test]
+    fn m_at_works() {
+        let mut m = Matrix([[0, 1, 2], [3, 4, 5]]);
+        assert_eq!(m_at!(m; row: y, col: z), 5);
+        let col = 1;
+        m_at!(m; col: (col), row: x) += 10;
+        assert_eq!(m.0[0], [0, 11, 2]);
+    }
+
+}
@@ -0,0 +1,112 @@
+//!
+//! Labels, units and scales describing the axes of a buffer, for charts and scientific data
+//!
+
+use core::fmt;
+
+use crate::{AxisScale, Ticks};
+
+
+///
+/// What one axis of a buffer or chart stands for, usually held per axis in an
+/// [`AxisMap<AxisMeta, N>`](crate::AxisMap) passed alongside the data
+///
+/// The label and unit are borrowed, so metadata can be built in constants without allocating. Displaying
+/// it gives the axis title, with the unit in parentheses when there is one
+///
+/// ```
+/// use axmac::{Axis, AxisMap, AxisMeta, AxisScale};
+///
+/// let meta = AxisMap::new([
+///     AxisMeta::new("Time").unit("s").range(0.0, 10.0),
+///     AxisMeta::new("Intensity").scale(AxisScale::Log10),
+/// ]);
+///
+/// assert_eq!(meta[Axis::X].to_string(), "Time (s)");
+/// assert_eq!(meta[Axis::Y].to_string(), "Intensity");
+/// assert_eq!(meta[Axis::X].ticks(5).unwrap().collect::<Vec<_>>(), [0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+/// ```
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AxisMeta<'a> {
+    /// The name shown along the axis
+    pub label: &'a str,
+    /// The unit of the values, or empty for none
+    pub unit: &'a str,
+    /// How values are spread along the axis
+    pub scale: AxisScale,
+    /// The smallest and largest values shown, if fixed rather than fitted to the data
+    pub range: Option<(f64, f64)>,
+}
+
+impl<'a> AxisMeta<'a> {
+
+    ///
+    /// Creates metadata for a linear axis with a label and nothing else
+    ///
+    pub const fn new(label: &'a str) -> Self {
+        Self { label, unit: "", scale: AxisScale::Linear, range: None }
+    }
+
+    ///
+    /// Sets the unit of the values
+    ///
+    pub const fn unit(mut self, unit: &'a str) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    ///
+    /// Sets how values are spread along the axis
+    ///
+    pub const fn scale(mut self, scale: AxisScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    ///
+    /// Fixes the values shown to `min..=max`
+    ///
+    pub const fn range(mut self, min: f64, max: f64) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+
+    ///
+    /// Returns about `target_count` ticks over the fixed range on the scale of the axis, or `None` if it
+    /// has no fixed range
+    ///
+    pub fn ticks(&self, target_count: usize) -> Option<Ticks> {
+        self.range.map(|(min, max)| self.scale.ticks(min, max, target_count))
+    }
+
+}
+
+impl fmt::Display for AxisMeta<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label)?;
+        if !self.unit.is_empty() {
+            write!(f, " ({})", self.unit)?;
+        }
+        Ok(())
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::string::ToString;
+
+    #[test]
+    fn builds_in_constants() {
+        const DEPTH: AxisMeta<'static> = AxisMeta::new("Depth").unit("m").range(-50.0, 0.0);
+        assert_eq!(DEPTH.to_string(), "Depth (m)");
+        assert_eq!(DEPTH.ticks(2).map(|t| t.step()), Some(20.0));
+        assert!(AxisMeta::default().ticks(2).is_none());
+        assert_eq!(AxisMeta::default().to_string(), "");
+    }
+
+}
@@ -0,0 +1,224 @@
+//!
+//! Motion axes of CNC machines and 3D printers
+//!
+
+use crate::InvalidAxis;
+
+
+///
+/// A linear, extruder or rotary axis as named by G-code
+///
+/// Discriminants match the indices produced by the `motion` vocabulary of `ax!`
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(usize)]
+pub enum MotionAxis {
+    X = ax!(x),
+    Y = ax!(y),
+    Z = ax!(z),
+    E = ax!(e),
+    A = ax!(a),
+    B = ax!(b),
+    C = ax!(c),
+}
+
+impl MotionAxis {
+
+    ///
+    /// Every motion axis, in index order
+    ///
+    pub const ALL: [Self; 7] = [Self::X, Self::Y, Self::Z, Self::E, Self::A, Self::B, Self::C];
+
+    ///
+    /// Returns the same index `ax!` produces for this axis
+    ///
+    pub const fn as_usize(self) -> usize {
+        self as usize
+    }
+
+    ///
+    /// Returns the upper case G-code word letter of this axis
+    ///
+    pub const fn gcode(self) -> char {
+        match self {
+            Self::X => 'X',
+            Self::Y => 'Y',
+            Self::Z => 'Z',
+            Self::E => 'E',
+            Self::A => 'A',
+            Self::B => 'B',
+            Self::C => 'C',
+        }
+    }
+
+    ///
+    /// Converts a G-code word letter, ignoring case
+    ///
+    pub const fn from_gcode(letter: char) -> Option<Self> {
+        match letter.to_ascii_uppercase() {
+            'X' => Some(Self::X),
+            'Y' => Some(Self::Y),
+            'Z' => Some(Self::Z),
+            'E' => Some(Self::E),
+            'A' => Some(Self::A),
+            'B' => Some(Self::B),
+            'C' => Some(Self::C),
+            _   => None,
+        }
+    }
+
+}
+
+impl From<MotionAxis> for usize {
+    fn from(axis: MotionAxis) -> Self {
+        axis.as_usize()
+    }
+}
+
+impl TryFrom<usize> for MotionAxis {
+    type Error = InvalidAxis;
+
+    fn try_from(index: usize) -> Result<Self, Self::Error> {
+        Self::ALL.get(index).copied().ok_or(InvalidAxis(index))
+    }
+}
+
+impl From<MotionAxis> for char {
+    fn from(axis: MotionAxis) -> Self {
+        axis.gcode()
+    }
+}
+
+
+///
+/// The axes a particular machine actually has, in the order its firmware stores them
+///
+/// The `motion` vocabulary numbers every axis a machine could have. Most machines only have a few of them,
+/// so per-axis step counts and feeds are usually stored in a shorter array. A layout maps between the two
+///
+/// ```
+/// use axmac::{MotionAxis, MotionLayout};
+///
+/// // A five axis mill without an extruder
+/// const MILL: MotionLayout<5> = MotionLayout::new([
+///     MotionAxis::X, MotionAxis::Y, MotionAxis::Z, MotionAxis::A, MotionAxis::C,
+/// ]);
+///
+/// let steps_per_mm = [80.0, 80.0, 400.0, 10.0, 10.0];
+/// assert_eq!(MILL.index_of(MotionAxis::Z), Some(2));
+/// assert_eq!(MILL.index_of(MotionAxis::E), None);
+/// assert_eq!(MILL.get(&steps_per_mm, MotionAxis::C), Some(&10.0));
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MotionLayout<const N: usize> {
+    axes: [MotionAxis; N],
+}
+
+impl<const N: usize> MotionLayout<N> {
+
+    ///
+    /// Creates a layout storing `axes` at their positions in the array
+    ///
+    /// # Panics
+    ///
+    /// If an axis appears more than once
+    ///
+    pub const fn new(axes: [MotionAxis; N]) -> Self {
+        let mut i = 0;
+        while i < N {
+            let mut j = i + 1;
+            while j < N {
+                assert!(axes[i] as usize != axes[j] as usize, "axmac: motion axis repeated in layout");
+                j += 1;
+            }
+            i += 1;
+        }
+        Self { axes }
+    }
+
+    ///
+    /// Returns the axes of the layout in storage order
+    ///
+    pub const fn axes(&self) -> &[MotionAxis; N] {
+        &self.axes
+    }
+
+    ///
+    /// Returns the storage position of `axis`, if the machine has it
+    ///
+    pub const fn index_of(&self, axis: MotionAxis) -> Option<usize> {
+        let mut i = 0;
+        while i < N {
+            if self.axes[i] as usize == axis as usize {
+                return Some(i);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    ///
+    /// Returns the axis stored at `index`
+    ///
+    pub fn axis_at(&self, index: usize) -> Option<MotionAxis> {
+        self.axes.get(index).copied()
+    }
+
+    ///
+    /// Returns the value stored for `axis` in `values`
+    ///
+    pub fn get<'a, T>(&self, values: &'a [T; N], axis: MotionAxis) -> Option<&'a T> {
+        self.index_of(axis).map(|i| &values[i])
+    }
+
+    ///
+    /// Returns the value stored for `axis` in `values` mutably
+    ///
+    pub fn get_mut<'a, T>(&self, values: &'a mut [T; N], axis: MotionAxis) -> Option<&'a mut T> {
+        self.index_of(axis).map(move |i| &mut values[i])
+    }
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn gcode_round_trips() {
+        for axis in MotionAxis::ALL {
+            assert_eq!(MotionAxis::from_gcode(axis.gcode()), Some(axis));
+        }
+        assert_eq!(MotionAxis::from_gcode('e'), Some(MotionAxis::E));
+        assert_eq!(MotionAxis::from_gcode('F'), None);
+        assert_eq!(char::from(MotionAxis::B), 'B');
+    }
+
+    #[test]
+    fn matches_ax() {
+        assert_eq!(usize::from(MotionAxis::E), ax!(e));
+        assert_eq!(MotionAxis::try_from(ax!(c)), Ok(MotionAxis::C));
+        assert_eq!(MotionAxis::try_from(7), Err(InvalidAxis(7)));
+    }
+
+    #[test]
+    fn layout_works() {
+        let layout = MotionLayout::new([MotionAxis::X, MotionAxis::Y, MotionAxis::Z, MotionAxis::E]);
+        let mut feeds = [1, 2, 3, 4];
+        assert_eq!(layout.axis_at(3), Some(MotionAxis::E));
+        *layout.get_mut(&mut feeds, MotionAxis::E).unwrap() = 40;
+        assert_eq!(feeds, [1, 2, 3, 40]);
+        assert_eq!(layout.get(&feeds, MotionAxis::A), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn repeated_axis_panics() {
+        let _ = MotionLayout::new([MotionAxis::X, MotionAxis::X]);
+    }
+
+}
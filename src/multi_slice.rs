@@ -0,0 +1,68 @@
+//!
+//! Slicing several parallel containers by the same range at once
+//!
+
+
+///
+/// Slices every container in a parenthesised list by the same range, giving a tuple of the subslices
+///
+/// The range is usually an [`axr!`] over named axes and is evaluated once. Any container indexable by that
+/// range works, and the tuple has one element per container, even for a single one. With `mut` before the
+/// list the subslices are mutable, which takes each container by mutable borrow
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // Parallel per-axis arrays of a particle system
+/// let position = [1.0, 2.0, 3.0, 1.0];
+/// let velocity = [0.5, 0.0, -1.0, 0.0];
+/// let mask = [true, false, true, false];
+///
+/// let (p, v, m) = multi_slice!((position, velocity, mask); axr!(x..=z));
+/// assert_eq!((p, v, m), (&[1.0, 2.0, 3.0][..], &[0.5, 0.0, -1.0][..], &[true, false, true][..]));
+///
+/// let (mut a, mut b) = ([0; 4], [0; 4]);
+/// let (a_yz, b_yz) = multi_slice!(mut (a, b); axr!(y..w));
+/// a_yz.fill(1);
+/// b_yz[0] = 2;
+/// assert_eq!((a, b), ([0, 1, 1, 0], [0, 2, 0, 0]));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: multi_slice! needs at least one container to slice
+/// let () = multi_slice!((); axr!(x..z));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! multi_slice {
+    ( mut ( $( $container:expr ),+ $(,)? ) ; $range:expr $(,)? ) => {{
+        let range = $range;
+        ( $( &mut $container[::core::clone::Clone::clone(&range)], )+ )
+    }};
+    ( ( $( $container:expr ),+ $(,)? ) ; $range:expr $(,)? ) => {{
+        let range = $range;
+        ( $( &$container[::core::clone::Clone::clone(&range)], )+ )
+    }};
+    ( $( mut )? () ; $( $rest:tt )* ) => {
+        compile_error!("axmac: multi_slice! needs at least one container to slice")
+    };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn containers_share_the_range() {
+        let names = std::vec![1, 2, 3];
+        let weights = [4, 5, 6];
+        let (one,) = multi_slice!((weights); axr!(..z));
+        assert_eq!(one, [4, 5]);
+        let (n, w) = multi_slice!((names, weights); axr!((1)..=z));
+        assert_eq!((n, w), (&[2, 3][..], &[5, 6][..]));
+    }
+
+}
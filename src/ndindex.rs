@@ -0,0 +1,292 @@
+//!
+//! Iteration over every integer coordinate of an N dimensional region
+//!
+
+use core::iter::FusedIterator;
+use core::ops::{Range, RangeInclusive};
+
+use crate::{Aabb, AxisPriority, Scalar};
+
+
+///
+/// Ranges which can be expressed as a half-open `start..end` pair
+///
+pub trait HalfOpen<T> {
+
+    ///
+    /// Returns the first value in the range and the first value past it
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if an inclusive range ends at the largest value of `T`, which has no value past it
+    ///
+    fn half_open(self) -> (T, T);
+
+    ///
+    /// Returns the first and last values in the range, or `None` if it is empty
+    ///
+    /// Unlike [`half_open`](Self::half_open), ranges reaching the largest value of `T` are supported
+    ///
+    fn first_last(self) -> Option<(T, T)>;
+
+}
+
+impl<T: Scalar> HalfOpen<T> for Range<T> {
+    fn half_open(self) -> (T, T) {
+        (self.start, self.end)
+    }
+
+    fn first_last(self) -> Option<(T, T)> {
+        (self.start < self.end).then(|| (self.start, self.end - T::ONE))
+    }
+}
+
+impl<T: Scalar> HalfOpen<T> for RangeInclusive<T> {
+    fn half_open(self) -> (T, T) {
+        let (start, end) = self.into_inner();
+        (start, end + T::ONE)
+    }
+
+    fn first_last(self) -> Option<(T, T)> {
+        let (start, end) = self.into_inner();
+        (start <= end).then_some((start, end))
+    }
+}
+
+
+///
+/// Iterator over every coordinate in the half-open region `start..end`, or the inclusive region
+/// `start..=last`
+///
+/// The x axis varies fastest, matching the row-major layout where consecutive x values are adjacent in
+/// memory, unless another order is chosen with [`with_priority`](Self::with_priority). Created by
+/// [`ndindex!`], [`iter_box!`] and [`Aabb::cells`]
+///
+#[derive(Clone, Debug)]
+pub struct NdIndex<T, const N: usize> {
+    start: [T; N],
+    // Inclusive, so regions reaching the largest value of `T` need no value past them
+    last: [T; N],
+    // Axes innermost first
+    order: [usize; N],
+    next: Option<[T; N]>,
+}
+
+impl<T: Scalar, const N: usize> NdIndex<T, N> {
+
+    ///
+    /// Creates an iterator over every coordinate `c` where `start[i] <= c[i] < end[i]` on every axis
+    ///
+    pub fn new(start: [T; N], end: [T; N]) -> Self {
+        let empty = (0..N).any(|i| start[i] >= end[i]);
+        // Every end is past its start in a region with any coordinates, so stepping back cannot overflow
+        let mut index = Self::new_inclusive(start, if empty { start } else { end.map(|e| e - T::ONE) });
+        if empty {
+            index.next = None;
+        }
+        index
+    }
+
+    ///
+    /// Creates an iterator over every coordinate `c` where `start[i] <= c[i] <= last[i]` on every axis
+    ///
+    /// ```
+    /// use axmac::NdIndex;
+    ///
+    /// assert_eq!(NdIndex::new_inclusive([0u8, 254], [255, 255]).count(), 512);
+    /// ```
+    ///
+    pub fn new_inclusive(start: [T; N], last: [T; N]) -> Self {
+        let empty = N == 0 || (0..N).any(|i| start[i] > last[i]);
+        let order = core::array::from_fn(|i| i);
+        Self { start, last, order, next: if empty { None } else { Some(start) } }
+    }
+
+    ///
+    /// Visits the coordinates with the axes varying in the order of `priority`, its innermost axis fastest
+    ///
+    /// The order applies from the next coordinate on, so it is best chosen before iterating
+    ///
+    /// ```
+    /// use axmac::{AxisPriority, NdIndex};
+    ///
+    /// let cells: Vec<[u8; 2]> = NdIndex::new([0, 0], [2, 2]).with_priority(AxisPriority::LAST_FASTEST).collect();
+    /// assert_eq!(cells, [[0, 0], [0, 1], [1, 0], [1, 1]]);
+    /// ```
+    ///
+    pub fn with_priority(mut self, priority: AxisPriority<N>) -> Self {
+        self.order = priority.inner_to_outer();
+        self
+    }
+
+}
+
+impl<T: Scalar, const N: usize> Iterator for NdIndex<T, N> {
+    type Item = [T; N];
+
+    fn next(&mut self) -> Option<[T; N]> {
+        let current = self.next?;
+
+        let mut next = current;
+        for axis in self.order {
+            if next[axis] < self.last[axis] {
+                next[axis] = next[axis] + T::ONE;
+                self.next = Some(next);
+                return Some(current);
+            }
+            next[axis] = self.start[axis];
+        }
+
+        self.next = None;
+        Some(current)
+    }
+}
+
+impl<T: Scalar, const N: usize> FusedIterator for NdIndex<T, N> {}
+
+impl<T: Scalar, const N: usize> Aabb<T, N> {
+
+    ///
+    /// Iterates over every integer coordinate inside the box, including those on its boundary
+    ///
+    pub fn cells(&self) -> NdIndex<T, N> {
+        NdIndex::new_inclusive(self.min, self.max)
+    }
+
+}
+
+
+///
+/// Iterates over every coordinate of a region given as one range per named axis
+///
+/// Each axis takes either a `Range` or a `RangeInclusive`, and axes may be listed in any order. The
+/// iterator yields `[T; N]` arrays where `N` is the number of axes listed, which must be the first `N` axes.
+/// The x axis varies fastest, unless an [`AxisPriority`](crate::AxisPriority) follows the ranges after a
+/// semicolon
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let cells: Vec<[u32; 2]> = ndindex!(y in 5..=6, x in 0..2).collect();
+/// assert_eq!(cells, [[0, 5], [1, 5], [0, 6], [1, 6]]);
+///
+/// let columns: Vec<[u32; 2]> = ndindex!(x in 0..2, y in 5..=6; axmac::AxisPriority::LAST_FASTEST).collect();
+/// assert_eq!(columns, [[0, 5], [0, 6], [1, 5], [1, 6]]);
+/// # }
+/// ```
+///
+/// Leaving out an axis, or listing one twice, is a compile time error
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let cells = ndindex!(x in 0..2, z in 0..2);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! ndindex {
+
+    ( $( $axis:ident in $range:expr ),+ ; $priority:expr $(,)? ) => {
+        $crate::ndindex!($( $axis in $range ),+).with_priority($priority)
+    };
+
+    ( $( $axis:ident in $range:expr ),+ $(,)? ) => {{
+        const _: () = assert!(
+            $crate::__private::axes_are_permutation(&[ $( $crate::ax!($axis) ),+ ]),
+            "axmac: ndindex! must list each of the first N axes exactly once",
+        );
+
+        let mut start = [ $( $crate::__ndindex_zero!($axis) ),+ ];
+        let mut last = start;
+        $(
+            // An empty range starts past its last value, which empties the whole region
+            let (s, l) = $crate::HalfOpen::first_last($range).unwrap_or(($crate::Scalar::ONE, $crate::Scalar::ZERO));
+            start[$crate::ax!($axis)] = s;
+            last[$crate::ax!($axis)] = l;
+        )+
+        $crate::NdIndex::new_inclusive(start, last)
+    }};
+
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ndindex_zero {
+    ( $axis:ident ) => { $crate::Scalar::ZERO };
+}
+
+
+///
+/// Iterates over every integer coordinate inside an axis-aligned box
+///
+/// Takes either an [`Aabb`], whose boundary is included, or the per-axis ranges accepted by [`ndindex!`]
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Aabb;
+///
+/// let tile = Aabb::new([2i32, 2], [3, 3]);
+/// assert_eq!(iter_box!(tile).count(), 4);
+///
+/// let mut sum = 0;
+/// for [x, y] in iter_box!(x in 0..3, y in -1..=1) {
+///     sum += x * y;
+/// }
+/// assert_eq!(sum, 0);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! iter_box {
+
+    ( $( $axis:ident in $range:expr ),+ $(,)? ) => { $crate::ndindex!($( $axis in $range ),+) };
+
+    ( $aabb:expr ) => { $crate::Aabb::cells(&$aabb) };
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn order_is_x_fastest() {
+        let cells: [[u8; 3]; 8] = {
+            let mut out = [[0; 3]; 8];
+            for (o, c) in out.iter_mut().zip(NdIndex::new([0, 0, 0], [2, 2, 2])) {
+                *o = c;
+            }
+            out
+        };
+        assert_eq!(cells[1], [1, 0, 0]);
+        assert_eq!(cells[2], [0, 1, 0]);
+        assert_eq!(cells[4], [0, 0, 1]);
+        assert_eq!(cells[7], [1, 1, 1]);
+    }
+
+    #[test]
+    fn empty_regions_yield_nothing() {
+        assert_eq!(ndindex!(x in 0u8..0, y in 0..5).count(), 0);
+        assert_eq!(NdIndex::<i32, 0>::new([], []).count(), 0);
+        assert_eq!(Aabb::new([1, 1], [0, 1]).cells().count(), 0);
+    }
+
+    #[test]
+    fn macros_work() {
+        assert_eq!(ndindex!(x in 0..4, y in 0..3, z in 0..2).count(), 24);
+        assert_eq!(iter_box!(Aabb::new([-1, -1], [1, 1])).count(), 9);
+        assert_eq!(iter_box!(w in 0..=0, z in 0..=0, y in 0..=0, x in 7..=7).next(), Some([7, 0, 0, 0]));
+    }
+
+    #[test]
+    fn regions_reach_the_largest_value() {
+        assert_eq!(ndindex!(x in 0u8..=255).count(), 256);
+        assert_eq!(ndindex!(x in 0u8..=255).last(), Some([255]));
+        assert_eq!(ndindex!(x in 126i8..=127, y in i8::MIN..=i8::MAX).count(), 512);
+        assert_eq!(Aabb::new([250u8], [255]).cells().count(), 6);
+        assert_eq!(ndindex!(x in 3u8..3, y in 0..=255).count(), 0);
+    }
+
+}
@@ -0,0 +1,303 @@
+//!
+//! Headers of NumPy `.npy` files, describing a [`Shape`] to NumPy and back
+//!
+//! An `.npy` file is a short header giving the element type, order and extents of an array, followed by
+//! the raw elements. Shapes laid out as [`Layout::XFastest`] are written in Fortran order and shapes laid
+//! out as [`Layout::LastFastest`] in C order, so in both cases NumPy axis `i` is axis `i` of the shape
+//!
+//! ```
+//! use axmac::{Layout, NpyDtype, NpyHeader, Shape};
+//!
+//! let shape = Shape::new([640, 480, 3]);
+//! let header = shape.to_npy_header(f32::DESCR, Layout::XFastest).unwrap();
+//! assert_eq!(header.len() % 64, 0);
+//!
+//! let parsed = NpyHeader::<3>::parse(&header).unwrap();
+//! assert!(parsed.fortran_order);
+//! assert_eq!(parsed.shape(), shape);
+//! assert_eq!(parsed.data_offset, header.len());
+//! ```
+//!
+
+use core::fmt::{self, Write};
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{Layout, Shape};
+
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+
+// Headers are padded so the elements after them start on a multiple of this
+const ALIGN: usize = 64;
+
+
+///
+/// Returned when a shape cannot be written as an `.npy` header, or a header cannot be read
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NpyError {
+    /// The bytes do not start with the `.npy` magic string, or end inside the header
+    NotNpy,
+    /// The format version is not one of 1, 2 and 3
+    Version {
+        /// The major version found
+        major: u8,
+    },
+    /// The header lacks a key, holds a value of the wrong form, or describes more elements than a `usize`
+    /// can count
+    Malformed,
+    /// The array has a different number of dimensions than the shape it is read into
+    Dims {
+        /// The number of dimensions of the array
+        found: usize,
+    },
+    /// The shape is not laid out as the layout it is written with
+    Layout,
+    /// The element type is empty, or holds quotes, backslashes or characters outside printable ASCII
+    Dtype,
+}
+
+impl fmt::Display for NpyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NpyError::NotNpy => f.write_str("axmac: the data does not start with an npy header"),
+            NpyError::Version { major } => write!(f, "axmac: npy format version {} is not supported", major),
+            NpyError::Malformed => f.write_str("axmac: the npy header is malformed"),
+            NpyError::Dims { found } => write!(f, "axmac: the npy array has {} dimensions", found),
+            NpyError::Layout => f.write_str("axmac: the shape is not laid out as the npy layout given"),
+            NpyError::Dtype => f.write_str("axmac: the npy dtype cannot be written in a header"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NpyError {}
+
+
+///
+/// Element types with a NumPy type string, in the byte order of the target
+///
+pub trait NpyDtype {
+    /// The type string, such as `<f4`
+    const DESCR: &'static str;
+}
+
+macro_rules! impl_npy_dtype {
+    ( $( $t:ty => $little:literal, $big:literal );* $(;)? ) => {
+        $(
+            impl NpyDtype for $t {
+                const DESCR: &'static str = if cfg!(target_endian = "little") { $little } else { $big };
+            }
+        )*
+    };
+}
+
+impl_npy_dtype! {
+    u8 => "|u1", "|u1";
+    i8 => "|i1", "|i1";
+    bool => "|b1", "|b1";
+    u16 => "<u2", ">u2";
+    i16 => "<i2", ">i2";
+    u32 => "<u4", ">u4";
+    i32 => "<i4", ">i4";
+    u64 => "<u8", ">u8";
+    i64 => "<i8", ">i8";
+    f32 => "<f4", ">f4";
+    f64 => "<f8", ">f8";
+}
+
+
+///
+/// The contents of an `.npy` header, read by [`NpyHeader::parse`]
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NpyHeader<'a, const N: usize> {
+    /// The NumPy type string of the elements, such as `<f4`
+    pub descr: &'a str,
+    /// Whether the first axis is contiguous, rather than the last
+    pub fortran_order: bool,
+    /// The number of positions along every axis
+    pub extents: [usize; N],
+    /// The offset of the first element from the start of the file
+    pub data_offset: usize,
+}
+
+impl<'a, const N: usize> NpyHeader<'a, N> {
+
+    ///
+    /// Reads the header at the start of the bytes of an `.npy` file
+    ///
+    /// Versions 1 to 3 of the format are read. Only the header needs to be present, not the elements
+    ///
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, NpyError> {
+        let rest = bytes.strip_prefix(MAGIC).ok_or(NpyError::NotNpy)?;
+        let (&major, rest) = rest.split_first().ok_or(NpyError::NotNpy)?;
+        let rest = rest.get(1..).ok_or(NpyError::NotNpy)?;
+        let (len, rest) = match major {
+            1 => (rest.get(..2).map(|b| u16::from_le_bytes([b[0], b[1]]) as usize), rest.get(2..)),
+            2 | 3 => (rest.get(..4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize), rest.get(4..)),
+            major => return Err(NpyError::Version { major }),
+        };
+        let (len, rest) = len.zip(rest).ok_or(NpyError::NotNpy)?;
+        let header = rest.get(..len).ok_or(NpyError::NotNpy)?;
+        let header = core::str::from_utf8(header).map_err(|_| NpyError::Malformed)?;
+
+        let descr = quoted(value_of(header, "descr")?)?;
+        let fortran_order = match value_of(header, "fortran_order")? {
+            v if v.starts_with("True") => true,
+            v if v.starts_with("False") => false,
+            _ => return Err(NpyError::Malformed),
+        };
+        let tuple = value_of(header, "shape")?.strip_prefix('(').ok_or(NpyError::Malformed)?;
+        let tuple = &tuple[..tuple.find(')').ok_or(NpyError::Malformed)?];
+        let dims = || tuple.split(',').map(str::trim).filter(|d| !d.is_empty());
+        let found = dims().count();
+        if found != N {
+            return Err(NpyError::Dims { found });
+        }
+        let mut extents = [0; N];
+        for (extent, d) in extents.iter_mut().zip(dims()) {
+            *extent = d.parse().map_err(|_| NpyError::Malformed)?;
+        }
+        if Shape::dense_overflow_axis(&extents).is_some() {
+            return Err(NpyError::Malformed);
+        }
+
+        Ok(Self { descr, fortran_order, extents, data_offset: bytes.len() - rest.len() + len })
+    }
+
+    ///
+    /// Returns the shape of the elements following the header, with NumPy axis `i` as axis `i`
+    ///
+    pub fn shape(&self) -> Shape<N> {
+        if self.fortran_order { Shape::new(self.extents) } else { Shape::col_major(self.extents) }
+    }
+
+}
+
+// Returns the text after the colon following `'key'` in a header dictionary
+fn value_of<'h>(header: &'h str, key: &str) -> Result<&'h str, NpyError> {
+    let at = ["'", "\""]
+        .iter()
+        .find_map(|q| {
+            let mut quoted = String::new();
+            let _ = write!(quoted, "{}{}{}", q, key, q);
+            header.find(&quoted).map(|i| i + quoted.len())
+        })
+        .ok_or(NpyError::Malformed)?;
+    header[at..].trim_start().strip_prefix(':').map(str::trim_start).ok_or(NpyError::Malformed)
+}
+
+// Returns the contents of the string literal at the start of `value`
+fn quoted(value: &str) -> Result<&str, NpyError> {
+    let q = value.chars().next().filter(|&c| c == '\'' || c == '"').ok_or(NpyError::Malformed)?;
+    let value = &value[1..];
+    value.find(q).map(|end| &value[..end]).ok_or(NpyError::Malformed)
+}
+
+
+impl<const N: usize> Shape<N> {
+
+    ///
+    /// Returns the `.npy` header for the elements of a buffer laid out by the shape, with elements of the
+    /// NumPy type `dtype`, such as `<f4` or [`NpyDtype::DESCR`]
+    ///
+    /// `layout` must be one the shape matches. [`Layout::Dense`] is written in C order with the NumPy
+    /// axes in the shape's [`axis_order`](Self::axis_order), outermost first. The header is padded so the
+    /// elements written after it are aligned to 64 bytes
+    ///
+    /// ```
+    /// use axmac::{Layout, NpyDtype, Shape};
+    ///
+    /// // A 3x2 image with rows along x, as NumPy would store a (2, 3) array in C order
+    /// let header = Shape::col_major([2, 3]).to_npy_header(u16::DESCR, Layout::LastFastest).unwrap();
+    /// assert!(header.starts_with(b"\x93NUMPY\x01\x00v\x00{'descr': '<u2', 'fortran_order': False, 'shape': (2, 3), }  "));
+    /// assert!(Shape::new([2, 3]).to_npy_header("<u2", Layout::LastFastest).is_err());
+    /// ```
+    ///
+    pub fn to_npy_header(&self, dtype: &str, layout: Layout) -> Result<Vec<u8>, NpyError> {
+        if !self.matches_layout(layout) {
+            return Err(NpyError::Layout);
+        }
+        if dtype.is_empty() || !dtype.bytes().all(|b| b.is_ascii_graphic() && b != b'\'' && b != b'\\') {
+            return Err(NpyError::Dtype);
+        }
+
+        let (fortran_order, order) = match layout {
+            Layout::XFastest => (true, core::array::from_fn(|i| i)),
+            Layout::LastFastest => (false, core::array::from_fn(|i| i)),
+            Layout::Dense => (false, self.axis_order()),
+        };
+        let mut dict = String::new();
+        let _ = write!(dict, "{{'descr': '{}', 'fortran_order': {}, 'shape': (", dtype, if fortran_order { "True" } else { "False" });
+        for (i, &axis) in order.iter().enumerate() {
+            let _ = write!(dict, "{}{}", if i > 0 { ", " } else { "" }, self.extent(axis));
+        }
+        dict.push_str(if N == 1 { ",), }" } else { "), }" });
+
+        // Version 1 stores the header length in two bytes, later versions in four
+        let (version, prefix) = if dict.len() + 2 * ALIGN < u16::MAX as usize { (1, MAGIC.len() + 4) } else { (2, MAGIC.len() + 6) };
+        let len = (prefix + dict.len() + 1).div_ceil(ALIGN) * ALIGN - prefix;
+
+        let mut out = Vec::with_capacity(prefix + len);
+        out.extend_from_slice(MAGIC);
+        out.extend_from_slice(&[version, 0]);
+        if version == 1 {
+            out.extend_from_slice(&(len as u16).to_le_bytes());
+        } else {
+            out.extend_from_slice(&(len as u32).to_le_bytes());
+        }
+        out.extend_from_slice(dict.as_bytes());
+        out.resize(prefix + len - 1, b' ');
+        out.push(b'\n');
+        Ok(out)
+    }
+
+    ///
+    /// Reads the shape from the header at the start of the bytes of an `.npy` file, as
+    /// [`NpyHeader::shape`] gives it
+    ///
+    pub fn from_npy_header(bytes: &[u8]) -> Result<Self, NpyError> {
+        NpyHeader::parse(bytes).map(|header| header.shape())
+    }
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn headers_round_trip() {
+        for shape in [Shape::new([4, 1, 2]), Shape::new([4, 3, 2]), Shape::col_major([4, 3, 2])] {
+            let layout = if shape.matches_layout(Layout::XFastest) { Layout::XFastest } else { Layout::LastFastest };
+            let header = shape.to_npy_header("<i8", layout).unwrap();
+            assert_eq!(header.len() % ALIGN, 0);
+            assert_eq!(header.last(), Some(&b'\n'));
+            assert_eq!(Shape::from_npy_header(&header), Ok(shape));
+        }
+        let scalar = Shape::<0>::new([]).to_npy_header("|b1", Layout::Dense).unwrap();
+        assert_eq!(NpyHeader::<0>::parse(&scalar).map(|h| h.descr), Ok("|b1"));
+        assert_eq!(Shape::<2>::from_npy_header(&Shape::new([4]).to_npy_header("<i8", Layout::Dense).unwrap()), Err(NpyError::Dims { found: 1 }));
+    }
+
+    #[test]
+    fn numpy_headers_parse() {
+        let mut file = b"\x93NUMPY\x03\x00\x74\x00\x00\x00{\"shape\": (7, 5 ), \"fortran_order\": True, \"descr\": \"<f4\"}".to_vec();
+        file.resize(128 - 1, b' ');
+        file.extend_from_slice(b"\n\x00\x00\x80\x3f");
+        let header = NpyHeader::<2>::parse(&file).unwrap();
+        assert_eq!((header.descr, header.extents, header.data_offset), ("<f4", [7, 5], 128));
+        assert_eq!(NpyHeader::<2>::parse(&file[..40]), Err(NpyError::NotNpy));
+        assert_eq!(NpyHeader::<2>::parse(b"\x93NUMPY\x04\x00"), Err(NpyError::Version { major: 4 }));
+        let huge = b"\x93NUMPY\x01\x00\x4d\x00{'descr': '<f4', 'fortran_order': False, 'shape': (4294967296, 4294967296), }";
+        assert_eq!(Shape::<2>::from_npy_header(huge), Err(NpyError::Malformed));
+        assert_eq!(Shape::new([2]).to_npy_header("<'f4", Layout::Dense), Err(NpyError::Dtype));
+    }
+
+}
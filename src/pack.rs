@@ -0,0 +1,155 @@
+//!
+//! Packing bounded per-axis integers into single `u64` keys
+//!
+
+
+///
+/// Packs bounded per-axis integers into one `u64`, each in the number of bits given after it
+///
+/// Every entry takes the form `axis: value: bits`, where the value is an identifier, a literal or a
+/// parenthesized expression of an unsigned type converting losslessly into `u64`. The axes named must be
+/// the first `N`, each given once. Fields are packed in the order written, the first in the most
+/// significant bits, so sorting keys sorts cells by the first axis written, then the next. [`unpack_key!`]
+/// recovers the values from the same layout
+///
+/// Layouts are checked at compile time, so they cannot silently truncate coordinates. Widths must be at
+/// least one bit and add up to at most 64, and literal values must fit in their widths, each error naming
+/// the axis at fault
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let (cx, cy, cz) = (5u32, 1u32, 2u32);
+/// let key = pack_key!(x: cx: 21, y: cy: 21, z: cz: 21);
+/// assert_eq!(key, (5 << 42) | (1 << 21) | 2);
+/// assert_eq!(unpack_key!(key; x: 21, y: 21, z: 21), [5, 1, 2]);
+///
+/// // Sort by z first
+/// let key = pack_key!(z: cz: 8, x: (cx + 1): 8, y: cy: 8);
+/// assert_eq!(key, 0x02_06_01);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: pack_key! runs out of bits at `z`, widths must add up to at most 64
+/// let key = pack_key!(x: 1u32: 32, y: 2u32: 32, z: 3u32: 1);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: pack_key! value for `y` does not fit in 4 bits
+/// let key = pack_key!(x: 1u8: 4, y: 16u8: 4);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If a value given by an identifier or expression does not fit in its width
+///
+#[macro_export]
+macro_rules! pack_key {
+    // Literal values are checked against their width at compile time, so they may be left unsuffixed
+    ( @fields [ $( $done:tt )* ] [ $( $lits:tt )* ] $axis:ident : $value:literal : $bits:literal $( , $( $rest:tt )* )? ) => {
+        $crate::pack_key!(
+            @fields [ $( $done )* ($axis [ { #[allow(clippy::unnecessary_cast)] const VALUE: u64 = $value as u64; VALUE } ] $bits) ]
+            [ $( $lits )* ($axis $value $bits) ]
+            $( $( $rest )* )?
+        )
+    };
+    // Parenthesized values lose their parentheses, which would otherwise trip `unused_parens`
+    ( @fields [ $( $done:tt )* ] [ $( $lits:tt )* ] $axis:ident : ( $( $value:tt )* ) : $bits:literal $( , $( $rest:tt )* )? ) => {
+        $crate::pack_key!(@fields [ $( $done )* ($axis [ $( $value )* ] $bits) ] [ $( $lits )* ] $( $( $rest )* )?)
+    };
+    ( @fields [ $( $done:tt )* ] [ $( $lits:tt )* ] $axis:ident : $value:tt : $bits:literal $( , $( $rest:tt )* )? ) => {
+        $crate::pack_key!(@fields [ $( $done )* ($axis [ $value ] $bits) ] [ $( $lits )* ] $( $( $rest )* )?)
+    };
+    ( @fields [ $( ($axis:ident [ $( $value:tt )* ] $bits:literal) )+ ] [ $( ($lit_axis:ident $lit:literal $lit_bits:literal) )* ] ) => {{
+        $crate::__key_layout!("pack_key!"; $( $axis: $bits ),+);
+        $(
+            const _: () = assert!(
+                $crate::__private::fits_in_bits($lit as u128, $lit_bits),
+                concat!("axmac: pack_key! value for `", stringify!($lit_axis), "` does not fit in ", stringify!($lit_bits), " bits"),
+            );
+        )*
+        $crate::__private::pack_key([ $( (u64::from($( $value )*), $bits) ),+ ])
+    }};
+    ( $( $fields:tt )+ ) => {
+        $crate::pack_key!(@fields [] [] $( $fields )+)
+    };
+}
+
+
+///
+/// Recovers the per-axis values packed by [`pack_key!`], given the same layout without the values
+///
+/// Returns the values as an array in axis order, whatever order the layout lists them in
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let key = pack_key!(y: 3u8: 4, x: 9u8: 4);
+/// let [x, y] = unpack_key!(key; y: 4, x: 4);
+/// assert_eq!((x, y), (9, 3));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! unpack_key {
+    ( $key:expr; $( $axis:ident : $bits:literal ),+ $(,)? ) => {{
+        $crate::__key_layout!("unpack_key!"; $( $axis: $bits ),+);
+        $crate::__private::unpack_key($key, [ $( ($crate::ax!($axis), $bits) ),+ ])
+    }};
+}
+
+
+///
+/// Checks a key layout at compile time, naming the axis at fault in each error
+///
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __key_layout {
+    ( $name:literal; $( $axis:ident : $bits:literal ),+ ) => {
+        const _: () = {
+            assert!(
+                $crate::__private::axes_are_permutation(&[ $( $crate::ax!($axis) ),+ ]),
+                concat!("axmac: ", $name, " needs each of the first N axes exactly once"),
+            );
+            let overflow = $crate::__private::key_overflow(&[ $( ($crate::ax!($axis), $bits) ),+ ]);
+            $(
+                assert!($bits > 0, concat!("axmac: ", $name, " gives `", stringify!($axis), "` no bits"));
+                assert!(
+                    overflow != $crate::ax!($axis),
+                    concat!("axmac: ", $name, " runs out of bits at `", stringify!($axis), "`, widths must add up to at most 64"),
+                );
+            )+
+        };
+    };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn round_trips() {
+        let key = pack_key!(x: 0x1f_ffffu32: 21, z: 0u32: 21, y: 7u32: 22);
+        assert_eq!(unpack_key!(key; x: 21, z: 21, y: 22), [0x1f_ffff, 7, 0]);
+        assert_eq!(pack_key!(x: (u64::MAX): 64), u64::MAX);
+        assert_eq!(unpack_key!(u64::MAX; x: 64), [u64::MAX]);
+    }
+
+    #[test]
+    fn literals_fit_their_widths() {
+        assert_eq!(pack_key!(x: 15: 4, y: 0: 1, z: 1u8: 1), 0b11_1101);
+        assert_eq!(pack_key!(x: 0xffff_ffff: 32, y: 1: 32), 0xffff_ffff_0000_0001);
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac:")]
+    fn overflowing_value_panics() {
+        let too_wide = 16u8;
+        let _ = pack_key!(x: too_wide: 4);
+    }
+
+}
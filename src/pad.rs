@@ -0,0 +1,221 @@
+//!
+//! Padding shapes along each axis, as for convolution borders
+//!
+
+use core::iter::FusedIterator;
+
+use crate::{Roi, Shape};
+
+
+///
+/// A shape grown by a border along each axis, with the position of the original region inside it
+///
+/// Created by [`Shape::pad`] or [`pad_shape!`]. The padded shape is densely packed, keeping the order of
+/// the original axes from the smallest stride to the largest
+///
+/// ```
+/// use axmac::{Roi, Shape};
+///
+/// let padded = Shape::new([4, 3]).pad([1, 2], [1, 2]);
+/// assert_eq!(padded.shape(), Shape::new([6, 7]));
+/// assert_eq!(padded.offset(), [1, 2]);
+/// assert_eq!(padded.inner(), Roi::new([1, 2], [5, 5]));
+/// assert_eq!(padded.border().count(), 6 * 7 - 4 * 3);
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Padded<const N: usize> {
+    shape: Shape<N>,
+    inner: Roi<N>,
+}
+
+impl<const N: usize> Padded<N> {
+
+    ///
+    /// Returns the padded shape
+    ///
+    pub const fn shape(&self) -> Shape<N> {
+        self.shape
+    }
+
+    ///
+    /// Returns the coordinate in the padded shape of the original region's first corner
+    ///
+    pub const fn offset(&self) -> [usize; N] {
+        *self.inner.start()
+    }
+
+    ///
+    /// Returns the region of the padded shape covered by the original shape
+    ///
+    pub const fn inner(&self) -> Roi<N> {
+        self.inner
+    }
+
+    ///
+    /// Iterates over the coordinates of the padded shape outside the original region
+    ///
+    pub fn border(&self) -> Border<N> {
+        Border::new(*self.shape.extents(), self.inner)
+    }
+
+}
+
+
+///
+/// Iterator over the coordinates of a padded shape lying in its border, with the x axis varying fastest
+///
+/// Rows crossing the original region skip over it rather than testing each coordinate. Created by
+/// [`Padded::border`]
+///
+#[derive(Clone, Debug)]
+pub struct Border<const N: usize> {
+    extents: [usize; N],
+    inner: Roi<N>,
+    next: Option<[usize; N]>,
+}
+
+impl<const N: usize> Border<N> {
+
+    fn new(extents: [usize; N], inner: Roi<N>) -> Self {
+        let empty = N == 0 || extents.contains(&0);
+        let mut border = Self { extents, inner, next: None };
+        border.next = if empty { None } else { border.skip_inner(Some([0; N])) };
+        border
+    }
+
+    // Moves a coordinate lying inside the original region to the first one past it along x
+    fn skip_inner(&self, mut coord: Option<[usize; N]>) -> Option<[usize; N]> {
+        while let Some(c) = coord {
+            if !self.inner.contains(&c) {
+                break;
+            }
+            let mut c = c;
+            c[0] = self.inner.range(0).end - 1;
+            coord = self.step(c);
+        }
+        coord
+    }
+
+    fn step(&self, mut coord: [usize; N]) -> Option<[usize; N]> {
+        for axis in 0..N {
+            coord[axis] += 1;
+            if coord[axis] < self.extents[axis] {
+                return Some(coord);
+            }
+            coord[axis] = 0;
+        }
+        None
+    }
+
+}
+
+impl<const N: usize> Iterator for Border<N> {
+    type Item = [usize; N];
+
+    fn next(&mut self) -> Option<[usize; N]> {
+        let current = self.next?;
+        self.next = self.skip_inner(self.step(current));
+        Some(current)
+    }
+}
+
+impl<const N: usize> FusedIterator for Border<N> {}
+
+
+impl<const N: usize> Shape<N> {
+
+    ///
+    /// Returns this shape grown by `before` positions at the start of each axis and `after` at the end
+    ///
+    /// See [`pad_shape!`] for padding by axis name
+    ///
+    pub fn pad(&self, before: [usize; N], after: [usize; N]) -> Padded<N> {
+        let extents: [usize; N] = core::array::from_fn(|i| before[i] + self.extent(i) + after[i]);
+
+        let mut axes: [usize; N] = core::array::from_fn(|i| i);
+        axes.sort_unstable_by_key(|&i| (self.stride(i), i));
+        let mut strides = [0; N];
+        let mut stride = 1;
+        for axis in axes {
+            strides[axis] = stride;
+            stride *= extents[axis];
+        }
+
+        let end = core::array::from_fn(|i| before[i] + self.extent(i));
+        Padded { shape: Shape::with_strides(extents, strides), inner: Roi::new(before, end) }
+    }
+
+}
+
+
+///
+/// Pads a shape by axis name, each axis named taking the `(before, after)` positions added at its start
+/// and end
+///
+/// Axes not named are left unpadded. Evaluates to a [`Padded`], holding the padded shape, the offset of
+/// the original region and an iterator over the border. Naming an axis twice or past the last dimension
+/// fails to compile
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Shape;
+///
+/// let image = Shape::new([32, 24]);
+/// let padded = pad_shape!(image; x: (1, 1), y: (2, 2));
+/// assert_eq!(padded.shape().extents(), &[34, 28]);
+/// assert_eq!(padded.offset(), [1, 2]);
+///
+/// // Only the last row gains padding
+/// let padded = pad_shape!(Shape::new([2, 2]); y: (0, 1));
+/// assert!(padded.border().eq([[0, 2], [1, 2]]));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: pad_shape! names an axis past the last dimension
+/// let padded = pad_shape!(axmac::Shape::new([4, 4]); z: (1, 1));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! pad_shape {
+    ( $shape:expr; $( $axis:ident : ( $before:expr, $after:expr ) ),* $(,)? ) => {{
+        const _: () = assert!(
+            $crate::__private::indices_are_unique(&[ $( $crate::ax!($axis) ),* ]),
+            "axmac: pad_shape! names an axis more than once",
+        );
+        $crate::__private::Pads::<{ $crate::__private::max_index(&[ $( $crate::ax!($axis) ),* ]) }>::apply(
+            &$shape,
+            [ $( ($crate::ax!($axis), $before, $after) ),* ],
+        )
+    }};
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn border_skips_inner_region() {
+        let padded = Shape::new([2, 1]).pad([1, 1], [1, 0]);
+        assert!(padded.border().eq([[0, 0], [1, 0], [2, 0], [3, 0], [0, 1], [3, 1]]));
+
+        let padded = Shape::new([3, 3, 3]).pad([1; 3], [1; 3]);
+        assert!(padded.border().all(|c| !padded.inner().contains(&c)));
+        assert_eq!(padded.border().count(), 125 - 27);
+        assert_eq!(Shape::new([2, 2]).pad([0; 2], [0; 2]).border().count(), 0);
+    }
+
+    #[test]
+    fn padding_keeps_layout() {
+        let padded = pad_shape!(Shape::col_major([3, 4]); x: (1, 0));
+        assert_eq!(padded.shape(), Shape::col_major([4, 4]));
+        assert_eq!(padded.inner(), Roi::new([1, 0], [4, 4]));
+    }
+
+}
@@ -0,0 +1,113 @@
+//!
+//! Placement of shaped buffers on fixed size pages, for streaming large volumes from memory mapped files
+//!
+
+use crate::{Axis, Shape, Tiles};
+
+
+impl<const N: usize> Shape<N> {
+
+    ///
+    /// Splits the shape into slabs along `preferred_axis`, each as thick as fits in a span of `page_size`
+    /// elements of memory
+    ///
+    /// Every slab covers the full extent of every other axis, so each one is a single read from the file.
+    /// When one slice across `preferred_axis` is already wider than a page the slabs are one position thick,
+    /// and the last slab may be thinner than the rest. For pages measured in bytes, divide by the size of an
+    /// element
+    ///
+    /// ```
+    /// use axmac::{Axis, Shape};
+    ///
+    /// // A 64x64x100 volume of f32 voxels read 1 MiB at a time
+    /// let volume = Shape::new([64, 64, 100]);
+    /// let page = (1 << 20) / core::mem::size_of::<f32>();
+    ///
+    /// let slabs: Vec<_> = volume.chunks_for_pages(page, Axis::Z).map(|(roi, _)| roi.range(2)).collect();
+    /// assert_eq!(slabs, [0..64, 64..100]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `preferred_axis` is not less than `N`, or `page_size` is zero
+    ///
+    pub fn chunks_for_pages(&self, page_size: usize, preferred_axis: Axis) -> Tiles<N> {
+        let axis = preferred_axis.as_usize();
+        assert!(axis < N, "axmac: axis is past the last dimension of the shape");
+        assert!(page_size > 0, "axmac: pages must hold at least one element");
+
+        let mut slab = *self.extents();
+        slab[axis] = 1;
+        // Memory spanned by a single slice, from its lowest to its highest element
+        let slice_span = (0..N).map(|i| slab[i].saturating_sub(1) * self.strides()[i]).sum::<usize>() + 1;
+        slab[axis] = match (page_size.checked_sub(slice_span), self.strides()[axis]) {
+            (Some(spare), stride) if stride > 0 => (spare / stride + 1).min(self.extents()[axis]),
+            (Some(_), _) => self.extents()[axis],
+            (None, _) => 1,
+        }
+        .max(1);
+        Tiles::new([0; N], *self.extents(), slab)
+    }
+
+}
+
+
+///
+/// Returns the page holding the element at a flat index, given the number of elements per page
+///
+/// Both operands are evaluated once. Pair it with [`Shape::flat_index`] to find the page of a coordinate,
+/// or multiply the index by the size of an element first for pages counted in bytes
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Shape;
+///
+/// let volume = Shape::new([256, 256, 64]);
+/// let index = volume.flat_index(&[10, 20, 3]).unwrap();
+/// assert_eq!(page_of!(index; 4096), 49);
+/// assert_eq!(page_of!(index * core::mem::size_of::<u16>(); 4096), 98);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If the page size is zero
+///
+#[macro_export]
+macro_rules! page_of {
+    ( $index:expr ; $page_size:expr $(,)? ) => {{
+        let page_size: usize = $page_size;
+        assert!(page_size > 0, "axmac: pages must hold at least one element");
+        ($index) / page_size
+    }};
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn slabs_fit_their_pages() {
+        let shape = Shape::col_major([10, 4, 3]);
+        let slabs: std::vec::Vec<_> = shape.chunks_for_pages(25, Axis::X).map(|(roi, _)| roi.range(0)).collect();
+        assert_eq!(slabs, [0..2, 2..4, 4..6, 6..8, 8..10]);
+        for (roi, _) in shape.chunks_for_pages(25, Axis::X) {
+            let offsets = roi.offsets_in(&shape);
+            let (lo, hi) = offsets.fold((usize::MAX, 0), |(lo, hi), o| (lo.min(o), hi.max(o)));
+            assert!(hi - lo < 25);
+        }
+    }
+
+    #[test]
+    fn wide_slices_are_one_thick() {
+        let shape = Shape::new([100, 5]);
+        assert_eq!(shape.chunks_for_pages(10, Axis::Y).count(), 5);
+        assert_eq!(shape.chunks_for_pages(1 << 20, Axis::Y).count(), 1);
+        assert_eq!(page_of!(4095; 4096), 0);
+        assert_eq!(page_of!(4096; 4096), 1);
+    }
+
+}
@@ -0,0 +1,179 @@
+//!
+//! Permutations of axes
+//!
+
+use crate::__private::axes_are_permutation;
+use crate::InvalidPermutation;
+
+
+///
+/// A reordering of `N` axes, validated to name every axis exactly once
+///
+/// Applying the permutation to a coordinate moves component `perm[i]` into position `i`
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::AxisPerm;
+///
+/// const ZXY: AxisPerm<3> = AxisPerm::new(axs![z, x, y]);
+///
+/// assert_eq!(ZXY.apply([1, 2, 3]), [3, 1, 2]);
+/// assert_eq!(ZXY.inverse().apply([3, 1, 2]), [1, 2, 3]);
+/// assert_eq!(AxisPerm::try_new(axs![x, x]), None);
+/// # }
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AxisPerm<const N: usize>([usize; N]);
+
+impl<const N: usize> AxisPerm<N> {
+
+    ///
+    /// The permutation leaving every axis in place
+    ///
+    pub const IDENTITY: Self = Self({
+        let mut perm = [0; N];
+        let mut i = 0;
+        while i < N {
+            perm[i] = i;
+            i += 1;
+        }
+        perm
+    });
+
+    ///
+    /// Creates a permutation from the axis to place at each position
+    ///
+    /// # Panics
+    ///
+    /// If `axes` is not a permutation of `0..N`, which fails to compile in const contexts
+    ///
+    pub const fn new(axes: [usize; N]) -> Self {
+        assert!(axes_are_permutation(&axes), "axmac: axes must name every axis exactly once");
+        Self(axes)
+    }
+
+    ///
+    /// Creates a permutation, or returns `None` if `axes` is not a permutation of `0..N`
+    ///
+    pub const fn try_new(axes: [usize; N]) -> Option<Self> {
+        if axes_are_permutation(&axes) { Some(Self(axes)) } else { None }
+    }
+
+    ///
+    /// Returns the axis placed at every position
+    ///
+    pub const fn as_array(&self) -> &[usize; N] {
+        &self.0
+    }
+
+    ///
+    /// Returns the axis placed at `position`
+    ///
+    pub const fn get(&self, position: usize) -> usize {
+        self.0[position]
+    }
+
+    ///
+    /// Returns the permutation undoing this one
+    ///
+    pub const fn inverse(&self) -> Self {
+        let mut inv = [0; N];
+        let mut i = 0;
+        while i < N {
+            inv[self.0[i]] = i;
+            i += 1;
+        }
+        Self(inv)
+    }
+
+    ///
+    /// Returns the permutation placing the same axes in the opposite order
+    ///
+    pub const fn reversed(&self) -> Self {
+        let mut rev = [0; N];
+        let mut i = 0;
+        while i < N {
+            rev[i] = self.0[N - 1 - i];
+            i += 1;
+        }
+        Self(rev)
+    }
+
+    ///
+    /// Reorders the components of `coord`
+    ///
+    pub fn apply<T: Copy>(&self, coord: [T; N]) -> [T; N] {
+        core::array::from_fn(|i| coord[self.0[i]])
+    }
+
+}
+
+impl<const N: usize> Default for AxisPerm<N> {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl<const N: usize> TryFrom<[usize; N]> for AxisPerm<N> {
+    type Error = InvalidPermutation;
+
+    ///
+    /// Checks that plain axes, as `axs!` gives them, name every axis exactly once
+    ///
+    /// ```
+    /// # #[macro_use] extern crate axmac; fn main() {
+    /// use axmac::{AxisPerm, InvalidPermutation};
+    ///
+    /// assert_eq!(AxisPerm::try_from(axs![y, x]), Ok(AxisPerm::new([1, 0])));
+    /// assert_eq!(AxisPerm::try_from(axs![z, x, z]), Err(InvalidPermutation { position: 2, axis: 2 }));
+    /// # }
+    /// ```
+    ///
+    fn try_from(axes: [usize; N]) -> Result<Self, InvalidPermutation> {
+        let mut seen = [false; N];
+        for (position, &axis) in axes.iter().enumerate() {
+            if axis >= N || core::mem::replace(&mut seen[axis], true) {
+                return Err(InvalidPermutation { position, axis });
+            }
+        }
+        Ok(Self(axes))
+    }
+}
+
+impl<const N: usize> From<AxisPerm<N>> for [usize; N] {
+    fn from(perm: AxisPerm<N>) -> Self {
+        perm.0
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn apply_and_inverse_work() {
+        let perm = AxisPerm::new(axs![y, w, x, z]);
+        let coord = ['x', 'y', 'z', 'w'];
+        assert_eq!(perm.apply(coord), ['y', 'w', 'x', 'z']);
+        assert_eq!(perm.inverse().apply(perm.apply(coord)), coord);
+        assert_eq!(AxisPerm::<4>::IDENTITY.apply(coord), coord);
+    }
+
+    #[test]
+    fn validation_works() {
+        assert!(AxisPerm::try_new(axs![x, z, y]).is_some());
+        assert!(AxisPerm::try_new(axs![x, w]).is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac:")]
+    fn duplicate_axes_panic() {
+        let axes = axs![y, y];
+        let _ = AxisPerm::new(axes);
+    }
+
+}
@@ -0,0 +1,179 @@
+//!
+//! Double buffering of time slices along one axis of a buffer
+//!
+
+use crate::Axis;
+
+
+///
+/// Tracks which of two time slices stored along one axis is read and which is written at a time step
+///
+/// Simulations stepping a grid forward often keep the current and next states in one array with an
+/// extent of two along an extra axis, alternating the roles of the two slices every step instead of
+/// copying. At even steps slice zero is read and slice one written, and the other way round at odd steps.
+/// [`pp_index!`] names the axis and step together
+///
+/// ```
+/// use axmac::{Axis, PingPong, Shape};
+///
+/// // A 4x4 heat grid holding two time slices along z
+/// let shape = Shape::new([4, 4, 2]);
+/// let mut cells = [0.0f32; 32];
+/// cells[shape.flat_index(&[1, 1, 0]).unwrap()] = 1.0;
+///
+/// let mut pp = PingPong::new(Axis::Z);
+/// for _ in 0..3 {
+///     for [x, y] in axmac::NdIndex::new([0, 0], [4, 4]) {
+///         let read = shape.flat_index_unchecked(&pp.read_coord([x, y, 0]));
+///         let write = shape.flat_index_unchecked(&pp.write_coord([x, y, 0]));
+///         cells[write] = cells[read] * 0.5;
+///     }
+///     pp.advance();
+/// }
+/// assert_eq!(pp.read_slot(), 1);
+/// assert_eq!(cells[shape.flat_index_unchecked(&pp.read_coord([1, 1, 0]))], 0.125);
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PingPong {
+    axis: Axis,
+    step: u64,
+}
+
+impl PingPong {
+
+    ///
+    /// Creates the double buffer along `axis` at step zero
+    ///
+    pub const fn new(axis: Axis) -> Self {
+        Self { axis, step: 0 }
+    }
+
+    ///
+    /// Moves the double buffer to time step `step`
+    ///
+    pub const fn at_step(mut self, step: u64) -> Self {
+        self.step = step;
+        self
+    }
+
+    ///
+    /// Returns the axis the two slices are stored along
+    ///
+    pub const fn axis(&self) -> Axis {
+        self.axis
+    }
+
+    ///
+    /// Returns the current time step
+    ///
+    pub const fn step(&self) -> u64 {
+        self.step
+    }
+
+    ///
+    /// Returns the index along the axis of the slice read at the current step, holding its state
+    ///
+    pub const fn read_slot(&self) -> usize {
+        (self.step % 2) as usize
+    }
+
+    ///
+    /// Returns the index along the axis of the slice written at the current step, becoming the state of
+    /// the next
+    ///
+    pub const fn write_slot(&self) -> usize {
+        1 - self.read_slot()
+    }
+
+    ///
+    /// Returns `coord` moved into the slice read at the current step
+    ///
+    /// # Panics
+    ///
+    /// If the axis is not less than `N`
+    ///
+    pub const fn read_coord<const N: usize>(&self, coord: [usize; N]) -> [usize; N] {
+        self.with_slot(coord, self.read_slot())
+    }
+
+    ///
+    /// Returns `coord` moved into the slice written at the current step
+    ///
+    /// # Panics
+    ///
+    /// If the axis is not less than `N`
+    ///
+    pub const fn write_coord<const N: usize>(&self, coord: [usize; N]) -> [usize; N] {
+        self.with_slot(coord, self.write_slot())
+    }
+
+    ///
+    /// Moves on to the next time step, swapping the roles of the two slices
+    ///
+    pub fn advance(&mut self) {
+        self.step = self.step.wrapping_add(1);
+    }
+
+    const fn with_slot<const N: usize>(&self, mut coord: [usize; N], slot: usize) -> [usize; N] {
+        let axis = self.axis.as_usize();
+        assert!(axis < N, "axmac: axis is past the last dimension of the coordinate");
+        coord[axis] = slot;
+        coord
+    }
+
+}
+
+
+///
+/// Creates a [`PingPong`] at time step `t` storing its two slices along the named axis
+///
+/// The axis defaults to w, the usual spare axis of a 3D simulation, when only the step is given
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let pp = pp_index!(7; w);
+/// assert_eq!((pp.read_slot(), pp.write_slot()), (1, 0));
+/// assert_eq!(pp.write_coord([5, 6, 7, 1]), [5, 6, 7, 0]);
+///
+/// assert_eq!(pp_index!(4; y).read_coord([3, 9]), [3, 0]);
+/// assert_eq!(pp_index!(4), pp_index!(4; w));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! pp_index {
+    ( $t:expr ; $axis:ident $(,)? ) => {
+        $crate::PingPong::new($crate::ax!(@enum $axis)).at_step($t)
+    };
+    ( $t:expr $(,)? ) => {
+        $crate::pp_index!($t; w)
+    };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn slots_alternate() {
+        let mut pp = PingPong::new(Axis::X);
+        assert_eq!((pp.read_slot(), pp.write_slot()), (0, 1));
+        pp.advance();
+        assert_eq!((pp.read_slot(), pp.write_slot()), (1, 0));
+        assert_eq!(pp.step(), 1);
+        let mut last = PingPong::new(Axis::X).at_step(u64::MAX);
+        last.advance();
+        assert_eq!(last.read_slot(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac: axis is past the last dimension of the coordinate")]
+    fn axis_must_fit() {
+        let _ = PingPong::new(Axis::W).read_coord([0, 0, 0]);
+    }
+
+}
@@ -0,0 +1,248 @@
+//!
+//! Per-axis transform pipelines for input handling
+//!
+
+
+///
+/// Response curve applied to an axis value once its deadzone has been removed
+///
+/// The built-in curves preserve the sign of the input, so a curve never flips the direction of a stick
+///
+#[derive(Clone, Copy, Debug)]
+pub enum Curve {
+    /// Leaves the value untouched
+    Linear,
+    /// `v * |v|`
+    Quadratic,
+    /// `v * v * v`
+    Cubic,
+    /// Any user supplied mapping
+    Custom(fn(f32) -> f32),
+}
+
+impl Curve {
+
+    ///
+    /// Applies the curve to a single value
+    ///
+    pub fn apply(&self, v: f32) -> f32 {
+        match self {
+            Curve::Linear    => v,
+            Curve::Quadratic => v * v.abs(),
+            Curve::Cubic     => v * v * v,
+            Curve::Custom(f) => f(v),
+        }
+    }
+
+}
+
+
+///
+/// The transforms applied to a single axis by an [`AxisPipeline`]
+///
+/// Transforms are applied in the order deadzone, curve, scale then invert
+///
+#[derive(Clone, Copy, Debug)]
+pub struct AxisTransform {
+    /// Negates the final value
+    pub invert: bool,
+    /// Magnitude below which the value is snapped to zero
+    pub deadzone: f32,
+    /// Multiplier applied after the curve
+    pub scale: f32,
+    /// Response curve applied after the deadzone
+    pub curve: Curve,
+}
+
+impl AxisTransform {
+
+    ///
+    /// A transform which leaves values untouched
+    ///
+    pub const IDENTITY: Self = Self {
+        invert: false,
+        deadzone: 0.0,
+        scale: 1.0,
+        curve: Curve::Linear,
+    };
+
+    ///
+    /// Applies the transform to a single value
+    ///
+    /// Values outside the deadzone are rescaled so the output still starts at zero at the edge of the
+    /// deadzone instead of jumping straight to `deadzone`
+    ///
+    pub fn apply(&self, v: f32) -> f32 {
+        let magnitude = v.abs();
+        let v = if magnitude <= self.deadzone {
+            0.0
+        } else if self.deadzone > 0.0 && self.deadzone < 1.0 {
+            ((magnitude - self.deadzone) / (1.0 - self.deadzone)).copysign(v)
+        } else {
+            v
+        };
+
+        let v = self.curve.apply(v) * self.scale;
+        if self.invert { -v } else { v }
+    }
+
+}
+
+impl Default for AxisTransform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+
+///
+/// Builder applying per-axis transforms to `[f32; N]` input, configured by axis index
+///
+/// Gamepads and joysticks report their axes as plain numbered arrays, so the pipeline is configured with
+/// the same `usize` values produced by `ax!`
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{AxisPipeline, Curve};
+///
+/// let pipeline = AxisPipeline::<2>::new()
+///     .deadzone(ax!(x), 0.5)
+///     .deadzone(ax!(y), 0.5)
+///     .curve(ax!(x), Curve::Quadratic)
+///     .invert(ax!(y), true);
+///
+/// assert_eq!(pipeline.apply([0.25, 0.25]), [0.0, 0.0]);
+/// assert_eq!(pipeline.apply([0.75, 0.75]), [0.25, -0.5]);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// All builder methods panic if `axis` is not less than `N`
+///
+#[derive(Clone, Copy, Debug)]
+pub struct AxisPipeline<const N: usize> {
+    transforms: [AxisTransform; N],
+}
+
+impl<const N: usize> AxisPipeline<N> {
+
+    ///
+    /// Creates a pipeline which leaves every axis untouched
+    ///
+    pub const fn new() -> Self {
+        Self { transforms: [AxisTransform::IDENTITY; N] }
+    }
+
+    ///
+    /// Sets whether the output of `axis` is negated
+    ///
+    pub fn invert(mut self, axis: usize, invert: bool) -> Self {
+        self.transforms[axis].invert = invert;
+        self
+    }
+
+    ///
+    /// Snaps values of `axis` whose magnitude is at most `deadzone` to zero
+    ///
+    pub fn deadzone(mut self, axis: usize, deadzone: f32) -> Self {
+        self.transforms[axis].deadzone = deadzone.abs();
+        self
+    }
+
+    ///
+    /// Multiplies the output of `axis` by `scale`
+    ///
+    pub fn scale(mut self, axis: usize, scale: f32) -> Self {
+        self.transforms[axis].scale = scale;
+        self
+    }
+
+    ///
+    /// Sets the response curve of `axis`
+    ///
+    pub fn curve(mut self, axis: usize, curve: Curve) -> Self {
+        self.transforms[axis].curve = curve;
+        self
+    }
+
+    ///
+    /// Replaces every transform of `axis` at once
+    ///
+    pub fn with(mut self, axis: usize, transform: AxisTransform) -> Self {
+        self.transforms[axis] = transform;
+        self
+    }
+
+    ///
+    /// Returns the transform currently configured for `axis`
+    ///
+    pub fn transform(&self, axis: usize) -> &AxisTransform {
+        &self.transforms[axis]
+    }
+
+    ///
+    /// Applies every axis' transform to `input`
+    ///
+    pub fn apply(&self, mut input: [f32; N]) -> [f32; N] {
+        self.apply_in_place(&mut input);
+        input
+    }
+
+    ///
+    /// Applies every axis' transform to `input` without copying it
+    ///
+    pub fn apply_in_place(&self, input: &mut [f32; N]) {
+        for (v, transform) in input.iter_mut().zip(self.transforms.iter()) {
+            *v = transform.apply(*v);
+        }
+    }
+
+}
+
+impl<const N: usize> Default for AxisPipeline<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn identity_works() {
+        let pipeline = AxisPipeline::<4>::new();
+        assert_eq!(pipeline.apply([0.1, -0.5, 1.0, 0.0]), [0.1, -0.5, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn deadzone_rescales() {
+        let pipeline = AxisPipeline::<2>::new().deadzone(ax!(y), 0.5);
+        assert_eq!(pipeline.apply([0.25, 0.25]), [0.25, 0.0]);
+        assert_eq!(pipeline.apply([0.25, -0.75]), [0.25, -0.5]);
+        assert_eq!(pipeline.apply([0.25, 1.0]), [0.25, 1.0]);
+    }
+
+    #[test]
+    fn curve_scale_and_invert_work() {
+        let pipeline = AxisPipeline::<3>::new()
+            .curve(ax!(x), Curve::Cubic)
+            .scale(ax!(y), 2.0)
+            .invert(ax!(z), true)
+            .curve(ax!(z), Curve::Custom(|v| v + 1.0));
+        assert_eq!(pipeline.apply([-0.5, 0.5, 0.5]), [-0.125, 1.0, -1.5]);
+        assert_eq!(pipeline.invert(ax!(z), true).apply([0.0, 0.0, 0.5]), [0.0, 0.0, -1.5]);
+        assert_eq!(pipeline.invert(ax!(z), false).apply([0.0, 0.0, 0.5]), [0.0, 0.0, 1.5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range_axis_panics() {
+        let _ = AxisPipeline::<2>::new().invert(ax!(z), true);
+    }
+
+}
@@ -0,0 +1,143 @@
+//!
+//! Axis-aligned planes
+//!
+
+use crate::SignedScalar;
+
+
+///
+/// Which side of an [`AxisPlane`] a point lies on
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// The point's coordinate along the axis is less than the plane's offset
+    Below,
+    /// The point lies exactly on the plane
+    On,
+    /// The point's coordinate along the axis is greater than the plane's offset
+    Above,
+}
+
+
+///
+/// The plane on which the coordinate along `axis` equals `offset`
+///
+/// Its normal is the positive direction of `axis`, which keeps every query down to a single comparison
+/// or lerp. This suits broad-phase collision and BSP style partitioning, where almost every splitting
+/// plane is axis-aligned
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{AxisPlane, Side};
+///
+/// let floor = AxisPlane::new(ax!(y), 0.0);
+///
+/// assert_eq!(floor.side_of(&[3.0, 2.0, 1.0]), Side::Above);
+/// assert_eq!(floor.project(&[3.0, 2.0, 1.0]), [3.0, 0.0, 1.0]);
+/// assert_eq!(floor.intersect_segment(&[0.0, 1.0, 0.0], &[2.0, -1.0, 0.0]), Some([1.0, 0.0, 0.0]));
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// The queries panic if `axis` is not less than the dimensionality of the points given
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AxisPlane<T> {
+    /// The axis the plane is perpendicular to
+    pub axis: usize,
+    /// The coordinate along `axis` of every point on the plane
+    pub offset: T,
+}
+
+impl<T: SignedScalar> AxisPlane<T> {
+
+    ///
+    /// Creates the plane perpendicular to `axis` at `offset`
+    ///
+    pub const fn new(axis: usize, offset: T) -> Self {
+        Self { axis, offset }
+    }
+
+    ///
+    /// Returns the signed distance from the plane to `point`, positive above it
+    ///
+    pub fn distance<const N: usize>(&self, point: &[T; N]) -> T {
+        point[self.axis] - self.offset
+    }
+
+    ///
+    /// Returns which side of the plane `point` lies on
+    ///
+    /// A NaN coordinate is reported as [`Side::On`]
+    ///
+    pub fn side_of<const N: usize>(&self, point: &[T; N]) -> Side {
+        let v = point[self.axis];
+        if v < self.offset {
+            Side::Below
+        } else if v > self.offset {
+            Side::Above
+        } else {
+            Side::On
+        }
+    }
+
+    ///
+    /// Returns the closest point on the plane to `point`
+    ///
+    pub fn project<const N: usize>(&self, point: &[T; N]) -> [T; N] {
+        let mut projected = *point;
+        projected[self.axis] = self.offset;
+        projected
+    }
+
+    ///
+    /// Returns where the segment from `a` to `b` crosses the plane, or `None` if both ends lie strictly
+    /// on the same side
+    ///
+    /// A segment lying within the plane returns `a`
+    ///
+    pub fn intersect_segment<const N: usize>(&self, a: &[T; N], b: &[T; N]) -> Option<[T; N]> {
+        let (side_a, side_b) = (self.side_of(a), self.side_of(b));
+        if side_a == side_b && side_a != Side::On {
+            return None;
+        }
+        if side_a == Side::On {
+            return Some(*a);
+        }
+
+        let t = (self.offset - a[self.axis]) / (b[self.axis] - a[self.axis]);
+        let mut hit: [T; N] = core::array::from_fn(|i| a[i] + (b[i] - a[i]) * t);
+        // Snap to the plane exactly, since the lerp can land a rounding error away from it
+        hit[self.axis] = self.offset;
+        Some(hit)
+    }
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn side_and_distance_work() {
+        let plane = AxisPlane::new(ax!(z), 2);
+        assert_eq!(plane.side_of(&[0, 0, 1]), Side::Below);
+        assert_eq!(plane.side_of(&[0, 0, 2]), Side::On);
+        assert_eq!(plane.side_of(&[9, 9, 3]), Side::Above);
+        assert_eq!(plane.distance(&[0, 0, -1]), -3);
+    }
+
+    #[test]
+    fn intersect_segment_works() {
+        let plane = AxisPlane::new(ax!(x), 1.0f32);
+        assert_eq!(plane.intersect_segment(&[0.0, 0.0], &[4.0, 8.0]), Some([1.0, 2.0]));
+        assert_eq!(plane.intersect_segment(&[4.0, 8.0], &[0.0, 0.0]), Some([1.0, 2.0]));
+        assert_eq!(plane.intersect_segment(&[2.0, 0.0], &[3.0, 0.0]), None);
+        assert_eq!(plane.intersect_segment(&[1.0, 5.0], &[1.0, 7.0]), Some([1.0, 5.0]));
+    }
+
+}
@@ -0,0 +1,273 @@
+//!
+//! Growable storage of fixed size points
+//!
+
+use alloc::vec::Vec;
+use core::ops::{Deref, DerefMut};
+
+use crate::{select_nth_along, Aabb, Axis, Scalar};
+
+
+///
+/// A growable list of `N` dimensional points, with bulk operations taking the axis to act along
+///
+/// Dereferences to a slice of `[T; N]`, so points can be indexed, iterated and sorted as usual
+///
+/// ```
+/// use axmac::{Axis, Points};
+///
+/// let mut cloud = Points::from(vec![[0.0, 1.0, 2.0], [4.0, -1.0, 0.5]]);
+/// cloud.push([2.0, 3.0, 1.0]);
+///
+/// cloud.translate_along(Axis::Z, 10.0);
+/// cloud.scale_axis(Axis::X, 0.5);
+/// assert_eq!(cloud[1], [2.0, -1.0, 10.5]);
+///
+/// let bounds = cloud.bounds().unwrap();
+/// assert_eq!(bounds.min, [0.0, -1.0, 10.5]);
+/// assert_eq!(bounds.max, [2.0, 3.0, 12.0]);
+/// ```
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Points<T, const N: usize> {
+    points: Vec<[T; N]>,
+}
+
+impl<T, const N: usize> Points<T, N> {
+
+    ///
+    /// Creates an empty list
+    ///
+    pub const fn new() -> Self {
+        Self { points: Vec::new() }
+    }
+
+    ///
+    /// Creates an empty list with room for `capacity` points
+    ///
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { points: Vec::with_capacity(capacity) }
+    }
+
+    ///
+    /// Appends a point
+    ///
+    pub fn push(&mut self, point: [T; N]) {
+        self.points.push(point);
+    }
+
+    ///
+    /// Removes and returns the last point, or `None` if there are none
+    ///
+    pub fn pop(&mut self) -> Option<[T; N]> {
+        self.points.pop()
+    }
+
+    ///
+    /// Removes every point, keeping the allocated capacity
+    ///
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+
+    ///
+    /// Unwraps the points
+    ///
+    pub fn into_vec(self) -> Vec<[T; N]> {
+        self.points
+    }
+
+}
+
+impl<T: Scalar, const N: usize> Points<T, N> {
+
+    ///
+    /// Adds `offset` to the `axis` component of every point
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not less than `N`
+    ///
+    pub fn translate_along(&mut self, axis: Axis, offset: T) {
+        let axis = check_axis::<N>(axis);
+        for p in &mut self.points {
+            p[axis] = p[axis] + offset;
+        }
+    }
+
+    ///
+    /// Multiplies the `axis` component of every point by `factor`
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not less than `N`
+    ///
+    pub fn scale_axis(&mut self, axis: Axis, factor: T) {
+        let axis = check_axis::<N>(axis);
+        for p in &mut self.points {
+            p[axis] = p[axis] * factor;
+        }
+    }
+
+    ///
+    /// Reorders the points so those whose `axis` component is less than `pivot` come first, returning
+    /// how many there are
+    ///
+    /// Points on either side of the returned index keep no particular order. Points with incomparable
+    /// components, such as NaN, go after the index
+    ///
+    /// ```
+    /// use axmac::{Axis, Points};
+    ///
+    /// let mut points: Points<f32, 2> = [[3.0, 0.0], [1.0, 5.0], [2.0, 1.0]].into_iter().collect();
+    /// let split = points.partition_along(Axis::Y, 1.0);
+    /// assert_eq!(split, 1);
+    /// assert_eq!(points[0], [3.0, 0.0]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not less than `N`
+    ///
+    pub fn partition_along(&mut self, axis: Axis, pivot: T) -> usize {
+        let axis = check_axis::<N>(axis);
+        let mut split = 0;
+        for i in 0..self.points.len() {
+            if self.points[i][axis] < pivot {
+                self.points.swap(i, split);
+                split += 1;
+            }
+        }
+        split
+    }
+
+    ///
+    /// Reorders the points about the median of their `axis` components, returning the index of the
+    /// median point
+    ///
+    /// Every point before the index has a component no greater than the median's, and every point after
+    /// it one no less, splitting the points into halves for a spatial tree or for separate workers. An empty
+    /// list returns zero
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not less than `N`
+    ///
+    pub fn split_at_axis_median(&mut self, axis: Axis) -> usize {
+        check_axis::<N>(axis);
+        let median = self.points.len() / 2;
+        if median < self.points.len() {
+            select_nth_along(&mut self.points, axis, median);
+        }
+        median
+    }
+
+    ///
+    /// Returns the smallest box containing every point, or `None` if there are none
+    ///
+    pub fn bounds(&self) -> Option<Aabb<T, N>> {
+        Aabb::from_points(&self.points)
+    }
+
+}
+
+fn check_axis<const N: usize>(axis: Axis) -> usize {
+    let axis = axis.as_usize();
+    assert!(axis < N, "axmac: axis is out of bounds for the points");
+    axis
+}
+
+impl<T, const N: usize> Deref for Points<T, N> {
+    type Target = [[T; N]];
+
+    fn deref(&self) -> &[[T; N]] {
+        &self.points
+    }
+}
+
+impl<T, const N: usize> DerefMut for Points<T, N> {
+    fn deref_mut(&mut self) -> &mut [[T; N]] {
+        &mut self.points
+    }
+}
+
+impl<T, const N: usize> From<Vec<[T; N]>> for Points<T, N> {
+    fn from(points: Vec<[T; N]>) -> Self {
+        Self { points }
+    }
+}
+
+impl<T, const N: usize> From<Points<T, N>> for Vec<[T; N]> {
+    fn from(points: Points<T, N>) -> Self {
+        points.points
+    }
+}
+
+impl<T, const N: usize> FromIterator<[T; N]> for Points<T, N> {
+    fn from_iter<I: IntoIterator<Item = [T; N]>>(iter: I) -> Self {
+        Self { points: iter.into_iter().collect() }
+    }
+}
+
+impl<T, const N: usize> Extend<[T; N]> for Points<T, N> {
+    fn extend<I: IntoIterator<Item = [T; N]>>(&mut self, iter: I) {
+        self.points.extend(iter);
+    }
+}
+
+impl<T, const N: usize> IntoIterator for Points<T, N> {
+    type Item = [T; N];
+    type IntoIter = alloc::vec::IntoIter<[T; N]>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.points.into_iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a Points<T, N> {
+    type Item = &'a [T; N];
+    type IntoIter = core::slice::Iter<'a, [T; N]>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.points.iter()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn bulk_operations_work() {
+        let mut points: Points<i32, 2> = (0..4).map(|i| [i, -i]).collect();
+        points.translate_along(Axis::Y, 10);
+        points.scale_axis(Axis::X, 3);
+        assert_eq!(points.last(), Some(&[9, 7]));
+        assert_eq!(points.bounds(), Some(Aabb::new([0, 7], [9, 10])));
+        points.clear();
+        assert_eq!(points.bounds(), None);
+    }
+
+    #[test]
+    fn splits_work() {
+        let mut points: Points<i32, 3> = [7, 3, 9, 1, 5, 8, 2].into_iter().map(|z| [0, z, z * 2]).collect();
+        let median = points.split_at_axis_median(Axis::Z);
+        assert_eq!((median, points[median][2]), (3, 10));
+        assert!(points[..median].iter().all(|p| p[2] <= 10) && points[median..].iter().all(|p| p[2] >= 10));
+
+        let split = points.partition_along(Axis::Y, 4);
+        assert_eq!(split, 3);
+        assert!(points[..split].iter().all(|p| p[1] < 4) && points[split..].iter().all(|p| p[1] >= 4));
+        assert_eq!(Points::<f32, 2>::new().split_at_axis_median(Axis::X), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac:")]
+    fn out_of_range_axis_panics() {
+        Points::<f32, 2>::new().scale_axis(Axis::Z, 2.0);
+    }
+
+}
@@ -0,0 +1,31 @@
+//!
+//! Everything needed for everyday use, in one glob import
+//!
+//! ```
+//! use axmac::prelude::*;
+//!
+//! let v = Coord3::new([1, 2, 3]);
+//! assert_eq!(v[ax!(z)], 3);
+//! assert_eq!(axr!(x..z), 0..2);
+//! assert_eq!(Axis::Y.as_usize(), Y::INDEX);
+//! ```
+//!
+
+pub use crate::{assert_ax_in_bounds, assert_coord_eq, assert_same_axes, assert_shape_eq, ax, ax_concat, ax_const, axi, axm, axr, axs, buffer_for, cell_hash, component_iter, conv_out_len, conv_out_shape, debug_ax_assert, define_axes, delta, epsilon, euler, fixed_index, flip_index, for_axes, halton, has_axes, iter_box, m_at, m_view, max_axis, mc_corner, mc_edge, min_axis, multi_slice, ndindex, pack_key, pad_shape, page_of, pp_index, roi, rot90_index, rules, s_ax, screen_to_world, set_swz, slice_by_name, spacing, splat_ax, split_axes_mut, stencil_offsets, swz, tiled_ndindex, toggle_axis, unit, unpack_key, window_coords, window_origin, with_axis, world_to_screen};
+
+pub use crate::marker::{AxisMarker, ConstAxis, DynAxis, W, X, Y, Z};
+pub use crate::{Aabb, Axis, AxisMap, AxisPerm, AxisSet, Coord, Coord2, Coord3, Coord4, Roi, Shape, SignedAxis};
+pub use crate::{HalfOpen, MathBackend, Scalar, SignedScalar};
+
+#[cfg(feature = "input")]
+pub use crate::GamepadAxis;
+#[cfg(feature = "motion")]
+pub use crate::MotionAxis;
+#[cfg(feature = "rgba")]
+pub use crate::channel_range;
+#[cfg(feature = "spectral")]
+pub use crate::{bin_freq, rfft_len};
+#[cfg(feature = "alloc")]
+pub use crate::soa_struct;
+#[cfg(feature = "alloc")]
+pub use crate::Points;
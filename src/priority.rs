@@ -0,0 +1,183 @@
+//!
+//! Reusable traversal orders, ranking the axes from the one varying fastest to the one varying slowest
+//!
+
+use core::cmp::Ordering;
+
+use crate::{AxisPerm, Shape};
+
+
+///
+/// The order in which a traversal steps through the axes, starting with the innermost
+///
+/// The axis ranked first varies fastest, as the innermost of a set of nested loops, and the axis ranked
+/// last varies slowest. [`ndindex!`], [`NdIndex::with_priority`](crate::NdIndex::with_priority) and
+/// [`copy_region_by`](crate::copy_region_by) take one, so a loop order chosen once can be shared by every
+/// traversal of a buffer
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{AxisPriority, Shape};
+///
+/// // A volume stored with z contiguous and x outermost
+/// let volume = Shape::col_major([4, 4, 4]);
+/// let order = AxisPriority::contiguous_first(&volume);
+/// assert_eq!(order.innermost(), ax!(z));
+/// assert_eq!(order.outer_to_inner(), axs![x, y, z]);
+///
+/// let mut axes = axs![x, z, y];
+/// order.sort_axes(&mut axes);
+/// assert_eq!(axes, axs![z, y, x]);
+/// # }
+/// ```
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AxisPriority<const N: usize>(AxisPerm<N>);
+
+impl<const N: usize> AxisPriority<N> {
+
+    ///
+    /// The order varying x fastest, then y, z and w, matching [`Shape::new`]
+    ///
+    pub const X_FASTEST: Self = Self(AxisPerm::IDENTITY);
+
+    ///
+    /// The order varying the last axis fastest and x slowest, matching [`Shape::col_major`]
+    ///
+    pub const LAST_FASTEST: Self = Self(AxisPerm::IDENTITY.reversed());
+
+    ///
+    /// Creates the order ranking the axes as listed, innermost first
+    ///
+    /// # Panics
+    ///
+    /// If `axes` is not a permutation of `0..N`, which fails to compile in const contexts
+    ///
+    pub const fn new(axes: [usize; N]) -> Self {
+        Self(AxisPerm::new(axes))
+    }
+
+    ///
+    /// Creates the order ranking the axes as a permutation lists them, innermost first
+    ///
+    pub const fn from_perm(perm: AxisPerm<N>) -> Self {
+        Self(perm)
+    }
+
+    ///
+    /// Creates the order visiting a shape front to back through memory, the axis of smallest stride first
+    ///
+    /// Axes of equal stride rank the lower axis first, as [`Shape::axis_order`] does in reverse
+    ///
+    pub fn contiguous_first(shape: &Shape<N>) -> Self {
+        let mut axes = shape.axis_order();
+        axes.reverse();
+        Self(AxisPerm::new(axes))
+    }
+
+    ///
+    /// Returns the permutation listing the axes innermost first
+    ///
+    pub const fn perm(&self) -> &AxisPerm<N> {
+        &self.0
+    }
+
+    ///
+    /// Returns the axes innermost first
+    ///
+    pub const fn inner_to_outer(&self) -> [usize; N] {
+        *self.0.as_array()
+    }
+
+    ///
+    /// Returns the axes outermost first, the order [`view`](crate::view) takes
+    ///
+    pub const fn outer_to_inner(&self) -> [usize; N] {
+        *self.0.reversed().as_array()
+    }
+
+    ///
+    /// Returns the axis varying fastest
+    ///
+    /// # Panics
+    ///
+    /// If `N` is zero
+    ///
+    pub const fn innermost(&self) -> usize {
+        self.0.get(0)
+    }
+
+    ///
+    /// Returns the axis varying slowest
+    ///
+    /// # Panics
+    ///
+    /// If `N` is zero
+    ///
+    pub const fn outermost(&self) -> usize {
+        self.0.get(N - 1)
+    }
+
+    ///
+    /// Returns the rank of `axis`, zero for the innermost
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not less than `N`
+    ///
+    pub const fn rank(&self, axis: usize) -> usize {
+        self.0.inverse().get(axis)
+    }
+
+    ///
+    /// Compares two axes by rank, the inner one ordering first
+    ///
+    pub fn cmp_axes(&self, a: usize, b: usize) -> Ordering {
+        self.rank(a).cmp(&self.rank(b))
+    }
+
+    ///
+    /// Sorts a list of axes innermost first
+    ///
+    /// # Panics
+    ///
+    /// If any axis is not less than `N`
+    ///
+    pub fn sort_axes(&self, axes: &mut [usize]) {
+        let ranks = self.0.inverse();
+        axes.sort_unstable_by_key(|&axis| ranks.get(axis));
+    }
+
+}
+
+impl<const N: usize> From<AxisPerm<N>> for AxisPriority<N> {
+    fn from(perm: AxisPerm<N>) -> Self {
+        Self(perm)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn presets_rank_axes() {
+        assert_eq!(AxisPriority::<3>::X_FASTEST.inner_to_outer(), [0, 1, 2]);
+        assert_eq!(AxisPriority::<3>::LAST_FASTEST.inner_to_outer(), [2, 1, 0]);
+        assert_eq!(AxisPriority::<3>::LAST_FASTEST.rank(0), 2);
+        assert_eq!(AxisPriority::new([1, 2, 0]).cmp_axes(0, 2), Ordering::Greater);
+        assert_eq!(AxisPriority::<4>::default(), AxisPriority::X_FASTEST);
+    }
+
+    #[test]
+    fn strides_decide_contiguous_first() {
+        let image = Shape::with_strides([3, 640, 480], [1, 3, 1920]);
+        assert_eq!(AxisPriority::contiguous_first(&image).inner_to_outer(), [0, 1, 2]);
+        let flipped = Shape::with_strides([4, 4], [4, 1]);
+        assert_eq!(AxisPriority::contiguous_first(&flipped).outermost(), 0);
+    }
+
+}
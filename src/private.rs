@@ -0,0 +1,953 @@
+//!
+//! Support items for macro expansions, not part of the public API
+//!
+
+#[cfg(feature = "alloc")]
+pub use alloc::vec::Vec;
+
+
+///
+/// Returns whether `axes` holds every index `0..axes.len()` exactly once, in any order
+///
+pub const fn axes_are_permutation(axes: &[usize]) -> bool {
+    let mut i = 0;
+    while i < axes.len() {
+        if axes[i] >= axes.len() {
+            return false;
+        }
+        let mut j = i + 1;
+        while j < axes.len() {
+            if axes[i] == axes[j] {
+                return false;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+///
+/// Returns whether `axes` is exactly `0..axes.len()` in ascending order
+///
+pub const fn axes_are_in_order(axes: &[usize]) -> bool {
+    let mut i = 0;
+    while i < axes.len() {
+        if axes[i] != i {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+
+///
+/// Axis arguments checked by `debug_ax_assert!`
+///
+pub trait AxisBounds: core::fmt::Debug {
+    /// Returns whether every index referred to lies below `count`
+    fn within(&self, count: usize) -> bool;
+}
+
+impl AxisBounds for usize {
+    fn within(&self, count: usize) -> bool {
+        *self < count
+    }
+}
+
+impl AxisBounds for core::ops::Range<usize> {
+    fn within(&self, count: usize) -> bool {
+        self.start <= count && self.end <= count
+    }
+}
+
+impl AxisBounds for core::ops::RangeInclusive<usize> {
+    fn within(&self, count: usize) -> bool {
+        *self.start() <= count && *self.end() < count
+    }
+}
+
+impl AxisBounds for core::ops::RangeFrom<usize> {
+    fn within(&self, count: usize) -> bool {
+        self.start <= count
+    }
+}
+
+impl AxisBounds for core::ops::RangeTo<usize> {
+    fn within(&self, count: usize) -> bool {
+        self.end <= count
+    }
+}
+
+impl AxisBounds for core::ops::RangeToInclusive<usize> {
+    fn within(&self, count: usize) -> bool {
+        self.end < count
+    }
+}
+
+impl AxisBounds for core::ops::RangeFull {
+    fn within(&self, _count: usize) -> bool {
+        true
+    }
+}
+
+impl<const N: usize> AxisBounds for [usize; N] {
+    fn within(&self, count: usize) -> bool {
+        self.iter().all(|axis| *axis < count)
+    }
+}
+
+
+///
+/// Identity on `usize`, fixing the type of indices given to `define_axes!`
+///
+pub const fn axis_index(index: usize) -> usize {
+    index
+}
+
+
+///
+/// Returns how many times `index` occurs in `indices`
+///
+pub const fn count_index(index: usize, indices: &[usize]) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < indices.len() {
+        if indices[i] == index {
+            count += 1;
+        }
+        i += 1;
+    }
+    count
+}
+
+
+///
+/// Returns how many times `name` occurs in `names`
+///
+pub const fn count_name(name: &str, names: &[&str]) -> usize {
+    let mut count = 0;
+    let mut i = 0;
+    while i < names.len() {
+        if str_eq(name, names[i]) {
+            count += 1;
+        }
+        i += 1;
+    }
+    count
+}
+
+
+const fn str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+
+///
+/// Returns the `N` consecutive indices starting from `start`
+///
+pub const fn axis_range<const N: usize>(start: usize) -> [usize; N] {
+    let mut axes = [0; N];
+    let mut i = 0;
+    while i < N {
+        axes[i] = start + i;
+        i += 1;
+    }
+    axes
+}
+
+
+///
+/// Returns the flat index of `coord` in a densely packed shape, panicking if it lies outside
+///
+pub const fn flat_index<const N: usize>(coord: [usize; N], extents: [usize; N], col_major: bool) -> usize {
+    let shape = if col_major { crate::Shape::col_major(extents) } else { crate::Shape::new(extents) };
+    match shape.flat_index(&coord) {
+        Some(index) => index,
+        None => panic!("axmac: axi! coordinate lies outside the shape"),
+    }
+}
+
+
+///
+/// Returns `extents` with the one at `axis` replaced by the number of bins of a real FFT along it
+///
+pub const fn rfft_extents<const N: usize>(mut extents: [usize; N], axis: usize) -> [usize; N] {
+    assert!(axis < N, "axmac: rfft_len! names an axis past the last dimension");
+    extents[axis] = extents[axis] / 2 + 1;
+    extents
+}
+
+
+///
+/// Returns the [`Axis`](crate::Axis) `ax!` maps to `index`, panicking past _w_
+///
+pub const fn axis(index: usize) -> crate::Axis {
+    match crate::Axis::from_usize(index) {
+        Some(axis) => axis,
+        None => panic!("axmac: only x, y, z and w have an Axis"),
+    }
+}
+
+
+///
+/// Fixed-length values whose components can be swizzled
+///
+pub trait Components {
+    /// The type of every component
+    type Item: Copy;
+    /// The number of components
+    const LEN: usize;
+    /// Returns the components in axis order
+    fn components(&self) -> &[Self::Item];
+}
+
+impl<T: Copy, const N: usize> Components for [T; N] {
+    type Item = T;
+    const LEN: usize = N;
+
+    fn components(&self) -> &[T] {
+        self
+    }
+}
+
+impl<T: Copy, const N: usize> Components for crate::Coord<T, N> {
+    type Item = T;
+    const LEN: usize = N;
+
+    fn components(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T: Copy, const N: usize> Components for crate::AxisMap<T, N> {
+    type Item = T;
+    const LEN: usize = N;
+
+    fn components(&self) -> &[T] {
+        self.as_array()
+    }
+}
+
+impl<V: Components> Components for &V {
+    type Item = V::Item;
+    const LEN: usize = V::LEN;
+
+    fn components(&self) -> &[V::Item] {
+        (**self).components()
+    }
+}
+
+impl<V: Components> Components for &mut V {
+    type Item = V::Item;
+    const LEN: usize = V::LEN;
+
+    fn components(&self) -> &[V::Item] {
+        (**self).components()
+    }
+}
+
+
+///
+/// Fixed-length values whose components can be written through a swizzle
+///
+pub trait ComponentsMut: Components {
+    /// Returns the components in axis order
+    fn components_mut(&mut self) -> &mut [Self::Item];
+}
+
+impl<T: Copy, const N: usize> ComponentsMut for [T; N] {
+    fn components_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+impl<T: Copy, const N: usize> ComponentsMut for crate::Coord<T, N> {
+    fn components_mut(&mut self) -> &mut [T] {
+        &mut self.0
+    }
+}
+
+impl<T: Copy, const N: usize> ComponentsMut for crate::AxisMap<T, N> {
+    fn components_mut(&mut self) -> &mut [T] {
+        self.as_mut_array()
+    }
+}
+
+impl<V: ComponentsMut> ComponentsMut for &mut V {
+    fn components_mut(&mut self) -> &mut [V::Item] {
+        (**self).components_mut()
+    }
+}
+
+
+///
+/// Returns whether no index occurs twice in `indices`
+///
+pub const fn indices_are_unique(indices: &[usize]) -> bool {
+    let mut i = 0;
+    while i < indices.len() {
+        if count_index(indices[i], indices) > 1 {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+
+///
+/// Returns the largest of `indices`, or zero if there are none
+///
+pub const fn max_index(indices: &[usize]) -> usize {
+    let mut max = 0;
+    let mut i = 0;
+    while i < indices.len() {
+        if indices[i] > max {
+            max = indices[i];
+        }
+        i += 1;
+    }
+    max
+}
+
+
+///
+/// Swizzles with the largest index `MAX` known at compile time, so it can be checked against the length
+/// of the input
+///
+pub struct Swizzle<const MAX: usize, const M: usize>;
+
+impl<const MAX: usize, const M: usize> Swizzle<MAX, M> {
+
+    ///
+    /// Gathers the components of `v` at `indices`, failing to compile if `MAX` lies past its last component
+    ///
+    pub fn gather<V: Components>(v: &V, indices: [usize; M]) -> [V::Item; M] {
+        const { assert!(MAX < V::LEN, "axmac: swizzle names an axis past the last component of its input") };
+        let components = v.components();
+        core::array::from_fn(|i| components[indices[i]])
+    }
+
+    ///
+    /// Writes the components of `src` to `dst` at `indices`, failing to compile if `MAX` lies past the last
+    /// component of `dst` or `src` does not hold exactly `M` components
+    ///
+    pub fn scatter<D: ComponentsMut, S: Components<Item = D::Item>>(dst: &mut D, indices: [usize; M], src: &S) {
+        const { assert!(MAX < D::LEN, "axmac: swizzle names an axis past the last component of its input") };
+        const { assert!(S::LEN == M, "axmac: a swizzle write needs exactly one source component per axis named") };
+        let components = dst.components_mut();
+        for (&i, &v) in indices.iter().zip(src.components()) {
+            components[i] = v;
+        }
+    }
+
+    ///
+    /// Returns a copy of `v` with every component set to the one at `MAX`
+    ///
+    pub fn splat<V: ComponentsMut + Copy>(v: &V) -> V {
+        const { assert!(MAX < V::LEN, "axmac: swizzle names an axis past the last component of its input") };
+        let mut out = *v;
+        let value = v.components()[MAX];
+        out.components_mut().fill(value);
+        out
+    }
+
+    ///
+    /// Returns `M` copies of the component of `v` at `MAX`
+    ///
+    pub fn splat_to<V: Components>(v: &V) -> [V::Item; M] {
+        const { assert!(MAX < V::LEN, "axmac: swizzle names an axis past the last component of its input") };
+        [v.components()[MAX]; M]
+    }
+
+}
+
+
+///
+/// Per dimension slices with the largest named axis `MAX` known at compile time, so it can be checked
+/// against the number of dimensions
+///
+pub struct AxisSlices<const MAX: usize>;
+
+impl<const MAX: usize> AxisSlices<MAX> {
+
+    ///
+    /// Returns `N` slices, each whole unless named in `named`, failing to compile if `MAX` lies past the
+    /// last dimension
+    ///
+    pub fn build<const N: usize, const M: usize>(named: [(usize, crate::AxisSlice); M]) -> [crate::AxisSlice; N] {
+        const { assert!(M == 0 || MAX < N, "axmac: s_ax! names an axis past the last dimension") };
+        let mut slices = [crate::AxisSlice::FULL; N];
+        for (axis, slice) in named {
+            slices[axis] = slice;
+        }
+        slices
+    }
+
+}
+
+
+///
+/// Per axis padding with the largest named axis `MAX` known at compile time, so it can be checked against
+/// the number of dimensions
+///
+pub struct Pads<const MAX: usize>;
+
+impl<const MAX: usize> Pads<MAX> {
+
+    ///
+    /// Pads `shape` by the `(axis, before, after)` entries in `named`, failing to compile if `MAX` lies past
+    /// the last dimension
+    ///
+    pub fn apply<const N: usize, const M: usize>(shape: &crate::Shape<N>, named: [(usize, usize, usize); M]) -> crate::Padded<N> {
+        const { assert!(M == 0 || MAX < N, "axmac: pad_shape! names an axis past the last dimension") };
+        let (mut before, mut after) = ([0; N], [0; N]);
+        for (axis, b, a) in named {
+            before[axis] = b;
+            after[axis] = a;
+        }
+        shape.pad(before, after)
+    }
+
+}
+
+
+///
+/// Parameters of a convolution along one axis, filled in by name by `conv_out_len!`
+///
+#[derive(Clone, Copy, Debug)]
+pub struct ConvParams {
+    pub kernel: usize,
+    pub stride: usize,
+    pub pad: usize,
+    pub dilation: usize,
+}
+
+impl ConvParams {
+
+    ///
+    /// The defaults for parameters not given, with a kernel of zero standing in for a missing one
+    ///
+    pub const DEFAULT: Self = Self { kernel: 0, stride: 1, pad: 0, dilation: 1 };
+
+    ///
+    /// Returns the number of outputs along an axis of `len` inputs
+    ///
+    pub const fn out_len(self, len: usize) -> usize {
+        assert!(self.kernel > 0, "axmac: conv_out_len! needs a kernel of at least one");
+        assert!(self.stride > 0, "axmac: conv_out_len! needs a stride of at least one");
+        match (len + 2 * self.pad).checked_sub(self.dilation * (self.kernel - 1) + 1) {
+            Some(span) => span / self.stride + 1,
+            None => 0,
+        }
+    }
+
+    ///
+    /// Returns the input position of the first tap of the window producing output `out`
+    ///
+    pub const fn window_origin(self, out: usize) -> isize {
+        (out * self.stride) as isize - self.pad as isize
+    }
+
+}
+
+
+///
+/// Per axis convolutions with the largest named axis `MAX` known at compile time, so it can be checked
+/// against the number of dimensions
+///
+pub struct ConvAxes<const MAX: usize>;
+
+impl<const MAX: usize> ConvAxes<MAX> {
+
+    ///
+    /// Replaces the extents of the axes in `named` by their convolution outputs, failing to compile if
+    /// `MAX` lies past the last dimension
+    ///
+    pub fn apply<const N: usize, const M: usize>(mut extents: [usize; N], named: [(usize, ConvParams); M]) -> [usize; N] {
+        const { assert!(M == 0 || MAX < N, "axmac: conv_out_shape! names an axis past the last dimension") };
+        for (axis, params) in named {
+            extents[axis] = params.out_len(extents[axis]);
+        }
+        extents
+    }
+
+    ///
+    /// Returns the window producing the output at `out` from an input of `extents`, failing to compile if
+    /// `MAX` lies past the last dimension
+    ///
+    pub fn window<const N: usize, const M: usize>(out: [usize; N], extents: [usize; N], named: [(usize, ConvParams); M]) -> crate::Window<N> {
+        const { assert!(M == 0 || MAX < N, "axmac: window_coords! names an axis past the last dimension") };
+        let mut origin = out.map(|o| o as isize);
+        let (mut kernel, mut dilation) = ([1; N], [1; N]);
+        for (axis, params) in named {
+            assert!(params.kernel > 0, "axmac: window_coords! needs a kernel of at least one");
+            assert!(params.stride > 0, "axmac: window_coords! needs a stride of at least one");
+            origin[axis] = params.window_origin(out[axis]);
+            kernel[axis] = params.kernel;
+            dilation[axis] = params.dilation;
+        }
+        crate::Window::new(origin, kernel, dilation, extents)
+    }
+
+}
+
+
+///
+/// Rearranges slices given per axis into the order of the dimensions in `dims`
+///
+pub fn slices_in_order<const N: usize>(dims: &[usize; N], by_axis: [crate::AxisSlice; N]) -> [crate::AxisSlice; N] {
+    assert!(axes_are_permutation(dims), "axmac: slice_by_name! dimensions must hold each of the first N axes once");
+    dims.map(|axis| by_axis[axis])
+}
+
+
+///
+/// Unit vectors along an axis known at compile time
+///
+pub struct Unit<const AXIS: usize>;
+
+impl<const AXIS: usize> Unit<AXIS> {
+
+    ///
+    /// Returns the unit vector along `AXIS`, failing to compile if it lies past the end of the vector
+    ///
+    pub fn vector<T: crate::Scalar, const N: usize>() -> [T; N] {
+        const { assert!(AXIS < N, "axmac: a unit vector needs a component for its axis") };
+        core::array::from_fn(|i| if i == AXIS { T::ONE } else { T::ZERO })
+    }
+
+}
+
+
+///
+/// Iterates over every `stride`-th element of `buf`, starting from `offset`
+///
+pub fn strided<T>(buf: &[T], stride: usize, offset: usize) -> core::iter::StepBy<core::slice::Iter<'_, T>> {
+    assert!(offset < stride, "axmac: component_iter! axis must be less than the stride");
+    buf.get(offset..).unwrap_or_default().iter().step_by(stride)
+}
+
+///
+/// Iterates mutably over every `stride`-th element of `buf`, starting from `offset`
+///
+pub fn strided_mut<T>(buf: &mut [T], stride: usize, offset: usize) -> core::iter::StepBy<core::slice::IterMut<'_, T>> {
+    assert!(offset < stride, "axmac: component_iter! axis must be less than the stride");
+    buf.get_mut(offset..).unwrap_or_default().iter_mut().step_by(stride)
+}
+
+
+///
+/// Places coordinates given with their axis indices into a [`CellKey`](crate::CellKey)
+///
+pub fn cell_key<const N: usize>(named: [(usize, i64); N]) -> crate::CellKey<N> {
+    let mut cell = [0; N];
+    for (axis, coord) in named {
+        cell[axis] = coord;
+    }
+    crate::CellKey::new(cell)
+}
+
+
+///
+/// Packs `(value, bits)` fields into one key, the first in the most significant bits, panicking if a value
+/// does not fit in its width
+///
+pub fn pack_key<const N: usize>(fields: [(u64, u32); N]) -> u64 {
+    let mut key: u64 = 0;
+    for (value, bits) in fields {
+        assert!(bits >= 64 || value >> bits == 0, "axmac: pack_key! value does not fit in its width");
+        key = key.checked_shl(bits).unwrap_or(0) | value;
+    }
+    key
+}
+
+///
+/// Returns the axis index of the first `(axis, bits)` field whose bits run past the end of a `u64`, or
+/// `usize::MAX` if every field fits
+///
+pub const fn key_overflow(fields: &[(usize, u32)]) -> usize {
+    let mut used: u64 = 0;
+    let mut i = 0;
+    while i < fields.len() {
+        used += fields[i].1 as u64;
+        if used > 64 {
+            return fields[i].0;
+        }
+        i += 1;
+    }
+    usize::MAX
+}
+
+///
+/// Returns whether `value` can be stored in `bits` bits
+///
+pub const fn fits_in_bits(value: u128, bits: u32) -> bool {
+    bits >= 128 || value >> bits == 0
+}
+
+///
+/// Splits a key packed by [`pack_key`] into values placed by the axis index given with each width
+///
+pub fn unpack_key<const N: usize>(mut key: u64, fields: [(usize, u32); N]) -> [u64; N] {
+    let mut values = [0; N];
+    for &(axis, bits) in fields.iter().rev() {
+        let mask = 1u64.checked_shl(bits).map_or(u64::MAX, |bit| bit - 1);
+        values[axis] = key & mask;
+        key = key.checked_shr(bits).unwrap_or(0);
+    }
+    values
+}
+
+
+///
+/// The extents a coordinate is checked against by `assert_ax_in_bounds!`
+///
+pub trait Extents<const N: usize> {
+    /// Returns the number of positions along every axis
+    fn extents(&self) -> [usize; N];
+}
+
+impl<const N: usize> Extents<N> for [usize; N] {
+    fn extents(&self) -> [usize; N] {
+        *self
+    }
+}
+
+impl<const N: usize> Extents<N> for crate::Shape<N> {
+    fn extents(&self) -> [usize; N] {
+        *crate::Shape::extents(self)
+    }
+}
+
+impl<const N: usize, E: Extents<N>> Extents<N> for &E {
+    fn extents(&self) -> [usize; N] {
+        (**self).extents()
+    }
+}
+
+///
+/// Panics with every axis along which `left` and `right` differ, for `assert_coord_eq!`
+///
+#[track_caller]
+pub fn assert_coord_eq<L, R>(left: &L, right: &R, message: Option<core::fmt::Arguments<'_>>)
+where
+    L: Components,
+    R: Components<Item = L::Item>,
+    L::Item: PartialEq + core::fmt::Debug,
+{
+    const { assert!(L::LEN == R::LEN, "axmac: assert_coord_eq! compares coordinates of different lengths") };
+    let (left, right) = (left.components(), right.components());
+    if left != right {
+        let differ = axes_where(|f, first| {
+            for (axis, (l, r)) in left.iter().zip(right).enumerate().filter(|(_, (l, r))| l != r) {
+                write_axis(f, axis, first)?;
+                write!(f, " ({:?} != {:?})", l, r)?;
+            }
+            Ok(())
+        });
+        panic!("axmac: coordinates differ along {}{}\n  left: {:?}\n right: {:?}", differ, Note(message), left, right);
+    }
+}
+
+///
+/// Panics with every axis along which the extent, stride or direction of two shapes differ, for
+/// `assert_shape_eq!`
+///
+#[track_caller]
+pub fn assert_shape_eq<const N: usize>(left: &crate::Shape<N>, right: &crate::Shape<N>, message: Option<core::fmt::Arguments<'_>>) {
+    if left != right {
+        let (l, r) = (left.describe(), right.describe());
+        let differ = axes_where(|f, first| {
+            for axis in 0..N {
+                let extent = (l.extents[axis], r.extents[axis]);
+                let stride = (l.strides[axis], r.strides[axis]);
+                let reversed = (l.reversed[axis], r.reversed[axis]);
+                if extent.0 == extent.1 && stride.0 == stride.1 && reversed.0 == reversed.1 {
+                    continue;
+                }
+                write_axis(f, axis, first)?;
+                let mut sep = " (";
+                if extent.0 != extent.1 {
+                    write!(f, "{}extent {} != {}", sep, extent.0, extent.1)?;
+                    sep = ", ";
+                }
+                if stride.0 != stride.1 {
+                    write!(f, "{}stride {} != {}", sep, stride.0, stride.1)?;
+                    sep = ", ";
+                }
+                if reversed.0 != reversed.1 {
+                    write!(f, "{}reversed {} != {}", sep, reversed.0, reversed.1)?;
+                }
+                f.write_str(")")?;
+            }
+            Ok(())
+        });
+        panic!("axmac: shapes differ along {}{}\n  left: {}\n right: {}", differ, Note(message), l, r);
+    }
+}
+
+///
+/// Panics with every axis along which `coord` lies outside `extents`, for `assert_ax_in_bounds!`
+///
+#[track_caller]
+pub fn assert_in_bounds<C, E, const N: usize>(coord: &C, extents: &E, message: Option<core::fmt::Arguments<'_>>)
+where
+    C: Components<Item = usize>,
+    E: Extents<N>,
+{
+    const { assert!(C::LEN == N, "axmac: assert_ax_in_bounds! checks a coordinate of the wrong length") };
+    let (coord, extents) = (coord.components(), extents.extents());
+    if coord.iter().zip(&extents).any(|(c, e)| c >= e) {
+        let outside = axes_where(|f, first| {
+            for (axis, (c, e)) in coord.iter().zip(&extents).enumerate().filter(|(_, (c, e))| c >= e) {
+                write_axis(f, axis, first)?;
+                write!(f, " ({} is not below {})", c, e)?;
+            }
+            Ok(())
+        });
+        panic!("axmac: {:?} is out of bounds along {}{}\n  extents: {:?}", coord, outside, Note(message), extents);
+    }
+}
+
+// Writes the name of `axis`, or its index past the last named axis, after a comma unless it is `first`
+fn write_axis(f: &mut core::fmt::Formatter<'_>, axis: usize, first: &mut bool) -> core::fmt::Result {
+    if !core::mem::take(first) {
+        f.write_str(", ")?;
+    }
+    match crate::Axis::from_usize(axis).map(crate::Axis::name) {
+        Some(name) => f.write_str(name),
+        None => write!(f, "axis {}", axis),
+    }
+}
+
+// Displays the axes an assertion failed along, written by the closure
+struct AxesWhere<F>(F);
+
+fn axes_where<F: Fn(&mut core::fmt::Formatter<'_>, &mut bool) -> core::fmt::Result>(write: F) -> AxesWhere<F> {
+    AxesWhere(write)
+}
+
+impl<F: Fn(&mut core::fmt::Formatter<'_>, &mut bool) -> core::fmt::Result> core::fmt::Display for AxesWhere<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        (self.0)(f, &mut true)
+    }
+}
+
+// Displays the message given to an assertion after a colon, or nothing
+struct Note<'a>(Option<core::fmt::Arguments<'a>>);
+
+impl core::fmt::Display for Note<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.0 {
+            Some(message) => write!(f, ": {}", message),
+            None => Ok(()),
+        }
+    }
+}
+
+
+///
+/// Fails to compile unless `A` and `B` have the same number of axes, for `assert_same_axes!`
+///
+pub const fn same_axes<A: crate::HasAxes + ?Sized, B: crate::HasAxes + ?Sized>(_: &A, _: &B) {
+    const { assert!(A::DIM == B::DIM, "axmac: assert_same_axes! was given values with different numbers of axes") };
+}
+
+
+///
+/// The scale and offset of one axis for `world_to_screen!` and `screen_to_world!`, defaulting to a scale
+/// of one and no offset
+///
+#[derive(Clone, Copy)]
+pub struct PanZoom<T> {
+    pub scale: T,
+    pub offset: T,
+}
+
+impl<T: crate::Scalar> PanZoom<T> {
+
+    ///
+    /// The parameters for fields not given
+    ///
+    pub const DEFAULT: Self = Self { scale: T::ONE, offset: T::ZERO };
+
+}
+
+
+///
+/// Applies the per axis transforms of `world_to_screen!` and `screen_to_world!`, with `MAX` the highest axis
+/// named
+///
+pub struct PanZoomAxes<const MAX: usize>;
+
+impl<const MAX: usize> PanZoomAxes<MAX> {
+
+    ///
+    /// Maps `world` to `world * scale + offset` along every axis in `named`, failing to compile if `MAX`
+    /// lies past the last axis of `V`
+    ///
+    pub fn to_screen<V: crate::HasAxes, const M: usize>(mut world: V, named: [(crate::Axis, PanZoom<V::Item>); M]) -> V
+    where
+        V::Item: crate::Scalar,
+    {
+        const { assert!(M == 0 || MAX < V::DIM, "axmac: world_to_screen! names an axis past the last of the value") };
+        for (axis, PanZoom { scale, offset }) in named {
+            if let Some(c) = world.get_axis_mut(axis) {
+                *c = *c * scale + offset;
+            }
+        }
+        world
+    }
+
+    ///
+    /// Maps `screen` to `(screen - offset) / scale` along every axis in `named`, failing to compile if `MAX`
+    /// lies past the last axis of `V`
+    ///
+    pub fn to_world<V: crate::HasAxes, const M: usize>(mut screen: V, named: [(crate::Axis, PanZoom<V::Item>); M]) -> V
+    where
+        V::Item: crate::Scalar,
+    {
+        const { assert!(M == 0 || MAX < V::DIM, "axmac: screen_to_world! names an axis past the last of the value") };
+        for (axis, PanZoom { scale, offset }) in named {
+            if let Some(c) = screen.get_axis_mut(axis) {
+                *c = (*c - offset) / scale;
+            }
+        }
+        screen
+    }
+
+}
+
+
+///
+/// Collects the sides named per axis for `mc_corner!` and `mc_edge!` into a cube coordinate, leaving axes
+/// not named at zero
+///
+pub const fn cube_sides<const M: usize>(named: [(usize, usize); M]) -> [usize; 3] {
+    let mut coord = [0; 3];
+    let mut i = 0;
+    while i < M {
+        coord[named[i].0] = named[i].1;
+        i += 1;
+    }
+    coord
+}
+
+
+///
+/// Places values named per axis at the index of their axis, leaving the others at `fill`
+///
+pub const fn by_axis<T: Copy, const N: usize>(named: [(usize, T); N], fill: T) -> [T; N] {
+    let mut out = [fill; N];
+    let mut i = 0;
+    while i < N {
+        out[named[i].0] = named[i].1;
+        i += 1;
+    }
+    out
+}
+
+
+///
+/// Lists the offsets of the star stencil of `radius` for `stencil_offsets!`, axis by axis with the
+/// negative offset of each distance first
+///
+pub const fn star_offsets<const N: usize, const LEN: usize>(radius: usize) -> [[isize; N]; LEN] {
+    assert!(LEN == 2 * N * radius, "axmac: a star stencil holds two offsets per axis and distance");
+    let mut out = [[0; N]; LEN];
+    let mut i = 0;
+    let mut axis = 0;
+    while axis < N {
+        let mut d = 1;
+        while d <= radius {
+            out[i][axis] = -(d as isize);
+            out[i + 1][axis] = d as isize;
+            i += 2;
+            d += 1;
+        }
+        axis += 1;
+    }
+    out
+}
+
+///
+/// Returns the number of offsets in the box stencil of `radius` over `n` axes, every position of the box
+/// but its centre
+///
+pub const fn box_len(n: usize, radius: usize) -> usize {
+    (2 * radius + 1).pow(n as u32) - 1
+}
+
+///
+/// Lists the offsets of the box stencil of `radius` for `stencil_offsets!`, with the x axis varying fastest
+/// and the centre left out
+///
+pub const fn box_offsets<const N: usize, const LEN: usize>(radius: usize) -> [[isize; N]; LEN] {
+    assert!(LEN == box_len(N, radius), "axmac: a box stencil holds every position of its box but the centre");
+    let mut out = [[0; N]; LEN];
+    let side = 2 * radius + 1;
+    let mut cell = 0;
+    let mut i = 0;
+    while i < LEN {
+        // Skipping the middle cell of the box, which has no offset along any axis
+        let k = if cell * 2 + 1 == side.pow(N as u32) { cell + 1 } else { cell };
+        let mut rest = k;
+        let mut axis = 0;
+        while axis < N {
+            out[i][axis] = (rest % side) as isize - radius as isize;
+            rest /= side;
+            axis += 1;
+        }
+        cell = k + 1;
+        i += 1;
+    }
+    out
+}
+
+
+///
+/// Places the coordinates named per axis for `fixed_index!` into a four axis coordinate, leaving the axes
+/// not named at zero
+///
+pub const fn fixed_coord<const M: usize>(named: [(usize, usize); M]) -> [usize; 4] {
+    let mut coord = [0; 4];
+    let mut i = 0;
+    while i < M {
+        assert!(named[i].0 < 4, "axmac: FixedShape has at most four axes");
+        coord[named[i].0] = named[i].1;
+        i += 1;
+    }
+    coord
+}
+
+
+///
+/// Borrows the elements at distinct `indices` of `slice` mutably all at once, for `split_axes_mut!`
+///
+/// # Panics
+///
+/// If an index is not less than the length of `slice`
+///
+pub fn disjoint_mut<T, const M: usize>(slice: &mut [T], indices: [usize; M]) -> [&mut T; M] {
+    let mut slots: [Option<&mut T>; M] = [const { None }; M];
+    for (i, item) in slice.iter_mut().enumerate() {
+        if let Some(k) = indices.iter().position(|&j| j == i) {
+            slots[k] = Some(item);
+        }
+    }
+    slots.map(|slot| slot.expect("axmac: axis is past the end of the container"))
+}
@@ -0,0 +1,190 @@
+//!
+//! Random jitter and sampling with control per named axis, for procedural generation
+//!
+//! [`RngCore`] mirrors the trait of the same name from the `rand_core` crate, so any generator can be
+//! plugged in by forwarding `next_u64`, and closures returning `u64` implement it directly. [`SplitMix64`]
+//! is a small seedable generator for when no other is at hand
+//!
+//! ```
+//! use axmac::random::{jitter_axes, sample_in_aabb, SplitMix64};
+//! use axmac::{Aabb, AxisMap};
+//!
+//! let mut rng = SplitMix64::new(7);
+//!
+//! // Scatter trees over a terrain tile, moving them up to 0.25 along the ground but never vertically
+//! let offset = jitter_axes(&mut rng, &AxisMap::new([0.25f32, 0.0, 0.25]));
+//! assert!(offset[1] == 0.0 && offset[0].abs() <= 0.25);
+//!
+//! let cell = sample_in_aabb(&mut rng, &Aabb::new([0, 0], [15, 15]));
+//! assert!(cell.iter().all(|&c| c <= 15));
+//! ```
+//!
+
+use crate::{Aabb, AxisMap, SignedScalar};
+
+
+///
+/// A source of uniformly distributed random bits
+///
+pub trait RngCore {
+    /// Returns the next 64 random bits
+    fn next_u64(&mut self) -> u64;
+}
+
+impl<F: FnMut() -> u64> RngCore for F {
+    fn next_u64(&mut self) -> u64 {
+        self()
+    }
+}
+
+
+///
+/// The SplitMix64 generator, fast and statistically sound but not cryptographically secure
+///
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+
+    ///
+    /// Creates a generator producing the sequence of `seed`
+    ///
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+}
+
+impl RngCore for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+
+///
+/// Numbers which can be drawn uniformly from a closed range
+///
+/// Implemented for every primitive integer up to 64 bits and every float
+///
+pub trait SampleUniform: Sized {
+    ///
+    /// Returns a value in `lo..=hi`, where `lo` must not be greater than `hi`
+    ///
+    /// Floats come from `lo..hi` in practice, the chance of drawing `hi` itself being negligible
+    ///
+    fn sample_inclusive<R: RngCore + ?Sized>(rng: &mut R, lo: Self, hi: Self) -> Self;
+}
+
+macro_rules! impl_sample_int {
+    ( $( $t:ty => $u:ty ),* ) => {
+        $(
+            impl SampleUniform for $t {
+                fn sample_inclusive<R: RngCore + ?Sized>(rng: &mut R, lo: Self, hi: Self) -> Self {
+                    let span = hi.wrapping_sub(lo) as $u as u64;
+                    let bits = rng.next_u64();
+                    // Scaling by the span with a widening multiply avoids the bias of a remainder
+                    let offset = match span.checked_add(1) {
+                        Some(len) => ((bits as u128 * len as u128) >> 64) as u64,
+                        None => bits,
+                    };
+                    lo.wrapping_add(offset as $t)
+                }
+            }
+        )*
+    };
+}
+
+impl_sample_int!(u8 => u8, u16 => u16, u32 => u32, u64 => u64, usize => usize);
+impl_sample_int!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, isize => usize);
+
+macro_rules! impl_sample_float {
+    ( $( $t:ty, $bits:literal );* ) => {
+        $(
+            impl SampleUniform for $t {
+                fn sample_inclusive<R: RngCore + ?Sized>(rng: &mut R, lo: Self, hi: Self) -> Self {
+                    // The top bits of the mantissa's width give a fraction in 0..1 with no rounding
+                    let unit = (rng.next_u64() >> (64 - $bits)) as $t / (1u64 << $bits) as $t;
+                    let v = lo + (hi - lo) * unit;
+                    if v > hi { hi } else { v }
+                }
+            }
+        )*
+    };
+}
+
+impl_sample_float!(f32, 24; f64, 53);
+
+
+///
+/// Returns an offset along every axis, uniform between minus and plus the amplitude of that axis
+///
+/// Axes with an amplitude of zero are left untouched, and negative amplitudes act as positive ones
+///
+pub fn jitter_axes<R, T, const N: usize>(rng: &mut R, amplitude: &AxisMap<T, N>) -> AxisMap<T, N>
+where
+    R: RngCore + ?Sized,
+    T: SampleUniform + SignedScalar,
+{
+    AxisMap::from_fn(|axis| {
+        let a = crate::math::abs(amplitude[axis]);
+        if a == T::ZERO { T::ZERO } else { T::sample_inclusive(rng, -a, a) }
+    })
+}
+
+///
+/// Returns a point drawn uniformly from inside a box, boundary included
+///
+/// # Panics
+///
+/// If the box is empty
+///
+pub fn sample_in_aabb<R, T, const N: usize>(rng: &mut R, aabb: &Aabb<T, N>) -> [T; N]
+where
+    R: RngCore + ?Sized,
+    T: SampleUniform + crate::Scalar,
+{
+    assert!(!aabb.is_empty(), "axmac: cannot sample from an empty box");
+    core::array::from_fn(|i| T::sample_inclusive(rng, aabb.min[i], aabb.max[i]))
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn integers_cover_their_range() {
+        let mut rng = SplitMix64::new(1);
+        let mut seen = [false; 7];
+        for _ in 0..200 {
+            let v = i8::sample_inclusive(&mut rng, -3, 3);
+            seen[(v + 3) as usize] = true;
+        }
+        assert!(seen.iter().all(|&s| s));
+        assert_eq!(u64::sample_inclusive(&mut || 42, 0, u64::MAX), 42);
+        assert_eq!(i64::sample_inclusive(&mut || u64::MAX, i64::MIN, i64::MAX), i64::MAX);
+    }
+
+    #[test]
+    fn samples_stay_inside() {
+        let mut rng = SplitMix64::new(99);
+        let aabb = Aabb::new([-1.0, 10.0, 5.0], [1.0, 10.0, 6.0]);
+        for _ in 0..100 {
+            let p = sample_in_aabb(&mut rng, &aabb);
+            assert!(aabb.contains(&p));
+            let j = jitter_axes(&mut rng, &AxisMap::new([-2, 0]));
+            assert!((-2..=2).contains(&j[0]) && j[1] == 0);
+        }
+        assert_eq!(f64::sample_inclusive(&mut || u64::MAX, 0.0, 1.0), 1.0 - f64::EPSILON / 2.0);
+    }
+
+}
@@ -0,0 +1,132 @@
+//!
+//! Ray casts against axis-aligned boxes
+//!
+
+use core::ops::Range;
+
+use crate::{Aabb, AxisMap};
+
+
+///
+/// The per-axis results of a slab test, as returned by [`ray_aabb`]
+///
+/// Along every axis the ray lies between the box's two bounding planes, its slab, for a range of the ray
+/// parameter `t`. The ray is inside the box where all of those ranges overlap
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct RaySlabs<const N: usize> {
+    /// The range of `t` over which the ray lies within each axis' slab, empty if it never does
+    pub slabs: AxisMap<Range<f32>, N>,
+}
+
+impl<const N: usize> RaySlabs<N> {
+
+    ///
+    /// Returns the range of `t` over which the whole line, extended backwards, lies inside the box
+    ///
+    pub fn overlap(&self) -> Range<f32> {
+        self.slabs.iter().fold(f32::NEG_INFINITY..f32::INFINITY, |acc, (_, slab)| {
+            acc.start.max(slab.start)..acc.end.min(slab.end)
+        })
+    }
+
+    ///
+    /// Returns the range of `t` at or after the origin over which the ray lies inside the box, or `None` if
+    /// it misses
+    ///
+    /// The range starts at zero when the origin is inside the box
+    ///
+    pub fn hit(&self) -> Option<Range<f32>> {
+        let overlap = self.overlap();
+        let hit = overlap.start.max(0.0)..overlap.end;
+        if hit.start <= hit.end { Some(hit) } else { None }
+    }
+
+    ///
+    /// Returns the axis whose slab the ray entered last, which is the axis of the face the ray enters the
+    /// box through
+    ///
+    pub fn entry_axis(&self) -> Option<usize> {
+        self.slabs
+            .iter()
+            .filter(|(_, slab)| slab.start.is_finite())
+            .max_by(|(_, a), (_, b)| a.start.total_cmp(&b.start))
+            .map(|(axis, _)| axis)
+    }
+
+}
+
+
+///
+/// Tests the ray from `origin` along `dir` against `aabb` one axis at a time
+///
+/// Components of `dir` need not be normalized, and may be zero for rays parallel to an axis
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{ray_aabb, Aabb};
+///
+/// let aabb = Aabb::new([1.0, -1.0], [3.0, 1.0]);
+/// let slabs = ray_aabb(&[0.0, 0.0], &[1.0, 0.0], &aabb);
+///
+/// assert_eq!(slabs.slabs[ax!(x)], 1.0..3.0);
+/// assert_eq!(slabs.slabs[ax!(y)], f32::NEG_INFINITY..f32::INFINITY);
+/// assert_eq!(slabs.hit(), Some(1.0..3.0));
+/// assert_eq!(slabs.entry_axis(), Some(ax!(x)));
+/// # }
+/// ```
+///
+pub fn ray_aabb<const N: usize>(origin: &[f32; N], dir: &[f32; N], aabb: &Aabb<f32, N>) -> RaySlabs<N> {
+    let slabs = AxisMap::from_fn(|axis| {
+        let (o, d) = (origin[axis], dir[axis]);
+        let (min, max) = (aabb.min[axis], aabb.max[axis]);
+        if d == 0.0 {
+            if min <= o && o <= max {
+                f32::NEG_INFINITY..f32::INFINITY
+            } else {
+                f32::INFINITY..f32::NEG_INFINITY
+            }
+        } else {
+            let (t0, t1) = ((min - o) / d, (max - o) / d);
+            t0.min(t1)..t0.max(t1)
+        }
+    });
+    RaySlabs { slabs }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn diagonal_hit_works() {
+        let aabb = Aabb::new([2.0, 2.0, 2.0], [4.0, 6.0, 4.0]);
+        let slabs = ray_aabb(&[0.0, 0.0, 0.0], &[1.0, 1.0, 1.0], &aabb);
+        assert_eq!(slabs.slabs[ax!(y)], 2.0..6.0);
+        assert_eq!(slabs.hit(), Some(2.0..4.0));
+
+        let backwards = ray_aabb(&[5.0, 5.0, 5.0], &[-1.0, 0.0, 0.0], &aabb);
+        assert_eq!(backwards.slabs[ax!(x)], 1.0..3.0);
+        assert_eq!(backwards.hit(), None);
+    }
+
+    #[test]
+    fn inside_and_behind_work() {
+        let aabb = Aabb::new([0.0, 0.0], [2.0, 2.0]);
+        assert_eq!(ray_aabb(&[1.0, 1.0], &[0.0, 2.0], &aabb).hit(), Some(0.0..0.5));
+        assert_eq!(ray_aabb(&[3.0, 1.0], &[1.0, 0.0], &aabb).hit(), None);
+        assert_eq!(ray_aabb(&[1.0, 3.0], &[1.0, 0.0], &aabb).hit(), None);
+    }
+
+    #[test]
+    fn entry_axis_works() {
+        let aabb = Aabb::new([0.0, 0.0], [2.0, 2.0]);
+        let slabs = ray_aabb(&[-1.0, -4.0], &[1.0, 2.0], &aabb);
+        assert_eq!(slabs.hit(), Some(2.0..3.0));
+        assert_eq!(slabs.entry_axis(), Some(ax!(y)));
+    }
+
+}
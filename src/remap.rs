@@ -0,0 +1,244 @@
+//!
+//! Conversions between axis conventions
+//!
+
+use core::ops::Neg;
+
+use crate::{Axis, Coord, SignedAxis, SignedScalar};
+
+
+///
+/// Reorders and negates the components of a vector to convert it between axis conventions
+///
+/// Component `i` of the output is taken from the input along `axes[i]`, negated when that direction is
+/// negative. Every conversion between Y-up and Z-up worlds, handedness or graphics API conventions is of
+/// this form, and the common ones are provided as constants
+///
+/// ```
+/// use axmac::{AxisRemap, Coord3, SignedAxis};
+///
+/// let up_in_y = [0.0, 1.0, 0.0];
+/// assert_eq!(AxisRemap::Y_UP_TO_Z_UP.apply(up_in_y), [0.0, 0.0, 1.0]);
+///
+/// let forward = Coord3::new([0.0, 0.0, -1.0]);
+/// assert_eq!(AxisRemap::Y_UP_TO_Z_UP.apply_coord(forward), Coord3::new([0.0, 1.0, 0.0]));
+///
+/// let custom = AxisRemap::new([SignedAxis::POS_Y, SignedAxis::NEG_X]);
+/// assert_eq!(custom.apply([1, 2]), [2, -1]);
+/// assert_eq!(custom.inverse().apply([2, -1]), [1, 2]);
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AxisRemap<const N: usize = 3> {
+    axes: [SignedAxis; N],
+}
+
+impl AxisRemap<3> {
+
+    ///
+    /// From right-handed Y-up, as used by OpenGL, glTF and Maya, to right-handed Z-up, as used by Blender
+    /// and most CAD tools
+    ///
+    /// The Y-up forward direction, negative z, becomes positive y
+    ///
+    pub const Y_UP_TO_Z_UP: Self = Self::new([SignedAxis::POS_X, SignedAxis::NEG_Z, SignedAxis::POS_Y]);
+
+    ///
+    /// The inverse of [`AxisRemap::Y_UP_TO_Z_UP`]
+    ///
+    pub const Z_UP_TO_Y_UP: Self = Self::new([SignedAxis::POS_X, SignedAxis::POS_Z, SignedAxis::NEG_Y]);
+
+    ///
+    /// Between right and left-handed coordinates by mirroring z, as between OpenGL and Direct3D world space
+    ///
+    /// The remap is its own inverse
+    ///
+    pub const FLIP_HANDEDNESS: Self = Self::new([SignedAxis::POS_X, SignedAxis::POS_Y, SignedAxis::NEG_Z]);
+
+    ///
+    /// Between OpenGL and Vulkan normalized device coordinates, in which y points up and down respectively
+    ///
+    /// The remap is its own inverse. The differing depth ranges are not an axis remap, and are left alone
+    ///
+    pub const GL_VULKAN_NDC: Self = Self::new([SignedAxis::POS_X, SignedAxis::NEG_Y, SignedAxis::POS_Z]);
+
+}
+
+impl<const N: usize> AxisRemap<N> {
+
+    ///
+    /// The remap leaving every component where it is
+    ///
+    pub const IDENTITY: Self = Self {
+        axes: {
+            let mut axes = [SignedAxis::POS_X; N];
+            let mut i = 0;
+            while i < N {
+                axes[i] = match Axis::from_usize(i) {
+                    Some(axis) => SignedAxis::pos(axis),
+                    None => panic!("axmac: AxisRemap supports at most 4 axes"),
+                };
+                i += 1;
+            }
+            axes
+        },
+    };
+
+    ///
+    /// Creates a remap taking output component `i` from the input along `axes[i]`
+    ///
+    /// # Panics
+    ///
+    /// If `axes` does not name every axis below `N` exactly once, which fails to compile in const contexts
+    ///
+    pub const fn new(axes: [SignedAxis; N]) -> Self {
+        let mut indices = [0; N];
+        let mut i = 0;
+        while i < N {
+            indices[i] = axes[i].axis().as_usize();
+            i += 1;
+        }
+        assert!(
+            crate::__private::axes_are_permutation(&indices),
+            "axmac: an AxisRemap must name every axis exactly once",
+        );
+        Self { axes }
+    }
+
+    ///
+    /// Returns the signed input axis of every output component
+    ///
+    pub const fn axes(&self) -> &[SignedAxis; N] {
+        &self.axes
+    }
+
+    ///
+    /// Returns the remap undoing this one
+    ///
+    pub const fn inverse(&self) -> Self {
+        let mut axes = self.axes;
+        let mut i = 0;
+        while i < N {
+            let from = self.axes[i];
+            axes[from.axis().as_usize()] = match Axis::from_usize(i) {
+                Some(axis) => SignedAxis::new(axis, from.is_negative()),
+                None => panic!("axmac: AxisRemap supports at most 4 axes"),
+            };
+            i += 1;
+        }
+        Self { axes }
+    }
+
+    ///
+    /// Returns the remap applying this one, then `next`
+    ///
+    pub const fn then(&self, next: &Self) -> Self {
+        let mut axes = self.axes;
+        let mut i = 0;
+        while i < N {
+            let via = next.axes[i];
+            let from = self.axes[via.axis().as_usize()];
+            axes[i] = SignedAxis::new(from.axis(), from.is_negative() != via.is_negative());
+            i += 1;
+        }
+        Self { axes }
+    }
+
+    ///
+    /// Converts the vector `v`
+    ///
+    pub fn apply<T: Copy + Neg<Output = T>>(&self, v: [T; N]) -> [T; N] {
+        core::array::from_fn(|i| {
+            let from = self.axes[i];
+            let c = v[from.axis().as_usize()];
+            if from.is_negative() { -c } else { c }
+        })
+    }
+
+    ///
+    /// Converts the vector `v`
+    ///
+    pub fn apply_coord<T: Copy + Neg<Output = T>>(&self, v: Coord<T, N>) -> Coord<T, N> {
+        Coord(self.apply(v.0))
+    }
+
+}
+
+impl<const N: usize> Default for AxisRemap<N> {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+
+///
+/// Returns the rotation or reflection matrix performing `remap`, as an array of rows
+///
+/// Multiplying the matrix by a column vector gives the same result as [`AxisRemap::apply`]. Every row and
+/// column holds a single `1` or `-1`, and the determinant is `-1` exactly when the remap changes
+/// handedness
+///
+/// ```
+/// use axmac::{basis_from, AxisRemap};
+///
+/// let m: [[f32; 3]; 3] = basis_from(&AxisRemap::Y_UP_TO_Z_UP);
+/// assert_eq!(m, [
+///     [1.0, 0.0,  0.0],
+///     [0.0, 0.0, -1.0],
+///     [0.0, 1.0,  0.0],
+/// ]);
+/// ```
+///
+pub fn basis_from<T: SignedScalar, const N: usize>(remap: &AxisRemap<N>) -> [[T; N]; N] {
+    core::array::from_fn(|row| {
+        let from = remap.axes()[row];
+        let one = if from.is_negative() { -T::ONE } else { T::ONE };
+        core::array::from_fn(|col| if col == from.axis().as_usize() { one } else { T::ZERO })
+    })
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn presets_round_trip() {
+        let v = [1, 2, 3];
+        assert_eq!(AxisRemap::Y_UP_TO_Z_UP.apply(v), [1, -3, 2]);
+        assert_eq!(AxisRemap::Z_UP_TO_Y_UP.apply(AxisRemap::Y_UP_TO_Z_UP.apply(v)), v);
+        assert_eq!(AxisRemap::Y_UP_TO_Z_UP.inverse(), AxisRemap::Z_UP_TO_Y_UP);
+        assert_eq!(AxisRemap::FLIP_HANDEDNESS.then(&AxisRemap::FLIP_HANDEDNESS), AxisRemap::IDENTITY);
+        assert_eq!(AxisRemap::GL_VULKAN_NDC.apply(v), [1, -2, 3]);
+    }
+
+    #[test]
+    fn then_matches_applying_twice() {
+        let a = AxisRemap::new([SignedAxis::NEG_Z, SignedAxis::POS_X, SignedAxis::NEG_Y]);
+        let b = AxisRemap::Y_UP_TO_Z_UP;
+        let v = [4, 5, 6];
+        assert_eq!(a.then(&b).apply(v), b.apply(a.apply(v)));
+        assert_eq!(a.then(&a.inverse()), AxisRemap::IDENTITY);
+    }
+
+    #[test]
+    fn basis_matches_apply() {
+        let remaps = [AxisRemap::Y_UP_TO_Z_UP, AxisRemap::FLIP_HANDEDNESS, AxisRemap::GL_VULKAN_NDC];
+        let v = [2, 3, 5];
+        for remap in remaps {
+            let m: [[i32; 3]; 3] = basis_from(&remap);
+            let product = m.map(|row| (0..3).map(|i| row[i] * v[i]).sum::<i32>());
+            assert_eq!(product, remap.apply(v));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac:")]
+    fn repeated_axes_panic() {
+        let axes = [SignedAxis::POS_X, SignedAxis::NEG_X];
+        let _ = AxisRemap::new(axes);
+    }
+
+}
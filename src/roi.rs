@@ -0,0 +1,248 @@
+//!
+//! Regions of interest tying a range to every axis
+//!
+
+use core::ops::{Bound, Range, RangeBounds};
+
+use crate::{NdIndex, Shape};
+
+
+///
+/// A rectangular region given as one half-open range per axis
+///
+/// Ranges may be unbounded above, in which case they extend to the end of whichever [`Shape`] the region
+/// is applied to. Build one with [`roi!`]
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Shape;
+///
+/// let shape = Shape::new([4, 3]);
+/// let roi = roi!(x: 1..3, y: 1..);
+///
+/// assert!(roi.contains(&[2, 100]));
+/// assert_eq!(roi.resolve(&shape), [1..3, 1..3]);
+///
+/// let offsets: Vec<usize> = roi.offsets_in(&shape).collect();
+/// assert_eq!(offsets, [5, 6, 9, 10]);
+/// # }
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Roi<const N: usize> {
+    start: [usize; N],
+    // usize::MAX stands in for an unbounded end
+    end: [usize; N],
+    #[cfg(feature = "debug-dims")]
+    pub(crate) labels: crate::dims::DimLabels<N>,
+}
+
+impl<const N: usize> Roi<N> {
+
+    ///
+    /// The region covering every position of any shape
+    ///
+    pub const FULL: Self = Self::new([0; N], [usize::MAX; N]);
+
+    ///
+    /// Creates a region from one bounded range per axis
+    ///
+    pub const fn new(start: [usize; N], end: [usize; N]) -> Self {
+        Self {
+            start,
+            end,
+            #[cfg(feature = "debug-dims")]
+            labels: crate::dims::DimLabels::NONE,
+        }
+    }
+
+    ///
+    /// Creates a region from one range of any kind per axis
+    ///
+    pub fn from_bounds<R: RangeBounds<usize>>(ranges: [R; N]) -> Self {
+        let mut roi = Self::FULL;
+        for (axis, range) in ranges.into_iter().enumerate() {
+            roi.set(axis, range);
+        }
+        roi
+    }
+
+    ///
+    /// Replaces the range of `axis`
+    ///
+    pub fn set<R: RangeBounds<usize>>(&mut self, axis: usize, range: R) {
+        self.start[axis] = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s.saturating_add(1),
+            Bound::Unbounded    => 0,
+        };
+        self.end[axis] = match range.end_bound() {
+            Bound::Included(&e) => e.saturating_add(1),
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded    => usize::MAX,
+        };
+    }
+
+    ///
+    /// Returns the start of every axis' range
+    ///
+    pub const fn start(&self) -> &[usize; N] {
+        &self.start
+    }
+
+    ///
+    /// Returns the range of `axis`, with `usize::MAX` as the end of an unbounded range
+    ///
+    pub const fn range(&self, axis: usize) -> Range<usize> {
+        self.start[axis]..self.end[axis]
+    }
+
+    ///
+    /// Returns whether `axis` extends to the end of any shape
+    ///
+    pub const fn is_unbounded(&self, axis: usize) -> bool {
+        self.end[axis] == usize::MAX
+    }
+
+    ///
+    /// Returns whether `coord` lies inside the region
+    ///
+    pub fn contains(&self, coord: &[usize; N]) -> bool {
+        (0..N).all(|i| self.start[i] <= coord[i] && coord[i] < self.end[i])
+    }
+
+    ///
+    /// Returns the region covered by both regions, which may be empty
+    ///
+    /// # Panics
+    ///
+    /// With the `debug-dims` feature, if the regions label an axis differently
+    ///
+    pub fn intersect(&self, other: &Self) -> Self {
+        Self {
+            start: core::array::from_fn(|i| self.start[i].max(other.start[i])),
+            end: core::array::from_fn(|i| self.end[i].min(other.end[i])),
+            #[cfg(feature = "debug-dims")]
+            labels: self.labels.merge(other.labels),
+        }
+    }
+
+    ///
+    /// Returns whether the region contains no positions, regardless of shape
+    ///
+    pub fn is_empty(&self) -> bool {
+        (0..N).any(|i| self.start[i] >= self.end[i])
+    }
+
+    ///
+    /// Returns the ranges of the region clipped to the extents of `shape`
+    ///
+    pub fn resolve(&self, shape: &Shape<N>) -> [Range<usize>; N] {
+        core::array::from_fn(|i| {
+            let end = self.end[i].min(shape.extent(i));
+            self.start[i].min(end)..end
+        })
+    }
+
+    ///
+    /// Returns the number of positions along every axis once clipped to `shape`
+    ///
+    pub fn extents_in(&self, shape: &Shape<N>) -> [usize; N] {
+        self.resolve(shape).map(|r| r.len())
+    }
+
+    ///
+    /// Iterates over the coordinates of the region clipped to `shape`, with the x axis varying fastest
+    ///
+    pub fn coords_in(&self, shape: &Shape<N>) -> NdIndex<usize, N> {
+        let ranges = self.resolve(shape);
+        NdIndex::new(ranges.clone().map(|r| r.start), ranges.map(|r| r.end))
+    }
+
+    ///
+    /// Iterates over the flat buffer offsets of the region clipped to `shape`
+    ///
+    pub fn offsets_in(&self, shape: &Shape<N>) -> impl Iterator<Item = usize> {
+        let shape = *shape;
+        self.coords_in(&shape).map(move |c| shape.flat_index_unchecked(&c))
+    }
+
+}
+
+impl<const N: usize> Default for Roi<N> {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+impl<const N: usize> From<[Range<usize>; N]> for Roi<N> {
+    fn from(ranges: [Range<usize>; N]) -> Self {
+        Self::from_bounds(ranges)
+    }
+}
+
+
+///
+/// Creates a [`Roi`] from one range per named axis
+///
+/// Ranges may be of any kind, including `..`, and can come from `axr!`. As with [`ndindex!`], the axes
+/// listed must be the first `N` axes, in any order
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let roi = roi!(y: 2..=4, x: .., z: axr!(x..y));
+/// assert_eq!(roi.range(ax!(y)), 2..5);
+/// assert!(roi.is_unbounded(ax!(x)));
+/// assert_eq!(roi.range(ax!(z)), 0..1);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! roi {
+
+    ( $( $axis:ident : $range:expr ),+ $(,)? ) => {{
+        const _: () = assert!(
+            $crate::__private::axes_are_permutation(&[ $( $crate::ax!($axis) ),+ ]),
+            "axmac: roi! must list each of the first N axes exactly once",
+        );
+
+        let mut roi = $crate::Roi::<{ [ $( $crate::ax!($axis) ),+ ].len() }>::FULL;
+        $( roi.set($crate::ax!($axis), $range); )+
+        roi
+    }};
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn intersect_works() {
+        let a = roi!(x: 0..10, y: 5..);
+        let b = roi!(x: 5..20, y: ..8);
+        let both = a.intersect(&b);
+        assert_eq!(both, Roi::new([5, 5], [10, 8]));
+        assert!(!both.is_empty());
+        assert!(a.intersect(&roi!(x: 10.., y: ..)).is_empty());
+    }
+
+    #[test]
+    fn resolve_clips() {
+        let shape = Shape::new([4, 4]);
+        assert_eq!(roi!(x: 2.., y: 6..9).resolve(&shape), [2..4, 4..4]);
+        assert_eq!(roi!(x: 2.., y: 6..9).offsets_in(&shape).count(), 0);
+        assert_eq!(Roi::FULL.extents_in(&shape), [4, 4]);
+    }
+
+    #[test]
+    fn offsets_follow_strides() {
+        let shape = Shape::col_major([3, 3]);
+        let offsets: std::vec::Vec<_> = roi!(x: 1..3, y: 0..=0).offsets_in(&shape).collect();
+        assert_eq!(offsets, [3, 6]);
+    }
+
+}
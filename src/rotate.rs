@@ -0,0 +1,132 @@
+//!
+//! Quarter turn rotations of 2-D grids
+//!
+//! Turns are clockwise as seen with the y axis pointing down the screen, the usual convention for images
+//! and tile maps. Negative turns rotate anticlockwise. Rotating by an odd number of turns swaps the
+//! extents of the x and y axes
+//!
+
+use crate::Shape;
+
+
+///
+/// Returns the extents of a grid of `extents` once rotated by `turns` quarter turns
+///
+pub const fn rot90_extents(extents: [usize; 2], turns: i32) -> [usize; 2] {
+    if turns.rem_euclid(2) == 0 { extents } else { [extents[ax!(y)], extents[ax!(x)]] }
+}
+
+///
+/// Returns where `coord` ends up once a grid of `extents` is rotated by `turns` quarter turns
+///
+pub const fn rot90_coord(coord: [usize; 2], extents: [usize; 2], turns: i32) -> [usize; 2] {
+    let [x, y] = coord;
+    let [w, h] = extents;
+    match turns.rem_euclid(4) {
+        0 => [x, y],
+        1 => [h - 1 - y, x],
+        2 => [w - 1 - x, h - 1 - y],
+        _ => [y, w - 1 - x],
+    }
+}
+
+///
+/// Writes `src` rotated by `turns` quarter turns into `dst`, returning the densely packed shape of `dst`
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{rotate90, Shape};
+///
+/// // a b c      d a
+/// // d e f  ->  e b
+/// //            f c
+/// let src = ['a', 'b', 'c', 'd', 'e', 'f'];
+/// let mut dst = [' '; 6];
+///
+/// let rotated = rotate90(&src, &Shape::new([3, 2]), 1, &mut dst);
+/// assert_eq!(rotated.extents(), &[2, 3]);
+/// assert_eq!(dst, ['d', 'a', 'e', 'b', 'f', 'c']);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If `src` is too short for `shape`, or `dst` is shorter than the number of positions in `shape`
+///
+pub fn rotate90<T: Copy>(src: &[T], shape: &Shape<2>, turns: i32, dst: &mut [T]) -> Shape<2> {
+    assert!(src.len() >= shape.required_len(), "axmac: source buffer is too short for its shape");
+    assert!(dst.len() >= shape.len(), "axmac: destination buffer is too short for the rotated grid");
+
+    let rotated = Shape::new(rot90_extents(*shape.extents(), turns));
+    for coord in shape.coords() {
+        let to = rot90_coord(coord, *shape.extents(), turns);
+        dst[rotated.flat_index_unchecked(&to)] = src[shape.flat_index_unchecked(&coord)];
+    }
+    rotated
+}
+
+
+///
+/// Computes where an `(x, y)` coordinate ends up once a 2-D [`Shape`] is rotated by quarter turns
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Shape;
+///
+/// let shape = Shape::new([640, 480]);
+///
+/// assert_eq!(rot90_index!((0, 0); shape; turns: 1), [479, 0]);
+/// assert_eq!(rot90_index!((0, 0); shape; turns: -1), [0, 639]);
+/// assert_eq!(rot90_index!((10, 20); shape; turns: 4), [10, 20]);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! rot90_index {
+
+    ( ( $x:expr, $y:expr ); $shape:expr; turns: $k:expr ) => {
+        $crate::rot90_coord([$x, $y], *$crate::Shape::extents(&$shape), $k)
+    };
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn four_turns_are_identity() {
+        let shape = Shape::new([3, 5]);
+        let src: [u8; 15] = core::array::from_fn(|i| i as u8);
+
+        let mut a = [0; 15];
+        let mut b = [0; 15];
+        let s = rotate90(&src, &shape, 1, &mut a);
+        let s = rotate90(&a, &s, 1, &mut b);
+        let s = rotate90(&b, &s, 1, &mut a);
+        let s = rotate90(&a, &s, 1, &mut b);
+        assert_eq!(s, shape);
+        assert_eq!(b, src);
+    }
+
+    #[test]
+    fn opposite_turns_cancel() {
+        let extents = [7, 2];
+        for coord in Shape::new(extents).coords() {
+            let turned = rot90_coord(coord, extents, 3);
+            assert_eq!(rot90_coord(turned, rot90_extents(extents, 3), -3), coord);
+        }
+    }
+
+    #[test]
+    fn half_turn_works() {
+        let src = [1, 2, 3, 4];
+        let mut dst = [0; 4];
+        rotate90(&src, &Shape::new([2, 2]), 2, &mut dst);
+        assert_eq!(dst, [4, 3, 2, 1]);
+    }
+
+}
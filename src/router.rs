@@ -0,0 +1,146 @@
+//!
+//! Routing per-axis events to per-axis handlers
+//!
+
+
+///
+/// Stores at most one handler per axis and calls the right one for each event
+///
+/// Handlers all share the type `F`, typically a function pointer or `&mut dyn FnMut`. Axes may be given as
+/// an [`Axis`](crate::Axis), the `usize` from `ax!`, or any of the other axis types converting into `usize`
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{Axis, AxisRouter};
+///
+/// let mut router = AxisRouter::<fn(f32) -> f32, 2>::new()
+///     .with(Axis::X, |v| v * 2.0)
+///     .with(ax!(y), |v| -v);
+///
+/// assert_eq!(router.dispatch(Axis::X, 1.5), Some(3.0));
+/// assert_eq!(router.dispatch(Axis::Y, 1.5), Some(-1.5));
+///
+/// router.remove(Axis::Y);
+/// assert_eq!(router.dispatch(Axis::Y, 1.5), None);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// All methods taking an `axis` panic if it is not less than `N`
+///
+#[derive(Clone, Debug)]
+pub struct AxisRouter<F, const N: usize> {
+    handlers: [Option<F>; N],
+}
+
+impl<F, const N: usize> AxisRouter<F, N> {
+
+    ///
+    /// Creates a router with no handlers
+    ///
+    pub const fn new() -> Self {
+        Self { handlers: [const { None }; N] }
+    }
+
+    ///
+    /// Adds the handler of `axis`, replacing any existing one
+    ///
+    pub fn with(mut self, axis: impl Into<usize>, handler: F) -> Self {
+        self.set(axis, handler);
+        self
+    }
+
+    ///
+    /// Sets the handler of `axis`, returning the one it replaced
+    ///
+    pub fn set(&mut self, axis: impl Into<usize>, handler: F) -> Option<F> {
+        self.handlers[axis.into()].replace(handler)
+    }
+
+    ///
+    /// Removes and returns the handler of `axis`
+    ///
+    pub fn remove(&mut self, axis: impl Into<usize>) -> Option<F> {
+        self.handlers[axis.into()].take()
+    }
+
+    ///
+    /// Returns the handler of `axis`
+    ///
+    pub fn handler(&self, axis: impl Into<usize>) -> Option<&F> {
+        self.handlers[axis.into()].as_ref()
+    }
+
+    ///
+    /// Returns the handler of `axis` mutably
+    ///
+    pub fn handler_mut(&mut self, axis: impl Into<usize>) -> Option<&mut F> {
+        self.handlers[axis.into()].as_mut()
+    }
+
+    ///
+    /// Calls the handler of `axis` with `args`, returning its result or `None` if there is no handler
+    ///
+    pub fn dispatch<A, R>(&mut self, axis: impl Into<usize>, args: A) -> Option<R>
+    where
+        F: FnMut(A) -> R,
+    {
+        self.handlers[axis.into()].as_mut().map(|handler| handler(args))
+    }
+
+    ///
+    /// Calls every handler with a copy of `args`, in axis order
+    ///
+    pub fn broadcast<A: Clone>(&mut self, args: A)
+    where
+        F: FnMut(A),
+    {
+        for handler in self.handlers.iter_mut().flatten() {
+            handler(args.clone());
+        }
+    }
+
+}
+
+impl<F, const N: usize> Default for AxisRouter<F, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::Axis;
+
+    #[test]
+    fn closures_with_state_work() {
+        let mut x_hits = 0;
+        let mut z_hits = 0;
+        let mut on_x = |n: u32| x_hits += n;
+        let mut on_z = |n: u32| z_hits += n;
+
+        {
+            let mut router = AxisRouter::<&mut dyn FnMut(u32), 3>::new();
+            router.set(Axis::X, &mut on_x);
+            router.set(Axis::Z, &mut on_z);
+
+            router.dispatch(Axis::X, 2);
+            router.broadcast(1);
+            assert_eq!(router.dispatch(Axis::Y, 5), None);
+        }
+
+        assert_eq!((x_hits, z_hits), (3, 1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn out_of_range_axis_panics() {
+        let _ = AxisRouter::<fn(), 2>::new().with(Axis::W, || {});
+    }
+
+}
@@ -0,0 +1,238 @@
+//!
+//! Per-axis validation of coordinates read from untrusted input
+//!
+
+use core::fmt;
+use core::ops::{Bound, RangeBounds};
+
+use crate::{Axis, AxisSet, HasAxes, Scalar};
+
+
+///
+/// Returned by [`AxisRules::validate`] for the first axis whose component breaks its rule
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RuleError {
+    /// The value has no component along an axis with a rule
+    Missing {
+        /// The axis at fault
+        axis: Axis,
+    },
+    /// The component lies outside the range of its rule
+    OutOfRange {
+        /// The axis at fault
+        axis: Axis,
+    },
+    /// The component is infinite or NaN where the rule asks for a finite number
+    NotFinite {
+        /// The axis at fault
+        axis: Axis,
+    },
+}
+
+impl RuleError {
+
+    ///
+    /// Returns the axis at fault
+    ///
+    pub const fn axis(&self) -> Axis {
+        match *self {
+            RuleError::Missing { axis } | RuleError::OutOfRange { axis } | RuleError::NotFinite { axis } => axis,
+        }
+    }
+
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.axis().name();
+        match self {
+            RuleError::Missing { .. } => write!(f, "axmac: the value has no {} component", name),
+            RuleError::OutOfRange { .. } => write!(f, "axmac: the {} component is out of range", name),
+            RuleError::NotFinite { .. } => write!(f, "axmac: the {} component is not finite", name),
+        }
+    }
+}
+
+
+///
+/// A range and finiteness check for every axis, applied to any [`HasAxes`] value
+///
+/// Axes without a rule accept anything. Build rules by chaining [`range`](Self::range) and
+/// [`finite`](Self::finite), or name the axes with [`rules!`]. A NaN component lies outside every
+/// range, so a bounded float range also rejects NaN
+///
+/// ```
+/// use axmac::{Axis, AxisRules, RuleError};
+///
+/// let rules = AxisRules::new().range(Axis::X, 0.0..=640.0).finite(Axis::Z);
+/// assert_eq!(rules.validate(&[320.0, -1e9, 0.5]), Ok(()));
+/// assert_eq!(rules.validate(&[700.0, 0.0, 0.5]), Err(RuleError::OutOfRange { axis: Axis::X }));
+/// assert_eq!(rules.validate(&[1.0, 0.0, f64::NAN]), Err(RuleError::NotFinite { axis: Axis::Z }));
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AxisRules<T> {
+    rules: [Rule<T>; Axis::COUNT],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Rule<T> {
+    start: Bound<T>,
+    end: Bound<T>,
+    finite: bool,
+}
+
+impl<T: Scalar> AxisRules<T> {
+
+    ///
+    /// Creates rules accepting every value
+    ///
+    pub const fn new() -> Self {
+        Self { rules: [Rule { start: Bound::Unbounded, end: Bound::Unbounded, finite: false }; Axis::COUNT] }
+    }
+
+    ///
+    /// Requires the component along `axis` to lie in `range`, replacing any range given before
+    ///
+    pub fn range<R: RangeBounds<T>>(mut self, axis: Axis, range: R) -> Self {
+        let rule = &mut self.rules[axis.as_usize()];
+        rule.start = range.start_bound().cloned();
+        rule.end = range.end_bound().cloned();
+        self
+    }
+
+    ///
+    /// Requires the component along `axis` to be neither infinite nor NaN
+    ///
+    /// Integer components are always finite
+    ///
+    pub fn finite(mut self, axis: Axis) -> Self {
+        self.rules[axis.as_usize()].finite = true;
+        self
+    }
+
+    ///
+    /// Checks every component of `value` against the rule of its axis, returning the first broken rule
+    /// in axis order
+    ///
+    pub fn validate<V: HasAxes<Item = T> + ?Sized>(&self, value: &V) -> Result<(), RuleError> {
+        for axis in Axis::iter() {
+            self.check(value, axis)?;
+        }
+        Ok(())
+    }
+
+    ///
+    /// Returns the set of every axis whose rule `value` breaks, for reporting them all at once
+    ///
+    pub fn violations<V: HasAxes<Item = T> + ?Sized>(&self, value: &V) -> AxisSet {
+        Axis::iter().filter(|&axis| self.check(value, axis).is_err()).map(Axis::as_usize).collect()
+    }
+
+    fn check<V: HasAxes<Item = T> + ?Sized>(&self, value: &V, axis: Axis) -> Result<(), RuleError> {
+        let rule = &self.rules[axis.as_usize()];
+        let unconstrained = matches!((rule.start, rule.end), (Bound::Unbounded, Bound::Unbounded)) && !rule.finite;
+        let v = match value.get_axis(axis) {
+            Some(&v) => v,
+            None if unconstrained => return Ok(()),
+            None => return Err(RuleError::Missing { axis }),
+        };
+        // Infinities and NaN are the only values not cancelling themselves out
+        #[allow(clippy::eq_op)]
+        if rule.finite && v - v != T::ZERO {
+            return Err(RuleError::NotFinite { axis });
+        }
+        if !(rule.start, rule.end).contains(&v) {
+            return Err(RuleError::OutOfRange { axis });
+        }
+        Ok(())
+    }
+
+}
+
+impl<T: Scalar> Default for AxisRules<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+
+///
+/// Creates [`AxisRules`] from a rule per named axis, each a range or `finite`
+///
+/// Ranges may be of any kind over the component type. Naming an axis twice fails to compile
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{Axis, RuleError};
+///
+/// const WIDTH: i32 = 640;
+/// const HEIGHT: i32 = 480;
+///
+/// let rules = rules!(x: 0..WIDTH, y: 0..HEIGHT);
+/// assert_eq!(rules.validate(&[10, 20]), Ok(()));
+/// assert_eq!(rules.validate(&[10, 480]), Err(RuleError::OutOfRange { axis: Axis::Y }));
+///
+/// let untrusted = rules!(x: -1.0..=1.0, z: finite);
+/// assert_eq!(untrusted.validate(&[0.5, 0.0]), Err(RuleError::Missing { axis: Axis::Z }));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: rules! names an axis more than once
+/// let rules = rules!(x: 0..10, x: finite);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! rules {
+
+    ( @rule [ $( $axes:ident )* ] [ $acc:expr ] $axis:ident : finite $( , $( $rest:tt )* )? ) => {
+        $crate::rules!(@rule [ $( $axes )* $axis ] [ $acc.finite($crate::ax!(@enum $axis)) ] $( $( $rest )* )?)
+    };
+    ( @rule [ $( $axes:ident )* ] [ $acc:expr ] $axis:ident : $range:expr $( , $( $rest:tt )* )? ) => {
+        $crate::rules!(@rule [ $( $axes )* $axis ] [ $acc.range($crate::ax!(@enum $axis), $range) ] $( $( $rest )* )?)
+    };
+
+    ( @rule [ $( $axes:ident )* ] [ $acc:expr ] ) => {{
+        const _: () = assert!(
+            $crate::__private::indices_are_unique(&[ $( $crate::ax!($axes) ),* ]),
+            "axmac: rules! names an axis more than once",
+        );
+        $acc
+    }};
+
+    ( $( $rules:tt )+ ) => {
+        $crate::rules!(@rule [] [$crate::AxisRules::new()] $( $rules )+)
+    };
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::Coord3;
+
+    #[test]
+    fn reports_every_violation() {
+        let rules = rules!(x: ..=10.0, y: 0.0.., z: finite);
+        let bad = Coord3::new([11.0, -1.0, f32::INFINITY]);
+        assert_eq!(rules.violations(&bad), AxisSet::from_axes(&axs![x, y, z]));
+        assert_eq!(rules.validate(&bad).map_err(|e| e.axis()), Err(Axis::X));
+        assert!(rules.violations(&(1.0, 2.0, 3.0)).is_empty());
+    }
+
+    #[test]
+    fn integers_are_finite() {
+        let rules = AxisRules::new().finite(Axis::X).range(Axis::X, 0..4).range(Axis::X, 2..);
+        assert_eq!(rules.validate(&[i64::MAX]), Ok(()));
+        assert_eq!(rules.validate(&[1]), Err(RuleError::OutOfRange { axis: Axis::X }));
+        assert_eq!(std::string::ToString::to_string(&RuleError::NotFinite { axis: Axis::W }), "axmac: the w component is not finite");
+    }
+
+}
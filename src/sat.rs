@@ -0,0 +1,135 @@
+//!
+//! Per-axis overlap tests between axis-aligned boxes
+//!
+//! By the separating axis theorem, two axis-aligned boxes are disjoint exactly when their extents fail to
+//! overlap along at least one axis. These helpers report which axis that is, and by how much the boxes
+//! overlap along the others, so collision code can push boxes apart along the axis of least penetration
+//!
+
+use core::ops::Range;
+
+use crate::aabb::{max, min};
+use crate::{Aabb, Axis, AxisMap, Scalar};
+
+
+///
+/// Returns the range of coordinates along `axis` covered by both boxes, or `None` if they are separated
+/// along it
+///
+/// Boxes are closed, so boxes which only touch overlap in an empty range
+///
+/// ```
+/// use axmac::{overlap_along, Aabb, Axis};
+///
+/// let a = Aabb::new([0, 0], [4, 4]);
+/// let b = Aabb::new([3, 6], [8, 9]);
+///
+/// assert_eq!(overlap_along(&a, &b, Axis::X), Some(3..4));
+/// assert_eq!(overlap_along(&a, &b, Axis::Y), None);
+/// ```
+///
+/// # Panics
+///
+/// If `axis` is not less than `N`
+///
+pub fn overlap_along<T: PartialOrd + Copy, const N: usize>(a: &Aabb<T, N>, b: &Aabb<T, N>, axis: Axis) -> Option<Range<T>> {
+    let i = axis.as_usize();
+    let lo = max(a.min[i], b.min[i]);
+    let hi = min(a.max[i], b.max[i]);
+    if lo <= hi { Some(lo..hi) } else { None }
+}
+
+///
+/// Returns the overlap of the boxes along every axis, or the first axis separating them
+///
+/// ```
+/// use axmac::{overlaps_all_axes, Aabb, Axis};
+///
+/// let a = Aabb::new([0.0, 0.0, 0.0], [1.0, 1.0, 1.0]);
+/// let b = Aabb::new([0.5, 0.0, 2.0], [1.5, 1.0, 3.0]);
+///
+/// assert_eq!(overlaps_all_axes(&a, &b), Err(Axis::Z));
+/// assert_eq!(overlaps_all_axes(&a, &a).unwrap()[Axis::Y], 0.0..1.0);
+/// ```
+///
+/// # Panics
+///
+/// If `N` is greater than 4, since axes are reported as [`Axis`]
+///
+pub fn overlaps_all_axes<T: PartialOrd + Copy, const N: usize>(a: &Aabb<T, N>, b: &Aabb<T, N>) -> Result<AxisMap<Range<T>, N>, Axis> {
+    assert!(N <= Axis::COUNT, "axmac: axes past w have no Axis to report");
+
+    for i in 0..N {
+        let axis = Axis::from_usize(i).expect("N is at most Axis::COUNT");
+        if overlap_along(a, b, axis).is_none() {
+            return Err(axis);
+        }
+    }
+    Ok(AxisMap::from_fn(|i| max(a.min[i], b.min[i])..min(a.max[i], b.max[i])))
+}
+
+///
+/// Returns the axis along which the overlapping boxes penetrate each other the least, or `None` if they
+/// do not overlap
+///
+/// Moving either box by the overlap along that axis separates them with the smallest displacement
+///
+/// ```
+/// use axmac::{min_penetration_axis, Aabb, Axis};
+///
+/// let player = Aabb::new([0, 0], [10, 10]);
+/// let wall = Aabb::new([8, -50], [20, 50]);
+///
+/// assert_eq!(min_penetration_axis(&player, &wall), Some(Axis::X));
+/// ```
+///
+/// # Panics
+///
+/// If `N` is greater than 4
+///
+pub fn min_penetration_axis<T: Scalar, const N: usize>(a: &Aabb<T, N>, b: &Aabb<T, N>) -> Option<Axis> {
+    let overlaps = overlaps_all_axes(a, b).ok()?;
+    let mut best: Option<(Axis, T)> = None;
+    for (i, overlap) in overlaps.iter() {
+        let depth = overlap.end - overlap.start;
+        if best.is_none_or(|(_, d)| depth < d) {
+            best = Axis::from_usize(i).map(|axis| (axis, depth));
+        }
+    }
+    best.map(|(axis, _)| axis)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn touching_boxes_overlap() {
+        let a = Aabb::new([0, 0], [1, 1]);
+        let b = Aabb::new([1, 0], [2, 1]);
+        assert_eq!(overlap_along(&a, &b, Axis::X), Some(1..1));
+        assert!(overlaps_all_axes(&a, &b).is_ok());
+        assert_eq!(min_penetration_axis(&a, &b), Some(Axis::X));
+    }
+
+    #[test]
+    fn separation_matches_intersects() {
+        let boxes = [
+            Aabb::new([0, 0, 0], [2, 2, 2]),
+            Aabb::new([1, 1, 1], [3, 3, 3]),
+            Aabb::new([0, 5, 0], [2, 6, 2]),
+            Aabb::new([-4, 0, 1], [-1, 2, 1]),
+        ];
+        for a in &boxes {
+            for b in &boxes {
+                assert_eq!(overlaps_all_axes(a, b).is_ok(), a.intersects(b));
+            }
+        }
+        assert_eq!(overlaps_all_axes(&boxes[0], &boxes[2]), Err(Axis::Y));
+        assert_eq!(min_penetration_axis(&boxes[0], &boxes[2]), None);
+    }
+
+}
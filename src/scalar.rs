@@ -0,0 +1,78 @@
+//!
+//! The numeric bound shared by the coordinate and geometry helpers
+//!
+
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+
+///
+/// Numbers the coordinate, bounding box and statistics helpers can operate on
+///
+/// Implemented for every primitive integer and float. Embedded targets without an FPU can implement it for
+/// their fixed-point type of choice, since nothing beyond ordering and the four basic operations is needed
+///
+pub trait Scalar:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+    /// The additive identity
+    const ZERO: Self;
+    /// The multiplicative identity
+    const ONE: Self;
+
+    ///
+    /// Converts a count, such as the number of points averaged, into this type
+    ///
+    /// Floats round to the nearest value they can hold
+    ///
+    /// # Panics
+    ///
+    /// For integers, if `n` does not fit the type, rather than truncating into a wrong count or zero
+    ///
+    fn from_usize(n: usize) -> Self;
+}
+
+///
+/// [`Scalar`]s which can be negated
+///
+pub trait SignedScalar: Scalar + Neg<Output = Self> {}
+
+impl<T: Scalar + Neg<Output = T>> SignedScalar for T {}
+
+
+macro_rules! impl_scalar {
+    ( int; $( $t:ty ), * ) => {
+        $(
+            impl Scalar for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn from_usize(n: usize) -> Self {
+                    match <$t>::try_from(n) {
+                        Ok(n) => n,
+                        Err(_) => panic!("axmac: the count {} does not fit in {}", n, stringify!($t)),
+                    }
+                }
+            }
+        )*
+    };
+    ( float; $( $t:ty ), * ) => {
+        $(
+            impl Scalar for $t {
+                const ZERO: Self = 0.0;
+                const ONE: Self = 1.0;
+
+                fn from_usize(n: usize) -> Self {
+                    n as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar!(int; u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_scalar!(float; f32, f64);
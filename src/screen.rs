@@ -0,0 +1,107 @@
+//!
+//! Pan and zoom between world and screen coordinates with a scale and offset per axis, without matrices
+//!
+
+
+///
+/// Maps a world position to the screen, as `world * scale + offset` along each axis named
+///
+/// Takes any [`HasAxes`](crate::HasAxes) value, then per axis its `scale` and `offset` in parentheses by
+/// name, defaulting to one and zero. Axes not named pass through unchanged, and a negative scale flips an
+/// axis, as for a y axis growing downwards on screen. Naming an axis twice or past the last of the value
+/// fails to compile
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // 100 pixels per unit, origin at the centre of a 640x480 window, y up
+/// let screen = world_to_screen!([1.5, 2.0]; x: (scale: 100.0, offset: 320.0), y: (scale: -100.0, offset: 240.0));
+/// assert_eq!(screen, [470.0, 40.0]);
+///
+/// // Only pan the time axis of a plot
+/// assert_eq!(world_to_screen!((3, 7); x: (offset: -2)), (1, 7));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: world_to_screen! names an axis past the last of the value
+/// let screen = world_to_screen!([1.0, 2.0]; z: (scale: 2.0));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! world_to_screen {
+    ( $world:expr; $( $axis:ident : ( $( $param:ident : $value:expr ),* $(,)? ) ),* $(,)? ) => {{
+        const _: () = assert!(
+            $crate::__private::indices_are_unique(&[ $( $crate::ax!($axis) ),* ]),
+            "axmac: world_to_screen! names an axis more than once",
+        );
+        #[allow(clippy::needless_update)]
+        let named = [ $( (
+            $crate::ax!(@enum $axis),
+            $crate::__private::PanZoom { $( $param: $value, )* ..$crate::__private::PanZoom::DEFAULT },
+        ) ),* ];
+        $crate::__private::PanZoomAxes::<{ $crate::__private::max_index(&[ $( $crate::ax!($axis) ),* ]) }>::to_screen($world, named)
+    }};
+}
+
+
+///
+/// Maps a screen position back into the world, as `(screen - offset) / scale` along each axis named
+///
+/// The inverse of [`world_to_screen!`] given the same parameters, such as for finding what lies under the
+/// cursor. A scale of zero divides by zero, which panics for integer components
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let cursor = screen_to_world!([470.0, 40.0]; x: (scale: 100.0, offset: 320.0), y: (scale: -100.0, offset: 240.0));
+/// assert_eq!(cursor, [1.5, 2.0]);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: screen_to_world! names an axis more than once
+/// let world = screen_to_world!([1.0, 2.0]; x: (scale: 2.0), x: (offset: 1.0));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! screen_to_world {
+    ( $screen:expr; $( $axis:ident : ( $( $param:ident : $value:expr ),* $(,)? ) ),* $(,)? ) => {{
+        const _: () = assert!(
+            $crate::__private::indices_are_unique(&[ $( $crate::ax!($axis) ),* ]),
+            "axmac: screen_to_world! names an axis more than once",
+        );
+        #[allow(clippy::needless_update)]
+        let named = [ $( (
+            $crate::ax!(@enum $axis),
+            $crate::__private::PanZoom { $( $param: $value, )* ..$crate::__private::PanZoom::DEFAULT },
+        ) ),* ];
+        $crate::__private::PanZoomAxes::<{ $crate::__private::max_index(&[ $( $crate::ax!($axis) ),* ]) }>::to_world($screen, named)
+    }};
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{AxisMap, Coord3};
+
+    #[test]
+    fn round_trips_per_axis() {
+        let world = Coord3::new([-2.0f32, 0.5, 8.0]);
+        let screen = world_to_screen!(world; z: (scale: 0.25), x: (offset: 4.0, scale: 2.0));
+        assert_eq!(screen, Coord3::new([0.0, 0.5, 2.0]));
+        assert_eq!(screen_to_world!(screen; x: (scale: 2.0, offset: 4.0), z: (scale: 0.25)), world);
+    }
+
+    #[test]
+    fn unnamed_axes_pass_through() {
+        let map = AxisMap::new([10u32, 20, 30, 40]);
+        assert_eq!(world_to_screen!(map;), map);
+        assert_eq!(screen_to_world!(map; w: (scale: 4, offset: 8)), AxisMap::new([10, 20, 30, 8]));
+    }
+
+}
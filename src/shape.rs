@@ -0,0 +1,698 @@
+//!
+//! Extents and strides of N dimensional buffers stored in flat memory
+//!
+
+use core::fmt;
+
+use crate::{Axis, AxisPriority, NdIndex, OutOfBounds};
+
+
+///
+/// Describes how an N dimensional grid is laid out in a flat buffer
+///
+/// Every axis has an extent, the number of positions along it, and a stride, the distance in elements
+/// between neighbouring positions along it. An axis may also be reversed, counting its positions from
+/// the far end of memory as a negative stride would, so flipped views need no copy
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Shape;
+///
+/// // A 640x480 RGB image with interleaved channels, indexed as [channel, x, y]
+/// let image = Shape::new([3, 640, 480]);
+/// assert_eq!(image.len(), 921_600);
+/// assert_eq!(image.stride(ax!(z)), 1920);
+/// assert_eq!(image.flat_index(&[2, 1, 1]), Some(1925));
+/// assert_eq!(image.flat_index(&[0, 640, 0]), None);
+/// # }
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Shape<const N: usize> {
+    extents: [usize; N],
+    strides: [usize; N],
+    reversed: [bool; N],
+    #[cfg(feature = "debug-dims")]
+    pub(crate) labels: crate::dims::DimLabels<N>,
+}
+
+impl<const N: usize> Shape<N> {
+
+    ///
+    /// Creates a densely packed shape where the x axis is contiguous, followed by y, then z and w
+    ///
+    /// This is the layout produced by indexing as `x + y * width + z * width * height`
+    ///
+    pub const fn new(extents: [usize; N]) -> Self {
+        let mut strides = [0; N];
+        let mut stride = 1;
+        let mut i = 0;
+        while i < N {
+            strides[i] = stride;
+            stride *= extents[i];
+            i += 1;
+        }
+        Self::with_strides(extents, strides)
+    }
+
+    ///
+    /// Creates a densely packed shape where the last axis is contiguous, and the x axis is outermost
+    ///
+    pub const fn col_major(extents: [usize; N]) -> Self {
+        let mut strides = [0; N];
+        let mut stride = 1;
+        let mut i = N;
+        while i > 0 {
+            i -= 1;
+            strides[i] = stride;
+            stride *= extents[i];
+        }
+        Self::with_strides(extents, strides)
+    }
+
+    ///
+    /// Returns a densely packed shape of the same extents, with the axes laid out in memory in the order of
+    /// `priority`, its innermost axis contiguous
+    ///
+    /// ```
+    /// use axmac::{AxisPriority, Shape};
+    ///
+    /// let shape = Shape::new([4, 3, 2]);
+    /// assert_eq!(shape.dense_in_order(AxisPriority::X_FASTEST), shape);
+    /// assert_eq!(shape.dense_in_order(AxisPriority::LAST_FASTEST), Shape::col_major([4, 3, 2]));
+    /// ```
+    ///
+    pub fn dense_in_order(&self, priority: AxisPriority<N>) -> Shape<N> {
+        let mut strides = [0; N];
+        let mut stride = 1;
+        for axis in priority.inner_to_outer() {
+            strides[axis] = stride;
+            stride *= self.extent(axis);
+        }
+        Shape::with_strides(*self.extents(), strides)
+    }
+
+    ///
+    /// Creates a shape with explicit strides, such as a view into a larger buffer
+    ///
+    pub const fn with_strides(extents: [usize; N], strides: [usize; N]) -> Self {
+        Self {
+            extents,
+            strides,
+            reversed: [false; N],
+            #[cfg(feature = "debug-dims")]
+            labels: crate::dims::DimLabels::NONE,
+        }
+    }
+
+    ///
+    /// Returns the number of positions along `axis`
+    ///
+    pub const fn extent(&self, axis: usize) -> usize {
+        self.extents[axis]
+    }
+
+    ///
+    /// Returns the number of positions along every axis
+    ///
+    pub const fn extents(&self) -> &[usize; N] {
+        &self.extents
+    }
+
+    ///
+    /// Returns the distance in elements between neighbouring positions along `axis`
+    ///
+    pub const fn stride(&self, axis: usize) -> usize {
+        self.strides[axis]
+    }
+
+    ///
+    /// Returns the stride of every axis
+    ///
+    pub const fn strides(&self) -> &[usize; N] {
+        &self.strides
+    }
+
+    ///
+    /// Returns this shape with the positions along `axis` running the other way, as a view flipped along
+    /// it without copying the buffer
+    ///
+    /// Position zero of a reversed axis lies where its last position did, and reversing it again restores
+    /// the original. Iteration through [`flat_index`](Self::flat_index) and the views built on it visits
+    /// the elements in flipped order
+    ///
+    /// ```
+    /// use axmac::{view, Axis, Roi, Shape};
+    ///
+    /// // 0 1 2
+    /// // 3 4 5
+    /// let buf = [0, 1, 2, 3, 4, 5];
+    /// let mirrored = Shape::new([3, 2]).reverse_axis(Axis::X);
+    /// assert_eq!(mirrored.flat_index(&[0, 1]), Some(5));
+    ///
+    /// let rows: Vec<_> = view(&buf, &mirrored, &Roi::FULL, [1, 0]).copied().collect();
+    /// assert_eq!(rows, [2, 1, 0, 5, 4, 3]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If `axis` is not less than `N`
+    ///
+    pub const fn reverse_axis(self, axis: Axis) -> Self {
+        self.reverse_index(axis.as_usize())
+    }
+
+    // Reverses an axis by index, reaching those past w
+    pub(crate) const fn reverse_index(mut self, axis: usize) -> Self {
+        assert!(axis < N, "axmac: axis is out of bounds for the shape");
+        self.reversed[axis] = !self.reversed[axis];
+        self
+    }
+
+    ///
+    /// Returns whether the positions along `axis` run from the far end of memory
+    ///
+    pub const fn is_reversed(&self, axis: usize) -> bool {
+        self.reversed[axis]
+    }
+
+    ///
+    /// Returns the axis whose neighbouring positions are adjacent in memory, the natural inner loop
+    ///
+    /// Axes with an extent of one are only chosen when no longer axis is unit stride. Returns `None` when
+    /// no axis has a stride of one and runs forwards, or the contiguous axis has no [`Axis`] name
+    ///
+    /// ```
+    /// # #[macro_use] extern crate axmac; fn main() {
+    /// use axmac::{Axis, Shape};
+    ///
+    /// assert_eq!(Shape::new([8, 8, 8]).contiguous_axis(), Some(Axis::X));
+    /// assert_eq!(Shape::col_major([8, 8, 8]).contiguous_axis(), Some(Axis::Z));
+    /// assert_eq!(Shape::with_strides([4, 4], [2, 8]).contiguous_axis(), None);
+    /// # }
+    /// ```
+    ///
+    pub const fn contiguous_axis(&self) -> Option<Axis> {
+        let mut fallback = None;
+        let mut i = 0;
+        while i < N {
+            if self.strides[i] == 1 && !self.reversed[i] {
+                if self.extents[i] > 1 {
+                    return Axis::from_usize(i);
+                }
+                if fallback.is_none() {
+                    fallback = Axis::from_usize(i);
+                }
+            }
+            i += 1;
+        }
+        fallback
+    }
+
+    ///
+    /// Returns whether neighbouring positions along `axis` are adjacent in memory
+    ///
+    /// Axes beyond the shape's dimensionality and reversed axes, whose neighbours run backwards through
+    /// memory, are never contiguous
+    ///
+    pub const fn is_contiguous_along(&self, axis: Axis) -> bool {
+        let axis = axis.as_usize();
+        axis < N && self.strides[axis] == 1 && !self.reversed[axis]
+    }
+
+    ///
+    /// Returns the number of positions in the grid
+    ///
+    pub const fn len(&self) -> usize {
+        let mut len = 1;
+        let mut i = 0;
+        while i < N {
+            len *= self.extents[i];
+            i += 1;
+        }
+        len
+    }
+
+    ///
+    /// Returns whether any axis has an extent of zero
+    ///
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    ///
+    /// Returns the smallest buffer length able to hold every position, one past the largest flat index
+    ///
+    pub const fn required_len(&self) -> usize {
+        if self.is_empty() {
+            return 0;
+        }
+        let mut last = 0;
+        let mut i = 0;
+        while i < N {
+            last += (self.extents[i] - 1) * self.strides[i];
+            i += 1;
+        }
+        last + 1
+    }
+
+    // Returns the first axis at which counting the positions of `extents`, or densely packed strides for
+    // them, overflows a `usize`. Shapes do not check their arithmetic, so extents from untrusted input are
+    // checked with this before building shapes of them
+    pub(crate) fn dense_overflow_axis(extents: &[usize; N]) -> Option<usize> {
+        let mut len = 1usize;
+        for (axis, &extent) in extents.iter().enumerate() {
+            // A zero extent empties the shape, but strides still multiply every other extent
+            if extent != 0 {
+                len = match len.checked_mul(extent) {
+                    Some(len) => len,
+                    None => return Some(axis),
+                };
+            }
+        }
+        None
+    }
+
+    // Returns the first axis at which counting the positions of `extents`, or the elements they span with
+    // `strides`, overflows a `usize`, for checking untrusted input as `dense_overflow_axis` does
+    pub(crate) fn strided_overflow_axis(extents: &[usize; N], strides: &[usize; N]) -> Option<usize> {
+        if let Some(axis) = Self::dense_overflow_axis(extents) {
+            return Some(axis);
+        }
+        let mut span = 1usize;
+        for (axis, (&extent, &stride)) in extents.iter().zip(strides).enumerate() {
+            match extent.saturating_sub(1).checked_mul(stride).and_then(|s| span.checked_add(s)) {
+                Some(s) => span = s,
+                None => return Some(axis),
+            }
+        }
+        None
+    }
+
+    ///
+    /// Returns whether `coord` lies inside the grid
+    ///
+    pub const fn contains(&self, coord: &[usize; N]) -> bool {
+        let mut i = 0;
+        while i < N {
+            if coord[i] >= self.extents[i] {
+                return false;
+            }
+            i += 1;
+        }
+        true
+    }
+
+    ///
+    /// Returns the flat index of `coord`, or `None` if it lies outside the grid
+    ///
+    pub const fn flat_index(&self, coord: &[usize; N]) -> Option<usize> {
+        if self.contains(coord) {
+            Some(self.flat_index_unchecked(coord))
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Returns the flat index of `coord`, or which axis it lies outside of
+    ///
+    /// Unlike [`flat_index`](Self::flat_index), failures are reported to the [`trace`](crate::trace) hook
+    /// when the `tracing` feature is enabled
+    ///
+    pub fn try_flat_index(&self, coord: &[usize; N]) -> Result<usize, OutOfBounds> {
+        for (axis, (&got, &extent)) in coord.iter().zip(self.extents.iter()).enumerate() {
+            if got >= extent {
+                let error = OutOfBounds { axis, extent, got };
+                #[cfg(all(feature = "tracing", target_has_atomic = "ptr"))]
+                crate::trace::emit("Shape::try_flat_index", error);
+                return Err(error);
+            }
+        }
+        Ok(self.flat_index_unchecked(coord))
+    }
+
+    ///
+    /// Returns the flat index of `coord` without checking it lies inside the grid
+    ///
+    /// An out of bounds coordinate produces a meaningless index, but never undefined behaviour
+    ///
+    pub const fn flat_index_unchecked(&self, coord: &[usize; N]) -> usize {
+        let mut index = 0;
+        let mut i = 0;
+        while i < N {
+            let c = if self.reversed[i] { self.extents[i].wrapping_sub(1).wrapping_sub(coord[i]) } else { coord[i] };
+            index = c.wrapping_mul(self.strides[i]).wrapping_add(index);
+            i += 1;
+        }
+        index
+    }
+
+    ///
+    /// Returns the coordinate whose flat index is `index`, or `None` if no coordinate maps to it
+    ///
+    /// Works for any layout in which distinct coordinates have distinct flat indices, including strided
+    /// views into larger buffers. Axes with an extent of one are skipped, whatever their stride, as
+    /// [`matches_layout`](Self::matches_layout) skips them
+    ///
+    pub fn coord_of(&self, index: usize) -> Option<[usize; N]> {
+        let mut coord = [0; N];
+        let mut rem = index;
+        for axis in self.axis_order() {
+            let stride = self.strides[axis];
+            if stride == 0 || self.extents[axis] <= 1 {
+                continue;
+            }
+            coord[axis] = rem / stride;
+            rem %= stride;
+        }
+
+        if rem != 0 || !self.contains(&coord) {
+            return None;
+        }
+        for (axis, c) in coord.iter_mut().enumerate() {
+            if self.reversed[axis] {
+                *c = self.extents[axis] - 1 - *c;
+            }
+        }
+        Some(coord)
+    }
+
+    ///
+    /// Iterates over every coordinate of the grid, with the x axis varying fastest
+    ///
+    pub fn coords(&self) -> NdIndex<usize, N> {
+        NdIndex::new([0; N], self.extents)
+    }
+
+    ///
+    /// Returns the axes from the outermost, of largest stride, to the innermost
+    ///
+    /// This is the order [`view`](crate::view) takes, and the order of the nested loops visiting the
+    /// buffer front to back. Axes of equal stride keep the higher axis outermost, as [`Shape::new`] would
+    ///
+    /// ```
+    /// # #[macro_use] extern crate axmac; fn main() {
+    /// use axmac::Shape;
+    ///
+    /// assert_eq!(Shape::new([4, 3, 2]).axis_order(), axs![z, y, x]);
+    /// assert_eq!(Shape::col_major([4, 3, 2]).axis_order(), axs![x, y, z]);
+    ///
+    /// // An image handed over with channels innermost and rows outermost
+    /// let image = Shape::with_strides([3, 640, 480], [1, 3, 1920]);
+    /// assert_eq!(image.axis_order(), axs![z, y, x]);
+    /// # }
+    /// ```
+    ///
+    pub fn axis_order(&self) -> [usize; N] {
+        let mut axes: [usize; N] = core::array::from_fn(|i| i);
+        axes.sort_unstable_by_key(|&i| core::cmp::Reverse((self.strides[i], i)));
+        axes
+    }
+
+    ///
+    /// Returns whether the shape is laid out as `layout` describes
+    ///
+    /// Strides of axes with an extent of one never matter, since no two positions differ along them. No
+    /// layout has a longer axis [reversed](Self::reverse_axis)
+    ///
+    /// ```
+    /// use axmac::{Layout, Shape};
+    ///
+    /// assert!(Shape::new([4, 3]).matches_layout(Layout::XFastest));
+    /// assert!(Shape::col_major([4, 3]).matches_layout(Layout::LastFastest));
+    /// assert!(Shape::with_strides([3, 4], [4, 1]).matches_layout(Layout::Dense));
+    /// assert!(!Shape::with_strides([3, 4], [1, 8]).matches_layout(Layout::Dense));
+    /// ```
+    ///
+    pub fn matches_layout(&self, layout: Layout) -> bool {
+        let order = match layout {
+            Layout::XFastest => core::array::from_fn(|i| N - 1 - i),
+            Layout::LastFastest => core::array::from_fn(|i| i),
+            Layout::Dense => self.axis_order(),
+        };
+        let mut stride = 1;
+        for &axis in order.iter().rev() {
+            if self.extents[axis] > 1 {
+                if self.strides[axis] != stride || self.reversed[axis] {
+                    return false;
+                }
+                stride *= self.extents[axis];
+            }
+        }
+        true
+    }
+
+    ///
+    /// Returns a summary of the layout which displays with axis names, for logging why an index
+    /// computation is off
+    ///
+    /// ```
+    /// use axmac::Shape;
+    ///
+    /// let view = Shape::with_strides([3, 2], [1, 8]);
+    /// assert_eq!(
+    ///     view.describe().to_string(),
+    ///     "x: extent 3, stride 1 (contiguous); y: extent 2, stride 8; 6 positions over 11 elements",
+    /// );
+    /// ```
+    ///
+    pub const fn describe(&self) -> ShapeDescription<N> {
+        ShapeDescription {
+            extents: self.extents,
+            strides: self.strides,
+            reversed: self.reversed,
+            contiguous: self.contiguous_axis(),
+            len: self.len(),
+            required_len: self.required_len(),
+        }
+    }
+
+}
+
+
+///
+/// How the positions of a [`Shape`] are laid out in memory, checked by [`Shape::matches_layout`]
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Layout {
+    /// Densely packed with the x axis contiguous, then y, z and w, as [`Shape::new`] creates
+    XFastest,
+    /// Densely packed with the last axis contiguous and the x axis outermost, as [`Shape::col_major`]
+    /// creates
+    LastFastest,
+    /// Densely packed with the axes in any order, leaving no gaps between positions
+    Dense,
+}
+
+
+///
+/// A summary of the layout of a [`Shape`], created by [`Shape::describe`]
+///
+/// Displays as one entry per axis, naming it and giving its extent, stride and whether it is reversed or
+/// contiguous, followed by the number of positions and the buffer length needed to hold them
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ShapeDescription<const N: usize> {
+    /// The number of positions along every axis
+    pub extents: [usize; N],
+    /// The stride of every axis
+    pub strides: [usize; N],
+    /// Whether every axis is [reversed](Shape::reverse_axis)
+    pub reversed: [bool; N],
+    /// The axis whose neighbouring positions are adjacent in memory, as [`Shape::contiguous_axis`]
+    pub contiguous: Option<Axis>,
+    /// The number of positions
+    pub len: usize,
+    /// The smallest buffer length able to hold every position
+    pub required_len: usize,
+}
+
+impl<const N: usize> fmt::Display for ShapeDescription<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for axis in 0..N {
+            match Axis::from_usize(axis) {
+                Some(name) => write!(f, "{}: ", name)?,
+                None => write!(f, "axis {}: ", axis)?,
+            }
+            write!(f, "extent {}, stride {}", self.extents[axis], self.strides[axis])?;
+            if self.reversed[axis] {
+                f.write_str(" (reversed)")?;
+            }
+            if self.contiguous.is_some_and(|c| c.as_usize() == axis) {
+                f.write_str(" (contiguous)")?;
+            }
+            f.write_str("; ")?;
+        }
+        write!(f, "{} positions over {} elements", self.len, self.required_len)
+    }
+}
+
+
+///
+/// Computes the flat index of a coordinate in a densely packed shape
+///
+/// By default the x axis is contiguous, as with [`Shape::new`], which is the layout of the hand written
+/// `x + y * width + z * width * height`. Writing `col_major` before `shape` makes the last axis contiguous
+/// instead, as with [`Shape::col_major`]
+///
+/// Coordinates are literals, axis identifiers standing for their index, or parenthesised expressions.
+/// The macro is a const expression, so with constant inputs an out of bounds coordinate fails to compile
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// const I: usize = axi!((2, 5, 1) in shape (10, 20, 30));
+/// assert_eq!(I, 2 + 5 * 10 + 1 * 10 * 20);
+///
+/// assert_eq!(axi!((2, 5, 1) in col_major shape (10, 20, 30)), 2 * 20 * 30 + 5 * 30 + 1);
+///
+/// let (width, height, row) = (640, 480, 7);
+/// assert_eq!(axi!((z, (row + 1)) in shape (width, height)), 2 + 8 * 640);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: axi! coordinate lies outside the shape
+/// const I: usize = axi!((4, 0) in shape (4, 4));
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If the coordinate lies outside the shape, for inputs only known at runtime
+///
+#[macro_export]
+macro_rules! axi {
+    ( ( $( $coord:tt ),+ $(,)? ) in shape ( $( $extent:expr ),+ $(,)? ) ) => {
+        $crate::__private::flat_index([ $( $crate::axi!(@coord $coord) ),+ ], [ $( $extent ),+ ], false)
+    };
+    ( ( $( $coord:tt ),+ $(,)? ) in col_major shape ( $( $extent:expr ),+ $(,)? ) ) => {
+        $crate::__private::flat_index([ $( $crate::axi!(@coord $coord) ),+ ], [ $( $extent ),+ ], true)
+    };
+
+    ( @coord $axis:ident ) => { $crate::ax!($axis) };
+    ( @coord $coord:tt ) => { $coord };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn strides_work() {
+        let shape = Shape::new([4, 3, 2]);
+        assert_eq!(shape.strides(), &[1, 4, 12]);
+        assert_eq!(Shape::col_major([4, 3, 2]).strides(), &[6, 2, 1]);
+        assert_eq!(shape.flat_index(&[3, 2, 1]), Some(23));
+        assert_eq!(shape.try_flat_index(&[3, 2, 1]), Ok(23));
+        assert_eq!(shape.try_flat_index(&[3, 3, 1]), Err(OutOfBounds { axis: ax!(y), extent: 3, got: 3 }));
+        assert_eq!(shape.required_len(), shape.len());
+    }
+
+    #[test]
+    fn describe_names_axes() {
+        use std::string::ToString;
+
+        let description = Shape::col_major([2, 3]).describe();
+        assert_eq!(description.contiguous, Some(Axis::Y));
+        assert_eq!(description.to_string(), "x: extent 2, stride 3; y: extent 3, stride 1 (contiguous); 6 positions over 6 elements");
+    }
+
+    #[test]
+    fn layouts_match_constructors() {
+        let shape = Shape::new([1, 5, 2]);
+        assert!(shape.matches_layout(Layout::XFastest) && shape.matches_layout(Layout::Dense));
+        assert!(!shape.matches_layout(Layout::LastFastest));
+        assert_eq!(Shape::with_strides([2, 2], [0, 1]).axis_order(), [1, 0]);
+        assert!(!Shape::with_strides([2, 2], [0, 1]).matches_layout(Layout::Dense));
+    }
+
+    #[test]
+    fn strided_views_work() {
+        // A 2x2 window into a 4 wide buffer
+        let view = Shape::with_strides([2, 2], [1, 4]);
+        assert_eq!(view.len(), 4);
+        assert_eq!(view.required_len(), 6);
+        let offsets: std::vec::Vec<_> = view.coords().map(|c| view.flat_index_unchecked(&c)).collect();
+        assert_eq!(offsets, [0, 1, 4, 5]);
+    }
+
+    #[test]
+    fn coord_of_works() {
+        let shape = Shape::new([4, 3, 2]);
+        for coord in shape.coords() {
+            assert_eq!(shape.coord_of(shape.flat_index_unchecked(&coord)), Some(coord));
+        }
+        assert_eq!(shape.coord_of(24), None);
+
+        let view = Shape::with_strides([2, 2], [1, 4]);
+        assert_eq!(view.coord_of(5), Some([1, 1]));
+        assert_eq!(view.coord_of(2), None);
+
+        // Extent one axes share their stride with a neighbour, and must not take the quotient
+        for shape in [Shape::new([1, 5]), Shape::col_major([1, 5]), Shape::col_major([5, 1])] {
+            assert!(shape.coords().all(|coord| shape.coord_of(shape.flat_index_unchecked(&coord)) == Some(coord)));
+        }
+        for shape in [Shape::new([3, 1, 4]), Shape::col_major([3, 1, 4]), Shape::new([1, 1, 2])] {
+            assert!(shape.coords().all(|coord| shape.coord_of(shape.flat_index_unchecked(&coord)) == Some(coord)));
+        }
+        assert_eq!(Shape::new([1, 5]).coord_of(1), Some([0, 1]));
+        assert_eq!(Shape::new([3, 1, 4]).coord_of(3), Some([0, 0, 1]));
+    }
+
+    #[test]
+    fn contiguity_works() {
+        let image = Shape::new([3, 640, 480]);
+        assert_eq!(image.contiguous_axis(), Some(Axis::X));
+        assert!(image.is_contiguous_along(Axis::X));
+        assert!(!image.is_contiguous_along(Axis::Y));
+        assert!(!image.is_contiguous_along(Axis::W));
+
+        // A single channel image, where the degenerate x axis is skipped
+        assert_eq!(Shape::new([1, 640, 480]).contiguous_axis(), Some(Axis::Y));
+        assert_eq!(Shape::new([1, 1]).contiguous_axis(), Some(Axis::X));
+    }
+
+    #[test]
+    fn reversed_axes_flip_indices() {
+        use std::string::ToString;
+
+        let shape = Shape::new([4, 3]).reverse_axis(Axis::Y);
+        assert!(shape.is_reversed(1) && !shape.is_reversed(0));
+        assert_eq!(shape.flat_index(&[1, 0]), Some(9));
+        assert_eq!(shape.required_len(), 12);
+        for coord in shape.coords() {
+            assert_eq!(shape.coord_of(shape.flat_index_unchecked(&coord)), Some(coord));
+        }
+        assert_eq!(shape.reverse_axis(Axis::Y), Shape::new([4, 3]));
+
+        let mirrored = Shape::new([4, 3]).reverse_axis(Axis::X);
+        assert_eq!(mirrored.contiguous_axis(), None);
+        assert!(!mirrored.matches_layout(Layout::Dense));
+        assert!(mirrored.describe().to_string().starts_with("x: extent 4, stride 1 (reversed); "));
+    }
+
+    #[test]
+    fn axi_matches_shape() {
+        let shape = Shape::new([4, 3, 2]);
+        let col = Shape::col_major([4, 3, 2]);
+        for [x, y, z] in shape.coords() {
+            assert_eq!(axi!(((x), (y), (z)) in shape (4, 3, 2)), shape.flat_index_unchecked(&[x, y, z]));
+            assert_eq!(axi!(((x), (y), (z)) in col_major shape (4, 3, 2)), col.flat_index_unchecked(&[x, y, z]));
+        }
+        assert_eq!(axi!((w, y) in shape (4, 2)), 7);
+    }
+
+    #[test]
+    fn empty_works() {
+        let shape = Shape::new([3, 0]);
+        assert!(shape.is_empty());
+        assert_eq!(shape.required_len(), 0);
+        assert_eq!(shape.coords().count(), 0);
+    }
+
+}
@@ -0,0 +1,130 @@
+//!
+//! Axes paired with a direction
+//!
+
+use core::ops::Neg;
+
+use crate::Axis;
+
+
+///
+/// The positive or negative direction along one of the axes _x_, _y_, _z_ or _w_
+///
+/// Names face normals, the face a ray enters a voxel through, or the direction a camera looks in
+///
+/// ```
+/// use axmac::{Axis, SignedAxis};
+///
+/// let down = -SignedAxis::POS_Y;
+///
+/// assert_eq!(down, SignedAxis::NEG_Y);
+/// assert_eq!(down.axis(), Axis::Y);
+/// assert_eq!(down.signum(), -1);
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SignedAxis {
+    axis: Axis,
+    negative: bool,
+}
+
+impl SignedAxis {
+
+    /// The positive direction along _x_
+    pub const POS_X: Self = Self::pos(Axis::X);
+    /// The negative direction along _x_
+    pub const NEG_X: Self = Self::neg(Axis::X);
+    /// The positive direction along _y_
+    pub const POS_Y: Self = Self::pos(Axis::Y);
+    /// The negative direction along _y_
+    pub const NEG_Y: Self = Self::neg(Axis::Y);
+    /// The positive direction along _z_
+    pub const POS_Z: Self = Self::pos(Axis::Z);
+    /// The negative direction along _z_
+    pub const NEG_Z: Self = Self::neg(Axis::Z);
+    /// The positive direction along _w_
+    pub const POS_W: Self = Self::pos(Axis::W);
+    /// The negative direction along _w_
+    pub const NEG_W: Self = Self::neg(Axis::W);
+
+    ///
+    /// Creates the direction along `axis`, negative if `negative` is set
+    ///
+    pub const fn new(axis: Axis, negative: bool) -> Self {
+        Self { axis, negative }
+    }
+
+    ///
+    /// Creates the positive direction along `axis`
+    ///
+    pub const fn pos(axis: Axis) -> Self {
+        Self::new(axis, false)
+    }
+
+    ///
+    /// Creates the negative direction along `axis`
+    ///
+    pub const fn neg(axis: Axis) -> Self {
+        Self::new(axis, true)
+    }
+
+    ///
+    /// Returns the axis the direction lies along
+    ///
+    pub const fn axis(self) -> Axis {
+        self.axis
+    }
+
+    ///
+    /// Returns whether the direction points towards decreasing coordinates
+    ///
+    pub const fn is_negative(self) -> bool {
+        self.negative
+    }
+
+    ///
+    /// Returns `1` for positive directions and `-1` for negative ones
+    ///
+    pub const fn signum(self) -> i32 {
+        if self.negative { -1 } else { 1 }
+    }
+
+    ///
+    /// Returns the opposite direction along the same axis
+    ///
+    pub const fn flip(self) -> Self {
+        Self::new(self.axis, !self.negative)
+    }
+
+}
+
+impl Neg for SignedAxis {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        self.flip()
+    }
+}
+
+impl From<Axis> for SignedAxis {
+    fn from(axis: Axis) -> Self {
+        Self::pos(axis)
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        assert_eq!(SignedAxis::from(Axis::Z), SignedAxis::POS_Z);
+        assert_eq!(SignedAxis::POS_W.flip().flip(), SignedAxis::POS_W);
+        assert!(SignedAxis::NEG_X.is_negative());
+        assert_eq!(SignedAxis::NEG_X.axis().as_usize(), ax!(x));
+    }
+
+}
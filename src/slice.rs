@@ -0,0 +1,223 @@
+//!
+//! Strided slices of single axes, in the form used by `ndarray`
+//!
+
+use core::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+
+
+///
+/// A slice of one axis, with the same fields and meaning as `ndarray::Slice`
+///
+/// Negative bounds count back from the end of the axis, a missing end runs to the end of the axis, and
+/// a negative step walks the selected elements in reverse. Every range type `axr!` produces converts into
+/// one, and converting onwards to `ndarray::Slice::new(s.start, s.end, s.step)` is a field for field copy
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::AxisSlice;
+///
+/// assert_eq!(AxisSlice::from(axr!(y..=z)), AxisSlice::new(1, Some(3), 1));
+/// assert_eq!(AxisSlice::from(..).step_by(-1), AxisSlice::new(0, None, -1));
+/// # }
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AxisSlice {
+    /// The first index, counting back from the end if negative
+    pub start: isize,
+    /// One past the last index, counting back from the end if negative, or `None` to run to the end
+    pub end: Option<isize>,
+    /// The distance between selected indices, walking in reverse if negative
+    pub step: isize,
+}
+
+impl AxisSlice {
+
+    ///
+    /// The slice selecting a whole axis
+    ///
+    pub const FULL: Self = Self { start: 0, end: None, step: 1 };
+
+    ///
+    /// Creates a slice from its bounds and step
+    ///
+    /// # Panics
+    ///
+    /// If `step` is zero
+    ///
+    pub const fn new(start: isize, end: Option<isize>, step: isize) -> Self {
+        assert!(step != 0, "axmac: a slice step cannot be zero");
+        Self { start, end, step }
+    }
+
+    ///
+    /// Returns the slice with its step multiplied by `step`, as `ndarray::Slice::step_by` does
+    ///
+    /// # Panics
+    ///
+    /// If `step` is zero
+    ///
+    pub const fn step_by(self, step: isize) -> Self {
+        Self::new(self.start, self.end, self.step * step)
+    }
+
+}
+
+impl Default for AxisSlice {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+// Bounds past isize::MAX cannot address any allocation, so saturating them keeps the conversions total
+const fn to_isize(bound: usize) -> isize {
+    if bound > isize::MAX as usize { isize::MAX } else { bound as isize }
+}
+
+impl From<Range<usize>> for AxisSlice {
+    fn from(range: Range<usize>) -> Self {
+        Self { start: to_isize(range.start), end: Some(to_isize(range.end)), step: 1 }
+    }
+}
+
+impl From<RangeInclusive<usize>> for AxisSlice {
+    fn from(range: RangeInclusive<usize>) -> Self {
+        let end = to_isize(*range.end()).saturating_add(1);
+        Self { start: to_isize(*range.start()), end: Some(end), step: 1 }
+    }
+}
+
+impl From<RangeFrom<usize>> for AxisSlice {
+    fn from(range: RangeFrom<usize>) -> Self {
+        Self { start: to_isize(range.start), end: None, step: 1 }
+    }
+}
+
+impl From<RangeTo<usize>> for AxisSlice {
+    fn from(range: RangeTo<usize>) -> Self {
+        Self { start: 0, end: Some(to_isize(range.end)), step: 1 }
+    }
+}
+
+impl From<RangeToInclusive<usize>> for AxisSlice {
+    fn from(range: RangeToInclusive<usize>) -> Self {
+        Self { start: 0, end: Some(to_isize(range.end).saturating_add(1)), step: 1 }
+    }
+}
+
+impl From<RangeFull> for AxisSlice {
+    fn from(_: RangeFull) -> Self {
+        Self::FULL
+    }
+}
+
+
+///
+/// Builds one [`AxisSlice`] per dimension from axis-named ranges, like `ndarray`'s `s![]`
+///
+/// Each entry names an axis and gives a range, optionally followed by `; step`. Axes not named select
+/// their whole extent. The number of dimensions is inferred from where the slices are used, and naming an
+/// axis past the last dimension or naming an axis twice fails to compile
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::AxisSlice;
+///
+/// let slices: [AxisSlice; 3] = s_ax![z: 1..3, x: ..; 2];
+/// assert_eq!(slices, [AxisSlice::new(0, None, 2), AxisSlice::FULL, AxisSlice::new(1, Some(3), 1)]);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: s_ax! names an axis past the last dimension
+/// let slices: [axmac::AxisSlice; 2] = s_ax![z: 1..3];
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! s_ax {
+    ( $( $axis:ident : $range:expr $( ; $step:expr )? ),* $(,)? ) => {{
+        const _: () = assert!(
+            $crate::__private::indices_are_unique(&[ $( $crate::ax!($axis) ),* ]),
+            "axmac: s_ax! names an axis more than once",
+        );
+        $crate::__private::AxisSlices::<{ $crate::__private::max_index(&[ $( $crate::ax!($axis) ),* ]) }>::build([
+            $( ($crate::ax!($axis), $crate::AxisSlice::from($range) $( .step_by($step) )?) ),*
+        ])
+    }};
+}
+
+
+///
+/// Builds one [`AxisSlice`] per dimension from axis-named ranges, placed in the dimension order of an array
+///
+/// The first argument lists the axis stored in each dimension, such as `axs![y, x]` for an image indexed
+/// `[row, column]`. Entries then take the same form as in [`s_ax!`] and may be given in any order, so
+/// slicing never depends on remembering which position an axis is in
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::AxisSlice;
+///
+/// // A volume stored with z outermost and x innermost
+/// let dims = axs![z, y, x];
+/// let slices = slice_by_name!(dims; y: 2..5, x: ..);
+/// assert_eq!(slices, [AxisSlice::FULL, AxisSlice::from(2..5), AxisSlice::FULL]);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If the dimension order is not a permutation of the first `N` axes
+///
+#[macro_export]
+macro_rules! slice_by_name {
+    ( $dims:expr; $( $axis:ident : $range:expr $( ; $step:expr )? ),* $(,)? ) => {
+        $crate::__private::slices_in_order(&$dims, $crate::s_ax![ $( $axis : $range $( ; $step )? ),* ])
+    };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn conversions_work() {
+        assert_eq!(AxisSlice::from(2..5), AxisSlice::new(2, Some(5), 1));
+        assert_eq!(AxisSlice::from(axr!(z..)), AxisSlice::new(2, None, 1));
+        assert_eq!(AxisSlice::from(axr!(..=y)), AxisSlice::new(0, Some(2), 1));
+        assert_eq!(AxisSlice::from(usize::MAX..), AxisSlice::new(isize::MAX, None, 1));
+        assert_eq!(AxisSlice::default().step_by(3).step_by(-2), AxisSlice::new(0, None, -6));
+    }
+
+    #[test]
+    fn s_ax_works() {
+        let slices: [AxisSlice; 4] = s_ax![w: axr!(..y), y: 4..; -1];
+        assert_eq!(slices[ax!(x)], AxisSlice::FULL);
+        assert_eq!(slices[ax!(y)], AxisSlice::new(4, None, -1));
+        assert_eq!(slices[ax!(w)], AxisSlice::new(0, Some(1), 1));
+        let empty: [AxisSlice; 2] = s_ax![];
+        assert_eq!(empty, [AxisSlice::FULL; 2]);
+    }
+
+    #[test]
+    fn slice_by_name_reorders() {
+        let dims = axs![y, w, x, z];
+        let slices = slice_by_name!(dims; x: 1..2, w: ..; 2, z: axr!(y..z));
+        assert_eq!(slices[0], AxisSlice::FULL);
+        assert_eq!(slices[1], AxisSlice::new(0, None, 2));
+        assert_eq!(slices[2], AxisSlice::from(1..2));
+        assert_eq!(slices[3], AxisSlice::from(1..2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_step_panics() {
+        let _ = AxisSlice::FULL.step_by(0);
+    }
+
+}
@@ -0,0 +1,210 @@
+//!
+//! Struct of arrays containers with one column per axis
+//!
+
+
+///
+/// Defines a struct of arrays container holding one `Vec` column per named axis
+///
+/// Points are pushed and read back as arrays, while each column can be borrowed as a slice, either by
+/// the method named after its axis or by indexing with an [`Axis`](crate::Axis). Columns are private,
+/// so they always hold the same number of values
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Axis;
+///
+/// soa_struct!(
+///     #[derive(Clone, Debug)]
+///     pub Points3 { x: f32, y: f32, z: f32 }
+/// );
+///
+/// let mut points = Points3::new();
+/// points.push([1.0, 2.0, 3.0]);
+/// points.push([4.0, 5.0, 6.0]);
+///
+/// assert_eq!(points.len(), 2);
+/// assert_eq!(points.y(), [2.0, 5.0]);
+/// assert_eq!(points[Axis::Z], [3.0, 6.0]);
+/// assert_eq!(points.get(1), Some([4.0, 5.0, 6.0]));
+///
+/// points[Axis::X][0] = -1.0;
+/// assert_eq!(points.get(0), Some([-1.0, 2.0, 3.0]));
+/// # }
+/// ```
+///
+/// Every column shares the element type of the first, and the axes must be listed in order starting from
+/// the first, so that a point's components line up with the columns
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: soa_struct! axes must be listed in order from the first
+/// soa_struct!(Plane { x: f32, z: f32 });
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// Indexing with an axis the container has no column for
+///
+#[macro_export]
+macro_rules! soa_struct {
+
+    (
+        $( #[$meta:meta] )* $vis:vis $name:ident {
+            $first:ident : $ty:ty $( , $axis:ident : $rest:ty )* $(,)?
+        }
+    ) => {
+        const _: () = assert!(
+            $crate::__private::axes_are_in_order(&[ $crate::ax!($first) $( , $crate::ax!($axis) )* ]),
+            "axmac: soa_struct! axes must be listed in order from the first",
+        );
+
+        $( #[$meta] )*
+        $vis struct $name {
+            $first: $crate::__private::Vec<$ty>,
+            $( $axis: $crate::__private::Vec<$rest>, )*
+        }
+
+        #[allow(dead_code)]
+        impl $name {
+
+            ///
+            /// The number of columns, one per axis
+            ///
+            pub const AXES: usize = [ stringify!($first) $( , stringify!($axis) )* ].len();
+
+            ///
+            /// Creates an empty container
+            ///
+            pub const fn new() -> Self {
+                Self { $first: $crate::__private::Vec::new() $( , $axis: $crate::__private::Vec::new() )* }
+            }
+
+            ///
+            /// Creates an empty container with room for `capacity` points in every column
+            ///
+            pub fn with_capacity(capacity: usize) -> Self {
+                Self {
+                    $first: $crate::__private::Vec::with_capacity(capacity)
+                    $( , $axis: $crate::__private::Vec::with_capacity(capacity) )*
+                }
+            }
+
+            ///
+            /// Returns the number of points
+            ///
+            pub fn len(&self) -> usize {
+                self.$first.len()
+            }
+
+            ///
+            /// Returns whether the container holds no points
+            ///
+            pub fn is_empty(&self) -> bool {
+                self.$first.is_empty()
+            }
+
+            ///
+            /// Appends a point, one component to each column
+            ///
+            pub fn push(&mut self, point: [$ty; Self::AXES]) {
+                let [$first $( , $axis )*] = point;
+                self.$first.push($first);
+                $( self.$axis.push($axis); )*
+            }
+
+            ///
+            /// Returns the point at `index`, or `None` if it is out of bounds
+            ///
+            pub fn get(&self, index: usize) -> Option<[$ty; Self::AXES]> {
+                if index < self.len() {
+                    Some([self.$first[index].clone() $( , self.$axis[index].clone() )*])
+                } else {
+                    None
+                }
+            }
+
+            ///
+            /// Removes every point, keeping the allocated capacity
+            ///
+            pub fn clear(&mut self) {
+                self.$first.clear();
+                $( self.$axis.clear(); )*
+            }
+
+            $crate::soa_struct!(@column $first $ty);
+            $( $crate::soa_struct!(@column $axis $rest); )*
+
+        }
+
+        impl ::core::default::Default for $name {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl ::core::ops::Index<$crate::Axis> for $name {
+            type Output = [$ty];
+
+            fn index(&self, axis: $crate::Axis) -> &[$ty] {
+                let axis = axis.as_usize();
+                if axis == $crate::ax!($first) {
+                    return &self.$first;
+                }
+                $( if axis == $crate::ax!($axis) { return &self.$axis; } )*
+                panic!(concat!("axmac: ", stringify!($name), " has no column for axis {}"), axis)
+            }
+        }
+
+        impl ::core::ops::IndexMut<$crate::Axis> for $name {
+            fn index_mut(&mut self, axis: $crate::Axis) -> &mut [$ty] {
+                let axis = axis.as_usize();
+                if axis == $crate::ax!($first) {
+                    return &mut self.$first;
+                }
+                $( if axis == $crate::ax!($axis) { return &mut self.$axis; } )*
+                panic!(concat!("axmac: ", stringify!($name), " has no column for axis {}"), axis)
+            }
+        }
+    };
+
+    ( @column $axis:ident $ty:ty ) => {
+        #[doc = concat!(" Returns the ", stringify!($axis), " component of every point")]
+        pub fn $axis(&self) -> &[$ty] {
+            &self.$axis
+        }
+    };
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use crate::Axis;
+
+    soa_struct!(Points2 { x: i32, y: i32 });
+
+    #[test]
+    fn columns_stay_in_step() {
+        let mut points = Points2::with_capacity(2);
+        points.push([1, 2]);
+        points.push([3, 4]);
+        assert_eq!(Points2::AXES, 2);
+        assert_eq!(points.x(), [1, 3]);
+        assert_eq!(&points[Axis::Y], [2, 4]);
+        assert_eq!(points.get(2), None);
+        points.clear();
+        assert!(points.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn missing_column_panics() {
+        let points = Points2::default();
+        let _ = &points[Axis::Z];
+    }
+
+}
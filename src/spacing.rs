@@ -0,0 +1,145 @@
+//!
+//! Grid spacing per axis and the time steps it allows, for PDE solvers
+//!
+
+use crate::AxisMap;
+
+
+impl<const N: usize> AxisMap<f64, N> {
+
+    ///
+    /// Creates the spacing of a grid dividing a domain of `lengths` into `cells` cells along each axis
+    ///
+    /// [`spacing!`] gives the spacing of each axis by name instead
+    ///
+    /// ```
+    /// use axmac::AxisMap;
+    ///
+    /// let h = AxisMap::spacing_over([2.0, 1.0], [20, 40]);
+    /// assert_eq!(h.as_array(), &[0.1, 0.025]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// If any axis has no cells
+    ///
+    pub fn spacing_over(lengths: [f64; N], cells: [usize; N]) -> Self {
+        assert!(!cells.contains(&0), "axmac: a grid needs at least one cell along every axis");
+        Self::from_fn(|axis| lengths[axis] / cells[axis] as f64)
+    }
+
+    ///
+    /// Returns the reciprocal of the spacing along every axis, the factor of a first difference
+    ///
+    pub fn inv_spacing(&self) -> Self {
+        self.map(|h| 1.0 / h)
+    }
+
+    ///
+    /// Returns the reciprocal of the squared spacing along every axis, the factor of a second difference
+    ///
+    pub fn inv_spacing_sq(&self) -> Self {
+        self.map(|h| 1.0 / (h * h))
+    }
+
+    ///
+    /// Returns the smallest spacing of any axis, or infinity without axes
+    ///
+    pub fn min_spacing(&self) -> f64 {
+        self.as_array().iter().fold(f64::INFINITY, |min, &h| min.min(h))
+    }
+
+    ///
+    /// Returns the longest time step of an explicit advection scheme meeting the CFL condition for
+    /// `courant`, given the speed along every axis
+    ///
+    /// The step is `courant / Σ |v| / h`, summed over the axes, and infinite when nothing moves. A
+    /// `courant` of one is the limit of first order upwind schemes
+    ///
+    /// ```
+    /// # #[macro_use] extern crate axmac; fn main() {
+    /// use axmac::AxisMap;
+    ///
+    /// let h = spacing!(x: 0.1, y: 0.1, z: 0.2);
+    /// let dt = h.cfl_step(&AxisMap::new([1.0, -1.0, 4.0]), 0.8);
+    /// assert!((dt - 0.02).abs() < 1e-15);
+    /// # }
+    /// ```
+    ///
+    pub fn cfl_step(&self, speeds: &AxisMap<f64, N>, courant: f64) -> f64 {
+        let rate: f64 = (0..N).map(|axis| crate::math::abs(speeds[axis]) / self[axis]).sum();
+        courant / rate
+    }
+
+    ///
+    /// Returns the longest stable time step of the explicit forward Euler scheme for diffusion with
+    /// `diffusivity`
+    ///
+    /// The step is `1 / (2 D Σ 1 / h²)`, summed over the axes, and infinite with no diffusion
+    ///
+    pub fn diffusion_step(&self, diffusivity: f64) -> f64 {
+        let rate: f64 = self.inv_spacing_sq().as_array().iter().sum();
+        1.0 / (2.0 * diffusivity * rate)
+    }
+
+}
+
+
+///
+/// Creates an [`AxisMap`](crate::AxisMap) of the grid spacing along each named axis
+///
+/// The axes may be named in any order, but must each be one of the first N axes and named once, or the
+/// call fails to compile
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let h = spacing!(z: 0.2, x: 0.1, y: 0.1);
+/// assert_eq!(h.as_array(), &[0.1, 0.1, 0.2]);
+/// assert_eq!(h.inv_spacing()[ax!(z)], 5.0);
+/// assert!((h.diffusion_step(1.0) - 1.0 / 450.0).abs() < 1e-15);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: spacing! needs each of the first N axes once
+/// let h = spacing!(x: 0.1, x: 0.2);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! spacing {
+    ( $( $axis:ident : $h:expr ),+ $(,)? ) => {{
+        const _: () = assert!(
+            $crate::__private::axes_are_permutation(&[ $( $crate::ax!($axis) ),+ ]),
+            "axmac: spacing! needs each of the first N axes once",
+        );
+        $crate::AxisMap::<f64, _>::new($crate::__private::by_axis([ $( ($crate::ax!($axis), $h) ),+ ], 0.0))
+    }};
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn derived_quantities() {
+        let h = AxisMap::new([0.5, 0.25]);
+        assert_eq!(h.inv_spacing().into_array(), [2.0, 4.0]);
+        assert_eq!(h.inv_spacing_sq().into_array(), [4.0, 16.0]);
+        assert_eq!(h.min_spacing(), 0.25);
+        assert_eq!(AxisMap::<f64, 0>::new([]).min_spacing(), f64::INFINITY);
+    }
+
+    #[test]
+    fn steps_are_infinite_at_rest() {
+        let h = spacing!(x: 0.1, y: 0.1);
+        assert_eq!(h.cfl_step(&AxisMap::splat(0.0), 1.0), f64::INFINITY);
+        assert_eq!(h.diffusion_step(0.0), f64::INFINITY);
+        assert!((h.diffusion_step(1.0) - 1.0 / 400.0).abs() < 1e-15);
+    }
+
+}
@@ -0,0 +1,175 @@
+//!
+//! Layouts of the spectra of real signals along a named axis
+//!
+
+use crate::Axis;
+
+
+///
+/// Returns the number of complex bins in the spectrum of `n` real samples, `n / 2 + 1`
+///
+/// With the extents of an N dimensional signal and an axis, returns the extents of its spectrum along
+/// that axis, every other extent unchanged. The macro is a const expression
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// assert_eq!(rfft_len!(1024), 513);
+/// assert_eq!(rfft_len!(7), 4);
+///
+/// // A spectrogram of 40 frames of 256 samples each, indexed [freq, time]
+/// const SPECTROGRAM: [usize; 2] = rfft_len!([256, 40]; freq);
+/// assert_eq!(SPECTROGRAM, [129, 40]);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! rfft_len {
+    ( $extents:expr; $axis:ident ) => {
+        $crate::__private::rfft_extents($extents, $crate::ax!($axis))
+    };
+    ( $n:expr ) => {
+        $n / 2 + 1
+    };
+}
+
+
+///
+/// Returns the frequency of bin `k` of an FFT over `n` samples taken at `sample_rate`, as an `f64` in the
+/// unit of the sample rate
+///
+/// The sample rate may be any type converting losslessly into `f64`
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// assert_eq!(bin_freq!(1; 1024, 44_100), 44_100.0 / 1024.0);
+/// assert_eq!(bin_freq!(rfft_len!(512) - 1; 512, 8_000.0), 4_000.0);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! bin_freq {
+    ( $k:expr; $n:expr, $sample_rate:expr ) => {
+        $crate::spectral::bin_freq($k, $n, f64::from($sample_rate))
+    };
+}
+
+
+///
+/// Returns the frequency of bin `k` of an FFT over `n` samples taken at `sample_rate`
+///
+/// See [`bin_freq!`] for a form taking any sample rate type
+///
+pub fn bin_freq(k: usize, n: usize, sample_rate: f64) -> f64 {
+    k as f64 * sample_rate / n as f64
+}
+
+
+///
+/// Returns the positions of the real and imaginary parts of bin `k` in the half-complex layout of the
+/// spectrum of `n` real samples, or `None` if there is no such bin
+///
+/// The half-complex layout packs the spectrum into `n` reals as `r0, r1, .., r(n/2), i((n+1)/2-1), .., i1`.
+/// The imaginary parts of bin zero and, for even `n`, of bin `n / 2` are always zero and so not stored
+///
+/// ```
+/// use axmac::spectral::halfcomplex_index;
+///
+/// assert_eq!(halfcomplex_index(0, 8), Some((0, None)));
+/// assert_eq!(halfcomplex_index(1, 8), Some((1, Some(7))));
+/// assert_eq!(halfcomplex_index(4, 8), Some((4, None)));
+/// assert_eq!(halfcomplex_index(5, 8), None);
+/// ```
+///
+pub const fn halfcomplex_index(k: usize, n: usize) -> Option<(usize, Option<usize>)> {
+    if k > n / 2 {
+        return None;
+    }
+    if k == 0 || 2 * k == n {
+        Some((k, None))
+    } else {
+        Some((k, Some(n - k)))
+    }
+}
+
+
+///
+/// Copies the half-complex spectra of `halfcomplex`, every line along `axis` of a densely packed grid
+/// with x contiguous, into `(re, im)` pairs in `complex`, whose extent along `axis` is the bin count
+///
+/// Lines of `n` reals expand to `n / 2 + 1` bins, zero filling the imaginary parts that are not stored,
+/// so the output has the extents given by [`rfft_len!`]
+///
+/// ```
+/// use axmac::{spectral::halfcomplex_to_complex, Axis};
+///
+/// // Two spectra of 4 samples, stored as the rows of a 4x2 grid
+/// let halfcomplex = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+/// let mut complex = [(0.0, 0.0); 6];
+/// halfcomplex_to_complex(&halfcomplex, [4, 2], Axis::X, &mut complex);
+/// assert_eq!(complex, [(1.0, 0.0), (2.0, 4.0), (3.0, 0.0), (5.0, 0.0), (6.0, 8.0), (7.0, 0.0)]);
+/// ```
+///
+/// # Panics
+///
+/// If `axis` is not less than `N`, or either buffer is not exactly the length of its extents
+///
+pub fn halfcomplex_to_complex<T: crate::Scalar, const N: usize>(halfcomplex: &[T], extents: [usize; N], axis: Axis, complex: &mut [(T, T)]) {
+    let axis = axis.as_usize();
+    assert!(axis < N, "axmac: axis is out of bounds for the extents");
+    let bins = crate::__private::rfft_extents(extents, axis);
+    assert_eq!(halfcomplex.len(), extents.iter().product::<usize>(), "axmac: half-complex buffer does not match its extents");
+    assert_eq!(complex.len(), bins.iter().product::<usize>(), "axmac: complex buffer does not match the spectrum extents");
+
+    let (from, to) = (crate::Shape::new(extents), crate::Shape::new(bins));
+    let n = extents[axis];
+    let mut lines = extents;
+    lines[axis] = 1;
+    for start in crate::NdIndex::new([0; N], lines) {
+        let mut coord = start;
+        for k in 0..bins[axis] {
+            let Some((re, im)) = halfcomplex_index(k, n) else { break };
+            coord[axis] = re;
+            let re = halfcomplex[from.flat_index_unchecked(&coord)];
+            let im = im.map_or(T::ZERO, |im| {
+                coord[axis] = im;
+                halfcomplex[from.flat_index_unchecked(&coord)]
+            });
+            coord[axis] = k;
+            complex[to.flat_index_unchecked(&coord)] = (re, im);
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn halfcomplex_covers_every_real() {
+        for n in 1..10 {
+            let mut seen = [0; 10];
+            for k in 0..rfft_len!(n) {
+                let (re, im) = halfcomplex_index(k, n).unwrap();
+                seen[re] += 1;
+                if let Some(im) = im {
+                    seen[im] += 1;
+                }
+            }
+            assert!(seen[..n].iter().all(|&count| count == 1));
+        }
+    }
+
+    #[test]
+    fn expands_along_outer_axis() {
+        // Two columns, each a spectrum of 3 samples along y
+        let halfcomplex = [1, 10, 2, 20, 3, 30];
+        let mut complex = [(0, 0); 4];
+        halfcomplex_to_complex(&halfcomplex, [2, 3], Axis::Y, &mut complex);
+        assert_eq!(complex, [(1, 0), (10, 0), (2, 3), (20, 30)]);
+        assert_eq!(bin_freq!(2; 8, 16u32), 4.0);
+    }
+
+}
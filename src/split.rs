@@ -0,0 +1,65 @@
+//!
+//! Mutable borrows of several named components at once
+//!
+
+
+///
+/// Borrows the components of `arr` along each named axis mutably, giving a tuple of the references
+///
+/// Takes anything sliceable, such as an array, a `Vec` or a mutable slice. Naming an axis twice would give
+/// two references to one component, so it fails to compile. An axis past the end of the container panics
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let mut velocity = [3.0, -9.8, 1.5];
+///
+/// // Trading vertical speed for forward speed without copying either out first
+/// let (y, z) = split_axes_mut!(velocity; y, z);
+/// *z -= *y * 0.1;
+/// *y = 0.0;
+/// assert_eq!(velocity, [3.0, 0.0, 2.48]);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: split_axes_mut! names an axis more than once
+/// let mut v = [0; 3];
+/// let (a, b) = split_axes_mut!(v; x, x);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! split_axes_mut {
+    ( $arr:expr ; $( $axis:ident ),+ $(,)? ) => {{
+        const _: () = assert!(
+            $crate::__private::indices_are_unique(&[ $( $crate::ax!($axis) ),+ ]),
+            "axmac: split_axes_mut! names an axis more than once",
+        );
+        let [ $( $axis ),+ ] = $crate::__private::disjoint_mut(&mut $arr[..], [ $( $crate::ax!($axis) ),+ ]);
+        ( $( $axis, )+ )
+    }};
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    #[test]
+    fn order_follows_the_names() {
+        let mut v = std::vec![1, 2, 3, 4];
+        let (w, x, z) = split_axes_mut!(v; w, x, z);
+        core::mem::swap(w, x);
+        *z += 10;
+        assert_eq!(v, [4, 2, 13, 1]);
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac: axis is past the end of the container")]
+    fn short_containers_panic() {
+        let mut v = [0; 2];
+        let _ = split_axes_mut!(v; x, z);
+    }
+
+}
@@ -0,0 +1,124 @@
+//!
+//! Per-axis statistics over slices of points
+//!
+//! Every function takes the points as `[T; N]` arrays and the axis as the `usize` produced by `ax!`. Points
+//! held as [`Coord`](crate::Coord)s can be passed through `Coord::as_array`. Comparisons use `PartialOrd`,
+//! so for floats the result is unspecified if a NaN is present
+//!
+//! ```
+//! # #[macro_use] extern crate axmac; fn main() {
+//! use axmac::stats;
+//!
+//! let points = [[1, 10], [4, 30], [7, 20]];
+//!
+//! assert_eq!(stats::axis_min(&points, ax!(y)), Some(10));
+//! assert_eq!(stats::axis_max(&points, ax!(x)), Some(7));
+//! assert_eq!(stats::axis_sum(&points, ax!(y)), 60);
+//! assert_eq!(stats::axis_mean(&points, ax!(x)), Some(4));
+//! # }
+//! ```
+//!
+
+use crate::aabb::{max, min};
+use crate::Scalar;
+
+
+///
+/// Returns the smallest component along `axis`, or `None` if there are no points
+///
+pub fn axis_min<T: Scalar, const N: usize>(points: &[[T; N]], axis: usize) -> Option<T> {
+    points.iter().map(|p| p[axis]).reduce(min)
+}
+
+///
+/// Returns the largest component along `axis`, or `None` if there are no points
+///
+pub fn axis_max<T: Scalar, const N: usize>(points: &[[T; N]], axis: usize) -> Option<T> {
+    points.iter().map(|p| p[axis]).reduce(max)
+}
+
+///
+/// Returns the smallest and largest components along `axis`, or `None` if there are no points
+///
+pub fn axis_min_max<T: Scalar, const N: usize>(points: &[[T; N]], axis: usize) -> Option<(T, T)> {
+    let mut iter = points.iter().map(|p| p[axis]);
+    let first = iter.next()?;
+    Some(iter.fold((first, first), |(lo, hi), v| (min(lo, v), max(hi, v))))
+}
+
+///
+/// Returns the sum of the components along `axis`
+///
+pub fn axis_sum<T: Scalar, const N: usize>(points: &[[T; N]], axis: usize) -> T {
+    points.iter().fold(T::ZERO, |sum, p| sum + p[axis])
+}
+
+///
+/// Returns the mean of the components along `axis`, or `None` if there are no points
+///
+/// # Panics
+///
+/// For integer types, if there are more points than the type can count
+///
+pub fn axis_mean<T: Scalar, const N: usize>(points: &[[T; N]], axis: usize) -> Option<T> {
+    if points.is_empty() {
+        None
+    } else {
+        Some(axis_sum(points, axis) / T::from_usize(points.len()))
+    }
+}
+
+///
+/// Returns the population variance of the components along `axis`, or `None` if there are no points
+///
+/// # Panics
+///
+/// For integer types, if there are more points than the type can count
+///
+pub fn axis_variance<T: Scalar, const N: usize>(points: &[[T; N]], axis: usize) -> Option<T> {
+    let mean = axis_mean(points, axis)?;
+    let squares = points.iter().fold(T::ZERO, |sum, p| {
+        // Ordered so unsigned types never underflow
+        let d = if p[axis] < mean { mean - p[axis] } else { p[axis] - mean };
+        sum + d * d
+    });
+    Some(squares / T::from_usize(points.len()))
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn it_works() {
+        let points = [[1.0, -2.0, 0.0], [3.0, 2.0, 0.0]];
+        assert_eq!(axis_min_max(&points, ax!(y)), Some((-2.0, 2.0)));
+        assert_eq!(axis_mean(&points, ax!(x)), Some(2.0));
+        assert_eq!(axis_variance(&points, ax!(y)), Some(4.0));
+        assert_eq!(axis_sum(&points, ax!(z)), 0.0);
+    }
+
+    #[test]
+    fn unsigned_variance_works() {
+        let points = [[2u32], [4], [6]];
+        assert_eq!(axis_variance(&points, ax!(x)), Some(2));
+    }
+
+    #[test]
+    fn empty_works() {
+        let points: [[i8; 2]; 0] = [];
+        assert_eq!(axis_min(&points, ax!(x)), None);
+        assert_eq!(axis_mean(&points, ax!(x)), None);
+        assert_eq!(axis_sum(&points, ax!(x)), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "axmac: the count 256 does not fit in u8")]
+    fn counts_past_the_type_panic() {
+        let _ = axis_mean(&[[0u8]; 256], ax!(x));
+    }
+
+}
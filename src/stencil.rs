@@ -0,0 +1,221 @@
+//!
+//! Stencil neighbourhoods of grid cells, for finite differences and cellular automata
+//!
+
+use core::iter::FusedIterator;
+
+use crate::Shape;
+
+
+///
+/// What a stencil sees where its offsets run past the edge of the grid
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Boundary<T> {
+    /// Positions outside the grid are left out
+    Skip,
+    /// Positions outside the grid hold this value
+    Constant(T),
+    /// Positions outside the grid take the value of the nearest edge
+    Clamp,
+    /// The grid repeats, as on a torus
+    Wrap,
+    /// The grid is mirrored about its edge cells, so the position one before the start is the second
+    Reflect,
+}
+
+impl<T> Boundary<T> {
+
+    ///
+    /// Returns the position within `shape` standing for `coord` moved by `offset`, or `None` where the
+    /// boundary supplies no position of the grid
+    ///
+    pub fn resolve<const N: usize>(&self, shape: &Shape<N>, coord: &[usize; N], offset: &[isize; N]) -> Option<[usize; N]> {
+        let mut out = [0; N];
+        for axis in 0..N {
+            let extent = shape.extent(axis) as isize;
+            let pos = coord[axis] as isize + offset[axis];
+            out[axis] = match self {
+                _ if (0..extent).contains(&pos) => pos,
+                _ if extent == 0 => return None,
+                Self::Skip | Self::Constant(_) => return None,
+                Self::Clamp => pos.clamp(0, extent - 1),
+                Self::Wrap => pos.rem_euclid(extent),
+                Self::Reflect if extent == 1 => 0,
+                Self::Reflect => {
+                    let period = 2 * (extent - 1);
+                    let p = pos.rem_euclid(period);
+                    if p < extent { p } else { period - p }
+                }
+            } as usize;
+        }
+        Some(out)
+    }
+
+}
+
+
+///
+/// Iterator over the values a stencil sees around one cell, in the order of its offsets
+///
+/// Created by [`stencil_values`]
+///
+#[derive(Clone, Debug)]
+pub struct StencilValues<'a, T, const N: usize> {
+    buf: &'a [T],
+    shape: Shape<N>,
+    coord: [usize; N],
+    offsets: core::slice::Iter<'a, [isize; N]>,
+    boundary: Boundary<T>,
+}
+
+impl<'a, T: Copy, const N: usize> Iterator for StencilValues<'a, T, N> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        for offset in self.offsets.by_ref() {
+            match self.boundary.resolve(&self.shape, &self.coord, offset) {
+                Some(pos) => return Some(self.buf[self.shape.flat_index_unchecked(&pos)]),
+                None => {
+                    if let Boundary::Constant(value) = self.boundary {
+                        return Some(value);
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.offsets.len();
+        match self.boundary {
+            Boundary::Skip => (0, Some(len)),
+            _ => (len, Some(len)),
+        }
+    }
+}
+
+impl<'a, T: Copy, const N: usize> FusedIterator for StencilValues<'a, T, N> {}
+
+
+///
+/// Iterates over the values of `buf` at every offset of a stencil around `coord`, with `boundary` deciding
+/// what lies past the edge of the grid
+///
+/// Offsets usually come from [`stencil_offsets!`], but any list works, such as the weighted taps of a
+/// finite difference zipped with their coefficients
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{stencil_values, Boundary, Shape};
+///
+/// // Counting the live neighbours of a corner cell in Conway's game of life on a torus
+/// let shape = Shape::new([4, 4]);
+/// let mut cells = [0u8; 16];
+/// for coord in [[1, 0], [3, 3], [0, 3], [2, 2]] {
+///     cells[shape.flat_index_unchecked(&coord)] = 1;
+/// }
+///
+/// const MOORE: [[isize; 2]; 8] = stencil_offsets!(box: 1);
+/// let live: u8 = stencil_values(&cells, &shape, [0, 0], &MOORE, Boundary::Wrap).sum();
+/// assert_eq!(live, 3);
+/// let live: u8 = stencil_values(&cells, &shape, [0, 0], &MOORE, Boundary::Skip).sum();
+/// assert_eq!(live, 1);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If `coord` lies outside `shape`, or `buf` is too short for `shape`
+///
+pub fn stencil_values<'a, T: Copy, const N: usize>(
+    buf: &'a [T],
+    shape: &Shape<N>,
+    coord: [usize; N],
+    offsets: &'a [[isize; N]],
+    boundary: Boundary<T>,
+) -> StencilValues<'a, T, N> {
+    assert!(shape.contains(&coord), "axmac: stencil centre is outside the shape");
+    assert!(buf.len() >= shape.required_len(), "axmac: buffer is too short for its shape");
+    StencilValues { buf, shape: *shape, coord, offsets: offsets.iter(), boundary }
+}
+
+
+///
+/// Lists the offsets of a stencil neighbourhood as a const array of per-axis offsets
+///
+/// `star: r` gives the cells up to `r` positions away along a single axis, axis by axis with the negative
+/// offset of each distance first, as the von Neumann neighbourhood and the second order Laplacian use.
+/// `box: r` gives every cell of the box reaching `r` positions along every axis, with the x axis varying
+/// fastest, as the Moore neighbourhood uses. The centre is left out of both. The number of axes comes from
+/// the array type the offsets are given, or follows the radius after a semicolon
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// const STAR: [[isize; 2]; 4] = stencil_offsets!(star: 1);
+/// assert_eq!(STAR, [[-1, 0], [1, 0], [0, -1], [0, 1]]);
+///
+/// let moore = stencil_offsets!(box: 1; 3);
+/// assert_eq!(moore.len(), 26);
+/// assert_eq!(moore[0], [-1, -1, -1]);
+/// assert!(!moore.contains(&[0, 0, 0]));
+///
+/// assert_eq!(stencil_offsets!(star: 2; 3).len(), 12);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: a star stencil holds two offsets per axis and distance
+/// const STAR: [[isize; 2]; 6] = stencil_offsets!(star: 1);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! stencil_offsets {
+    ( star : $radius:expr ; $n:expr $(,)? ) => {
+        $crate::__private::star_offsets::<{ $n }, { 2 * $n * $radius }>($radius)
+    };
+    ( box : $radius:expr ; $n:expr $(,)? ) => {
+        $crate::__private::box_offsets::<{ $n }, { $crate::__private::box_len($n, $radius) }>($radius)
+    };
+    ( star : $radius:expr $(,)? ) => {
+        const { $crate::__private::star_offsets($radius) }
+    };
+    ( box : $radius:expr $(,)? ) => {
+        const { $crate::__private::box_offsets($radius) }
+    };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn boundaries_resolve() {
+        let shape = Shape::new([4, 1]);
+        let at = |b: Boundary<u8>, x| b.resolve(&shape, &[0, 0], &[x, 1]);
+        assert_eq!(at(Boundary::Skip, -1), None);
+        assert_eq!(at(Boundary::Clamp, -3), Some([0, 0]));
+        assert_eq!(at(Boundary::Wrap, -1), Some([3, 0]));
+        assert_eq!(at(Boundary::Reflect, -1), Some([1, 0]));
+        assert_eq!(at(Boundary::Reflect, 5), Some([1, 0]));
+        assert_eq!(at(Boundary::Reflect, 7), Some([1, 0]));
+    }
+
+    #[test]
+    fn laplacian_with_constant_edges() {
+        let shape = Shape::new([3]);
+        let buf = [1.0, 4.0, 9.0];
+        const STAR: [[isize; 1]; 2] = stencil_offsets!(star: 1);
+        let at = |x| -> f64 {
+            stencil_values(&buf, &shape, [x], &STAR, Boundary::Constant(0.0)).sum::<f64>() - 2.0 * buf[x]
+        };
+        assert_eq!([at(0), at(1), at(2)], [2.0, 2.0, -14.0]);
+        assert_eq!(stencil_values(&buf, &shape, [0], &STAR, Boundary::Skip).size_hint(), (0, Some(2)));
+    }
+
+}
@@ -0,0 +1,148 @@
+//!
+//! Sweep and prune broad-phase helpers
+//!
+
+use crate::{Aabb, Axis, Scalar};
+
+
+///
+/// Returns the axis along which the centers of `boxes` are most spread out, or `None` if there are no
+/// boxes or no axes
+///
+/// Sweeping along the axis of greatest variance leaves the fewest boxes overlapping along it, and so the
+/// fewest candidate pairs. Ties go to the lowest axis
+///
+/// ```
+/// use axmac::{best_sweep_axis, Aabb, Axis};
+///
+/// // A row of boxes laid out along y
+/// let boxes = [
+///     Aabb::new([0.0, 0.0], [1.0, 1.0]),
+///     Aabb::new([0.5, 4.0], [1.5, 5.0]),
+///     Aabb::new([0.0, 8.0], [1.0, 9.0]),
+/// ];
+/// assert_eq!(best_sweep_axis(&boxes), Some(Axis::Y));
+/// ```
+///
+/// # Panics
+///
+/// If `N` is greater than 4, since the axis is reported as an [`Axis`], or for integer types if there are
+/// more boxes than the type can count
+///
+pub fn best_sweep_axis<T: Scalar, const N: usize>(boxes: &[Aabb<T, N>]) -> Option<Axis> {
+    assert!(N <= Axis::COUNT, "axmac: axes past w have no Axis to report");
+    if boxes.is_empty() {
+        return None;
+    }
+
+    let count = T::from_usize(boxes.len());
+    let mut best: Option<(usize, T)> = None;
+    for axis in 0..N {
+        let mean = boxes.iter().fold(T::ZERO, |sum, b| sum + b.center()[axis]) / count;
+        let variance = boxes.iter().fold(T::ZERO, |sum, b| {
+            let c = b.center()[axis];
+            // Ordered so unsigned types never underflow
+            let d = if c > mean { c - mean } else { mean - c };
+            sum + d * d
+        }) / count;
+        if best.is_none_or(|(_, v)| variance > v) {
+            best = Some((axis, variance));
+        }
+    }
+    best.and_then(|(axis, _)| Axis::from_usize(axis))
+}
+
+///
+/// Sorts `boxes` by their lower bound along `axis`, as [`sweep_pairs`] expects
+///
+/// Bounds that never compare, such as NaN, order after all others, so those boxes end up last
+///
+pub fn sort_along<T: PartialOrd, const N: usize>(boxes: &mut [Aabb<T, N>], axis: Axis) {
+    let i = axis.as_usize();
+    boxes.sort_unstable_by(|a, b| crate::math::nan_last(&a.min[i], &b.min[i]));
+}
+
+///
+/// Calls `f` with the indices of every pair of boxes whose extents overlap along `axis`
+///
+/// `boxes` must already be sorted by [`sort_along`] for the same axis. Pairs are reported once, lower
+/// index first. They are only candidates, and need a full [`Aabb::intersects`] test
+///
+/// ```
+/// use axmac::{sort_along, sweep_pairs, Aabb, Axis};
+///
+/// let mut boxes = [
+///     Aabb::new([5, 0], [6, 1]),
+///     Aabb::new([0, 0], [2, 1]),
+///     Aabb::new([1, 9], [3, 9]),
+/// ];
+/// sort_along(&mut boxes, Axis::X);
+///
+/// let mut pairs = Vec::new();
+/// sweep_pairs(&boxes, Axis::X, |a, b| pairs.push((a, b)));
+/// assert_eq!(pairs, [(0, 1)]);
+/// ```
+///
+pub fn sweep_pairs<T: PartialOrd, const N: usize, F: FnMut(usize, usize)>(boxes: &[Aabb<T, N>], axis: Axis, mut f: F) {
+    let i = axis.as_usize();
+    for (a, first) in boxes.iter().enumerate() {
+        for (b, second) in boxes.iter().enumerate().skip(a + 1) {
+            if second.min[i] > first.max[i] {
+                break;
+            }
+            f(a, b);
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn best_sweep_axis_works() {
+        let boxes = [
+            Aabb::new([0u32, 0, 0], [1, 1, 1]),
+            Aabb::new([1, 0, 20], [2, 1, 21]),
+            Aabb::new([2, 1, 40], [3, 2, 41]),
+        ];
+        assert_eq!(best_sweep_axis(&boxes), Some(Axis::Z));
+        assert_eq!(best_sweep_axis::<f32, 2>(&[]), None);
+        assert_eq!(best_sweep_axis(&boxes[..1]), Some(Axis::X));
+    }
+
+    #[test]
+    fn sweep_finds_every_intersecting_pair() {
+        let mut boxes = [
+            Aabb::new([0.0, 0.0], [1.0, 1.0]),
+            Aabb::new([3.0, 0.0], [4.0, 1.0]),
+            Aabb::new([0.5, 5.0], [3.5, 6.0]),
+            Aabb::new([0.9, 0.9], [2.0, 2.0]),
+        ];
+        let axis = best_sweep_axis(&boxes).unwrap();
+        sort_along(&mut boxes, axis);
+
+        let mut candidates = Vec::new();
+        sweep_pairs(&boxes, axis, |a, b| candidates.push((a, b)));
+        for a in 0..boxes.len() {
+            for b in a + 1..boxes.len() {
+                if boxes[a].intersects(&boxes[b]) {
+                    assert!(candidates.contains(&(a, b)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn nan_bounds_sort_last() {
+        let mut boxes = [3.0, f64::NAN, 1.0, f64::NAN, 2.0].map(|x| Aabb::new([x], [x]));
+        sort_along(&mut boxes, Axis::X);
+        assert_eq!(boxes[..3].iter().map(|b| b.min[0]).collect::<Vec<_>>(), [1.0, 2.0, 3.0]);
+        assert!(boxes[3..].iter().all(|b| b.min[0].is_nan()));
+    }
+
+}
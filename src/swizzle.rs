@@ -0,0 +1,209 @@
+//!
+//! GLSL style swizzles over fixed-length values
+//!
+
+
+///
+/// Gathers components of an array, [`Coord`](crate::Coord) or [`AxisMap`](crate::AxisMap) by axis name into
+/// a new array
+///
+/// Components may be repeated or left out, and the result may be longer than the input. Naming an axis
+/// past the end of the input fails to compile
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Coord3;
+///
+/// let v = [1, 2, 3, 4];
+/// assert_eq!(swz!(v; w, w, x), [4, 4, 1]);
+///
+/// let c = Coord3::new([0.5, 1.5, 2.5]);
+/// assert_eq!(swz!(c; z, y, x), [2.5, 1.5, 0.5]);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: swizzle names an axis past the last component of its input
+/// let v = swz!([1, 2]; z, x);
+/// # }
+/// ```
+///
+/// Separating the value from the axes with a comma instead indexes it directly, so slices, `Vec`s and
+/// any other type indexable by `usize` can be swizzled too. Axes may be listed bare or in brackets for an
+/// array, or in parentheses for a tuple. These forms check bounds at runtime, panicking like ordinary
+/// indexing
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let v: &[i32] = &[1, 2, 3, 4];
+/// assert_eq!(swz!(v, x z y), [1, 3, 2]);
+/// assert_eq!(swz!(v, [w, w, x]), [4, 4, 1]);
+/// assert_eq!(swz!(v, (y, x)), (2, 1));
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! swz {
+    ( $v:expr; $( $axis:ident ),+ $(,)? ) => {
+        $crate::__private::Swizzle::<
+            { $crate::__private::max_index(&[ $( $crate::ax!($axis) ),+ ]) },
+            { [ $( $crate::ax!($axis) ),+ ].len() },
+        >::gather(&$v, [ $( $crate::ax!($axis) ),+ ])
+    };
+
+    // Indexing forms, for anything indexable by `usize`
+    //  swz!(v, [w, w, x]) => [v[3], v[3], v[0]]
+    ( $v:expr, [ $( $axis:ident ),+ $(,)? ] ) => {{
+        let v = &$v;
+        [ $( v[$crate::ax!($axis)] ),+ ]
+    }};
+    //  swz!(v, (z, x)) => (v[2], v[0])
+    ( $v:expr, ( $( $axis:ident ),+ $(,)? ) ) => {{
+        let v = &$v;
+        ( $( v[$crate::ax!($axis)], )+ )
+    }};
+    //  swz!(v, x z y) => [v[0], v[2], v[1]]
+    ( $v:expr, $( $axis:ident )+ ) => { $crate::swz!($v, [ $( $axis ),+ ]) };
+}
+
+
+///
+/// Writes the components of a source to the named components of an array, [`Coord`](crate::Coord) or
+/// [`AxisMap`](crate::AxisMap), leaving the others untouched
+///
+/// Follows GLSL write-mask semantics: the source must hold exactly one component per axis named, and no
+/// axis may be named twice. Both mistakes fail to compile, as does naming an axis past the end of the
+/// destination
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Coord4;
+///
+/// let mut v = [0, 0, 0];
+/// set_swz!(v; x, z = [1, 3]);
+/// assert_eq!(v, [1, 0, 3]);
+///
+/// let mut c = Coord4::splat(0.0);
+/// set_swz!(c; w, y = swz!([1.0, 2.0]; y, x));
+/// assert_eq!(c, Coord4::new([0.0, 1.0, 0.0, 2.0]));
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: a swizzle write mask cannot name an axis twice
+/// let mut v = [0, 0, 0];
+/// set_swz!(v; x, x = [1, 2]);
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// # #[macro_use] extern crate axmac; fn main() {
+/// // error: axmac: a swizzle write needs exactly one source component per axis named
+/// let mut v = [0, 0, 0];
+/// set_swz!(v; x, y = [1, 2, 3]);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! set_swz {
+    ( $dst:expr; $( $axis:ident ),+ = $src:expr ) => {{
+        const {
+            assert!(
+                $crate::__private::indices_are_unique(&[ $( $crate::ax!($axis) ),+ ]),
+                "axmac: a swizzle write mask cannot name an axis twice",
+            )
+        };
+        $crate::__private::Swizzle::<
+            { $crate::__private::max_index(&[ $( $crate::ax!($axis) ),+ ]) },
+            { [ $( $crate::ax!($axis) ),+ ].len() },
+        >::scatter(&mut $dst, [ $( $crate::ax!($axis) ),+ ], &$src)
+    }};
+}
+
+
+///
+/// Broadcasts one named component to every component, as `v.yyyy` does in a shader
+///
+/// The result has the type and length of the input, unless a target array type is given after `=>`.
+/// Naming an axis past the end of the input fails to compile
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::Coord3;
+///
+/// assert_eq!(splat_ax!([1, 2, 3]; y), [2, 2, 2]);
+/// assert_eq!(splat_ax!(Coord3::new([1, 2, 3]); z), Coord3::splat(3));
+/// assert_eq!(splat_ax!([1.0, 0.5]; y => [f32; 4]), [0.5; 4]);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! splat_ax {
+    ( $v:expr; $axis:ident ) => {
+        $crate::__private::Swizzle::<{ $crate::ax!($axis) }, 1>::splat(&$v)
+    };
+    ( $v:expr; $axis:ident => [ $t:ty; $n:expr ] ) => {{
+        let splat: [$t; $n] = $crate::__private::Swizzle::<{ $crate::ax!($axis) }, { $n }>::splat_to(&$v);
+        splat
+    }};
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use crate::{AxisMap, Coord2};
+
+    #[test]
+    fn it_works() {
+        let v = [10, 20, 30];
+        assert_eq!(swz!(v; x), [10]);
+        assert_eq!(swz!(v; z, z, z, z, y), [30, 30, 30, 30, 20]);
+        assert_eq!(swz!(&v; y, x), [20, 10]);
+    }
+
+    #[test]
+    fn wrappers_work() {
+        assert_eq!(swz!(Coord2::new(['a', 'b']); y, x, y), ['b', 'a', 'b']);
+        assert_eq!(swz!(AxisMap::<u8>::from_fn(|i| i as u8); w, x), [3, 0]);
+    }
+
+    #[test]
+    fn indexing_forms_work() {
+        let v = std::vec![1.5, 2.5, 3.5];
+        assert_eq!(swz!(v, z), [3.5]);
+        assert_eq!(swz!(v[..2], y x y), [2.5, 1.5, 2.5]);
+        assert_eq!(swz!(v, (z,)), (3.5,));
+        assert_eq!(swz!([7u8, 8], [y, x,]), [8, 7]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn indexing_forms_check_bounds() {
+        let v: &[u8] = &[1, 2];
+        let _ = swz!(v, x z);
+    }
+
+    #[test]
+    fn splat_ax_works() {
+        let v = [1u8, 2, 3, 4];
+        assert_eq!(splat_ax!(v; w), [4; 4]);
+        assert_eq!(splat_ax!(&v; x => [u8; 2]), [1, 1]);
+        assert_eq!(splat_ax!(AxisMap::<i32, 2>::new([5, 6]); x).into_array(), [5, 5]);
+    }
+
+    #[test]
+    fn set_swz_works() {
+        let mut v = [1, 2, 3, 4];
+        set_swz!(v; w, x = [40, 10]);
+        assert_eq!(v, [10, 2, 3, 40]);
+
+        let mut map = AxisMap::<char, 3>::splat('-');
+        set_swz!(map; z = swz!(Coord2::new(['q', 'z']); y));
+        assert_eq!(map.into_array(), ['-', '-', 'z']);
+    }
+
+}
@@ -0,0 +1,117 @@
+//!
+//! Index symbols of tensor algebra
+//!
+
+
+///
+/// Returns `1` if `i` and `j` are the same axis, `0` otherwise
+///
+pub const fn kronecker_delta(i: usize, j: usize) -> i32 {
+    if i == j { 1 } else { 0 }
+}
+
+///
+/// Returns the sign of the permutation `indices` of `0..indices.len()`, or `0` if any index is repeated or
+/// out of range
+///
+/// With three indices this is the Levi-Civita symbol `ε_ijk` behind cross products and 3x3 determinants
+///
+pub const fn levi_civita(indices: &[usize]) -> i32 {
+    if !crate::__private::axes_are_permutation(indices) {
+        return 0;
+    }
+    // Every pair out of order flips the sign
+    let mut sign = 1;
+    let mut i = 0;
+    while i < indices.len() {
+        let mut j = i + 1;
+        while j < indices.len() {
+            if indices[i] > indices[j] {
+                sign = -sign;
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    sign
+}
+
+
+///
+/// The Kronecker delta of two axes, as a constant `i32`
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// assert_eq!(delta!(x, x), 1);
+/// assert_eq!(delta!(x, y), 0);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! delta {
+    ( $i:ident, $j:ident $(,)? ) => {{
+        const DELTA: i32 = $crate::kronecker_delta($crate::ax!($i), $crate::ax!($j));
+        DELTA
+    }};
+}
+
+///
+/// The Levi-Civita symbol of a sequence of axes, as a constant `i32`
+///
+/// `1` for even permutations of the first `n` axes, `-1` for odd ones and `0` when an axis is repeated
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// assert_eq!(epsilon!(x, y, z), 1);
+/// assert_eq!(epsilon!(y, x, z), -1);
+/// assert_eq!(epsilon!(z, z, x), 0);
+///
+/// // The z component of a cross product, written index-wise
+/// let (a, b) = ([1, 2, 3], [4, 5, 6]);
+/// let z = epsilon!(z, x, y) * a[ax!(x)] * b[ax!(y)] + epsilon!(z, y, x) * a[ax!(y)] * b[ax!(x)];
+/// assert_eq!(z, -3);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! epsilon {
+    ( $( $axis:ident ),+ $(,)? ) => {{
+        const EPSILON: i32 = $crate::levi_civita(&[ $( $crate::ax!($axis) ),+ ]);
+        EPSILON
+    }};
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn epsilon_works() {
+        assert_eq!(epsilon!(x, y), 1);
+        assert_eq!(epsilon!(y, x), -1);
+        assert_eq!(epsilon!(z, x, y), 1);
+        assert_eq!(epsilon!(x, z, y), -1);
+        assert_eq!(epsilon!(w, z, y, x), 1);
+        assert_eq!(levi_civita(&[0, 1, 3]), 0);
+    }
+
+    #[test]
+    fn contraction_identity_holds() {
+        // Σ_k ε_ijk ε_lmk = δ_il δ_jm - δ_im δ_jl
+        let d = kronecker_delta;
+        for i in 0..3 {
+            for j in 0..3 {
+                for l in 0..3 {
+                    for m in 0..3 {
+                        let lhs: i32 = (0..3).map(|k| levi_civita(&[i, j, k]) * levi_civita(&[l, m, k])).sum();
+                        assert_eq!(lhs, d(i, l) * d(j, m) - d(i, m) * d(j, l));
+                    }
+                }
+            }
+        }
+        assert_eq!(delta!(w, w), 1);
+    }
+}
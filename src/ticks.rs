@@ -0,0 +1,380 @@
+//!
+//! Scales and tick positions for plot axes
+//!
+//! Linear scales tick at round numbers and log scales at powers of ten. Logarithms and exponentials come
+//! from [`DefaultMath`], the standard library's with the `std` feature and portable ones otherwise
+//!
+
+use core::iter::FusedIterator;
+
+use crate::math::{abs, DefaultMath};
+use crate::{Axis, AxisMap, HasAxes, MathBackend};
+
+
+///
+/// How values are spread along a plot axis, mapping data values to positions and back
+///
+/// Plot code usually holds one per axis, as an [`AxisMap<AxisScale, 2>`](crate::AxisMap), which can
+/// [transform](AxisMap::apply) whole points at once
+///
+/// ```
+/// use axmac::{Axis, AxisMap, AxisScale};
+///
+/// let scales = AxisMap::new([AxisScale::Linear, AxisScale::Log10]);
+/// let y: Vec<f64> = scales[Axis::Y].ticks(0.5, 2000.0, 5).collect();
+/// assert_eq!(y, [1.0, 10.0, 100.0, 1000.0]);
+///
+/// let symlog = AxisScale::SymLog { linthresh: 1.0 };
+/// assert_eq!(symlog.apply(0.0), 0.0);
+/// assert!((symlog.apply(-9.0) + 1.0).abs() < 1e-12);
+///
+/// let square = AxisScale::Custom { apply: |v| v * v, invert: |v| v.sqrt() };
+/// assert_eq!(square.invert(square.apply(3.0)), 3.0);
+/// ```
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub enum AxisScale {
+    /// Equal distances stand for equal differences
+    #[default]
+    Linear,
+    /// Equal distances stand for equal ratios, for positive values spanning several orders of magnitude
+    Log10,
+    /// Logarithmic in both directions away from zero, and nearly linear within `linthresh` of it, for
+    /// values of either sign spanning several orders of magnitude
+    SymLog {
+        /// The distance from zero within which the scale is nearly linear
+        linthresh: f64,
+    },
+    /// A mapping given by its two directions, which should invert each other
+    Custom {
+        /// Maps data values to positions
+        apply: fn(f64) -> f64,
+        /// Maps positions back to data values
+        invert: fn(f64) -> f64,
+    },
+}
+
+impl AxisScale {
+
+    ///
+    /// Maps a data value to its position along the axis
+    ///
+    /// Log scales give NaN for negative values and negative infinity for zero. Symmetric log scales map
+    /// `v` to `sign(v) * log10(1 + |v| / linthresh)`
+    ///
+    pub fn apply(self, v: f64) -> f64 {
+        let math = DefaultMath::default();
+        match self {
+            AxisScale::Linear => v,
+            AxisScale::Log10 => math.ln(v) / core::f64::consts::LN_10,
+            AxisScale::SymLog { linthresh } => sign(v) * math.ln_1p(abs(v) / linthresh) / core::f64::consts::LN_10,
+            AxisScale::Custom { apply, .. } => apply(v),
+        }
+    }
+
+    ///
+    /// Maps a position along the axis back to its data value, undoing [`apply`](Self::apply)
+    ///
+    pub fn invert(self, p: f64) -> f64 {
+        let math = DefaultMath::default();
+        match self {
+            AxisScale::Linear => p,
+            AxisScale::Log10 => math.exp(p * core::f64::consts::LN_10),
+            AxisScale::SymLog { linthresh } => sign(p) * linthresh * math.exp_m1(abs(p) * core::f64::consts::LN_10),
+            AxisScale::Custom { invert, .. } => invert(p),
+        }
+    }
+
+    ///
+    /// Returns about `target_count` tick positions covering `min..=max` on this scale, as data values
+    ///
+    /// Log scales tick at powers of ten, skipping decades evenly when more than `target_count` of them
+    /// fit, and give no ticks unless `min` is positive. Other scales use [`ticks_along`]
+    ///
+    pub fn ticks(self, min: f64, max: f64, target_count: usize) -> Ticks {
+        match self {
+            AxisScale::Log10 => log_ticks(min, max, target_count),
+            _ => ticks_along(min, max, target_count),
+        }
+    }
+
+}
+
+impl<const N: usize> AxisMap<AxisScale, N> {
+
+    ///
+    /// Maps every component of a point through the scale of its axis
+    ///
+    /// The point must have one component per scale, or the call fails to compile
+    ///
+    /// ```
+    /// use axmac::{AxisMap, AxisScale};
+    ///
+    /// let scales = AxisMap::new([AxisScale::Linear, AxisScale::Log10]);
+    /// let p = scales.apply([0.5, 100.0]);
+    /// assert!((p[1] - 2.0).abs() < 1e-12);
+    /// assert!((scales.invert(p)[1] - 100.0).abs() < 1e-9);
+    /// ```
+    ///
+    pub fn apply<V: HasAxes<Item = f64>>(&self, point: V) -> V {
+        const { assert!(N == V::DIM, "axmac: AxisMap::apply needs one scale per axis") };
+        self.each(point, AxisScale::apply)
+    }
+
+    ///
+    /// Maps every component of a position back through the scale of its axis, undoing
+    /// [`apply`](Self::apply)
+    ///
+    pub fn invert<V: HasAxes<Item = f64>>(&self, position: V) -> V {
+        const { assert!(N == V::DIM, "axmac: AxisMap::invert needs one scale per axis") };
+        self.each(position, AxisScale::invert)
+    }
+
+    fn each<V: HasAxes<Item = f64>>(&self, mut value: V, f: fn(AxisScale, f64) -> f64) -> V {
+        for axis in Axis::iter() {
+            if let (Some(&scale), Some(c)) = (self.get(axis.as_usize()), value.get_axis_mut(axis)) {
+                *c = f(scale, *c);
+            }
+        }
+        value
+    }
+
+}
+
+
+///
+/// Returns about `target_count` tick positions covering `min..=max`, spaced by one, two or five times a
+/// power of ten
+///
+/// Every tick lies inside the range, and ticks are computed from their index rather than by repeated
+/// addition, so they print as the round numbers they stand for. An empty or non-finite range, or a
+/// `target_count` of zero, gives no ticks
+///
+/// ```
+/// use axmac::ticks_along;
+///
+/// let x: Vec<f64> = ticks_along(0.0, 1.0, 5).collect();
+/// assert_eq!(x, [0.0, 0.2, 0.4, 0.6, 0.8, 1.0]);
+///
+/// let y = ticks_along(-13.0, 87.0, 4);
+/// assert_eq!(y.step(), 20.0);
+/// assert_eq!(y.collect::<Vec<_>>(), [0.0, 20.0, 40.0, 60.0, 80.0]);
+/// ```
+///
+pub fn ticks_along(min: f64, max: f64, target_count: usize) -> Ticks {
+    let span = max - min;
+    if !(span > 0.0 && span.is_finite()) || target_count == 0 {
+        return Ticks::EMPTY;
+    }
+
+    let raw = span / target_count as f64;
+    let mut exp = exponent_of(raw);
+    let frac = raw / pow10(exp);
+    let mantissa = if frac < 1.5 {
+        1.0
+    } else if frac < 3.0 {
+        2.0
+    } else if frac < 7.0 {
+        5.0
+    } else {
+        exp += 1;
+        1.0
+    };
+
+    let step = scaled(mantissa, exp);
+    let first = ceil(min / step - SLACK);
+    let last = floor(max / step + SLACK);
+    Ticks { log: false, first, mantissa, exp, front: 0, back: (last - first + 1.0) as usize }
+}
+
+fn log_ticks(min: f64, max: f64, target_count: usize) -> Ticks {
+    if !(min > 0.0 && max >= min && max.is_finite()) || target_count == 0 {
+        return Ticks::EMPTY;
+    }
+
+    let mut first = exponent_of(min);
+    if pow10(first) < min * (1.0 - SLACK) {
+        first += 1;
+    }
+    // The slack would carry the largest floats to infinity
+    let last = exponent_of((max * (1.0 + SLACK)).min(f64::MAX));
+    if last < first {
+        return Ticks::EMPTY;
+    }
+
+    let decades = (last - first) as usize + 1;
+    let stride = decades.div_ceil(target_count);
+    Ticks {
+        log: true,
+        first: first as f64,
+        mantissa: stride as f64,
+        exp: 0,
+        front: 0,
+        back: (decades - 1) / stride + 1,
+    }
+}
+
+// Relative error allowed when deciding whether a range end falls on a tick
+const SLACK: f64 = 1e-9;
+
+// Returns the largest exponent whose power of ten is at most `v`, for positive finite `v`, kept to the
+// decades of normal floats
+fn exponent_of(v: f64) -> i32 {
+    let mut exp = 0;
+    while exp < f64::MAX_10_EXP && pow10(exp + 1) <= v {
+        exp += 1;
+    }
+    while exp > f64::MIN_10_EXP && pow10(exp) > v {
+        exp -= 1;
+    }
+    exp
+}
+
+// Computed by multiplication, which is exact up to 1e22
+fn pow10(exp: i32) -> f64 {
+    let mut p = 1.0;
+    for _ in 0..exp.unsigned_abs() {
+        p *= 10.0;
+    }
+    if exp < 0 { 1.0 / p } else { p }
+}
+
+// Dividing by an exact power rather than multiplying by an inexact one keeps decimals round
+fn scaled(v: f64, exp: i32) -> f64 {
+    if exp < 0 { v / pow10(-exp) } else { v * pow10(exp) }
+}
+
+fn floor(v: f64) -> f64 {
+    // Floats this large have no fractional part
+    if v.abs() >= 4503599627370496.0 {
+        return v;
+    }
+    let t = v as i64 as f64;
+    if t > v { t - 1.0 } else { t }
+}
+
+fn ceil(v: f64) -> f64 {
+    -floor(-v)
+}
+
+fn sign(v: f64) -> f64 {
+    if v < 0.0 { -1.0 } else { 1.0 }
+}
+
+
+///
+/// Iterator over tick positions in increasing order, created by [`ticks_along`] and [`AxisScale::ticks`]
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Ticks {
+    log: bool,
+    // Tick `i` is at `(first + i) * mantissa * 10^exp` on linear scales, and at ten to the power of
+    // `first + i * mantissa` on log scales
+    first: f64,
+    mantissa: f64,
+    exp: i32,
+    front: usize,
+    back: usize,
+}
+
+impl Ticks {
+
+    const EMPTY: Self = Self { log: false, first: 0.0, mantissa: 1.0, exp: 0, front: 0, back: 0 };
+
+    ///
+    /// Returns the distance between ticks, or on log scales the ratio between them
+    ///
+    pub fn step(&self) -> f64 {
+        if self.log { pow10(self.mantissa as i32) } else { scaled(self.mantissa, self.exp) }
+    }
+
+    fn at(&self, i: usize) -> f64 {
+        if self.log {
+            pow10(self.first as i32 + i as i32 * self.mantissa as i32)
+        } else {
+            scaled((self.first + i as f64) * self.mantissa, self.exp)
+        }
+    }
+
+}
+
+impl Iterator for Ticks {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.at(self.front - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Ticks {
+    fn next_back(&mut self) -> Option<f64> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.at(self.back))
+    }
+}
+
+impl ExactSizeIterator for Ticks {}
+
+impl FusedIterator for Ticks {}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn linear_ticks_are_round() {
+        let small: Vec<f64> = ticks_along(0.013, 0.071, 6).collect();
+        assert_eq!(small, [0.02, 0.03, 0.04, 0.05, 0.06, 0.07]);
+        let large = ticks_along(-2.5e6, 1e6, 3);
+        assert_eq!(large.step(), 1e6);
+        assert_eq!(large.rev().collect::<Vec<_>>(), [1e6, 0.0, -1e6, -2e6]);
+        assert_eq!(ticks_along(1.0, 1.0, 5).len(), 0);
+        assert_eq!(ticks_along(0.0, f64::NAN, 5).len(), 0);
+    }
+
+    #[test]
+    fn scales_invert() {
+        let scales = [AxisScale::Linear, AxisScale::Log10, AxisScale::SymLog { linthresh: 0.5 }];
+        for scale in scales {
+            for v in [1e-300, 3e-7, 0.5, 1.0, 7.25, 1e5, 1.5e300] {
+                let back = scale.invert(scale.apply(v));
+                assert!(abs(back - v) <= v * 1e-12, "{:?} maps {} back to {}", scale, v, back);
+            }
+        }
+        assert!(AxisScale::Log10.apply(-1.0).is_nan());
+        assert_eq!(AxisScale::Log10.apply(0.0), f64::NEG_INFINITY);
+        assert!(abs(AxisScale::SymLog { linthresh: 2.0 }.apply(-18.0) + 1.0) < 1e-15);
+    }
+
+    #[test]
+    fn log_ticks_skip_decades() {
+        let ticks = AxisScale::Log10.ticks(1e-3, 1e6, 4);
+        assert_eq!(ticks.step(), 1e3);
+        assert_eq!(ticks.collect::<Vec<_>>(), [1e-3, 1.0, 1e3, 1e6]);
+        assert_eq!(AxisScale::Log10.ticks(2.0, 9.0, 3).len(), 0);
+        assert_eq!(AxisScale::Log10.ticks(-1.0, 9.0, 3).len(), 0);
+
+        let mut widest = AxisScale::Log10.ticks(1.0, f64::MAX, 5);
+        assert_eq!(widest.len(), 5);
+        assert_eq!(widest.next_back().map(exponent_of), Some(248));
+        assert_eq!(AxisScale::Log10.ticks(1.0, f64::INFINITY, 5).len(), 0);
+        assert_eq!(AxisScale::Log10.ticks(f64::MIN_POSITIVE / 4.0, 1.0, 400).next().map(exponent_of), Some(-307));
+    }
+
+}
@@ -0,0 +1,151 @@
+//!
+//! Cache-blocked traversal of N dimensional regions
+//!
+
+use core::iter::FusedIterator;
+
+use crate::{NdIndex, Roi};
+
+
+///
+/// Iterator over the tiles covering a region, yielding each tile's [`Roi`] and an iterator over its
+/// coordinates
+///
+/// Tiles are visited with the x axis varying fastest, as are the coordinates within each tile. Tiles along
+/// the far edge of the region are cut short rather than running past it. Created by [`tiled_ndindex!`]
+///
+#[derive(Clone, Debug)]
+pub struct Tiles<const N: usize> {
+    start: [usize; N],
+    end: [usize; N],
+    tile: [usize; N],
+    tiles: NdIndex<usize, N>,
+}
+
+impl<const N: usize> Tiles<N> {
+
+    ///
+    /// Creates an iterator over the tiles of size `tile` covering `start..end`
+    ///
+    /// # Panics
+    ///
+    /// If any tile size is zero
+    ///
+    pub fn new(start: [usize; N], end: [usize; N], tile: [usize; N]) -> Self {
+        assert!(tile.iter().all(|&t| t > 0), "axmac: tiles must be at least one cell wide along every axis");
+        let counts = core::array::from_fn(|i| end[i].saturating_sub(start[i]).div_ceil(tile[i]));
+        Self { start, end, tile, tiles: NdIndex::new([0; N], counts) }
+    }
+
+}
+
+impl<const N: usize> Iterator for Tiles<N> {
+    type Item = (Roi<N>, NdIndex<usize, N>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.tiles.next()?;
+        let origin: [usize; N] = core::array::from_fn(|i| self.start[i] + index[i] * self.tile[i]);
+        let end: [usize; N] = core::array::from_fn(|i| origin[i].saturating_add(self.tile[i]).min(self.end[i]));
+        Some((Roi::new(origin, end), NdIndex::new(origin, end)))
+    }
+}
+
+impl<const N: usize> FusedIterator for Tiles<N> {}
+
+
+///
+/// Iterates over a region tile by tile, for cache-blocked loops over large grids
+///
+/// Takes the same per-axis ranges as [`ndindex!`], each followed by `by` and the tile size along that axis.
+/// Yields one `(Roi, coordinates)` pair per tile, replacing the four nested loops of a hand blocked 2D
+/// traversal with two
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// let (width, height) = (100, 70);
+/// let mut visited = 0;
+///
+/// for (roi, cells) in tiled_ndindex!(x in 0..width by 64, y in 0..height by 64) {
+///     for [x, y] in cells {
+///         assert!(roi.contains(&[x, y]));
+///         visited += 1;
+///     }
+/// }
+/// assert_eq!(visited, width * height);
+/// # }
+/// ```
+///
+#[macro_export]
+macro_rules! tiled_ndindex {
+
+    // Ranges are munched a token at a time, since an `expr` fragment may not be followed by `by`
+    ( @axis [ $( $done:tt )* ] $axis:ident in $( $rest:tt )+ ) => {
+        $crate::tiled_ndindex!(@range [ $( $done )* ] $axis [] $( $rest )+)
+    };
+
+    ( @range [ $( $done:tt )* ] $axis:ident [ $( $r:tt )+ ] by $tile:expr, $( $rest:tt )+ ) => {
+        $crate::tiled_ndindex!(@axis [ $( $done )* ($axis, ($( $r )+), $tile) ] $( $rest )+)
+    };
+    ( @range [ $( $done:tt )* ] $axis:ident [ $( $r:tt )+ ] by $tile:expr $(,)? ) => {
+        $crate::tiled_ndindex!(@emit $( $done )* ($axis, ($( $r )+), $tile))
+    };
+    ( @range [ $( $done:tt )* ] $axis:ident [ $( $r:tt )* ] $next:tt $( $rest:tt )* ) => {
+        $crate::tiled_ndindex!(@range [ $( $done )* ] $axis [ $( $r )* $next ] $( $rest )*)
+    };
+
+    ( @emit $( ($axis:ident, $range:expr, $tile:expr) )+ ) => {{
+        const _: () = assert!(
+            $crate::__private::axes_are_permutation(&[ $( $crate::ax!($axis) ),+ ]),
+            "axmac: tiled_ndindex! must list each of the first N axes exactly once",
+        );
+
+        let mut start = [ $( $crate::__ndindex_zero!($axis) ),+ ];
+        let mut end = start;
+        let mut tile = start;
+        $(
+            let (s, e) = $crate::HalfOpen::<usize>::half_open($range);
+            start[$crate::ax!($axis)] = s;
+            end[$crate::ax!($axis)] = e;
+            tile[$crate::ax!($axis)] = $tile;
+        )+
+        $crate::Tiles::new(start, end, tile)
+    }};
+
+    ( $( $tokens:tt )+ ) => { $crate::tiled_ndindex!(@axis [] $( $tokens )+) };
+
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn edge_tiles_are_cut_short() {
+        let rois: Vec<_> = Tiles::new([0, 0], [5, 3], [2, 2]).map(|(roi, _)| roi).collect();
+        assert_eq!(rois.len(), 6);
+        assert_eq!(rois[2], Roi::new([4, 0], [5, 2]));
+        assert_eq!(rois[5], Roi::new([4, 2], [5, 3]));
+
+        let near_max = usize::MAX - 3;
+        let rois: Vec<_> = Tiles::new([near_max], [usize::MAX], [usize::MAX]).map(|(roi, _)| roi).collect();
+        assert_eq!(rois, [Roi::new([near_max], [usize::MAX])]);
+    }
+
+    #[test]
+    fn every_cell_is_visited_once() {
+        let mut seen = [[0u8; 7]; 5];
+        for (_, cells) in tiled_ndindex!(y in 0..5 by 3, x in 1..=6 by 4) {
+            for [x, y] in cells {
+                seen[y][x] += 1;
+            }
+        }
+        for row in seen {
+            assert_eq!(row, [0, 1, 1, 1, 1, 1, 1]);
+        }
+    }
+
+}
@@ -0,0 +1,107 @@
+//!
+//! Structured events describing indexing failures
+//!
+//! A single process-wide hook receives an [`IndexEvent`] whenever a checked indexing API such as
+//! [`Shape::try_flat_index`](crate::Shape::try_flat_index) fails, so an application can forward them to
+//! whichever logging or tracing framework it uses
+//!
+//! ```
+//! use axmac::{trace, Shape};
+//!
+//! fn log(event: &trace::IndexEvent) {
+//!     assert_eq!(event.op, "Shape::try_flat_index");
+//!     assert_eq!(event.error.axis_name(), Some("y"));
+//! }
+//!
+//! trace::set_hook(log);
+//! assert!(Shape::new([4, 4]).try_flat_index(&[1, 9]).is_err());
+//! trace::clear_hook();
+//! ```
+//!
+
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+use crate::OutOfBounds;
+
+
+///
+/// An indexing failure reported to the installed hook
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndexEvent {
+    /// The API which failed, such as `"Shape::try_flat_index"`
+    pub op: &'static str,
+    /// The offending axis, its extent and the index given
+    pub error: OutOfBounds,
+}
+
+
+static HOOK: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+
+///
+/// Installs `hook` to receive every subsequent [`IndexEvent`], replacing any previous hook
+///
+pub fn set_hook(hook: fn(&IndexEvent)) {
+    HOOK.store(hook as *mut (), Ordering::Release);
+}
+
+
+///
+/// Removes the installed hook, if any
+///
+pub fn clear_hook() {
+    HOOK.store(core::ptr::null_mut(), Ordering::Release);
+}
+
+
+pub(crate) fn emit(op: &'static str, error: OutOfBounds) {
+    let hook = HOOK.load(Ordering::Acquire);
+    if !hook.is_null() {
+        // SAFETY: only `set_hook` stores non-null values, and it always stores a `fn(&IndexEvent)`
+        let hook: fn(&IndexEvent) = unsafe { core::mem::transmute::<*mut (), fn(&IndexEvent)>(hook) };
+        hook(&IndexEvent { op, error });
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use core::sync::atomic::AtomicBool;
+    use crate::Shape;
+
+    static SAW_OWN_EVENT: AtomicBool = AtomicBool::new(false);
+    static SAW_COPY_EVENT: AtomicBool = AtomicBool::new(false);
+
+    // Other tests fail their own checked indexing while this one runs, so only its events are recorded
+    fn record(event: &IndexEvent) {
+        if event.op == "Shape::try_flat_index" && event.error == (OutOfBounds { axis: 2, extent: 4243, got: 90001 }) {
+            SAW_OWN_EVENT.store(true, Ordering::SeqCst);
+        }
+        if event.op == "copy_region_by" && event.error == (OutOfBounds { axis: 1, extent: 4243, got: 4243 }) {
+            SAW_COPY_EVENT.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn hook_receives_events() {
+        set_hook(record);
+        let shape = Shape::new([2, 3, 4243]);
+        assert!(shape.try_flat_index(&[1, 2, 3]).is_ok());
+        assert!(!SAW_OWN_EVENT.load(Ordering::SeqCst));
+
+        let err = shape.try_flat_index(&[1, 2, 90001]).unwrap_err();
+        assert_eq!(err, OutOfBounds { axis: ax!(z), extent: 4243, got: 90001 });
+        assert!(SAW_OWN_EVENT.load(Ordering::SeqCst));
+
+        let (src, mut dst) = ([0u8; 2], [0u8; 4243]);
+        let copied = crate::copy_region(&src, &Shape::new([1, 2]), &crate::Roi::FULL, &mut dst, &Shape::new([1, 4243]), [0, 4242]);
+        assert_eq!(copied, Err(OutOfBounds { axis: 1, extent: 4243, got: 4243 }));
+        assert!(SAW_COPY_EVENT.load(Ordering::SeqCst));
+        clear_hook();
+    }
+
+}
@@ -0,0 +1,257 @@
+//!
+//! Integer grid transforms built from translations, flips and axis swaps
+//!
+
+use crate::aabb::{max, min};
+use crate::{Aabb, Roi, SignedScalar};
+
+
+///
+/// An axis-aligned transform of grid coordinates, without any general matrix machinery
+///
+/// Every transform built from translations, flips and axis swaps can be written as
+/// `out[i] = ±in[perm[i]] + offset[i]`, which is how it is stored. Builder methods apply their step after
+/// the steps already added
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::GridTransform;
+///
+/// // Transpose a tile, then move it 10 cells right
+/// let t = GridTransform::<i32, 2>::IDENTITY
+///     .swap(ax!(x), ax!(y))
+///     .translate(ax!(x), 10);
+///
+/// assert_eq!(t.apply([1, 2]), [12, 1]);
+/// assert_eq!(t.inverse().apply([12, 1]), [1, 2]);
+///
+/// // Mirror a 16 wide chunk along x
+/// let mirror = GridTransform::<i32, 2>::IDENTITY.flip_within(ax!(x), 16);
+/// assert_eq!(mirror.apply([0, 5]), [15, 5]);
+/// # }
+/// ```
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GridTransform<T, const N: usize> {
+    perm: [usize; N],
+    negate: [bool; N],
+    offset: [T; N],
+}
+
+impl<T: SignedScalar, const N: usize> GridTransform<T, N> {
+
+    ///
+    /// The transform leaving every coordinate where it is
+    ///
+    pub const IDENTITY: Self = Self {
+        perm: {
+            let mut perm = [0; N];
+            let mut i = 0;
+            while i < N {
+                perm[i] = i;
+                i += 1;
+            }
+            perm
+        },
+        negate: [false; N],
+        offset: [T::ZERO; N],
+    };
+
+    ///
+    /// Adds a translation of `by` along `axis`
+    ///
+    pub fn translate(mut self, axis: usize, by: T) -> Self {
+        self.offset[axis] = self.offset[axis] + by;
+        self
+    }
+
+    ///
+    /// Adds a translation of every axis at once
+    ///
+    pub fn translate_all(mut self, by: [T; N]) -> Self {
+        for (o, by) in self.offset.iter_mut().zip(by) {
+            *o = *o + by;
+        }
+        self
+    }
+
+    ///
+    /// Adds a reflection of `axis` about zero
+    ///
+    pub fn flip(mut self, axis: usize) -> Self {
+        self.negate[axis] = !self.negate[axis];
+        self.offset[axis] = -self.offset[axis];
+        self
+    }
+
+    ///
+    /// Adds a reflection of `axis` mapping positions `0..extent` onto themselves in reverse
+    ///
+    pub fn flip_within(self, axis: usize, extent: T) -> Self {
+        self.flip(axis).translate(axis, extent - T::ONE)
+    }
+
+    ///
+    /// Adds an exchange of axes `a` and `b`
+    ///
+    pub fn swap(mut self, a: usize, b: usize) -> Self {
+        self.perm.swap(a, b);
+        self.negate.swap(a, b);
+        self.offset.swap(a, b);
+        self
+    }
+
+    ///
+    /// Returns the transform applying `self` and then `next`
+    ///
+    pub fn then(&self, next: &Self) -> Self {
+        Self {
+            perm: core::array::from_fn(|i| self.perm[next.perm[i]]),
+            negate: core::array::from_fn(|i| self.negate[next.perm[i]] != next.negate[i]),
+            offset: core::array::from_fn(|i| {
+                let o = self.offset[next.perm[i]];
+                (if next.negate[i] { -o } else { o }) + next.offset[i]
+            }),
+        }
+    }
+
+    ///
+    /// Returns the transform undoing this one
+    ///
+    pub fn inverse(&self) -> Self {
+        let mut inv = Self::IDENTITY;
+        for i in 0..N {
+            let j = self.perm[i];
+            inv.perm[j] = i;
+            inv.negate[j] = self.negate[i];
+            inv.offset[j] = if self.negate[i] { self.offset[i] } else { -self.offset[i] };
+        }
+        inv
+    }
+
+    ///
+    /// Returns the input axis each output axis is read from
+    ///
+    pub const fn permutation(&self) -> &[usize; N] {
+        &self.perm
+    }
+
+    ///
+    /// Transforms a single coordinate
+    ///
+    pub fn apply(&self, coord: [T; N]) -> [T; N] {
+        core::array::from_fn(|i| {
+            let v = coord[self.perm[i]];
+            (if self.negate[i] { -v } else { v }) + self.offset[i]
+        })
+    }
+
+    ///
+    /// Transforms a box, which remains axis-aligned
+    ///
+    pub fn apply_aabb(&self, aabb: &Aabb<T, N>) -> Aabb<T, N> {
+        let a = self.apply(aabb.min);
+        let b = self.apply(aabb.max);
+        Aabb::new(
+            core::array::from_fn(|i| min(a[i], b[i])),
+            core::array::from_fn(|i| max(a[i], b[i])),
+        )
+    }
+
+}
+
+impl<const N: usize> GridTransform<isize, N> {
+
+    ///
+    /// Transforms a region of interest
+    ///
+    /// Returns `None` if any part of the region would land on a negative position, or if an unbounded
+    /// axis would be flipped, since neither can be expressed as a [`Roi`]
+    ///
+    pub fn apply_roi(&self, roi: &Roi<N>) -> Option<Roi<N>> {
+        let mut start = [0; N];
+        let mut end = [0; N];
+        for i in 0..N {
+            let from = roi.range(self.perm[i]);
+            let first = isize::try_from(from.start).ok()?;
+            let (lo, hi) = if roi.is_unbounded(self.perm[i]) {
+                if self.negate[i] {
+                    return None;
+                }
+                (first + self.offset[i], None)
+            } else if from.is_empty() {
+                (0, Some(0))
+            } else {
+                let last = isize::try_from(from.end - 1).ok()?;
+                let (a, b) = if self.negate[i] { (-last, -first) } else { (first, last) };
+                (a + self.offset[i], Some(b + self.offset[i] + 1))
+            };
+            start[i] = usize::try_from(lo).ok()?;
+            end[i] = match hi {
+                Some(hi) => usize::try_from(hi).ok()?,
+                None     => usize::MAX,
+            };
+        }
+        Some(Roi::new(start, end))
+    }
+
+}
+
+impl<T: SignedScalar, const N: usize> Default for GridTransform<T, N> {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn sample() -> GridTransform<i32, 3> {
+        GridTransform::IDENTITY
+            .translate(ax!(x), 3)
+            .flip(ax!(y))
+            .swap(ax!(x), ax!(z))
+            .translate(ax!(y), -2)
+            .flip(ax!(x))
+    }
+
+    #[test]
+    fn inverse_works() {
+        let t = sample();
+        for c in [[0, 0, 0], [1, -5, 9], [7, 7, -7]] {
+            assert_eq!(t.inverse().apply(t.apply(c)), c);
+        }
+        assert_eq!(t.then(&t.inverse()), GridTransform::IDENTITY);
+    }
+
+    #[test]
+    fn then_matches_sequential_application() {
+        let a = sample();
+        let b = GridTransform::IDENTITY.swap(ax!(y), ax!(z)).translate(ax!(z), 4).flip(ax!(y));
+        for c in [[1, 2, 3], [-4, 0, 6]] {
+            assert_eq!(a.then(&b).apply(c), b.apply(a.apply(c)));
+        }
+    }
+
+    #[test]
+    fn apply_aabb_sorts_corners() {
+        let t = GridTransform::<i32, 2>::IDENTITY.flip(ax!(x));
+        assert_eq!(t.apply_aabb(&Aabb::new([1, 1], [3, 2])), Aabb::new([-3, 1], [-1, 2]));
+    }
+
+    #[test]
+    fn apply_roi_works() {
+        let t = GridTransform::<isize, 2>::IDENTITY.flip_within(ax!(x), 10).swap(ax!(x), ax!(y));
+        assert_eq!(t.apply_roi(&Roi::new([0, 5], [2, 7])), Some(Roi::new([5, 8], [7, 10])));
+        assert_eq!(t.apply_roi(&Roi::FULL), None);
+
+        let shift = GridTransform::<isize, 2>::IDENTITY.translate(ax!(y), -1);
+        assert_eq!(shift.apply_roi(&Roi::new([0, 0], [1, 1])), None);
+        assert_eq!(shift.apply_roi(&Roi::new([0, 1], [1, usize::MAX])), Some(Roi::new([0, 0], [1, usize::MAX])));
+    }
+
+}
@@ -0,0 +1,444 @@
+//!
+//! Reading and copying regions of flat buffers through a [`Shape`] and [`Roi`]
+//!
+
+use core::hash::{Hash, Hasher};
+use core::iter::FusedIterator;
+
+use crate::{Axis, AxisPriority, NdIndex, OutOfBounds, Roi, Shape};
+
+
+///
+/// Iterator over the elements of a region of a flat buffer, created by [`view`]
+///
+#[derive(Clone, Debug)]
+pub struct View<'a, T, const N: usize> {
+    buf: &'a [T],
+    shape: Shape<N>,
+    order: [usize; N],
+    // Iterates the region with its axes permuted so index 0 is the innermost axis of `order`
+    inner: NdIndex<usize, N>,
+}
+
+impl<'a, T, const N: usize> Iterator for View<'a, T, N> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let permuted = self.inner.next()?;
+        let mut coord = [0; N];
+        for (depth, &axis) in self.order.iter().enumerate() {
+            coord[axis] = permuted[N - 1 - depth];
+        }
+        Some(&self.buf[self.shape.flat_index_unchecked(&coord)])
+    }
+}
+
+impl<T, const N: usize> FusedIterator for View<'_, T, N> {}
+
+
+///
+/// Iterates over the elements of `buf` inside `roi`, visiting axes in `order`
+///
+/// `order` lists every axis from outermost to innermost, exactly as the equivalent nested loops would be
+/// written, so `axs![y, x]` visits a 2-D region row by row. The region is clipped to `shape`
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{view, Shape};
+///
+/// // 0 1 2
+/// // 3 4 5
+/// let buf = [0, 1, 2, 3, 4, 5];
+/// let shape = Shape::new([3, 2]);
+///
+/// let rows: Vec<_> = view(&buf, &shape, &roi!(x: 1.., y: ..), axs![y, x]).copied().collect();
+/// assert_eq!(rows, [1, 2, 4, 5]);
+///
+/// let cols: Vec<_> = view(&buf, &shape, &roi!(x: 1.., y: ..), axs![x, y]).copied().collect();
+/// assert_eq!(cols, [1, 4, 2, 5]);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If `order` is not a permutation of the `N` axes, or `buf` is too short for `shape`
+///
+pub fn view<'a, T, const N: usize>(
+    buf: &'a [T],
+    shape: &Shape<N>,
+    roi: &Roi<N>,
+    order: [usize; N],
+) -> View<'a, T, N> {
+    assert!(crate::__private::axes_are_permutation(&order), "axmac: view order must be a permutation of the axes");
+    assert!(buf.len() >= shape.required_len(), "axmac: buffer is too short for its shape");
+
+    let ranges = roi.resolve(shape);
+    let start = core::array::from_fn(|i| ranges[order[N - 1 - i]].start);
+    let end = core::array::from_fn(|i| ranges[order[N - 1 - i]].end);
+    View { buf, shape: *shape, order, inner: NdIndex::new(start, end) }
+}
+
+///
+/// Copies the elements of `buf` inside `roi` into the front of `out`, visiting axes in `order`
+///
+/// Returns the number of elements copied. See [`view`] for the meaning of `order`
+///
+/// # Panics
+///
+/// As [`view`], or if `out` is too short to hold the region
+///
+pub fn view_into<T: Copy, const N: usize>(
+    buf: &[T],
+    shape: &Shape<N>,
+    roi: &Roi<N>,
+    order: [usize; N],
+    out: &mut [T],
+) -> usize {
+    let len = roi.extents_in(shape).iter().product();
+    assert!(out.len() >= len, "axmac: output buffer is too short for the region");
+
+    for (o, v) in out.iter_mut().zip(view(buf, shape, roi, order)) {
+        *o = *v;
+    }
+    len
+}
+
+
+///
+/// Feeds the elements of `buf` inside `roi` to `hasher`, for content hashes of tiles and slices
+///
+/// The extents of the region go first, then its elements with the x axis varying fastest, whatever the
+/// strides or reversed axes of `shape`. The same content therefore hashes the same in any memory layout,
+/// and regions of different extents holding the same elements hash differently. The region is clipped to
+/// `shape`
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use std::collections::hash_map::DefaultHasher;
+/// use std::hash::Hasher;
+/// use axmac::{hash_region, Shape};
+///
+/// let hash = |buf: &[u8], shape: &Shape<2>| {
+///     let mut hasher = DefaultHasher::new();
+///     hash_region(buf, shape, &roi!(x: 1..3, y: ..), &mut hasher);
+///     hasher.finish()
+/// };
+///
+/// // The same 3x2 image stored by rows and by columns
+/// let rows = [0, 1, 2, 3, 4, 5];
+/// let cols = [0, 3, 1, 4, 2, 5];
+/// assert_eq!(hash(&rows, &Shape::new([3, 2])), hash(&cols, &Shape::col_major([3, 2])));
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If `buf` is too short for `shape`
+///
+pub fn hash_region<T: Hash, H: Hasher, const N: usize>(buf: &[T], shape: &Shape<N>, roi: &Roi<N>, hasher: &mut H) {
+    assert!(buf.len() >= shape.required_len(), "axmac: buffer is too short for its shape");
+    roi.extents_in(shape).hash(hasher);
+    for coord in roi.coords_in(shape) {
+        buf[shape.flat_index_unchecked(&coord)].hash(hasher);
+    }
+}
+
+
+///
+/// Iterator over the contiguous runs of a shaped buffer, created by [`runs_along`]
+///
+#[derive(Clone, Debug)]
+pub struct Runs<const N: usize> {
+    strides: [usize; N],
+    order: [usize; N],
+    run_len: usize,
+    // Iterates the run starts with axes permuted by `order`, so index 0 is the axis of smallest stride
+    inner: NdIndex<usize, N>,
+    pending: Option<(usize, usize)>,
+}
+
+impl<const N: usize> Iterator for Runs<N> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        while let Some(permuted) = self.inner.next() {
+            let offset = (0..N).map(|i| permuted[i] * self.strides[self.order[i]]).sum();
+            match &mut self.pending {
+                Some((start, len)) if *start + *len == offset => *len += self.run_len,
+                pending => {
+                    let run = pending.replace((offset, self.run_len));
+                    if run.is_some() {
+                        return run;
+                    }
+                }
+            }
+        }
+        self.pending.take()
+    }
+}
+
+impl<const N: usize> FusedIterator for Runs<N> {}
+
+
+///
+/// Iterates over the maximal contiguous spans of `buf` laid out by `shape`, as `(start_offset, len)`
+/// pairs, so codecs and copy routines can work span by span rather than element by element
+///
+/// Lines along `axis` form the runs when it has a stride of one, and neighbouring lines that meet in
+/// memory are merged, so a densely packed buffer is a single run. Along any other axis every element
+/// starts its own run, merged in the same way. Runs are visited with the axis of smallest stride varying
+/// fastest, and together cover every element of the shape once. Reversing an axis leaves the memory the
+/// shape covers unchanged, so its runs are those of the shape unreversed
+///
+/// ```
+/// use axmac::{runs_along, Axis, Shape};
+///
+/// let buf = [0u8; 64];
+///
+/// // A dense 4x4 image is one run
+/// assert!(runs_along(&buf, &Shape::new([4, 4]), Axis::X).eq([(0, 16)]));
+///
+/// // The rows of a 3 wide view into an 8 wide image are not
+/// let view = Shape::with_strides([3, 2], [1, 8]);
+/// assert!(runs_along(&buf, &view, Axis::X).eq([(0, 3), (8, 3)]));
+/// ```
+///
+/// # Panics
+///
+/// If `axis` is not less than `N`, or `buf` is too short for `shape`
+///
+pub fn runs_along<T, const N: usize>(buf: &[T], shape: &Shape<N>, axis: Axis) -> Runs<N> {
+    let axis = axis.as_usize();
+    assert!(axis < N, "axmac: axis is out of bounds for the shape");
+    assert!(buf.len() >= shape.required_len(), "axmac: buffer is too short for its shape");
+
+    let strides = *shape.strides();
+    let mut extents = *shape.extents();
+    let contiguous = strides[axis] == 1;
+    let run_len = if contiguous { core::mem::replace(&mut extents[axis], 1) } else { 1 };
+    if shape.is_empty() {
+        extents = [0; N];
+    }
+
+    // The contiguous axis goes first, then the rest from the smallest stride up, so runs meet in order
+    let mut order: [usize; N] = core::array::from_fn(|i| i);
+    order.sort_unstable_by_key(|&i| (!(contiguous && i == axis), strides[i], i));
+    let end = order.map(|i| extents[i]);
+    Runs { strides, order, run_len, inner: NdIndex::new([0; N], end), pending: None }
+}
+
+
+///
+/// Copies the elements of `src` inside `src_roi` into `dst`, placing the region's first corner at
+/// `dst_offset`, and returns the number of elements copied
+///
+/// This is the strided block copy behind texture uploads and sub-image blits. Both shapes may have any
+/// strides and reversed axes, so a reversed source flips the region as it is copied, and lines are copied
+/// a slice at a time along an axis contiguous in both buffers. An unbounded range of `src_roi` extends to
+/// the end of `src_shape`, but nothing is clipped silently: the copy is refused with the first axis at
+/// fault when the region runs past `src_shape` or its destination past `dst_shape`
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{copy_region, OutOfBounds, Shape};
+///
+/// // Blit the 2x2 block at (1, 1) of a 4x4 sprite to (0, 2) of a 3x4 target
+/// let sprite: Vec<u8> = (0..16).collect();
+/// let mut target = [0u8; 12];
+/// let (src, dst) = (Shape::new([4, 4]), Shape::new([3, 4]));
+///
+/// assert_eq!(copy_region(&sprite, &src, &roi!(x: 1..3, y: 1..3), &mut target, &dst, [0, 2]), Ok(4));
+/// assert_eq!(target, [0, 0, 0, 0, 0, 0, 5, 6, 0, 9, 10, 0]);
+///
+/// let refused = copy_region(&sprite, &src, &roi!(x: 1..3, y: 1..3), &mut target, &dst, [2, 0]);
+/// assert_eq!(refused, Err(OutOfBounds { axis: ax!(x), extent: 3, got: 3 }));
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// If either buffer is too short for its shape, or with the `debug-dims` feature, if the shapes and the
+/// region label an axis differently
+///
+pub fn copy_region<T: Copy, const N: usize>(
+    src: &[T],
+    src_shape: &Shape<N>,
+    src_roi: &Roi<N>,
+    dst: &mut [T],
+    dst_shape: &Shape<N>,
+    dst_offset: [usize; N],
+) -> Result<usize, OutOfBounds> {
+    copy_region_by(src, src_shape, src_roi, dst, dst_shape, dst_offset, AxisPriority::X_FASTEST)
+}
+
+///
+/// Copies a region as [`copy_region`] does, visiting its positions in the order of `priority`
+///
+/// Lines are copied a slice at a time along the innermost ranked axis contiguous in both buffers, and the
+/// other axes vary in the order of their rank. Walking the destination front to back with
+/// [`AxisPriority::contiguous_first`] keeps writes sequential when the source is a transposed view
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{copy_region_by, AxisPriority, Roi, Shape};
+///
+/// // Repacking a 3x2 image stored x fastest into a buffer stored y fastest
+/// let src = [1, 2, 3, 4, 5, 6];
+/// let mut dst = [0; 6];
+/// let (from, to) = (Shape::new([3, 2]), Shape::col_major([3, 2]));
+///
+/// let order = AxisPriority::contiguous_first(&to);
+/// assert_eq!(copy_region_by(&src, &from, &Roi::FULL, &mut dst, &to, [0, 0], order), Ok(6));
+/// assert_eq!(dst, [1, 4, 2, 5, 3, 6]);
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// As [`copy_region`]
+///
+pub fn copy_region_by<T: Copy, const N: usize>(
+    src: &[T],
+    src_shape: &Shape<N>,
+    src_roi: &Roi<N>,
+    dst: &mut [T],
+    dst_shape: &Shape<N>,
+    dst_offset: [usize; N],
+    priority: AxisPriority<N>,
+) -> Result<usize, OutOfBounds> {
+    assert!(src.len() >= src_shape.required_len(), "axmac: source buffer is too short for its shape");
+    assert!(dst.len() >= dst_shape.required_len(), "axmac: destination buffer is too short for its shape");
+    #[cfg(feature = "debug-dims")]
+    src_shape.labels.merge(src_roi.labels).merge(dst_shape.labels);
+
+    let mut extents = [0; N];
+    for (axis, extent) in extents.iter_mut().enumerate() {
+        let range = src_roi.range(axis);
+        let end = if src_roi.is_unbounded(axis) { src_shape.extent(axis).max(range.start) } else { range.end };
+        if end > src_shape.extent(axis) {
+            let error = OutOfBounds { axis, extent: src_shape.extent(axis), got: end - 1 };
+            #[cfg(all(feature = "tracing", target_has_atomic = "ptr"))]
+            crate::trace::emit("copy_region_by", error);
+            return Err(error);
+        }
+        *extent = end.saturating_sub(range.start);
+    }
+    for axis in 0..N {
+        let end = dst_offset[axis] + extents[axis];
+        if end > dst_shape.extent(axis) {
+            let error = OutOfBounds { axis, extent: dst_shape.extent(axis), got: end - 1 };
+            #[cfg(all(feature = "tracing", target_has_atomic = "ptr"))]
+            crate::trace::emit("copy_region_by", error);
+            return Err(error);
+        }
+    }
+    if extents.contains(&0) {
+        return Ok(0);
+    }
+
+    // Copy whole lines along an axis of unit stride in both buffers, or single elements otherwise
+    let line_axis = priority.inner_to_outer().into_iter().find(|&i| {
+        let unit = |shape: &Shape<N>| shape.stride(i) == 1 && !shape.is_reversed(i);
+        unit(src_shape) && unit(dst_shape)
+    });
+    let line_len = line_axis.map_or(1, |axis| core::mem::replace(&mut extents[axis], 1));
+    let src_start = *src_roi.start();
+    for coord in NdIndex::new([0; N], extents).with_priority(priority) {
+        let from = src_shape.flat_index_unchecked(&core::array::from_fn(|i| src_start[i] + coord[i]));
+        let to = dst_shape.flat_index_unchecked(&core::array::from_fn(|i| dst_offset[i] + coord[i]));
+        if line_axis.is_some() {
+            dst[to..to + line_len].copy_from_slice(&src[from..from + line_len]);
+        } else {
+            dst[to] = src[from];
+        }
+    }
+    Ok(extents.iter().product::<usize>() * line_len)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use crate::roi;
+
+    #[test]
+    fn three_d_orders_work() {
+        let buf: [u8; 8] = core::array::from_fn(|i| i as u8);
+        let shape = Shape::new([2, 2, 2]);
+
+        let mut out = [0; 8];
+        assert_eq!(view_into(&buf, &shape, &Roi::FULL, axs![z, y, x], &mut out), 8);
+        assert_eq!(out, buf);
+
+        view_into(&buf, &shape, &Roi::FULL, axs![x, y, z], &mut out);
+        assert_eq!(out, [0, 4, 2, 6, 1, 5, 3, 7]);
+    }
+
+    #[test]
+    fn hashes_follow_content() {
+        let hash = |buf: &[u8], shape: &Shape<2>, roi: &Roi<2>| {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            hash_region(buf, shape, roi, &mut hasher);
+            core::hash::Hasher::finish(&hasher)
+        };
+        let shape = Shape::new([3, 2]);
+        let flipped = shape.reverse_axis(Axis::X);
+        assert_eq!(hash(&[1, 2, 3, 4, 5, 6], &shape, &Roi::FULL), hash(&[3, 2, 1, 6, 5, 4], &flipped, &Roi::FULL));
+        let (buf, square) = ([7; 9], Shape::new([3, 3]));
+        assert_ne!(hash(&buf, &square, &roi!(x: ..3, y: ..2)), hash(&buf, &square, &roi!(x: ..2, y: ..3)));
+        assert_eq!(hash(&buf, &square, &roi!(x: 1..9, y: ..)), hash(&buf, &square, &roi!(x: 1.., y: ..)));
+    }
+
+    #[test]
+    fn clipped_region_works() {
+        let buf = [1; 4];
+        let shape = Shape::new([2, 2]);
+        assert_eq!(view(&buf, &shape, &roi!(x: 1..10, y: 1..10), axs![y, x]).count(), 1);
+    }
+
+    #[test]
+    fn runs_merge_when_they_meet() {
+        let buf = [0; 24];
+        assert!(runs_along(&buf, &Shape::col_major([2, 3, 4]), Axis::Z).eq([(0, 24)]));
+        assert!(runs_along(&buf, &Shape::new([2, 3]), Axis::Y).eq([(0, 6)]));
+        assert!(runs_along(&buf, &Shape::with_strides([2, 3], [2, 6]), Axis::X).eq([(0, 1), (2, 1), (6, 1), (8, 1), (12, 1), (14, 1)]));
+        assert_eq!(runs_along(&buf, &Shape::new([0, 3]), Axis::X).count(), 0);
+    }
+
+    #[test]
+    fn copy_region_handles_strides() {
+        // Copies out of a col major source into a row major destination, one element at a time
+        let src: [u8; 6] = core::array::from_fn(|i| i as u8);
+        let mut dst = [0u8; 6];
+        let copied = copy_region(&src, &Shape::col_major([2, 3]), &Roi::FULL, &mut dst, &Shape::new([2, 3]), [0, 0]);
+        assert_eq!(copied, Ok(6));
+        assert_eq!(dst, [0, 3, 1, 4, 2, 5]);
+
+        let past_src = copy_region(&src, &Shape::new([2, 3]), &roi!(x: .., y: 1..4), &mut dst, &Shape::new([2, 3]), [0, 0]);
+        assert_eq!(past_src, Err(OutOfBounds { axis: ax!(y), extent: 3, got: 3 }));
+    }
+
+    #[test]
+    fn reversed_source_flips_copy() {
+        // Flips a 3x2 image upside down, copying rows whole, then mirrors it element by element
+        let src = [1, 2, 3, 4, 5, 6];
+        let mut dst = [0; 6];
+        let flipped = Shape::new([3, 2]).reverse_axis(Axis::Y);
+        assert_eq!(copy_region(&src, &flipped, &Roi::FULL, &mut dst, &Shape::new([3, 2]), [0, 0]), Ok(6));
+        assert_eq!(dst, [4, 5, 6, 1, 2, 3]);
+
+        let mirrored = Shape::new([3, 2]).reverse_axis(Axis::X);
+        copy_region(&src, &mirrored, &Roi::FULL, &mut dst, &Shape::new([3, 2]), [0, 0]).unwrap();
+        assert_eq!(dst, [3, 2, 1, 6, 5, 4]);
+        assert!(runs_along(&src, &mirrored, Axis::X).eq([(0, 6)]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn bad_order_panics() {
+        let _ = view(&[0; 4], &Shape::new([2, 2]), &Roi::FULL, axs![x, x]);
+    }
+
+}
@@ -0,0 +1,356 @@
+//!
+//! Feature gated axis vocabularies
+//!
+//! `ax!` forwards any identifier it does not recognise along a chain of hidden macros, one per
+//! vocabulary. Each link is defined twice: with its identifiers when the feature is enabled, and as a plain
+//! pass-through otherwise. This keeps the feature checks in this crate rather than in the crate calling
+//! the macro
+//!
+
+
+///
+/// Gamepad axes, numbered as SDL's game controller API numbers them
+///
+#[cfg(feature = "input")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_input {
+
+    (lx) => { 0usize };
+    (ly) => { 1usize };
+    (rx) => { 2usize };
+    (ry) => { 3usize };
+    (lt) => { 4usize };
+    (rt) => { 5usize };
+
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) lx ) => { $( $cb )::+!($( $p )* 0usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) ly ) => { $( $cb )::+!($( $p )* 1usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) rx ) => { $( $cb )::+!($( $p )* 2usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) ry ) => { $( $cb )::+!($( $p )* 3usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) lt ) => { $( $cb )::+!($( $p )* 4usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) rt ) => { $( $cb )::+!($( $p )* 5usize) };
+
+    ( @unknown ( $( $names:literal ),* ) $other:tt ) => { $crate::__ax_imu!(@unknown ( $( $names, )* ", lx, ly, rx, ry, lt, rt" ) $other) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:ident ) => { $crate::__ax_imu!(@with $( $cb )::+ ($( $p )*) $other) };
+    ( $other:ident ) => { $crate::__ax_imu!($other) };
+
+}
+
+#[cfg(not(feature = "input"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_input {
+    ( @unknown ( $( $names:literal ),* ) $other:tt ) => { $crate::__ax_imu!(@unknown ( $( $names ),* ) $other) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:ident ) => { $crate::__ax_imu!(@with $( $cb )::+ ($( $p )*) $other) };
+    ( $other:ident ) => { $crate::__ax_imu!($other) };
+}
+
+
+///
+/// Accelerometer then gyroscope axes of a 6-axis IMU, in the order most sensors lay out their registers
+///
+#[cfg(feature = "imu")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_imu {
+
+    (ax) => { 0usize };
+    (ay) => { 1usize };
+    (az) => { 2usize };
+    (gx) => { 3usize };
+    (gy) => { 4usize };
+    (gz) => { 5usize };
+
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) ax ) => { $( $cb )::+!($( $p )* 0usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) ay ) => { $( $cb )::+!($( $p )* 1usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) az ) => { $( $cb )::+!($( $p )* 2usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) gx ) => { $( $cb )::+!($( $p )* 3usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) gy ) => { $( $cb )::+!($( $p )* 4usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) gz ) => { $( $cb )::+!($( $p )* 5usize) };
+
+    ( @unknown ( $( $names:literal ),* ) $other:tt ) => { $crate::__ax_motion!(@unknown ( $( $names, )* ", ax, ay, az, gx, gy, gz" ) $other) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:ident ) => { $crate::__ax_motion!(@with $( $cb )::+ ($( $p )*) $other) };
+    ( $other:ident ) => { $crate::__ax_motion!($other) };
+
+}
+
+#[cfg(not(feature = "imu"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_imu {
+    ( @unknown ( $( $names:literal ),* ) $other:tt ) => { $crate::__ax_motion!(@unknown ( $( $names ),* ) $other) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:ident ) => { $crate::__ax_motion!(@with $( $cb )::+ ($( $p )*) $other) };
+    ( $other:ident ) => { $crate::__ax_motion!($other) };
+}
+
+
+///
+/// Extruder and rotary axes of CNC machines and printers, following on from x, y and z
+///
+#[cfg(feature = "motion")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_motion {
+
+    (e) => { 3usize };
+    (a) => { 4usize };
+    (b) => { 5usize };
+    (c) => { 6usize };
+
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) e ) => { $( $cb )::+!($( $p )* 3usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) a ) => { $( $cb )::+!($( $p )* 4usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) b ) => { $( $cb )::+!($( $p )* 5usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) c ) => { $( $cb )::+!($( $p )* 6usize) };
+
+    ( @unknown ( $( $names:literal ),* ) $other:tt ) => { $crate::__ax_rgba!(@unknown ( $( $names, )* ", e, a, b, c" ) $other) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:ident ) => { $crate::__ax_rgba!(@with $( $cb )::+ ($( $p )*) $other) };
+    ( $other:ident ) => { $crate::__ax_rgba!($other) };
+
+}
+
+#[cfg(not(feature = "motion"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_motion {
+    ( @unknown ( $( $names:literal ),* ) $other:tt ) => { $crate::__ax_rgba!(@unknown ( $( $names ),* ) $other) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:ident ) => { $crate::__ax_rgba!(@with $( $cb )::+ ($( $p )*) $other) };
+    ( $other:ident ) => { $crate::__ax_rgba!($other) };
+}
+
+
+///
+/// Color channels
+///
+#[cfg(feature = "rgba")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_rgba {
+
+    (r) => { 0usize };
+    (g) => { 1usize };
+    (b) => { 2usize };
+    (a) => { 3usize };
+
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) r ) => { $( $cb )::+!($( $p )* 0usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) g ) => { $( $cb )::+!($( $p )* 1usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) b ) => { $( $cb )::+!($( $p )* 2usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) a ) => { $( $cb )::+!($( $p )* 3usize) };
+
+    ( @unknown ( $( $names:literal ),* ) $other:tt ) => { $crate::__ax_uvst!(@unknown ( $( $names, )* ", r, g, b, a" ) $other) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:ident ) => { $crate::__ax_uvst!(@with $( $cb )::+ ($( $p )*) $other) };
+    ( $other:ident ) => { $crate::__ax_uvst!($other) };
+
+}
+
+#[cfg(not(feature = "rgba"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_rgba {
+    ( @unknown ( $( $names:literal ),* ) $other:tt ) => { $crate::__ax_uvst!(@unknown ( $( $names ),* ) $other) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:ident ) => { $crate::__ax_uvst!(@with $( $cb )::+ ($( $p )*) $other) };
+    ( $other:ident ) => { $crate::__ax_uvst!($other) };
+}
+
+// Both vocabularies claim `a` and `b`, and silently picking one would index the wrong channel or axis
+#[cfg(all(feature = "motion", feature = "rgba"))]
+compile_error!("axmac: the `motion` and `rgba` features both name axes `a` and `b`, and cannot be enabled together");
+
+
+///
+/// Texture coordinates
+///
+#[cfg(feature = "uvst")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_uvst {
+
+    (u) => { 0usize };
+    (v) => { 1usize };
+    (s) => { 2usize };
+    (t) => { 3usize };
+
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) u ) => { $( $cb )::+!($( $p )* 0usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) v ) => { $( $cb )::+!($( $p )* 1usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) s ) => { $( $cb )::+!($( $p )* 2usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) t ) => { $( $cb )::+!($( $p )* 3usize) };
+
+    ( @unknown ( $( $names:literal ),* ) $other:tt ) => { $crate::__ax_whd!(@unknown ( $( $names, )* ", u, v, s, t" ) $other) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:ident ) => { $crate::__ax_whd!(@with $( $cb )::+ ($( $p )*) $other) };
+    ( $other:ident ) => { $crate::__ax_whd!($other) };
+
+}
+
+#[cfg(not(feature = "uvst"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_uvst {
+    ( @unknown ( $( $names:literal ),* ) $other:tt ) => { $crate::__ax_whd!(@unknown ( $( $names ),* ) $other) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:ident ) => { $crate::__ax_whd!(@with $( $cb )::+ ($( $p )*) $other) };
+    ( $other:ident ) => { $crate::__ax_whd!($other) };
+}
+
+
+///
+/// Extents of grids and volumes
+///
+#[cfg(feature = "whd")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_whd {
+
+    (width) => { 0usize };
+    (height) => { 1usize };
+    (depth) => { 2usize };
+
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) width ) => { $( $cb )::+!($( $p )* 0usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) height ) => { $( $cb )::+!($( $p )* 1usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) depth ) => { $( $cb )::+!($( $p )* 2usize) };
+
+    ( @unknown ( $( $names:literal ),* ) $other:tt ) => { $crate::__ax_spectral!(@unknown ( $( $names, )* ", width, height, depth" ) $other) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:ident ) => { $crate::__ax_spectral!(@with $( $cb )::+ ($( $p )*) $other) };
+    ( $other:ident ) => { $crate::__ax_spectral!($other) };
+
+}
+
+#[cfg(not(feature = "whd"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_whd {
+    ( @unknown ( $( $names:literal ),* ) $other:tt ) => { $crate::__ax_spectral!(@unknown ( $( $names ),* ) $other) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:ident ) => { $crate::__ax_spectral!(@with $( $cb )::+ ($( $p )*) $other) };
+    ( $other:ident ) => { $crate::__ax_spectral!($other) };
+}
+
+
+///
+/// Frequency and time axes of spectra and spectrograms
+///
+#[cfg(feature = "spectral")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_spectral {
+
+    (freq) => { 0usize };
+    (time) => { 1usize };
+
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) freq ) => { $( $cb )::+!($( $p )* 0usize) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) time ) => { $( $cb )::+!($( $p )* 1usize) };
+
+    ( @unknown ( $( $names:literal ),* ) $other:tt ) => { $crate::__ax_end!(@unknown ( $( $names, )* ", freq, time" ) $other) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:ident ) => { $crate::__ax_end!(@with $( $cb )::+ ($( $p )*) $other) };
+    ( $other:ident ) => { $crate::__ax_end!($other) };
+
+}
+
+#[cfg(not(feature = "spectral"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_spectral {
+    ( @unknown ( $( $names:literal ),* ) $other:tt ) => { $crate::__ax_end!(@unknown ( $( $names ),* ) $other) };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:ident ) => { $crate::__ax_end!(@with $( $cb )::+ ($( $p )*) $other) };
+    ( $other:ident ) => { $crate::__ax_end!($other) };
+}
+
+
+///
+/// End of the vocabulary chain, reached only by identifiers no vocabulary recognises
+///
+/// Unknown identifiers are sent back along the chain as `@unknown`, each enabled vocabulary adding its
+/// names, so the error lists exactly the identifiers accepted with the current features
+///
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __ax_end {
+    ( @unknown ( $( $names:literal ),* ) $other:tt ) => {
+        compile_error!(concat!("axmac: expected one of ", $( $names, )* " (got `", stringify!($other), "`)"))
+    };
+    ( @with $( $cb:ident )::+ ( $( $p:tt )* ) $other:tt ) => { $crate::ax!(@unknown $other) };
+    ( $other:tt ) => { $crate::ax!(@unknown $other) };
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    #[cfg(feature = "input")]
+    mod input {
+        #[test]
+        fn it_works() {
+            assert_eq!(axs![lx, ly, rx, ry, lt, rt], [0, 1, 2, 3, 4, 5]);
+            assert_eq!(axr!(lx..=ry), 0..=3);
+        }
+
+        #[test]
+        fn with_callback_works() {
+            macro_rules! echo {
+                ( $i:literal ) => { $i };
+            }
+            assert_eq!(ax!(@with echo rt), 5);
+        }
+    }
+
+    #[cfg(feature = "imu")]
+    mod imu {
+        #[test]
+        fn it_works() {
+            assert_eq!(axs![ax, ay, az, gx, gy, gz], [0, 1, 2, 3, 4, 5]);
+            assert_eq!(axr!(gx..), 3..);
+        }
+    }
+
+    #[cfg(feature = "motion")]
+    mod motion {
+        #[test]
+        fn it_works() {
+            assert_eq!(axs![x, y, z, e, a, b, c], [0, 1, 2, 3, 4, 5, 6]);
+            assert_eq!(axr!(a..=c), 4..=6);
+        }
+    }
+
+    #[cfg(feature = "rgba")]
+    mod rgba {
+        #[test]
+        fn it_works() {
+            assert_eq!(axs![r, g, b, a], [0, 1, 2, 3]);
+            assert_eq!(axr!(r..a), 0..3);
+        }
+    }
+
+    #[cfg(feature = "uvst")]
+    mod uvst {
+        #[test]
+        fn it_works() {
+            assert_eq!(axs![u, v, s, t], [0, 1, 2, 3]);
+            assert_eq!(axr!(v..=t), 1..=3);
+        }
+    }
+
+    #[cfg(feature = "whd")]
+    mod whd {
+        #[test]
+        fn it_works() {
+            let extents = [640, 480, 16];
+            assert_eq!(extents[ax!(depth)], 16);
+            assert_eq!(axr!(width..depth), 0..2);
+        }
+
+        #[test]
+        fn with_callback_works() {
+            macro_rules! echo {
+                ( $i:literal ) => { $i };
+            }
+            assert_eq!(ax!(@with echo height), 1);
+        }
+    }
+
+    #[cfg(feature = "spectral")]
+    mod spectral {
+        #[test]
+        fn it_works() {
+            assert_eq!(axs![freq, time], [0, 1]);
+            assert_eq!(axr!(freq..=time), 0..=1);
+        }
+    }
+
+}
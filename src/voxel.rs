@@ -0,0 +1,191 @@
+//!
+//! Voxel traversal along rays
+//!
+
+use crate::{ray_aabb, Aabb, Axis, Shape, SignedAxis};
+
+
+///
+/// A voxel visited by a [`VoxelTraverse`]
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VoxelStep<const N: usize> {
+    /// The integer coordinate of the voxel
+    pub cell: [isize; N],
+    /// The ray parameter at which the voxel is entered
+    pub t: f32,
+    /// The direction the ray moved in to enter the voxel, or `None` for the first voxel
+    pub entered: Option<SignedAxis>,
+}
+
+
+///
+/// Iterates over every unit voxel a ray passes through, in order, using the Amanatides-Woo algorithm
+///
+/// Voxel `c` covers the points `c[i] <= p[i] < c[i] + 1` along every axis. Each step crosses exactly one
+/// voxel face, reported as the [`SignedAxis`] the ray moved along. When the ray passes exactly through an
+/// edge or corner, the crossings are reported one axis at a time, lowest axis first
+///
+/// ```
+/// # #[macro_use] extern crate axmac; fn main() {
+/// use axmac::{SignedAxis, VoxelTraverse};
+///
+/// let cells: Vec<_> = VoxelTraverse::new([0.5, 0.5], [1.0, 0.5], 2.0).map(|s| s.cell).collect();
+/// assert_eq!(cells, [[0, 0], [1, 0], [1, 1], [2, 1]]);
+///
+/// let mut steps = VoxelTraverse::new([0.5, 0.5], [0.0, -1.0], 1.0);
+/// assert_eq!(steps.nth(1).unwrap().entered, Some(SignedAxis::NEG_Y));
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// The constructors panic if `N` is greater than 4, since every step is reported as an [`Axis`]
+///
+#[derive(Clone, Debug)]
+pub struct VoxelTraverse<const N: usize> {
+    cell: [isize; N],
+    step: [isize; N],
+    t_next: [f32; N],
+    t_delta: [f32; N],
+    t_end: f32,
+    bounds: Option<[usize; N]>,
+    next: Option<VoxelStep<N>>,
+}
+
+impl<const N: usize> VoxelTraverse<N> {
+
+    ///
+    /// Traverses the unbounded grid from `origin` along `dir`, until the ray parameter passes `max_t`
+    ///
+    /// With a unit length `dir`, `max_t` is the distance travelled
+    ///
+    pub fn new(origin: [f32; N], dir: [f32; N], max_t: f32) -> Self {
+        Self::start(origin, dir, 0.0, max_t, None)
+    }
+
+    ///
+    /// Traverses the voxels of `shape` the ray passes through, from where it enters the grid to where it
+    /// leaves it
+    ///
+    /// Yields nothing if the ray misses the grid
+    ///
+    pub fn in_shape(origin: [f32; N], dir: [f32; N], shape: &Shape<N>) -> Self {
+        let grid = Aabb::new([0.0; N], shape.extents().map(|e| e as f32));
+        match ray_aabb(&origin, &dir, &grid).hit() {
+            Some(hit) if !shape.is_empty() => Self::start(origin, dir, hit.start, hit.end, Some(*shape.extents())),
+            _ => Self::start(origin, dir, 0.0, f32::NEG_INFINITY, None),
+        }
+    }
+
+    fn start(origin: [f32; N], dir: [f32; N], t0: f32, t_end: f32, bounds: Option<[usize; N]>) -> Self {
+        assert!(N <= Axis::COUNT, "axmac: VoxelTraverse supports at most 4 axes");
+
+        let mut cell = [0; N];
+        let mut step = [0; N];
+        let mut t_next = [f32::INFINITY; N];
+        let mut t_delta = [f32::INFINITY; N];
+        for axis in 0..N {
+            let p = origin[axis] + dir[axis] * t0;
+            cell[axis] = floor(p);
+            if let Some(extents) = bounds {
+                // A ray entering through the far face of the grid starts exactly on its boundary
+                cell[axis] = cell[axis].clamp(0, extents[axis] as isize - 1);
+            }
+            if dir[axis] != 0.0 {
+                let positive = dir[axis] > 0.0;
+                step[axis] = if positive { 1 } else { -1 };
+                t_delta[axis] = (1.0 / dir[axis]).abs();
+                let boundary = if positive { cell[axis] + 1 } else { cell[axis] } as f32;
+                t_next[axis] = t0 + (boundary - p) / dir[axis];
+            }
+        }
+
+        let next = (t0 <= t_end).then_some(VoxelStep { cell, t: t0, entered: None });
+        Self { cell, step, t_next, t_delta, t_end, bounds, next }
+    }
+
+    fn advance(&mut self) -> Option<VoxelStep<N>> {
+        let (axis, &t) = self
+            .t_next
+            .iter()
+            .enumerate()
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+        if t.partial_cmp(&self.t_end).is_none_or(|o| o.is_gt()) {
+            return None;
+        }
+
+        self.cell[axis] += self.step[axis];
+        self.t_next[axis] += self.t_delta[axis];
+        if let Some(extents) = self.bounds {
+            if self.cell[axis] < 0 || self.cell[axis] >= extents[axis] as isize {
+                return None;
+            }
+        }
+
+        let axis = Axis::from_usize(axis)?;
+        let entered = SignedAxis::new(axis, self.step[axis.as_usize()] < 0);
+        Some(VoxelStep { cell: self.cell, t, entered: Some(entered) })
+    }
+
+}
+
+impl<const N: usize> Iterator for VoxelTraverse<N> {
+    type Item = VoxelStep<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next.take()?;
+        self.next = self.advance();
+        Some(current)
+    }
+}
+
+impl<const N: usize> core::iter::FusedIterator for VoxelTraverse<N> {}
+
+
+fn floor(v: f32) -> isize {
+    let truncated = v as isize;
+    if (truncated as f32) > v { truncated - 1 } else { truncated }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::vec::Vec;
+
+    #[test]
+    fn negative_directions_work() {
+        let steps: Vec<_> = VoxelTraverse::new([0.5, 0.5, 0.5], [-1.0, 0.0, 0.0], 2.5).collect();
+        let cells: Vec<_> = steps.iter().map(|s| s.cell).collect();
+        assert_eq!(cells, [[0, 0, 0], [-1, 0, 0], [-2, 0, 0], [-3, 0, 0]]);
+        assert_eq!(steps[1].t, 0.5);
+        assert!(steps[1..].iter().all(|s| s.entered == Some(SignedAxis::NEG_X)));
+    }
+
+    #[test]
+    fn in_shape_works() {
+        let shape = Shape::new([3, 2]);
+        let cells: Vec<_> = VoxelTraverse::in_shape([-1.0, 0.5], [1.0, 0.0], &shape).map(|s| s.cell).collect();
+        assert_eq!(cells, [[0, 0], [1, 0], [2, 0]]);
+
+        let reversed: Vec<_> = VoxelTraverse::in_shape([5.0, 1.5], [-1.0, 0.0], &shape).map(|s| s.cell).collect();
+        assert_eq!(reversed, [[2, 1], [1, 1], [0, 1]]);
+
+        assert_eq!(VoxelTraverse::in_shape([-1.0, 5.0], [1.0, 0.0], &shape).count(), 0);
+        assert_eq!(VoxelTraverse::in_shape([0.5, 0.5], [1.0, 0.0], &Shape::new([0, 2])).count(), 0);
+    }
+
+    #[test]
+    fn every_step_moves_one_axis() {
+        let steps: Vec<_> = VoxelTraverse::new([0.1, 0.2, 0.3], [0.7, -0.3, 0.5], 20.0).collect();
+        for pair in steps.windows(2) {
+            let moved: isize = (0..3).map(|i| (pair[1].cell[i] - pair[0].cell[i]).abs()).sum();
+            assert_eq!(moved, 1);
+            assert!(pair[1].t >= pair[0].t);
+        }
+    }
+
+}
@@ -4,4 +4,120 @@ use axmac::ax;
 #[test]
 fn can_work() {
     assert_eq!(ax!(x), 0usize);
-}
\ No newline at end of file
+}
+
+// Each module imports a single macro, so any expansion relying on `ax!` being in scope at the call site
+// fails to compile here
+mod only_axs {
+    use axmac::axs;
+
+    #[test]
+    fn list_works() {
+        assert_eq!(axs![w, z, y, x], [3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn repeat_works() {
+        assert_eq!(axs![y; 3], [1, 1, 1]);
+    }
+
+    #[test]
+    fn range_works() {
+        assert_eq!(axs![x..=w], [0, 1, 2, 3]);
+        assert_eq!(axs![z..z], [0usize; 0]);
+    }
+}
+
+mod only_axr {
+    use axmac::axr;
+
+    #[test]
+    fn ident_forms_work() {
+        assert_eq!(axr!(x..w), 0..3);
+        assert_eq!(axr!(x..=w), 0..=3);
+        assert_eq!(axr!(..z), ..2);
+        assert_eq!(axr!(..=z), ..=2);
+        assert_eq!(axr!(y..), 1..);
+    }
+
+    #[test]
+    fn expr_forms_work() {
+        assert_eq!(axr!(y..5), 1..5);
+        assert_eq!(axr!(y..=5), 1..=5);
+        assert_eq!(axr!((0)..w), 0..3);
+        assert_eq!(axr!((0)..=w), 0..=3);
+    }
+
+    #[test]
+    fn step_and_rev_work() {
+        let n = 7;
+        assert!(axr!(y..(n) step 3).eq([1, 4]));
+        assert!(axr!(z..=y rev).eq([2, 1]));
+        assert!(axr!(y..x rev).eq([1]));
+    }
+}
+
+// The 0.1 expansions name `ax!` without a path, so they see whichever one is in scope where they are used
+#[cfg(feature = "compat-0-1")]
+mod compat_0_1 {
+    use axmac::compat::{axr, axs};
+
+    #[test]
+    fn expansions_match_0_1() {
+        use axmac::compat::ax;
+        assert_eq!(ax!(w), 3);
+        assert_eq!(axs![x, z, y], [0, 2, 1]);
+        assert_eq!(axs![z; 3], [2, 2, 2]);
+        assert_eq!(axr!((1)..=w), 1..=3);
+        assert_eq!(axr!(y..), 1..);
+    }
+
+    #[test]
+    fn call_site_ax_is_used() {
+        macro_rules! ax {
+            (u) => { 5usize };
+            ($axis:ident) => { axmac::ax!($axis) };
+        }
+        assert_eq!(axs![u, y], [5, 1]);
+        assert_eq!(axr!(..=u), ..=5);
+    }
+}
+
+mod only_helpers {
+    use axmac::{ax_concat, iter_box, ndindex, roi};
+
+    #[test]
+    fn it_works() {
+        assert_eq!(ndindex!(x in 0..2, y in 0..2).count(), 4);
+        assert_eq!(iter_box!(x in 0..=1).count(), 2);
+        assert_eq!(roi!(x: 1..2).range(0), 1..2);
+        assert_eq!(ax_concat!(get_, z), "get_z");
+    }
+}
+
+// Lookup tables of shapes, regions and permutations must be declarable as statics, without lazy
+// initialization
+mod statics {
+    use axmac::{Aabb, AxisMap, AxisPerm, Coord3, Roi, Shape};
+
+    static MIP_LEVELS: [Shape<2>; 3] = [Shape::new([256, 256]), Shape::new([128, 128]), Shape::col_major([64, 64])];
+    static TILES: [Roi<2>; 2] = [Roi::new([0, 0], [16, 16]), Roi::FULL];
+    static SWAP_XY: AxisPerm<3> = AxisPerm::new([1, 0, 2]);
+    static ORIGIN: Coord3<i32> = Coord3::new([0, 0, 0]);
+    static UNIT_BOX: Aabb<f32, 3> = Aabb::new([0.0; 3], [1.0; 3]);
+    static SIGNS: AxisMap<i8, 3> = AxisMap::new([1, -1, 1]);
+
+    const MIP_1_LEN: usize = MIP_LEVELS[1].len();
+    const ORIGIN_Y: Option<&i32> = ORIGIN.get(1);
+
+    #[test]
+    fn it_works() {
+        assert_eq!(MIP_1_LEN, 128 * 128);
+        assert_eq!(MIP_LEVELS[2].flat_index(&[1, 0]), Some(64));
+        assert_eq!(TILES[0].range(1), 0..16);
+        assert_eq!(SWAP_XY.apply([1, 2, 3]), [2, 1, 3]);
+        assert_eq!(ORIGIN_Y, Some(&0));
+        assert_eq!(UNIT_BOX.max, [1.0; 3]);
+        assert_eq!(SIGNS.get(1), Some(&-1));
+    }
+}
@@ -0,0 +1,123 @@
+
+// Builds every `compile_fail` example in the crate's docs which names one of the crate's own `axmac: ...`
+// errors, and checks that rustc reports that error word for word. Rustdoc only checks that such examples
+// fail, so the wording is locked in here
+//
+// The crate is compiled without any vocabulary features, since they extend the identifiers listed by
+// unknown axis errors
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+struct Example {
+    origin: String,
+    source: String,
+    errors: Vec<String>,
+}
+
+fn rustc() -> Command {
+    Command::new(std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()))
+}
+
+// The examples of a source file which are expected not to compile with an `// error: axmac: ...` comment
+fn examples(path: &Path) -> Vec<Example> {
+    let text = fs::read_to_string(path).unwrap();
+    let mut found = Vec::new();
+    let mut current: Option<(usize, Vec<&str>)> = None;
+
+    for (number, line) in text.lines().enumerate() {
+        let Some(doc) = line.trim_start().strip_prefix("///").or_else(|| line.trim_start().strip_prefix("//!")) else {
+            continue;
+        };
+        let doc = doc.strip_prefix(' ').unwrap_or(doc);
+        match current.take() {
+            None if doc.trim_end() == "```compile_fail" => current = Some((number + 1, Vec::new())),
+            None => {}
+            Some((start, lines)) if doc.trim_end() == "```" => {
+                let errors: Vec<String> = lines
+                    .iter()
+                    .filter_map(|l| l.trim_start().strip_prefix("// error: "))
+                    .filter(|error| error.starts_with("axmac: "))
+                    .map(String::from)
+                    .collect();
+                if errors.is_empty() {
+                    continue;
+                }
+                // Hidden lines are compiled as well, and rustdoc wraps examples lacking a `main`
+                let mut source: String = lines
+                    .iter()
+                    .map(|l| l.strip_prefix("# ").unwrap_or(if *l == "#" { "" } else { l }))
+                    .flat_map(|l| [l, "\n"])
+                    .collect();
+                if !source.contains("fn main") {
+                    source = format!("fn main() {{\n{source}}}\n");
+                }
+                let origin = format!("{}:{}", path.display(), start);
+                found.push(Example { origin, source, errors });
+            }
+            Some((start, mut lines)) => {
+                lines.push(doc);
+                current = Some((start, lines));
+            }
+        }
+    }
+    found
+}
+
+// Builds the crate once, for the examples to be checked against
+fn build_crate(out: &Path) -> PathBuf {
+    let status = rustc()
+        .args(["--crate-name", "axmac", "--crate-type", "rlib", "--edition", "2021"])
+        .args(["--cfg", "feature=\"alloc\""])
+        .arg("--out-dir")
+        .arg(out)
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("src/lib.rs"))
+        .status()
+        .unwrap();
+    assert!(status.success(), "the crate failed to compile for the ui tests");
+    out.join("libaxmac.rlib")
+}
+
+#[test]
+fn documented_errors_are_reported() {
+    let out = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ui");
+    fs::create_dir_all(&out).unwrap();
+    let rlib = build_crate(&out);
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(Path::new(env!("CARGO_MANIFEST_DIR")).join("src"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    paths.sort();
+
+    let mut checked = 0;
+    let mut failures = Vec::new();
+    for example in paths.iter().flat_map(|path| examples(path)) {
+        let file = out.join("example.rs");
+        fs::write(&file, &example.source).unwrap();
+        let output = rustc()
+            .args(["--crate-type", "bin", "--edition", "2021", "--error-format=short"])
+            .arg("--extern")
+            .arg(format!("axmac={}", rlib.display()))
+            .arg("--out-dir")
+            .arg(&out)
+            .arg(&file)
+            .output()
+            .unwrap();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if output.status.success() {
+            failures.push(format!("{} compiled", example.origin));
+        }
+        for error in &example.errors {
+            if !stderr.contains(error.as_str()) {
+                failures.push(format!("{} did not report `{}`, but:\n{}", example.origin, error, stderr));
+            }
+        }
+        checked += 1;
+    }
+
+    assert!(checked > 0, "no examples were found");
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}